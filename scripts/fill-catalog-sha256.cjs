@@ -0,0 +1,50 @@
+#!/usr/bin/env node
+/**
+ * Renseigne le sha256 des fichiers du catalogue de modèles qui n'en ont pas.
+ * Chaque fichier est téléchargé en flux (rien n'est gardé sur disque) puis
+ * son empreinte est écrite dans model_catalog.json.
+ *
+ * Usage : node scripts/fill-catalog-sha256.cjs [--force]
+ *   --force : recalcule aussi les empreintes déjà présentes
+ */
+
+const crypto = require('crypto');
+const fs = require('fs');
+const path = require('path');
+
+const CATALOG = path.join(__dirname, '../src-tauri/src/engines/model_catalog.json');
+
+async function sha256(url) {
+  const response = await fetch(url, { redirect: 'follow' });
+  if (!response.ok) {
+    throw new Error(`${url} : HTTP ${response.status}`);
+  }
+  const hash = crypto.createHash('sha256');
+  for await (const chunk of response.body) {
+    hash.update(chunk);
+  }
+  return hash.digest('hex');
+}
+
+async function main() {
+  const force = process.argv.includes('--force');
+  const catalog = JSON.parse(fs.readFileSync(CATALOG, 'utf8'));
+
+  for (const model of catalog.models) {
+    for (const file of model.files) {
+      if (file.sha256 && !force) {
+        continue;
+      }
+      process.stdout.write(`${model.engine}/${model.id} ${file.name}... `);
+      file.sha256 = await sha256(file.url);
+      console.log(file.sha256);
+      // Écrit après chaque fichier : une interruption ne perd pas le travail fait
+      fs.writeFileSync(CATALOG, JSON.stringify(catalog, null, 2) + '\n');
+    }
+  }
+}
+
+main().catch((error) => {
+  console.error(error.message);
+  process.exit(1);
+});
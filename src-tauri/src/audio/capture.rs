@@ -2,6 +2,7 @@ use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::{Device, Host, Stream, StreamConfig};
 use std::sync::{Arc, Mutex};
 
+use crate::audio::vad::SilenceTracker;
 use crate::types::AudioDevice;
 
 /// Limite du buffer audio : 10 minutes à 48kHz mono
@@ -10,6 +11,9 @@ const MAX_BUFFER_SAMPLES: usize = 48000 * 60 * 10;
 pub struct AudioCapture {
     stream: Option<Stream>,
     buffer: Arc<Mutex<Vec<f32>>>,
+    silence: SilenceTracker,
+    /// Échantillons du buffer déjà passés au détecteur de silence
+    analyzed: usize,
     sample_rate: u32,
     channels: u16,
 }
@@ -44,6 +48,8 @@ impl AudioCapture {
         Ok(Self {
             stream: None,
             buffer: Arc::new(Mutex::new(Vec::new())),
+            silence: SilenceTracker::new(config.sample_rate().0),
+            analyzed: 0,
             sample_rate: config.sample_rate().0,
             channels: config.channels(),
         })
@@ -72,6 +78,8 @@ impl AudioCapture {
         if let Ok(mut buf) = self.buffer.lock() {
             buf.clear();
         }
+        self.silence = SilenceTracker::new(self.sample_rate);
+        self.analyzed = 0;
 
         let buffer = self.buffer.clone();
         let channels = self.channels as usize;
@@ -131,10 +139,24 @@ impl AudioCapture {
         self.sample_rate
     }
 
-    /// Retourne un snapshot de l'audio accumulé sans arrêter l'enregistrement
-    pub fn get_audio_snapshot(&self) -> (Vec<f32>, u32) {
+    /// Durée du silence depuis la dernière parole détectée (0 tant que personne n'a parlé).
+    /// Les nouveaux échantillons sont analysés ici, dans le thread appelant,
+    /// pour que le callback audio se limite à remplir le buffer.
+    pub fn trailing_silence_ms(&mut self) -> u32 {
+        let samples = match self.buffer.lock() {
+            Ok(buf) => buf.get(self.analyzed..).map(<[f32]>::to_vec).unwrap_or_default(),
+            Err(_) => Vec::new(),
+        };
+        self.analyzed += samples.len();
+        self.silence.push(&samples);
+        self.silence.trailing_silence_ms()
+    }
+
+    /// Retourne l'audio accumulé à partir de l'échantillon `from` sans arrêter
+    /// l'enregistrement (le streaming ne demande que la partie nouvelle)
+    pub fn get_audio_snapshot(&self, from: usize) -> (Vec<f32>, u32) {
         let buffer = self.buffer.lock()
-            .map(|buf| buf.clone())
+            .map(|buf| buf.get(from..).map(<[f32]>::to_vec).unwrap_or_default())
             .unwrap_or_default();
         (buffer, self.sample_rate)
     }
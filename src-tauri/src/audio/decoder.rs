@@ -24,6 +24,20 @@ impl AudioDecoder {
             hint.with_extension(ext);
         }
 
+        Self::decode_stream(mss, hint)
+    }
+
+    /// Decode in-memory audio (e.g. a decrypted recording) to f32 samples at 16kHz mono
+    pub fn decode_bytes(bytes: Vec<u8>, extension: &str) -> Result<(Vec<f32>, u32), String> {
+        let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(bytes)), Default::default());
+
+        let mut hint = Hint::new();
+        hint.with_extension(extension);
+
+        Self::decode_stream(mss, hint)
+    }
+
+    fn decode_stream(mss: MediaSourceStream, hint: Hint) -> Result<(Vec<f32>, u32), String> {
         let format_opts = FormatOptions::default();
         let metadata_opts = MetadataOptions::default();
         let decoder_opts = DecoderOptions::default();
@@ -0,0 +1,461 @@
+//! Diarisation : chaque segment de la transcription reçoit une empreinte
+//! vocale calculée par un modèle ONNX exécuté avec tract, puis les empreintes
+//! sont regroupées par intervenant
+
+use crate::audio::fft::{fft, mel_filterbank};
+use crate::audio::resampling::resample_audio;
+use crate::types::{AppSettings, Segment, TranscriptionResult};
+use std::path::{Path, PathBuf};
+use tract_onnx::prelude::*;
+
+type TractModel = SimplePlan<TypedFact, Box<dyn TypedOp>, Graph<TypedFact, Box<dyn TypedOp>>>;
+
+/// Les modèles d'empreinte vocale (WeSpeaker, 3D-Speaker) prennent 80 bandes
+/// log-mel d'un audio à 16 kHz, fenêtres de 25 ms toutes les 10 ms
+const EMBEDDING_SAMPLE_RATE: u32 = 16000;
+const FBANK_BINS: usize = 80;
+const FBANK_WINDOW: usize = 400;
+const FBANK_HOP: usize = 160;
+const FBANK_FFT: usize = 512;
+
+/// Emplacement du modèle d'empreinte vocale optionnel
+pub fn embedding_model_path() -> PathBuf {
+    crate::storage::get_app_data_dir()
+        .join("models")
+        .join("speaker_embedding.onnx")
+}
+
+/// Nom par défaut du n-ième intervenant (à partir de 0)
+pub fn speaker_label(index: usize) -> String {
+    format!("Intervenant {}", index + 1)
+}
+
+/// Réglages du regroupement par intervenant
+#[derive(Debug, Clone)]
+pub struct DiarizationConfig {
+    /// Au-delà de cette distance cosinus, deux groupes sont deux intervenants
+    pub threshold: f32,
+    /// Nombre maximal d'intervenants, s'il est connu
+    pub max_speakers: Option<usize>,
+    /// Les segments plus courts prennent l'intervenant de leur voisin
+    pub min_segment_secs: f32,
+    /// Seul le milieu des segments plus longs est analysé
+    pub max_embed_secs: f32,
+}
+
+impl Default for DiarizationConfig {
+    fn default() -> Self {
+        Self {
+            threshold: 0.5,
+            max_speakers: None,
+            min_segment_secs: 0.5,
+            max_embed_secs: 10.0,
+        }
+    }
+}
+
+/// Transforme un passage de parole en empreinte vocale de taille fixe
+pub trait SpeakerEmbedder {
+    fn embed(&mut self, audio: &[f32], sample_rate: u32) -> Result<Vec<f32>, String>;
+}
+
+/// Modèle d'empreinte vocale exécuté avec tract : bandes `[1, trames, 80]` en
+/// entrée, empreinte `[1, dim]` en sortie
+pub struct OnnxEmbedder {
+    model: TractModel,
+}
+
+impl OnnxEmbedder {
+    pub fn new(model_path: &Path) -> Result<Self, String> {
+        if !model_path.exists() {
+            return Err(format!("Speaker embedding model not found: {:?}", model_path));
+        }
+
+        let model = tract_onnx::onnx()
+            .model_for_path(model_path)
+            .map_err(|e| format!("Failed to load speaker embedding model: {}", e))?
+            .into_optimized()
+            .map_err(|e| format!("Failed to optimize speaker embedding model: {}", e))?
+            .into_runnable()
+            .map_err(|e| format!("Failed to make speaker embedding model runnable: {}", e))?;
+
+        Ok(Self { model })
+    }
+}
+
+impl SpeakerEmbedder for OnnxEmbedder {
+    fn embed(&mut self, audio: &[f32], sample_rate: u32) -> Result<Vec<f32>, String> {
+        let resampled;
+        let audio = if sample_rate != EMBEDDING_SAMPLE_RATE {
+            resampled = resample_audio(audio, sample_rate, EMBEDDING_SAMPLE_RATE);
+            &resampled[..]
+        } else {
+            audio
+        };
+
+        let features = fbank(audio);
+        let frames = features.len() / FBANK_BINS;
+        let input: Tensor = tract_ndarray::Array3::from_shape_vec((1, frames, FBANK_BINS), features)
+            .map_err(|e| e.to_string())?
+            .into();
+
+        let outputs = self
+            .model
+            .run(tvec![input.into()])
+            .map_err(|e| format!("Speaker embedding error: {}", e))?;
+
+        Ok(outputs[0]
+            .to_array_view::<f32>()
+            .map_err(|e| e.to_string())?
+            .iter()
+            .copied()
+            .collect())
+    }
+}
+
+/// Bandes log-mel (`trames × 80`, ligne par ligne) centrées sur la moyenne
+/// du passage
+fn fbank(audio: &[f32]) -> Vec<f32> {
+    let frames = if audio.len() > FBANK_WINDOW {
+        (audio.len() - FBANK_WINDOW) / FBANK_HOP + 1
+    } else {
+        1
+    };
+    let filters = mel_filterbank(FBANK_FFT, EMBEDDING_SAMPLE_RATE, FBANK_BINS, 20.0, 7600.0);
+    let n_freqs = FBANK_FFT / 2 + 1;
+    let window: Vec<f32> = (0..FBANK_WINDOW)
+        .map(|i| 0.54 - 0.46 * (2.0 * std::f32::consts::PI * i as f32 / (FBANK_WINDOW - 1) as f32).cos())
+        .collect();
+
+    let mut features = vec![0.0f32; frames * FBANK_BINS];
+    let mut buf = vec![(0.0f32, 0.0f32); FBANK_FFT];
+
+    for frame in 0..frames {
+        let start = frame * FBANK_HOP;
+        for (i, slot) in buf.iter_mut().enumerate() {
+            *slot = match (window.get(i), audio.get(start + i)) {
+                (Some(w), Some(s)) => (s * w, 0.0),
+                _ => (0.0, 0.0),
+            };
+        }
+        fft(&mut buf);
+
+        for bin in 0..FBANK_BINS {
+            let energy: f32 = (0..n_freqs)
+                .map(|k| {
+                    let (re, im) = buf[k];
+                    (re * re + im * im) * filters[bin * n_freqs + k]
+                })
+                .sum();
+            features[frame * FBANK_BINS + bin] = energy.max(1e-10).ln();
+        }
+    }
+
+    for bin in 0..FBANK_BINS {
+        let mean = (0..frames).map(|f| features[f * FBANK_BINS + bin]).sum::<f32>() / frames as f32;
+        for f in 0..frames {
+            features[f * FBANK_BINS + bin] -= mean;
+        }
+    }
+    features
+}
+
+fn normalized(vector: &[f32]) -> Vec<f32> {
+    let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt().max(1e-10);
+    vector.iter().map(|x| x / norm).collect()
+}
+
+/// Au-delà de ce nombre de groupes, la passe en ligne ajoute les empreintes
+/// au groupe le plus proche au lieu d'en ouvrir un nouveau
+const MAX_CLUSTERS: usize = 64;
+
+fn cosine_distance(a: &[f32], b: &[f32]) -> f32 {
+    1.0 - a.iter().zip(b).map(|(x, y)| x * y).sum::<f32>()
+}
+
+/// Regroupe les empreintes selon la distance cosinus en deux passes bornées :
+/// une passe en ligne ajoute chaque empreinte au centroïde le plus proche (ou
+/// ouvre un groupe, jusqu'à [`MAX_CLUSTERS`]), puis un regroupement
+/// hiérarchique à liaison moyenne fusionne les centroïdes. Linéaire en nombre
+/// d'empreintes. Renvoie un groupe par empreinte, numéroté par ordre
+/// d'apparition.
+pub fn cluster(embeddings: &[Vec<f32>], threshold: f32, max_speakers: Option<usize>) -> Vec<usize> {
+    let mut sums: Vec<Vec<f32>> = Vec::new();
+    let mut centroids: Vec<Vec<f32>> = Vec::new();
+    let mut sizes: Vec<usize> = Vec::new();
+    let mut assignment = Vec::with_capacity(embeddings.len());
+
+    for embedding in embeddings {
+        let unit = normalized(embedding);
+        let nearest = centroids
+            .iter()
+            .map(|centroid| cosine_distance(&unit, centroid))
+            .enumerate()
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        let index = match nearest {
+            Some((index, d)) if d <= threshold || centroids.len() >= MAX_CLUSTERS => {
+                for (sum, x) in sums[index].iter_mut().zip(&unit) {
+                    *sum += x;
+                }
+                centroids[index] = normalized(&sums[index]);
+                sizes[index] += 1;
+                index
+            }
+            _ => {
+                centroids.push(unit.clone());
+                sums.push(unit);
+                sizes.push(1);
+                centroids.len() - 1
+            }
+        };
+        assignment.push(index);
+    }
+
+    let owner = agglomerate(&centroids, &sizes, threshold, max_speakers);
+    let mut order: Vec<usize> = Vec::new();
+    assignment
+        .iter()
+        .map(|&index| {
+            let o = owner[index];
+            match order.iter().position(|&x| x == o) {
+                Some(position) => position,
+                None => {
+                    order.push(o);
+                    order.len() - 1
+                }
+            }
+        })
+        .collect()
+}
+
+/// Regroupement hiérarchique à liaison moyenne de centroïdes pondérés.
+/// Renvoie le centroïde représentant chaque entrée.
+fn agglomerate(centroids: &[Vec<f32>], sizes: &[usize], threshold: f32, max_speakers: Option<usize>) -> Vec<usize> {
+    let n = centroids.len();
+    let mut distance = vec![vec![0.0f32; n]; n];
+    for i in 0..n {
+        for j in i + 1..n {
+            let d = cosine_distance(&centroids[i], &centroids[j]);
+            distance[i][j] = d;
+            distance[j][i] = d;
+        }
+    }
+
+    let mut owner: Vec<usize> = (0..n).collect();
+    let mut size = sizes.to_vec();
+    let mut active = vec![true; n];
+    let mut clusters = n;
+
+    while clusters > 1 {
+        let mut closest: Option<(usize, usize, f32)> = None;
+        for i in (0..n).filter(|&i| active[i]) {
+            for j in (i + 1..n).filter(|&j| active[j]) {
+                if closest.is_none_or(|(_, _, d)| distance[i][j] < d) {
+                    closest = Some((i, j, distance[i][j]));
+                }
+            }
+        }
+        let Some((a, b, d)) = closest else { break };
+        let too_many = max_speakers.is_some_and(|max| clusters > max.max(1));
+        if d > threshold && !too_many {
+            break;
+        }
+
+        // Fusion de b dans a (mise à jour de Lance-Williams pour la liaison moyenne)
+        for k in (0..n).filter(|&k| active[k] && k != a && k != b) {
+            let merged = (size[a] as f32 * distance[a][k] + size[b] as f32 * distance[b][k])
+                / (size[a] + size[b]) as f32;
+            distance[a][k] = merged;
+            distance[k][a] = merged;
+        }
+        size[a] += size[b];
+        active[b] = false;
+        for o in owner.iter_mut().filter(|o| **o == b) {
+            *o = a;
+        }
+        clusters -= 1;
+    }
+    owner
+}
+
+/// Attribue un intervenant à chaque segment. Renvoie le nombre d'intervenants.
+pub fn diarize(
+    embedder: &mut dyn SpeakerEmbedder,
+    audio: &[f32],
+    sample_rate: u32,
+    segments: &mut [Segment],
+    config: &DiarizationConfig,
+) -> Result<usize, String> {
+    let to_sample = |secs: f32| ((secs.max(0.0) * sample_rate as f32) as usize).min(audio.len());
+
+    let mut embedded = Vec::new();
+    let mut embeddings = Vec::new();
+    for (index, segment) in segments.iter().enumerate() {
+        let length = segment.end - segment.start;
+        if length < config.min_segment_secs {
+            continue;
+        }
+        let margin = (length - config.max_embed_secs).max(0.0) / 2.0;
+        let range = to_sample(segment.start + margin)..to_sample(segment.end - margin);
+        if range.is_empty() {
+            continue;
+        }
+        embeddings.push(embedder.embed(&audio[range], sample_rate)?);
+        embedded.push(index);
+    }
+
+    if embedded.is_empty() {
+        return Ok(0);
+    }
+
+    let labels = cluster(&embeddings, config.threshold, config.max_speakers);
+    for (&index, &label) in embedded.iter().zip(&labels) {
+        segments[index].speaker = Some(speaker_label(label));
+    }
+
+    // Les segments courts prennent l'intervenant du précédent (ou du suivant)
+    let mut last = None;
+    for segment in segments.iter_mut() {
+        match &segment.speaker {
+            Some(speaker) => last = Some(speaker.clone()),
+            None => segment.speaker = last.clone(),
+        }
+    }
+    let first = segments.iter().find_map(|s| s.speaker.clone());
+    for segment in segments.iter_mut().filter(|s| s.speaker.is_none()) {
+        segment.speaker = first.clone();
+    }
+
+    Ok(labels.iter().max().map_or(0, |max| max + 1))
+}
+
+/// Diarisation d'une transcription si l'option est active. Les échecs
+/// (modèle absent…) sont journalisés et laissent la transcription sans intervenants.
+pub fn diarize_if_enabled(
+    result: &mut TranscriptionResult,
+    audio: &[f32],
+    sample_rate: u32,
+    settings: &AppSettings,
+) {
+    if !settings.diarization_enabled || result.segments.is_empty() {
+        return;
+    }
+
+    let config = DiarizationConfig {
+        max_speakers: settings.diarization_max_speakers.map(|max| max as usize),
+        ..Default::default()
+    };
+    let outcome = OnnxEmbedder::new(&embedding_model_path()).and_then(|mut embedder| {
+        diarize(&mut embedder, audio, sample_rate, &mut result.segments, &config)
+    });
+
+    match outcome {
+        Ok(speakers) => log::info!("Diarization found {} speaker(s)", speakers),
+        Err(e) => log::warn!("Diarization skipped: {}", e),
+    }
+}
+
+/// Renomme un intervenant dans tous les segments. Renvoie le nombre de segments modifiés.
+pub fn rename_speaker(result: &mut TranscriptionResult, from: &str, to: &str) -> usize {
+    let to = to.trim();
+    let mut changed = 0;
+    for segment in result.segments.iter_mut() {
+        if segment.speaker.as_deref() == Some(from) {
+            segment.speaker = Some(to.to_string());
+            changed += 1;
+        }
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(start: f32, end: f32) -> Segment {
+        Segment {
+            start,
+            end,
+            text: "texte".to_string(),
+            words: Vec::new(),
+            confidence: 0.9,
+            speaker: None,
+        }
+    }
+
+    /// Faux modèle : l'empreinte est le signe de l'audio
+    struct SignEmbedder;
+
+    impl SpeakerEmbedder for SignEmbedder {
+        fn embed(&mut self, audio: &[f32], _sample_rate: u32) -> Result<Vec<f32>, String> {
+            let mean = audio.iter().sum::<f32>() / audio.len() as f32;
+            Ok(if mean > 0.0 { vec![1.0, 0.1] } else { vec![0.1, 1.0] })
+        }
+    }
+
+    #[test]
+    fn test_cluster_groups_close_embeddings() {
+        let embeddings = vec![
+            vec![1.0, 0.0, 0.1],
+            vec![0.0, 1.0, 0.0],
+            vec![0.9, 0.1, 0.0],
+            vec![0.1, 0.9, 0.1],
+        ];
+        assert_eq!(cluster(&embeddings, 0.5, None), vec![0, 1, 0, 1]);
+        assert_eq!(cluster(&embeddings, 0.5, Some(1)), vec![0, 0, 0, 0]);
+        assert_eq!(cluster(&embeddings, 0.0, None).len(), 4);
+        assert!(cluster(&[], 0.5, None).is_empty());
+
+        // Un long enregistrement reste rapide : des milliers de segments, deux voix
+        let many: Vec<Vec<f32>> = (0..5000)
+            .map(|i| if i % 3 == 0 { vec![1.0, 0.05 * (i % 5) as f32] } else { vec![0.05 * (i % 7) as f32, 1.0] })
+            .collect();
+        let labels = cluster(&many, 0.5, None);
+        assert_eq!(labels.iter().max(), Some(&1));
+        assert_eq!((labels[0], labels[1], labels[3]), (0, 1, 0));
+    }
+
+    #[test]
+    fn test_diarize_labels_segments() {
+        let sample_rate = 100;
+        let mut audio = vec![0.5f32; 200];
+        audio.extend(vec![-0.5f32; 200]);
+        audio.extend(vec![0.5f32; 100]);
+
+        let mut segments = vec![segment(0.0, 2.0), segment(2.0, 4.0), segment(4.0, 4.2), segment(4.2, 5.0)];
+        let speakers = diarize(
+            &mut SignEmbedder,
+            &audio,
+            sample_rate,
+            &mut segments,
+            &DiarizationConfig::default(),
+        )
+        .unwrap();
+
+        assert_eq!(speakers, 2);
+        let labels: Vec<_> = segments.iter().map(|s| s.speaker.clone().unwrap()).collect();
+        // Le segment de 0,2 s prend l'intervenant précédent
+        assert_eq!(labels, vec!["Intervenant 1", "Intervenant 2", "Intervenant 2", "Intervenant 1"]);
+    }
+
+    #[test]
+    fn test_rename_speaker() {
+        let mut result = TranscriptionResult {
+            text: String::new(),
+            confidence: 0.9,
+            duration_seconds: 2.0,
+            processing_time_ms: 0,
+            detected_language: None,
+            timestamp: 0,
+            model_used: None,
+            segments: vec![segment(0.0, 1.0), segment(1.0, 2.0)],
+            translation: None,
+        };
+        result.segments[0].speaker = Some(speaker_label(0));
+        result.segments[1].speaker = Some(speaker_label(1));
+
+        assert_eq!(rename_speaker(&mut result, "Intervenant 2", " Alice "), 1);
+        assert_eq!(result.segments[1].speaker.as_deref(), Some("Alice"));
+        assert_eq!(result.segments[0].speaker.as_deref(), Some("Intervenant 1"));
+    }
+}
@@ -0,0 +1,27 @@
+use flacenc::component::BitRepr;
+use flacenc::error::Verify;
+
+/// Profondeur des échantillons FLAC
+const BITS_PER_SAMPLE: usize = 16;
+
+/// Encode un signal mono f32 en FLAC 16 bits (relu par `AudioDecoder`)
+pub fn encode_flac(samples: &[f32], sample_rate: u32) -> Result<Vec<u8>, String> {
+    let max = ((1 << (BITS_PER_SAMPLE - 1)) - 1) as f32;
+    let pcm: Vec<i32> = samples
+        .iter()
+        .map(|s| (s.clamp(-1.0, 1.0) * max).round() as i32)
+        .collect();
+
+    let config = flacenc::config::Encoder::default()
+        .into_verified()
+        .map_err(|(_, e)| format!("Invalid FLAC encoder config: {:?}", e))?;
+    let source = flacenc::source::MemSource::from_samples(&pcm, 1, BITS_PER_SAMPLE, sample_rate as usize);
+    let stream = flacenc::encode_with_fixed_block_size(&config, source, config.block_size)
+        .map_err(|e| format!("FLAC encoding failed: {:?}", e))?;
+
+    let mut sink = flacenc::bitsink::ByteSink::new();
+    stream
+        .write(&mut sink)
+        .map_err(|e| format!("FLAC encoding failed: {:?}", e))?;
+    Ok(sink.as_slice().to_vec())
+}
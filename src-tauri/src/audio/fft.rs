@@ -0,0 +1,89 @@
+/// FFT radix-2 de Cooley-Tukey, en place (n doit être une puissance de 2)
+pub fn fft(buf: &mut [(f32, f32)]) {
+    let n = buf.len();
+    if n <= 1 {
+        return;
+    }
+
+    // Permutation par inversion des bits
+    let mut j = 0usize;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j ^= bit;
+        if i < j {
+            buf.swap(i, j);
+        }
+    }
+
+    // Passes papillon
+    let mut len = 2;
+    while len <= n {
+        let half = len / 2;
+        let angle = -2.0 * std::f32::consts::PI / len as f32;
+        let wn = (angle.cos(), angle.sin());
+        for i in (0..n).step_by(len) {
+            let mut w = (1.0f32, 0.0f32);
+            for k in 0..half {
+                let u = buf[i + k];
+                let t = buf[i + k + half];
+                let v = (t.0 * w.0 - t.1 * w.1, t.0 * w.1 + t.1 * w.0);
+                buf[i + k] = (u.0 + v.0, u.1 + v.1);
+                buf[i + k + half] = (u.0 - v.0, u.1 - v.1);
+                w = (w.0 * wn.0 - w.1 * wn.1, w.0 * wn.1 + w.1 * wn.0);
+            }
+        }
+        len <<= 1;
+    }
+}
+
+/// Banc de filtres mel triangulaires : `n_mels` lignes de `n_fft / 2 + 1` poids
+pub fn mel_filterbank(
+    n_fft: usize,
+    sample_rate: u32,
+    n_mels: usize,
+    fmin: f32,
+    fmax: f32,
+) -> Vec<f32> {
+    let n_freqs = n_fft / 2 + 1;
+    let mut filterbank = vec![0.0f32; n_mels * n_freqs];
+
+    let hz_to_mel = |hz: f32| 2595.0 * (1.0 + hz / 700.0).log10();
+    let mel_to_hz = |mel: f32| 700.0 * (10.0f32.powf(mel / 2595.0) - 1.0);
+
+    let mel_min = hz_to_mel(fmin);
+    let mel_max = hz_to_mel(fmax);
+
+    let mel_points: Vec<f32> = (0..=n_mels + 1)
+        .map(|i| mel_min + (mel_max - mel_min) * i as f32 / (n_mels + 1) as f32)
+        .collect();
+
+    let hz_points: Vec<f32> = mel_points.iter().map(|&m| mel_to_hz(m)).collect();
+    let bin_points: Vec<usize> = hz_points
+        .iter()
+        .map(|&hz| ((n_fft as f32 + 1.0) * hz / sample_rate as f32).floor() as usize)
+        .collect();
+
+    for m in 0..n_mels {
+        let start = bin_points[m];
+        let center = bin_points[m + 1];
+        let end = bin_points[m + 2];
+
+        for k in start..center {
+            if k < n_freqs && center > start {
+                filterbank[m * n_freqs + k] = (k - start) as f32 / (center - start) as f32;
+            }
+        }
+
+        for k in center..end {
+            if k < n_freqs && end > center {
+                filterbank[m * n_freqs + k] = (end - k) as f32 / (end - center) as f32;
+            }
+        }
+    }
+
+    filterbank
+}
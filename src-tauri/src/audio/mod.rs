@@ -1,7 +1,11 @@
 pub mod capture;
 pub mod decoder;
+pub mod diarization;
+pub mod encoder;
+pub mod fft;
 pub mod resampling;
 pub mod streaming;
+pub mod vad;
 
 pub use capture::*;
 pub use decoder::AudioDecoder;
@@ -1,25 +1,27 @@
-/// Resample l'audio en utilisant rubato (haute qualité sinc interpolation)
-pub fn resample_audio(input: &[f32], from_rate: u32, to_rate: u32) -> Vec<f32> {
-    if from_rate == to_rate || input.is_empty() {
-        return input.to_vec();
-    }
+use rubato::{
+    Resampler, SincFixedIn, SincInterpolationParameters, SincInterpolationType, WindowFunction,
+};
 
-    use rubato::{
-        Resampler, SincFixedIn, SincInterpolationParameters, SincInterpolationType, WindowFunction,
-    };
-
-    let params = SincInterpolationParameters {
+fn sinc_parameters() -> SincInterpolationParameters {
+    SincInterpolationParameters {
         sinc_len: 64,
         f_cutoff: 0.95,
         interpolation: SincInterpolationType::Linear,
         oversampling_factor: 8,
         window: WindowFunction::BlackmanHarris2,
-    };
+    }
+}
+
+/// Resample l'audio en utilisant rubato (haute qualité sinc interpolation)
+pub fn resample_audio(input: &[f32], from_rate: u32, to_rate: u32) -> Vec<f32> {
+    if from_rate == to_rate || input.is_empty() {
+        return input.to_vec();
+    }
 
     match SincFixedIn::<f32>::new(
         to_rate as f64 / from_rate as f64,
         2.0,
-        params,
+        sinc_parameters(),
         input.len(),
         1,
     ) {
@@ -39,3 +41,111 @@ pub fn resample_audio(input: &[f32], from_rate: u32, to_rate: u32) -> Vec<f32> {
         }
     }
 }
+
+/// Rééchantillonnage au fil de l'enregistrement : chaque appel ne traite que
+/// les nouveaux échantillons, et l'état du filtre est conservé d'un morceau à
+/// l'autre pour éviter les artefacts aux jonctions
+pub struct StreamResampler {
+    /// `None` quand les fréquences sont identiques
+    resampler: Option<SincFixedIn<f32>>,
+    ratio: f64,
+    pending: Vec<f32>,
+    /// Retard du filtre encore à retirer du début de la sortie
+    delay: usize,
+    input_len: usize,
+    output_len: usize,
+}
+
+impl StreamResampler {
+    const CHUNK_SIZE: usize = 1024;
+
+    pub fn new(from_rate: u32, to_rate: u32) -> Self {
+        let ratio = to_rate as f64 / from_rate as f64;
+        let resampler = (from_rate != to_rate)
+            .then(|| {
+                SincFixedIn::<f32>::new(ratio, 2.0, sinc_parameters(), Self::CHUNK_SIZE, 1)
+                    .map_err(|e| log::error!("Failed to create streaming resampler: {}", e))
+                    .ok()
+            })
+            .flatten();
+        Self {
+            delay: resampler.as_ref().map_or(0, |r| r.output_delay()),
+            resampler,
+            ratio,
+            pending: Vec::new(),
+            input_len: 0,
+            output_len: 0,
+        }
+    }
+
+    /// Rééchantillonne les morceaux complets ; le reste attend l'appel suivant
+    pub fn push(&mut self, input: &[f32]) -> Vec<f32> {
+        let Some(resampler) = self.resampler.as_mut() else {
+            return input.to_vec();
+        };
+        self.input_len += input.len();
+        self.pending.extend_from_slice(input);
+
+        let mut output = Vec::new();
+        let mut consumed = 0;
+        while self.pending.len() - consumed >= Self::CHUNK_SIZE {
+            let chunk = &self.pending[consumed..consumed + Self::CHUNK_SIZE];
+            match resampler.process(&[chunk], None) {
+                Ok(waves_out) => output.extend(waves_out.into_iter().next().unwrap_or_default()),
+                Err(e) => log::error!("Resample error: {}", e),
+            }
+            consumed += Self::CHUNK_SIZE;
+        }
+        self.pending.drain(..consumed);
+        self.emit(output)
+    }
+
+    /// Fin du flux : rééchantillonne le reste et vide le retard du filtre
+    pub fn flush(&mut self) -> Vec<f32> {
+        let Some(resampler) = self.resampler.as_mut() else {
+            return Vec::new();
+        };
+        let pending = std::mem::take(&mut self.pending);
+        let tail = [pending.as_slice()];
+
+        let mut output = Vec::new();
+        for input in [Some(&tail[..]), None] {
+            match resampler.process_partial(input, None) {
+                Ok(waves_out) => output.extend(waves_out.into_iter().next().unwrap_or_default()),
+                Err(e) => log::error!("Resample error: {}", e),
+            }
+        }
+        let mut output = self.emit(output);
+        let emitted_before = self.output_len - output.len();
+        let expected = (self.input_len as f64 * self.ratio).round() as usize;
+        output.truncate(expected.saturating_sub(emitted_before));
+        self.output_len = emitted_before + output.len();
+        output
+    }
+
+    /// Retire le retard du filtre et compte les échantillons rendus
+    fn emit(&mut self, mut output: Vec<f32>) -> Vec<f32> {
+        let skip = self.delay.min(output.len());
+        output.drain(..skip);
+        self.delay -= skip;
+        self.output_len += output.len();
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_resampler_keeps_duration() {
+        let audio: Vec<f32> = (0..48000).map(|i| (i as f32 * 0.01).sin()).collect();
+        let mut resampler = StreamResampler::new(48000, 16000);
+        let mut streamed = Vec::new();
+        for chunk in audio.chunks(4410) {
+            streamed.extend(resampler.push(chunk));
+        }
+        streamed.extend(resampler.flush());
+        assert_eq!(streamed.len(), 16000, "{} samples", streamed.len());
+    }
+}
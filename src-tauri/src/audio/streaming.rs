@@ -1,12 +1,16 @@
+use crate::audio::resampling::StreamResampler;
+use crate::engines::{SpeechEngine, SpeechStream};
+use crate::types::{TranscriptionResult, WordTiming};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
-/// Configuration for streaming transcription
+/// Réglages de la transcription en streaming
 pub struct StreamingConfig {
-    /// Chunk duration in seconds (default: 2.5s)
+    /// Durée d'un morceau en secondes (2,5 s par défaut)
     pub chunk_duration_secs: f32,
-    /// Overlap between chunks in seconds (helps with word boundaries)
+    /// Chevauchement entre morceaux en secondes (évite de couper les mots)
     pub overlap_secs: f32,
-    /// Sample rate of the audio
+    /// Fréquence d'échantillonnage de l'audio
     pub sample_rate: u32,
 }
 
@@ -20,15 +24,15 @@ impl Default for StreamingConfig {
     }
 }
 
-/// A streaming buffer that accumulates audio and provides chunks for transcription
+/// Buffer de streaming : accumule l'audio et fournit les morceaux à transcrire
 pub struct StreamingBuffer {
-    /// The accumulated audio samples
+    /// Échantillons accumulés
     buffer: Arc<Mutex<Vec<f32>>>,
-    /// Configuration
+    /// Réglages
     config: StreamingConfig,
-    /// Number of samples already processed (for overlap handling)
+    /// Nombre d'échantillons déjà traités (pour le chevauchement)
     processed_samples: usize,
-    /// Total accumulated text from all chunks
+    /// Texte accumulé de tous les morceaux
     accumulated_text: Arc<Mutex<String>>,
 }
 
@@ -42,36 +46,36 @@ impl StreamingBuffer {
         }
     }
 
-    /// Returns a clone of the buffer Arc for use in audio capture callbacks
+    /// Copie de l'Arc du buffer pour les callbacks de capture audio
     pub fn buffer_handle(&self) -> Arc<Mutex<Vec<f32>>> {
         self.buffer.clone()
     }
 
-    /// Returns the number of samples needed for one chunk
+    /// Nombre d'échantillons d'un morceau
     fn chunk_samples(&self) -> usize {
         (self.config.chunk_duration_secs * self.config.sample_rate as f32) as usize
     }
 
-    /// Returns the number of samples for overlap
+    /// Nombre d'échantillons du chevauchement
     fn overlap_samples(&self) -> usize {
         (self.config.overlap_secs * self.config.sample_rate as f32) as usize
     }
 
-    /// Check if there's enough audio for a new chunk
+    /// Assez d'audio pour un nouveau morceau
     pub fn has_chunk_available(&self) -> bool {
         let buffer = self.buffer.lock().unwrap();
         let available = buffer.len().saturating_sub(self.processed_samples);
         available >= self.chunk_samples()
     }
 
-    /// Extract the next chunk of audio for transcription
-    /// Returns None if not enough audio is available
+    /// Extrait le prochain morceau à transcrire
+    /// `None` s'il n'y a pas assez d'audio
     pub fn extract_chunk(&mut self) -> Option<Vec<f32>> {
         let buffer = self.buffer.lock().unwrap();
         let chunk_size = self.chunk_samples();
         let overlap = self.overlap_samples();
 
-        // Start position includes overlap from previous chunk (except for first chunk)
+        // Le début reprend le chevauchement du morceau précédent (sauf pour le premier)
         let start = if self.processed_samples > overlap {
             self.processed_samples - overlap
         } else {
@@ -86,13 +90,13 @@ impl StreamingBuffer {
 
         let chunk = buffer[start..end].to_vec();
 
-        // Update processed position (move forward by chunk minus overlap)
+        // Avance d'un morceau moins le chevauchement
         self.processed_samples = end - overlap;
 
         Some(chunk)
     }
 
-    /// Get all remaining audio (used when stopping recording)
+    /// Tout l'audio restant (à l'arrêt de l'enregistrement)
     pub fn get_remaining(&self) -> Vec<f32> {
         let buffer = self.buffer.lock().unwrap();
         let overlap = self.overlap_samples();
@@ -110,19 +114,19 @@ impl StreamingBuffer {
         }
     }
 
-    /// Get all audio accumulated so far
+    /// Tout l'audio accumulé
     pub fn get_all_audio(&self) -> Vec<f32> {
         self.buffer.lock().unwrap().clone()
     }
 
-    /// Clear the buffer and reset state
+    /// Vide le buffer et remet l'état à zéro
     pub fn clear(&mut self) {
         self.buffer.lock().unwrap().clear();
         self.processed_samples = 0;
         self.accumulated_text.lock().unwrap().clear();
     }
 
-    /// Append text from a chunk transcription
+    /// Ajoute le texte d'un morceau transcrit
     pub fn append_text(&self, text: &str) {
         let mut accumulated = self.accumulated_text.lock().unwrap();
         if !accumulated.is_empty() && !text.is_empty() {
@@ -131,30 +135,388 @@ impl StreamingBuffer {
         accumulated.push_str(text.trim());
     }
 
-    /// Get the accumulated text so far
+    /// Texte accumulé jusqu'ici
     pub fn get_accumulated_text(&self) -> String {
         self.accumulated_text.lock().unwrap().clone()
     }
 
-    /// Get the current buffer length in samples
+    /// Longueur actuelle du buffer en échantillons
     pub fn buffer_len(&self) -> usize {
         self.buffer.lock().unwrap().len()
     }
 
-    /// Get the current duration in seconds
+    /// Durée actuelle en secondes
     pub fn duration_secs(&self) -> f32 {
         self.buffer_len() as f32 / self.config.sample_rate as f32
     }
+
+    /// Ajoute des échantillons capturés (déjà à la fréquence configurée)
+    pub fn push(&self, samples: &[f32]) {
+        self.buffer.lock().unwrap().extend_from_slice(samples);
+    }
+
+    /// Audio pas encore couvert par le texte validé
+    pub fn pending_audio(&self) -> Vec<f32> {
+        let buffer = self.buffer.lock().unwrap();
+        buffer[self.processed_samples.min(buffer.len())..].to_vec()
+    }
+
+    /// Durée de l'audio en attente en secondes
+    pub fn pending_secs(&self) -> f32 {
+        self.buffer_len().saturating_sub(self.processed_samples) as f32
+            / self.config.sample_rate as f32
+    }
+
+    /// Marque `samples` échantillons de plus comme transcrits
+    pub fn advance(&mut self, samples: usize) {
+        self.processed_samples = (self.processed_samples + samples).min(self.buffer_len());
+    }
+}
+
+/// Accord local : un mot est validé quand deux hypothèses successives sur
+/// le même audio s'accordent dessus
+#[derive(Debug, Default)]
+pub struct LocalAgreement {
+    previous: Vec<WordTiming>,
+}
+
+impl LocalAgreement {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ajoute une hypothèse ; renvoie les mots désormais stables
+    pub fn insert(&mut self, hypothesis: Vec<WordTiming>) -> Vec<WordTiming> {
+        let agreed = self
+            .previous
+            .iter()
+            .zip(&hypothesis)
+            .take_while(|(a, b)| normalize_word(&a.text) == normalize_word(&b.text))
+            .count();
+
+        let mut hypothesis = hypothesis;
+        self.previous = hypothesis.split_off(agreed);
+        hypothesis
+    }
+
+    /// Oublie la fin non confirmée
+    pub fn reset(&mut self) {
+        self.previous.clear();
+    }
+
+    /// Mots vus une fois, pas encore confirmés
+    pub fn tentative(&self) -> &[WordTiming] {
+        &self.previous
+    }
+}
+
+/// En minuscules, sans ponctuation autour
+fn normalize_word(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase()
+}
+
+/// Lettres et chiffres seuls, en minuscules : la même parole découpée en mots
+/// différents ("porte-monnaie" / "porte monnaie") donne les mêmes caractères
+fn normalized_chars(text: &str) -> Vec<char> {
+    text.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
 }
 
-/// Result of a streaming chunk transcription
+/// Nombre de mots en tête de `words` déjà couverts par `committed`, alignés
+/// sur les caractères normalisés : une hypothèse redécoupée ne répète ni ne
+/// perd de mots. Si elle a révisé un mot validé, on retombe sur le nombre de
+/// mots validés.
+fn committed_prefix_len(words: &[WordTiming], committed: &str) -> usize {
+    let committed_chars = normalized_chars(committed);
+    if committed_chars.is_empty() {
+        return 0;
+    }
+
+    let mut matched = 0;
+    for (index, word) in words.iter().enumerate() {
+        let chars = normalized_chars(&word.text);
+        if !committed_chars[matched..].starts_with(&chars) {
+            break;
+        }
+        matched += chars.len();
+        if matched == committed_chars.len() {
+            return index + 1;
+        }
+    }
+    committed.split_whitespace().count().min(words.len())
+}
+
+fn join_words(words: &[WordTiming]) -> String {
+    words
+        .iter()
+        .map(|w| w.text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Mots d'une hypothèse avec leurs horodatages quand le moteur les fournit.
+/// Le texte du segment (corrigé par le dictionnaire) est préféré s'il
+/// correspond mot pour mot.
+fn hypothesis_words(result: &TranscriptionResult) -> Vec<WordTiming> {
+    let mut words = Vec::new();
+    for segment in &result.segments {
+        let tokens: Vec<&str> = segment.text.split_whitespace().collect();
+        if segment.words.len() == tokens.len() {
+            words.extend(segment.words.iter().zip(tokens).map(|(word, token)| WordTiming {
+                text: token.to_string(),
+                ..word.clone()
+            }));
+        } else {
+            words.extend(segment.words.iter().filter(|w| !w.text.trim().is_empty()).map(|w| {
+                WordTiming {
+                    text: w.text.trim().to_string(),
+                    ..w.clone()
+                }
+            }));
+        }
+    }
+
+    if words.is_empty() {
+        // Sans horodatage : mots seuls, la fenêtre n'est coupée qu'une fois tout validé
+        words = result
+            .text
+            .split_whitespace()
+            .map(|token| WordTiming {
+                start: 0.0,
+                end: 0.0,
+                text: token.to_string(),
+                confidence: None,
+            })
+            .collect();
+    }
+    words
+}
+
+/// État d'une transcription en streaming après une étape
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StreamingProgress {
+    /// Texte validé pendant cette étape
+    pub newly_committed: String,
+    /// Tout le texte validé jusqu'ici
+    pub committed: String,
+    /// Hypothèse actuelle pour la suite, susceptible de changer
+    pub tentative: String,
+}
+
+impl StreamingProgress {
+    /// Texte validé suivi de la fin provisoire, pour l'affichage
+    pub fn display_text(&self) -> String {
+        [self.committed.as_str(), self.tentative.as_str()]
+            .iter()
+            .filter(|t| !t.is_empty())
+            .copied()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Transcription en streaming partagée par l'interface, le push-to-talk, la
+/// CLI et le serveur.
+///
+/// Les moteurs à reconnaissance incrémentale native (Vosk) ne reçoivent que
+/// le nouvel audio. Les autres retranscrivent la fenêtre non validée et
+/// valident les mots par [`LocalAgreement`] ; la fenêtre est coupée après le
+/// dernier mot validé. [`Self::finish`] termine l'enregistrement et valide le
+/// reste : l'appelant n'a jamais à comparer le texte diffusé avec une autre
+/// transcription.
+pub struct StreamingTranscriber {
+    buffer: StreamingBuffer,
+    agreement: LocalAgreement,
+    native: Option<Box<dyn SpeechStream>>,
+    /// Créé à la fréquence de la capture au premier audio
+    resampler: Option<StreamResampler>,
+    /// Échantillons de capture déjà reçus (fréquence de la capture)
+    received_samples: usize,
+    /// Longueur du buffer à la dernière hypothèse
+    last_step_samples: usize,
+    /// Texte validé dans la fenêtre courante dont la fin n'est pas horodatée
+    window_committed: String,
+}
+
+impl StreamingTranscriber {
+    /// Audio minimal avant la première hypothèse
+    const MIN_WINDOW_SECS: f32 = 1.0;
+    /// Nouvel audio minimal entre deux hypothèses
+    const MIN_STEP_SECS: f32 = 0.5;
+    /// Au-delà, toute l'hypothèse est validée pour garder une fenêtre courte
+    const MAX_WINDOW_SECS: f32 = 15.0;
+
+    pub fn new(engine: Option<&dyn SpeechEngine>) -> Self {
+        let config = StreamingConfig::default();
+        let native = engine
+            .and_then(|engine| engine.as_streaming())
+            .and_then(|engine| match engine.start_stream(config.sample_rate) {
+                Ok(stream) => Some(stream),
+                Err(e) => {
+                    log::warn!("Native streaming unavailable ({}), using local agreement", e);
+                    None
+                }
+            });
+
+        Self {
+            buffer: StreamingBuffer::new(config),
+            agreement: LocalAgreement::new(),
+            native,
+            resampler: None,
+            received_samples: 0,
+            last_step_samples: 0,
+            window_committed: String::new(),
+        }
+    }
+
+    /// Fréquence à laquelle l'appelant récupère l'audio et appelle [`Self::step`]
+    pub fn poll_interval(&self) -> Duration {
+        if self.native.is_some() {
+            Duration::from_millis(300)
+        } else {
+            Duration::from_millis(500)
+        }
+    }
+
+    /// Échantillons de capture reçus : le prochain snapshot commence là
+    pub fn received_samples(&self) -> usize {
+        self.received_samples
+    }
+
+    /// Ajoute les échantillons enregistrés après [`Self::received_samples`]
+    pub fn push_audio(&mut self, audio: &[f32], sample_rate: u32) {
+        if audio.is_empty() {
+            return;
+        }
+        self.received_samples += audio.len();
+
+        let target_rate = self.buffer.config.sample_rate;
+        let resampler = self
+            .resampler
+            .get_or_insert_with(|| StreamResampler::new(sample_rate, target_rate));
+        let resampled = resampler.push(audio);
+        self.buffer.push(&resampled);
+    }
+
+    /// Durée de l'audio reçu
+    pub fn duration_secs(&self) -> f32 {
+        self.buffer.duration_secs()
+    }
+
+    pub fn committed_text(&self) -> String {
+        self.buffer.get_accumulated_text()
+    }
+
+    /// Reconnaît l'audio reçu depuis l'étape précédente. `None` s'il n'y avait
+    /// pas assez de nouvel audio.
+    pub fn step(&mut self, engine: &dyn SpeechEngine) -> Result<Option<StreamingProgress>, String> {
+        if let Some(stream) = self.native.as_mut() {
+            let pending = self.buffer.pending_audio();
+            if pending.is_empty() {
+                return Ok(None);
+            }
+            self.buffer.advance(pending.len());
+            let update = stream.accept(&pending)?;
+            let newly_committed = update.final_text.unwrap_or_default();
+            self.buffer.append_text(&newly_committed);
+            return Ok(Some(StreamingProgress {
+                newly_committed,
+                committed: self.buffer.get_accumulated_text(),
+                tentative: update.partial,
+            }));
+        }
+
+        let sample_rate = self.buffer.config.sample_rate;
+        let new_secs =
+            self.buffer.buffer_len().saturating_sub(self.last_step_samples) as f32 / sample_rate as f32;
+        if self.buffer.pending_secs() < Self::MIN_WINDOW_SECS || new_secs < Self::MIN_STEP_SECS {
+            return Ok(None);
+        }
+        self.last_step_samples = self.buffer.buffer_len();
+
+        let window = self.buffer.pending_audio();
+        let result = engine.transcribe(&window, sample_rate)?;
+        let mut words = hypothesis_words(&result);
+        // Mots de cette fenêtre déjà validés (moteur sans horodatage)
+        words.drain(..committed_prefix_len(&words, &self.window_committed));
+
+        let committed = if self.buffer.pending_secs() > Self::MAX_WINDOW_SECS {
+            self.agreement.reset();
+            self.window_committed.clear();
+            self.buffer.advance(window.len());
+            words
+        } else {
+            let committed = self.agreement.insert(words);
+            match committed.last().filter(|w| w.end > 0.0) {
+                Some(last) => {
+                    self.buffer.advance((last.end * sample_rate as f32) as usize);
+                    self.window_committed.clear();
+                }
+                None => {
+                    if !self.window_committed.is_empty() {
+                        self.window_committed.push(' ');
+                    }
+                    self.window_committed.push_str(&join_words(&committed));
+                }
+            }
+            committed
+        };
+
+        let newly_committed = join_words(&committed);
+        self.buffer.append_text(&newly_committed);
+
+        Ok(Some(StreamingProgress {
+            newly_committed,
+            committed: self.buffer.get_accumulated_text(),
+            tentative: join_words(self.agreement.tentative()),
+        }))
+    }
+
+    /// Fin de l'enregistrement : reconnaît l'audio restant et valide tout.
+    /// `newly_committed` contient les mots jamais validés auparavant.
+    pub fn finish(&mut self, engine: &dyn SpeechEngine) -> Result<StreamingProgress, String> {
+        if let Some(resampler) = self.resampler.as_mut() {
+            let tail = resampler.flush();
+            self.buffer.push(&tail);
+        }
+        let pending = self.buffer.pending_audio();
+        self.buffer.advance(pending.len());
+
+        let newly_committed = if let Some(mut stream) = self.native.take() {
+            let mut texts = Vec::new();
+            if !pending.is_empty() {
+                texts.extend(stream.accept(&pending)?.final_text);
+            }
+            texts.push(stream.finish()?);
+            texts.retain(|text| !text.is_empty());
+            texts.join(" ")
+        } else if pending.is_empty() {
+            String::new()
+        } else {
+            let result = engine.transcribe(&pending, self.buffer.config.sample_rate)?;
+            let mut words = hypothesis_words(&result);
+            words.drain(..committed_prefix_len(&words, &self.window_committed));
+            join_words(&words)
+        };
+        self.agreement.reset();
+        self.window_committed.clear();
+
+        self.buffer.append_text(&newly_committed);
+        Ok(StreamingProgress {
+            newly_committed,
+            committed: self.buffer.get_accumulated_text(),
+            tentative: String::new(),
+        })
+    }
+}
+
+/// Résultat de la transcription d'un morceau en streaming
 #[derive(Debug, Clone)]
 pub struct StreamingChunk {
-    /// The transcribed text for this chunk
+    /// Texte transcrit de ce morceau
     pub text: String,
-    /// Whether this is the final chunk (recording stopped)
+    /// Dernier morceau (enregistrement arrêté)
     pub is_final: bool,
-    /// Duration of audio processed so far
+    /// Durée de l'audio traité jusqu'ici
     pub duration_seconds: f32,
 }
 
@@ -173,24 +535,171 @@ mod tests {
         let mut buffer = StreamingBuffer::new(config);
         let handle = buffer.buffer_handle();
 
-        // Add 2 seconds of audio (32000 samples)
+        // 2 secondes d'audio (32000 échantillons)
         {
             let mut buf = handle.lock().unwrap();
             buf.extend(vec![0.0f32; 32000]);
         }
 
-        // Should be able to extract first chunk
+        // Le premier morceau est disponible
         assert!(buffer.has_chunk_available());
         let chunk1 = buffer.extract_chunk();
         assert!(chunk1.is_some());
-        assert_eq!(chunk1.unwrap().len(), 16000); // 1 second
+        assert_eq!(chunk1.unwrap().len(), 16000); // 1 seconde
 
-        // Should be able to extract second chunk
+        // Le second aussi
         assert!(buffer.has_chunk_available());
         let chunk2 = buffer.extract_chunk();
         assert!(chunk2.is_some());
     }
 
+    fn words(text: &str) -> Vec<WordTiming> {
+        text.split_whitespace()
+            .map(|t| WordTiming { start: 0.0, end: 0.0, text: t.to_string(), confidence: None })
+            .collect()
+    }
+
+    #[test]
+    fn test_local_agreement_commits_confirmed_prefix() {
+        let mut agreement = LocalAgreement::new();
+
+        assert!(agreement.insert(words("le chat")).is_empty());
+        let committed = agreement.insert(words("Le chat, est"));
+        assert_eq!(join_words(&committed), "Le chat,");
+        assert_eq!(join_words(agreement.tentative()), "est");
+
+        // Un mot révisé n'est jamais validé
+        assert!(agreement.insert(words("et noir")).is_empty());
+        assert_eq!(join_words(&agreement.insert(words("et noir"))), "et noir");
+    }
+
+    /// (texte, début, fin)
+    type ScriptedWord = (&'static str, f32, f32);
+
+    /// Faux moteur : rejoue des hypothèses écrites et note la longueur des fenêtres
+    struct ScriptedEngine {
+        hypotheses: Mutex<Vec<Vec<ScriptedWord>>>,
+        windows: Mutex<Vec<usize>>,
+    }
+
+    impl SpeechEngine for ScriptedEngine {
+        fn transcribe(&self, audio: &[f32], _sample_rate: u32) -> Result<TranscriptionResult, String> {
+            self.windows.lock().unwrap().push(audio.len());
+            let words: Vec<WordTiming> = self
+                .hypotheses
+                .lock()
+                .unwrap()
+                .remove(0)
+                .into_iter()
+                .map(|(text, start, end)| WordTiming { start, end, text: text.to_string(), confidence: None })
+                .collect();
+            let text = join_words(&words);
+            Ok(TranscriptionResult {
+                text: text.clone(),
+                confidence: 0.9,
+                duration_seconds: 0.0,
+                processing_time_ms: 0,
+                detected_language: None,
+                timestamp: 0,
+                model_used: None,
+                segments: vec![crate::types::Segment {
+                    start: 0.0,
+                    end: 0.0,
+                    text,
+                    words,
+                    confidence: 0.9,
+                    speaker: None,
+                }],
+                translation: None,
+            })
+        }
+
+        fn name(&self) -> &str {
+            "Scripted"
+        }
+
+        fn model_display_name(&self) -> String {
+            "Scripted".to_string()
+        }
+
+        fn set_vocabulary(&mut self, _vocabulary: crate::engines::vocabulary::Vocabulary) {}
+    }
+
+    #[test]
+    fn test_transcriber_commits_agreed_words_and_cuts_window() {
+        let engine = ScriptedEngine {
+            hypotheses: Mutex::new(vec![
+                vec![("bonjour", 0.0, 0.5), ("à", 0.5, 0.9)],
+                vec![("bonjour", 0.0, 0.5), ("à", 0.5, 0.9), ("tous", 0.9, 1.5)],
+                vec![("tous", 0.0, 0.6), ("les", 0.7, 1.0)],
+                vec![("les", 0.1, 0.4), ("amis", 0.5, 0.9)],
+            ]),
+            windows: Mutex::new(Vec::new()),
+        };
+        let mut transcriber = StreamingTranscriber::new(None);
+
+        transcriber.push_audio(&[0.0f32; 19200], 16000);
+        let progress = transcriber.step(&engine).unwrap().unwrap();
+        assert_eq!(progress.newly_committed, "");
+        assert_eq!(progress.display_text(), "bonjour à");
+
+        // Pas assez de nouvel audio pour une autre hypothèse
+        assert!(transcriber.step(&engine).unwrap().is_none());
+
+        transcriber.push_audio(&[0.0f32; 9600], 16000);
+        let progress = transcriber.step(&engine).unwrap().unwrap();
+        assert_eq!(progress.newly_committed, "bonjour à");
+        assert_eq!(progress.tentative, "tous");
+
+        transcriber.push_audio(&[0.0f32; 9600], 16000);
+        assert_eq!(transcriber.received_samples(), 38400);
+        let progress = transcriber.step(&engine).unwrap().unwrap();
+        assert_eq!(progress.newly_committed, "tous");
+        assert_eq!(progress.committed, "bonjour à tous");
+
+        // La fin de l'enregistrement valide une seule fois la fin provisoire
+        let progress = transcriber.finish(&engine).unwrap();
+        assert_eq!(progress.newly_committed, "les amis");
+        assert_eq!(progress.committed, "bonjour à tous les amis");
+
+        // La troisième fenêtre commence après « à » (0,9 s), la dernière après « tous »
+        assert_eq!(*engine.windows.lock().unwrap(), vec![19200, 28800, 24000, 14400]);
+    }
+
+    #[test]
+    fn test_transcriber_aligns_resegmented_hypotheses() {
+        // Moteur sans horodatage : la fenêtre n'est jamais coupée, les mots
+        // validés sont retrouvés dans chaque hypothèse
+        let engine = ScriptedEngine {
+            hypotheses: Mutex::new(vec![
+                vec![("porte", 0.0, 0.0), ("monnaie", 0.0, 0.0)],
+                vec![("porte", 0.0, 0.0), ("monnaie", 0.0, 0.0), ("rouge", 0.0, 0.0)],
+                vec![("porte-monnaie", 0.0, 0.0), ("rouge", 0.0, 0.0), ("vif", 0.0, 0.0)],
+                vec![("Porte-monnaie", 0.0, 0.0), ("rouge", 0.0, 0.0), ("vif.", 0.0, 0.0)],
+            ]),
+            windows: Mutex::new(Vec::new()),
+        };
+        let mut transcriber = StreamingTranscriber::new(None);
+
+        transcriber.push_audio(&[0.0f32; 16000], 16000);
+        assert_eq!(transcriber.step(&engine).unwrap().unwrap().newly_committed, "");
+        transcriber.push_audio(&[0.0f32; 8000], 16000);
+        assert_eq!(transcriber.step(&engine).unwrap().unwrap().newly_committed, "porte monnaie");
+        transcriber.push_audio(&[0.0f32; 8000], 16000);
+        let progress = transcriber.step(&engine).unwrap().unwrap();
+        assert_eq!(progress.newly_committed, "rouge");
+        assert_eq!(progress.tentative, "vif");
+
+        let progress = transcriber.finish(&engine).unwrap();
+        assert_eq!(progress.newly_committed, "vif.");
+        assert_eq!(progress.committed, "porte monnaie rouge vif.");
+
+        // Un mot coupé en deux est sauté en entier ; un mot révisé retombe sur le compte
+        let split = words("aujourd' hui il pleut");
+        assert_eq!(committed_prefix_len(&split, "Aujourd'hui"), 2);
+        assert_eq!(committed_prefix_len(&words("le chien dort"), "le chat"), 2);
+    }
+
     #[test]
     fn test_accumulated_text() {
         let config = StreamingConfig::default();
@@ -0,0 +1,583 @@
+use crate::audio::fft::fft;
+use crate::types::{AppSettings, VadEngine};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use tract_onnx::prelude::*;
+
+type TractModel = SimplePlan<TypedFact, Box<dyn TypedOp>, Graph<TypedFact, Box<dyn TypedOp>>>;
+
+/// Trames plus faibles jamais considérées comme de la parole (dBFS)
+const ABSOLUTE_FLOOR_DB: f32 = -50.0;
+
+/// Écart minimal entre bruit de fond et trames fortes pour le seuil adaptatif (dB)
+const MIN_DYNAMIC_RANGE_DB: f32 = 10.0;
+
+/// Position du seuil entre bruit de fond et niveau de parole
+const THRESHOLD_POSITION: f32 = 0.35;
+
+/// Au-delà de cet écart avec le bruit de fond, parole quel que soit le spectre (dB)
+const LOUD_MARGIN_DB: f32 = 20.0;
+
+/// Platitude spectrale au-delà de laquelle une trame ressemble à du bruit
+const MAX_SPEECH_FLATNESS: f32 = 0.45;
+
+/// Silero VAD v5 : fenêtres de 512 échantillons à 16 kHz, précédées de 64 échantillons de contexte
+const SILERO_SAMPLE_RATE: u32 = 16000;
+const SILERO_WINDOW: usize = 512;
+const SILERO_CONTEXT: usize = 64;
+const SILERO_STATE: usize = 128;
+const SILERO_THRESHOLD: f32 = 0.5;
+
+/// Emplacement du modèle Silero VAD optionnel
+pub fn silero_model_path() -> PathBuf {
+    crate::storage::get_app_data_dir()
+        .join("models")
+        .join("silero_vad.onnx")
+}
+
+/// Réglages de la détection d'activité vocale
+#[derive(Debug, Clone)]
+pub struct VadConfig {
+    /// Segments de parole plus courts ignorés
+    pub min_speech_ms: u32,
+    /// Silences plus courts ne coupent pas la parole
+    pub min_silence_ms: u32,
+    /// Marge conservée autour de chaque segment de parole
+    pub padding_ms: u32,
+}
+
+impl Default for VadConfig {
+    fn default() -> Self {
+        Self {
+            min_speech_ms: 200,
+            min_silence_ms: 400,
+            padding_ms: 200,
+        }
+    }
+}
+
+/// Classification parole / silence par trame
+pub trait SpeechDetector {
+    /// Nombre d'échantillons par trame d'analyse
+    fn frame_len(&self, sample_rate: u32) -> usize;
+    /// Présence de parole dans chaque trame de `audio`
+    fn speech_frames(&mut self, audio: &[f32], sample_rate: u32) -> Vec<bool>;
+}
+
+/// Énergie RMS de chaque trame en dBFS
+pub fn frame_energies(audio: &[f32], frame_len: usize) -> Vec<f32> {
+    audio
+        .chunks(frame_len.max(1))
+        .map(|frame| {
+            let power = frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32;
+            10.0 * (power + 1e-10).log10()
+        })
+        .collect()
+}
+
+/// Platitude spectrale (moyenne géométrique / arithmétique du spectre de
+/// puissance) : proche de 0 pour les sons tonaux comme la voix, plus haute pour le bruit
+fn spectral_flatness(frame: &[f32]) -> f32 {
+    let n = frame.len().next_power_of_two();
+    let mut buf: Vec<(f32, f32)> = frame.iter().map(|&s| (s, 0.0)).collect();
+    buf.resize(n, (0.0, 0.0));
+    fft(&mut buf);
+
+    let power: Vec<f32> = buf[1..=n / 2]
+        .iter()
+        .map(|(re, im)| re * re + im * im + 1e-12)
+        .collect();
+    let log_mean = power.iter().map(|p| p.ln()).sum::<f32>() / power.len() as f32;
+    let mean = power.iter().sum::<f32>() / power.len() as f32;
+    log_mean.exp() / mean
+}
+
+/// Détecteur par énergie avec bruit de fond adaptatif, filtré par la platitude
+/// spectrale pour ne pas prendre un bruit continu plus fort pour de la voix
+pub struct EnergyVad {
+    frame_ms: u32,
+}
+
+impl Default for EnergyVad {
+    fn default() -> Self {
+        Self { frame_ms: 30 }
+    }
+}
+
+impl SpeechDetector for EnergyVad {
+    fn frame_len(&self, sample_rate: u32) -> usize {
+        ((sample_rate as usize * self.frame_ms as usize) / 1000).max(1)
+    }
+
+    fn speech_frames(&mut self, audio: &[f32], sample_rate: u32) -> Vec<bool> {
+        let frame_len = self.frame_len(sample_rate);
+        let energies = frame_energies(audio, frame_len);
+        if energies.is_empty() {
+            return Vec::new();
+        }
+
+        let mut sorted = energies.clone();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let percentile = |p: f32| sorted[((sorted.len() - 1) as f32 * p) as usize];
+        let noise_floor = percentile(0.1);
+        let speech_level = percentile(0.95);
+
+        // Sans contraste net (parole continue ou bruit seul), seuil absolu plutôt
+        // que couper le signal en deux
+        let threshold = if speech_level - noise_floor < MIN_DYNAMIC_RANGE_DB {
+            ABSOLUTE_FLOOR_DB
+        } else {
+            (noise_floor + (speech_level - noise_floor) * THRESHOLD_POSITION).max(ABSOLUTE_FLOOR_DB)
+        };
+
+        audio
+            .chunks(frame_len)
+            .zip(energies)
+            .map(|(frame, energy)| {
+                energy > threshold
+                    && (energy > noise_floor + LOUD_MARGIN_DB
+                        || spectral_flatness(frame) < MAX_SPEECH_FLATNESS)
+            })
+            .collect()
+    }
+}
+
+/// Modèle Silero VAD exécuté avec tract
+pub struct SileroVad {
+    model: TractModel,
+}
+
+impl SileroVad {
+    pub fn new(model_path: &Path) -> Result<Self, String> {
+        if !model_path.exists() {
+            return Err(format!("Silero VAD model not found: {:?}", model_path));
+        }
+
+        let model = tract_onnx::onnx()
+            .model_for_path(model_path)
+            .map_err(|e| format!("Failed to load Silero VAD model: {}", e))?
+            .with_input_fact(0, f32::fact([1, SILERO_CONTEXT + SILERO_WINDOW]).into())
+            .map_err(|e| format!("Invalid Silero VAD input: {}", e))?
+            .with_input_fact(1, f32::fact([2, 1, SILERO_STATE]).into())
+            .map_err(|e| format!("Invalid Silero VAD state: {}", e))?
+            .with_input_fact(2, i64::scalar_fact().into())
+            .map_err(|e| format!("Invalid Silero VAD sample rate: {}", e))?
+            .into_optimized()
+            .map_err(|e| format!("Failed to optimize Silero VAD: {}", e))?
+            .into_runnable()
+            .map_err(|e| format!("Failed to make Silero VAD runnable: {}", e))?;
+
+        Ok(Self { model })
+    }
+
+    /// Probabilité de parole d'une fenêtre ; met à jour l'état récurrent
+    fn run_window(&self, input: Vec<f32>, state: &mut Vec<f32>) -> Result<f32, String> {
+        let input: Tensor = tract_ndarray::Array2::from_shape_vec((1, input.len()), input)
+            .map_err(|e| e.to_string())?
+            .into();
+        let state_tensor: Tensor =
+            tract_ndarray::Array3::from_shape_vec((2, 1, SILERO_STATE), state.clone())
+                .map_err(|e| e.to_string())?
+                .into();
+        let sample_rate: Tensor = tensor0(SILERO_SAMPLE_RATE as i64);
+
+        let outputs = self
+            .model
+            .run(tvec![input.into(), state_tensor.into(), sample_rate.into()])
+            .map_err(|e| format!("Silero VAD error: {}", e))?;
+
+        let probability = outputs[0]
+            .to_array_view::<f32>()
+            .map_err(|e| e.to_string())?
+            .iter()
+            .copied()
+            .next()
+            .ok_or("Empty Silero VAD output")?;
+        *state = outputs[1]
+            .to_array_view::<f32>()
+            .map_err(|e| e.to_string())?
+            .iter()
+            .copied()
+            .collect();
+
+        Ok(probability)
+    }
+}
+
+impl SpeechDetector for SileroVad {
+    fn frame_len(&self, _sample_rate: u32) -> usize {
+        SILERO_WINDOW
+    }
+
+    fn speech_frames(&mut self, audio: &[f32], sample_rate: u32) -> Vec<bool> {
+        let frames = audio.len().div_ceil(SILERO_WINDOW);
+        if sample_rate != SILERO_SAMPLE_RATE {
+            log::warn!("Silero VAD expects 16kHz audio, got {}Hz: keeping everything", sample_rate);
+            return vec![true; frames];
+        }
+
+        let mut state = vec![0.0f32; 2 * SILERO_STATE];
+        let mut context = vec![0.0f32; SILERO_CONTEXT];
+        let mut flags = Vec::with_capacity(frames);
+
+        for frame in audio.chunks(SILERO_WINDOW) {
+            let mut input = context.clone();
+            input.extend_from_slice(frame);
+            input.resize(SILERO_CONTEXT + SILERO_WINDOW, 0.0);
+            context.copy_from_slice(&input[SILERO_WINDOW..]);
+
+            match self.run_window(input, &mut state) {
+                Ok(probability) => flags.push(probability >= SILERO_THRESHOLD),
+                Err(e) => {
+                    // En cas d'erreur d'inférence, garder l'audio plutôt que perdre de la parole
+                    log::warn!("{}", e);
+                    flags.push(true);
+                }
+            }
+        }
+        flags
+    }
+}
+
+/// Détecteur d'activité vocale : regroupe les trames en segments de parole
+pub struct Vad {
+    config: VadConfig,
+    detector: Box<dyn SpeechDetector>,
+}
+
+impl Default for Vad {
+    fn default() -> Self {
+        Self::new(VadConfig::default(), Box::new(EnergyVad::default()))
+    }
+}
+
+impl Vad {
+    pub fn new(config: VadConfig, detector: Box<dyn SpeechDetector>) -> Self {
+        Self { config, detector }
+    }
+
+    /// Détecteur choisi dans les réglages ; détecteur par énergie si le modèle
+    /// Silero est absent ou ne se charge pas
+    pub fn for_engine(engine: VadEngine) -> Self {
+        match engine {
+            VadEngine::Energy => Self::default(),
+            VadEngine::Silero => match SileroVad::new(&silero_model_path()) {
+                Ok(silero) => Self::new(VadConfig::default(), Box::new(silero)),
+                Err(e) => {
+                    log::warn!("{}, using energy VAD", e);
+                    Self::default()
+                }
+            },
+        }
+    }
+
+    /// Plages d'échantillons contenant de la parole, lissées et élargies
+    pub fn speech_regions(&mut self, audio: &[f32], sample_rate: u32) -> Vec<Range<usize>> {
+        let flags = self.detector.speech_frames(audio, sample_rate);
+        let frame_len = self.detector.frame_len(sample_rate);
+        let frame_ms = (frame_len as f32 * 1000.0 / sample_rate as f32).max(1.0);
+        let ms_to_frames = |ms: u32| (ms as f32 / frame_ms).round() as usize;
+
+        // Suites de trames de parole
+        let mut runs: Vec<Range<usize>> = Vec::new();
+        let mut start = None;
+        for (i, &speech) in flags.iter().enumerate() {
+            match (speech, start) {
+                (true, None) => start = Some(i),
+                (false, Some(s)) => {
+                    runs.push(s..i);
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some(s) = start {
+            runs.push(s..flags.len());
+        }
+
+        // Combler les silences courts, puis ignorer les segments trop brefs
+        let min_silence = ms_to_frames(self.config.min_silence_ms);
+        let mut merged: Vec<Range<usize>> = Vec::new();
+        for run in runs {
+            match merged.last_mut() {
+                Some(last) if run.start - last.end < min_silence => last.end = run.end,
+                _ => merged.push(run),
+            }
+        }
+        let min_speech = ms_to_frames(self.config.min_speech_ms);
+        merged.retain(|r| r.len() >= min_speech.max(1));
+
+        // Conversion en plages d'échantillons avec marge
+        let padding = ms_to_frames(self.config.padding_ms) * frame_len;
+        let mut regions: Vec<Range<usize>> = Vec::new();
+        for run in merged {
+            let start = (run.start * frame_len).saturating_sub(padding);
+            let end = (run.end * frame_len + padding).min(audio.len());
+            match regions.last_mut() {
+                Some(last) if start <= last.end => last.end = end,
+                _ => regions.push(start..end),
+            }
+        }
+        regions
+    }
+
+    /// Audio sans le silence de début et de fin (vide sans parole)
+    pub fn trim_silence<'a>(&mut self, audio: &'a [f32], sample_rate: u32) -> &'a [f32] {
+        let regions = self.speech_regions(audio, sample_rate);
+        match (regions.first(), regions.last()) {
+            (Some(first), Some(last)) => &audio[first.start..last.end],
+            _ => &[],
+        }
+    }
+
+    /// Découpe en morceaux d'au plus `max_chunk_secs`, dans les silences. La
+    /// parole plus longue est coupée à la trame la plus calme avant la limite.
+    /// Le silence seul est écarté.
+    pub fn plan_chunks(&mut self, audio: &[f32], sample_rate: u32, max_chunk_secs: f32) -> Vec<Range<usize>> {
+        let max_len = (max_chunk_secs * sample_rate as f32) as usize;
+        let frame_len = self.detector.frame_len(sample_rate);
+        let energies = frame_energies(audio, frame_len);
+
+        let mut chunks: Vec<Range<usize>> = Vec::new();
+        for region in self.speech_regions(audio, sample_rate) {
+            if let Some(last) = chunks.last_mut() {
+                if region.end - last.start <= max_len {
+                    last.end = region.end;
+                    continue;
+                }
+            }
+
+            let mut start = region.start;
+            while region.end - start > max_len {
+                // Trame la plus calme dans le dernier tiers de la fenêtre
+                let search_from = (start + max_len * 2 / 3) / frame_len;
+                let search_to = ((start + max_len) / frame_len).max(search_from + 1);
+                let cut_frame = (search_from..search_to.min(energies.len()))
+                    .min_by(|&a, &b| energies[a].total_cmp(&energies[b]))
+                    .unwrap_or(search_to);
+                let cut = (cut_frame * frame_len).clamp(start + 1, start + max_len);
+                chunks.push(start..cut);
+                start = cut;
+            }
+            chunks.push(start..region.end);
+        }
+        chunks
+    }
+}
+
+/// Retire le silence de début et de fin avant transcription si l'option est
+/// active. Sans parole détectée (voix trop faible pour le détecteur), l'audio
+/// est gardé tel quel plutôt que perdu.
+pub fn trim_if_enabled(audio: Vec<f32>, sample_rate: u32, settings: &AppSettings) -> Vec<f32> {
+    if !settings.trim_silence {
+        return audio;
+    }
+
+    let trimmed = Vad::for_engine(settings.vad_engine).trim_silence(&audio, sample_rate);
+    if trimmed.is_empty() {
+        log::info!("No speech found while trimming silence, keeping the whole recording");
+        return audio;
+    }
+    log::info!(
+        "Trimmed silence: {:.2}s -> {:.2}s",
+        audio.len() as f32 / sample_rate as f32,
+        trimmed.len() as f32 / sample_rate as f32
+    );
+    trimmed.to_vec()
+}
+
+/// Détection incrémentale du silence pendant la capture : durée du silence
+/// depuis la dernière parole
+pub struct SilenceTracker {
+    frame_len: usize,
+    frame_ms: f32,
+    pending: Vec<f32>,
+    noise_floor: Option<f32>,
+    heard_speech: bool,
+    silent_frames: usize,
+}
+
+impl SilenceTracker {
+    pub fn new(sample_rate: u32) -> Self {
+        let detector = EnergyVad::default();
+        let frame_len = detector.frame_len(sample_rate);
+        Self {
+            frame_len,
+            frame_ms: frame_len as f32 * 1000.0 / sample_rate as f32,
+            pending: Vec::with_capacity(frame_len),
+            noise_floor: None,
+            heard_speech: false,
+            silent_frames: 0,
+        }
+    }
+
+    /// Analyse de nouveaux échantillons (FFT par trame) : à appeler hors du
+    /// callback audio
+    pub fn push(&mut self, samples: &[f32]) {
+        self.pending.extend_from_slice(samples);
+        let pending = std::mem::take(&mut self.pending);
+        let mut frames = pending.chunks_exact(self.frame_len);
+        for frame in &mut frames {
+            self.process_frame(frame);
+        }
+        let rest = frames.remainder().len();
+        self.pending = pending;
+        self.pending.drain(..self.pending.len() - rest);
+    }
+
+    fn process_frame(&mut self, frame: &[f32]) {
+        let energy = frame_energies(frame, frame.len())[0];
+
+        // Le bruit de fond suit les baisses immédiatement et monte lentement
+        let floor = match self.noise_floor {
+            Some(floor) if energy < floor => energy,
+            Some(floor) => floor + (energy - floor) * 0.01,
+            None => energy,
+        };
+        self.noise_floor = Some(floor);
+
+        let threshold = (floor + MIN_DYNAMIC_RANGE_DB).max(ABSOLUTE_FLOOR_DB);
+        let speech = energy > threshold
+            && (energy > floor + LOUD_MARGIN_DB || spectral_flatness(frame) < MAX_SPEECH_FLATNESS);
+
+        if speech {
+            self.heard_speech = true;
+            self.silent_frames = 0;
+        } else {
+            self.silent_frames += 1;
+        }
+    }
+
+    /// Silence depuis la dernière parole en millisecondes (0 tant que personne n'a parlé)
+    pub fn trailing_silence_ms(&self) -> u32 {
+        if self.heard_speech {
+            (self.silent_frames as f32 * self.frame_ms) as u32
+        } else {
+            0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: u32 = 16000;
+
+    fn tone(secs: f32, amplitude: f32) -> Vec<f32> {
+        let n = (secs * SAMPLE_RATE as f32) as usize;
+        (0..n)
+            .map(|i| amplitude * (2.0 * std::f32::consts::PI * 220.0 * i as f32 / SAMPLE_RATE as f32).sin())
+            .collect()
+    }
+
+    fn silence(secs: f32) -> Vec<f32> {
+        vec![0.0; (secs * SAMPLE_RATE as f32) as usize]
+    }
+
+    /// Bruit blanc déterministe
+    fn noise(secs: f32, amplitude: f32, seed: u32) -> Vec<f32> {
+        let mut state = seed;
+        (0..(secs * SAMPLE_RATE as f32) as usize)
+            .map(|_| {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                amplitude * ((state >> 8) as f32 / (1u32 << 24) as f32 * 2.0 - 1.0)
+            })
+            .collect()
+    }
+
+    fn mix(a: &[f32], b: &[f32]) -> Vec<f32> {
+        a.iter().zip(b).map(|(x, y)| x + y).collect()
+    }
+
+    fn secs(samples: usize) -> f32 {
+        samples as f32 / SAMPLE_RATE as f32
+    }
+
+    #[test]
+    fn test_speech_regions_on_tone_bursts() {
+        let mut audio = silence(1.0);
+        audio.extend(tone(2.0, 0.5));
+        audio.extend(silence(1.5));
+        audio.extend(tone(1.0, 0.3));
+        audio.extend(silence(1.0));
+
+        let regions = Vad::default().speech_regions(&audio, SAMPLE_RATE);
+        assert_eq!(regions.len(), 2);
+        assert!((secs(regions[0].start) - 0.8).abs() < 0.1);
+        assert!((secs(regions[0].end) - 3.2).abs() < 0.1);
+        assert!((secs(regions[1].start) - 4.3).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_noise_burst_is_not_speech() {
+        let mut signal = silence(1.0);
+        signal.extend(tone(1.5, 0.3));
+        signal.extend(silence(1.0));
+        signal.extend(noise(1.5, 0.1, 7));
+        signal.extend(silence(1.0));
+        let audio = mix(&signal, &noise(6.0, 0.02, 42));
+
+        let regions = Vad::default().speech_regions(&audio, SAMPLE_RATE);
+        assert_eq!(regions.len(), 1);
+        assert!((secs(regions[0].start) - 0.8).abs() < 0.1);
+        assert!((secs(regions[0].end) - 2.7).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_trim_silence() {
+        let mut audio = silence(2.0);
+        audio.extend(tone(1.0, 0.5));
+        audio.extend(silence(3.0));
+
+        let trimmed = Vad::default().trim_silence(&audio, SAMPLE_RATE);
+        assert!((secs(trimmed.len()) - 1.4).abs() < 0.1);
+        assert!(Vad::default().trim_silence(&silence(2.0), SAMPLE_RATE).is_empty());
+
+        // Sans parole détectée, l'enregistrement est transcrit tel quel
+        let settings = AppSettings {
+            trim_silence: true,
+            ..AppSettings::default()
+        };
+        let quiet = tone(1.0, 0.0005);
+        assert_eq!(trim_if_enabled(quiet.clone(), SAMPLE_RATE, &settings), quiet);
+    }
+
+    #[test]
+    fn test_plan_chunks_respects_max_length() {
+        let mut audio = Vec::new();
+        for _ in 0..6 {
+            audio.extend(tone(4.0, 0.5));
+            audio.extend(silence(1.0));
+        }
+        // Un seul long segment ininterrompu
+        audio.extend(tone(25.0, 0.5));
+
+        let chunks = Vad::default().plan_chunks(&audio, SAMPLE_RATE, 10.0);
+        let max_len = 10 * SAMPLE_RATE as usize;
+        assert!(chunks.len() >= 5);
+        assert!(chunks.iter().all(|c| c.len() <= max_len));
+        assert!(chunks.windows(2).all(|w| w[0].end <= w[1].start));
+        // Le premier morceau groupe deux segments et finit dans le silence suivant
+        assert!((secs(chunks[0].end) - 9.2).abs() < 0.1);
+        assert_eq!(chunks.last().unwrap().end, audio.len());
+    }
+
+    #[test]
+    fn test_silence_tracker_counts_trailing_silence() {
+        let mut tracker = SilenceTracker::new(SAMPLE_RATE);
+        let background = noise(5.0, 0.005, 3);
+
+        // Le silence avant toute parole ne compte pas
+        tracker.push(&background[..16000]);
+        assert_eq!(tracker.trailing_silence_ms(), 0);
+
+        tracker.push(&mix(&tone(1.0, 0.4), &background[16000..32000]));
+        assert!(tracker.trailing_silence_ms() < 100);
+
+        for chunk in background[32000..].chunks(1024) {
+            tracker.push(chunk);
+        }
+        assert!((tracker.trailing_silence_ms() as i32 - 3000).abs() < 100);
+    }
+}
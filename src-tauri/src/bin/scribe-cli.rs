@@ -0,0 +1,5 @@
+//! Interface en ligne de commande sans fenêtre : `scribe-cli transcribe file.mp3 --format srt`
+
+fn main() -> std::process::ExitCode {
+    scribe_lib::cli::run()
+}
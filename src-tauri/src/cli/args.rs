@@ -0,0 +1,146 @@
+//! Arguments de la ligne de commande de `scribe-cli`
+
+use clap::{Args, Parser, Subcommand};
+use serde::de::DeserializeOwned;
+use std::path::PathBuf;
+
+use crate::engines::catalog::{self, ModelFamily};
+use crate::types::{EngineType, ExportFormat};
+
+/// Lit un nom d'enum serde en minuscules (`srt`, `whisper`...)
+fn parse_enum<T: DeserializeOwned>(value: &str) -> Result<T, String> {
+    catalog::from_id(&value.to_lowercase()).ok_or_else(|| format!("invalid value '{}'", value))
+}
+
+// Les commentaires `///` des commandes et options forment le texte de
+// `--help` : ils restent en anglais, comme les messages de la CLI
+#[derive(Debug, Parser)]
+#[command(name = "scribe-cli", version, about = "Headless transcription with the Scribe engines")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Transcribe audio files to stdout or to an output directory
+    Transcribe(TranscribeArgs),
+    /// Manage downloaded models
+    #[command(subcommand)]
+    Models(ModelsCommand),
+    /// Transcribe the microphone to stdout until Ctrl+C
+    Dictate(DictateArgs),
+}
+
+/// Choix du moteur commun à `transcribe` et `dictate` ; une valeur absente
+/// reprend les réglages enregistrés
+#[derive(Debug, Args)]
+pub struct EngineArgs {
+    /// whisper, vosk or parakeet
+    #[arg(long, value_parser = parse_enum::<EngineType>)]
+    pub engine: Option<EngineType>,
+    /// Catalog id of the model (`small`, `large-v3-turbo`, `fr` for Vosk...)
+    #[arg(long)]
+    pub model: Option<String>,
+    /// Transcription language code, or `auto`
+    #[arg(long)]
+    pub language: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct TranscribeArgs {
+    #[arg(required = true)]
+    pub files: Vec<PathBuf>,
+    #[command(flatten)]
+    pub engine: EngineArgs,
+    /// txt, srt, vtt, ttml or json
+    #[arg(long, default_value = "txt", value_parser = parse_enum::<ExportFormat>)]
+    pub format: ExportFormat,
+    /// Write `<name>.<format>` files here instead of printing to stdout
+    #[arg(long, short = 'o')]
+    pub output_dir: Option<PathBuf>,
+    /// Label speakers
+    #[arg(long)]
+    pub diarize: bool,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ModelsCommand {
+    /// List catalog models and their installation state
+    List {
+        /// Only list one family (whisper, vosk, parakeet, llm)
+        #[arg(long, value_parser = parse_enum::<ModelFamily>)]
+        engine: Option<ModelFamily>,
+        /// Print JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+    /// Download a model
+    Download {
+        #[arg(value_parser = parse_enum::<ModelFamily>)]
+        engine: ModelFamily,
+        id: String,
+    },
+    /// Delete a downloaded model
+    Delete {
+        #[arg(value_parser = parse_enum::<ModelFamily>)]
+        engine: ModelFamily,
+        id: String,
+    },
+    /// Import a model file or directory from disk
+    Import {
+        #[arg(value_parser = parse_enum::<ModelFamily>)]
+        engine: ModelFamily,
+        path: PathBuf,
+        /// Catalog entry to replace; an unknown id registers a new model
+        #[arg(long)]
+        id: Option<String>,
+        /// Symlink instead of copying
+        #[arg(long)]
+        link: bool,
+    },
+}
+
+#[derive(Debug, Args)]
+pub struct DictateArgs {
+    #[command(flatten)]
+    pub engine: EngineArgs,
+    /// Input device name (default: settings, then system default)
+    #[arg(long)]
+    pub device: Option<String>,
+    /// Stop after this many seconds
+    #[arg(long)]
+    pub duration: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_transcribe_command() {
+        let cli = Cli::try_parse_from([
+            "scribe-cli", "transcribe", "file.mp3", "--engine", "whisper", "--model", "small", "--format", "SRT",
+        ])
+        .unwrap();
+        let Command::Transcribe(args) = cli.command else {
+            panic!("expected transcribe");
+        };
+        assert_eq!(args.files, vec![PathBuf::from("file.mp3")]);
+        assert_eq!(args.engine.engine, Some(EngineType::Whisper));
+        assert_eq!(args.engine.model.as_deref(), Some("small"));
+        assert_eq!(args.format, ExportFormat::Srt);
+        assert!(args.output_dir.is_none());
+    }
+
+    #[test]
+    fn test_parses_models_commands() {
+        let cli = Cli::try_parse_from(["scribe-cli", "models", "download", "vosk", "fr"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::Models(ModelsCommand::Download { engine: ModelFamily::Vosk, ref id }) if id == "fr"
+        ));
+        assert!(Cli::try_parse_from(["scribe-cli", "models", "download", "kaldi", "fr"]).is_err());
+        assert!(Cli::try_parse_from(["scribe-cli", "transcribe"]).is_err());
+    }
+}
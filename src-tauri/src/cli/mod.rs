@@ -0,0 +1,316 @@
+//! `scribe-cli` : transcription sans interface pour la CI et les scripts.
+//! Réutilise le décodeur, le gestionnaire de modèles et les moteurs de
+//! l'application ; une option absente reprend les réglages enregistrés.
+
+mod args;
+
+use clap::Parser;
+use std::io::Write;
+use std::path::Path;
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::audio::{diarization, AudioCapture, AudioDecoder, StreamingTranscriber};
+use crate::commands::CatalogModelInfo;
+use crate::engines::catalog::{self, ModelFamily};
+use crate::engines::download::CancellationToken;
+use crate::engines::{long_form, ModelManager, ParakeetEngine, SpeechEngine, Vocabulary, VoskEngine, WhisperEngine};
+use crate::storage::{self, config, dictionary, export};
+use crate::types::{AppSettings, EngineType, FileTranscriptionResult, SubtitleOptions};
+use args::{Cli, Command, DictateArgs, EngineArgs, ModelsCommand, TranscribeArgs};
+
+pub fn run() -> ExitCode {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn")).init();
+
+    let result = match Cli::parse().command {
+        Command::Transcribe(args) => transcribe(args),
+        Command::Models(command) => models(command),
+        Command::Dictate(args) => dictate(args),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn runtime() -> Result<tokio::runtime::Runtime, String> {
+    tokio::runtime::Runtime::new().map_err(|e| format!("Failed to start runtime: {}", e))
+}
+
+fn model_manager(settings: &AppSettings) -> ModelManager {
+    let manager = ModelManager::new(storage::get_app_data_dir(), None);
+    manager.set_mirror_url(settings.model_mirror_url.clone());
+    manager
+}
+
+fn not_installed(family: ModelFamily, id: &str) -> String {
+    let family = catalog::id_of(&family);
+    format!(
+        "{} model {} is not installed, run `scribe-cli models download {} {}`",
+        family, id, family, id
+    )
+}
+
+/// Charge le moteur demandé en ligne de commande, avec le dictionnaire utilisateur
+fn load_engine(
+    manager: &ModelManager,
+    settings: &AppSettings,
+    args: &EngineArgs,
+) -> Result<Box<dyn SpeechEngine>, String> {
+    let mut engine: Box<dyn SpeechEngine> = match args.engine.unwrap_or(settings.engine_type) {
+        EngineType::Whisper => {
+            let id = args.model.clone().unwrap_or_else(|| settings.whisper_model.clone());
+            let entry = manager.entry(ModelFamily::Whisper, &id)?;
+            let path = manager
+                .installed_path(&entry)
+                .ok_or_else(|| not_installed(ModelFamily::Whisper, &id))?;
+            let language = match args.language.as_deref() {
+                Some("auto") => None,
+                Some(language) => Some(language.to_string()),
+                None if settings.auto_detect_language => None,
+                None => Some(settings.transcription_language.clone()),
+            };
+            Box::new(WhisperEngine::new(&path, language, entry)?)
+        }
+        EngineType::Vosk => {
+            let catalog = manager.catalog();
+            let by_language = |code: &str| catalog.for_language(ModelFamily::Vosk, code).map(|e| e.id.clone());
+            let id = match args.model.clone() {
+                Some(id) => id,
+                None => args
+                    .language
+                    .as_deref()
+                    .map_or(settings.vosk_language.clone(), by_language)
+                    .or_else(|| by_language(&settings.transcription_language))
+                    .ok_or("No Vosk language configured, pass --model")?,
+            };
+            let entry = manager.entry(ModelFamily::Vosk, &id)?;
+            let path = manager
+                .installed_path(&entry)
+                .ok_or_else(|| not_installed(ModelFamily::Vosk, &id))?;
+            Box::new(VoskEngine::new(&path, entry)?)
+        }
+        EngineType::Parakeet => {
+            let id = args.model.clone().unwrap_or_else(|| settings.parakeet_model.clone());
+            let entry = manager.entry(ModelFamily::Parakeet, &id)?;
+            let path = manager
+                .installed_path(&entry)
+                .ok_or_else(|| not_installed(ModelFamily::Parakeet, &id))?;
+            Box::new(ParakeetEngine::new(&path, entry)?)
+        }
+    };
+    engine.set_vocabulary(Vocabulary::new(&dictionary::load_dictionary().words));
+    Ok(engine)
+}
+
+// ===== transcribe =====
+
+fn transcribe(args: TranscribeArgs) -> Result<(), String> {
+    let mut settings = config::load_settings();
+    settings.diarization_enabled = args.diarize;
+    let manager = model_manager(&settings);
+    let engine = load_engine(&manager, &settings, &args.engine)?;
+
+    if let Some(ref dir) = args.output_dir {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+
+    let mut failures = 0;
+    for path in &args.files {
+        let result = transcribe_file(engine.as_ref(), path, &settings);
+        if let Some(ref error) = result.error {
+            eprintln!("{}: {}", path.display(), error);
+            failures += 1;
+            continue;
+        }
+
+        let options = SubtitleOptions::default();
+        match args.output_dir {
+            Some(ref dir) => {
+                let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+                let output = dir.join(format!("{}.{}", stem, args.format.extension()));
+                export::export_to_file(&result, args.format, &options, &output)?;
+                eprintln!("{} -> {}", path.display(), output.display());
+            }
+            None => println!("{}", export::render(&result, args.format, &options)?.trim_end()),
+        }
+    }
+
+    if failures > 0 {
+        return Err(format!("{} of {} files failed", failures, args.files.len()));
+    }
+    Ok(())
+}
+
+fn transcribe_file(engine: &dyn SpeechEngine, path: &Path, settings: &AppSettings) -> FileTranscriptionResult {
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let transcription = if AudioDecoder::is_supported(path) {
+        AudioDecoder::decode_file(path)
+            .map_err(|e| format!("Failed to decode: {}", e))
+            .and_then(|(audio, sample_rate)| {
+                let mut result = long_form::transcribe_long(engine, &audio, sample_rate, |done, chunks| {
+                    if chunks > 1 {
+                        eprint!("\r{}: chunk {}/{}", file_name, done, chunks);
+                        if done == chunks {
+                            eprintln!();
+                        }
+                    }
+                })?;
+                diarization::diarize_if_enabled(&mut result, &audio, sample_rate, settings);
+                Ok(result)
+            })
+    } else {
+        Err("Unsupported audio format".to_string())
+    };
+
+    let (transcription, error) = match transcription {
+        Ok(result) => (Some(result), None),
+        Err(e) => (None, Some(e)),
+    };
+    FileTranscriptionResult {
+        file_path: path.display().to_string(),
+        file_name,
+        transcription,
+        error,
+        history_id: None,
+    }
+}
+
+// ===== models =====
+
+fn models(command: ModelsCommand) -> Result<(), String> {
+    let settings = config::load_settings();
+    let manager = model_manager(&settings);
+
+    match command {
+        ModelsCommand::List { engine, json } => {
+            let catalog = manager.catalog();
+            let models: Vec<CatalogModelInfo> = catalog
+                .models
+                .iter()
+                .filter(|entry| engine.is_none() || engine == Some(entry.family))
+                .map(|entry| CatalogModelInfo {
+                    entry: entry.clone(),
+                    size_bytes: entry.size_bytes(),
+                    available: manager.is_installed(entry),
+                })
+                .collect();
+
+            if json {
+                let json = serde_json::to_string_pretty(&models).map_err(|e| e.to_string())?;
+                println!("{}", json);
+                return Ok(());
+            }
+            for model in &models {
+                println!(
+                    "{:<9} {:<22} {:>8} MB  {:<10} {}",
+                    catalog::id_of(&model.entry.family),
+                    model.entry.id,
+                    model.size_bytes / 1_000_000,
+                    if model.available { "installed" } else { "-" },
+                    model.entry.display_name
+                );
+            }
+            Ok(())
+        }
+        ModelsCommand::Download { engine, id } => {
+            let entry = manager.entry(engine, &id)?;
+            let cancel = CancellationToken::new();
+            let mut last_percent = None;
+            let progress = move |downloaded: u64, total: u64| {
+                let percent = (downloaded as f64 / total.max(1) as f64 * 100.0) as u32;
+                if last_percent != Some(percent) {
+                    last_percent = Some(percent);
+                    eprint!("\r{} {:>3}%", id, percent);
+                }
+            };
+
+            let path = runtime()?.block_on(async {
+                let on_interrupt = cancel.clone();
+                tokio::spawn(async move {
+                    if tokio::signal::ctrl_c().await.is_ok() {
+                        on_interrupt.cancel();
+                    }
+                });
+                manager.download_entry(&entry, &cancel, progress).await
+            });
+            eprintln!();
+            println!("{}", path?.display());
+            Ok(())
+        }
+        ModelsCommand::Delete { engine, id } => {
+            let entry = manager.entry(engine, &id)?;
+            runtime()?.block_on(manager.delete_entry(&entry))?;
+            if !entry.is_downloadable() {
+                catalog::remove_user_entry(engine, &id)?;
+            }
+            Ok(())
+        }
+        ModelsCommand::Import { engine, path, id, link } => {
+            let entry = runtime()?.block_on(manager.import_model(engine, &path, id.as_deref(), link))?;
+            println!("{} {}", catalog::id_of(&entry.family), entry.id);
+            Ok(())
+        }
+    }
+}
+
+// ===== dictate =====
+
+fn dictate(args: DictateArgs) -> Result<(), String> {
+    let settings = config::load_settings();
+    let manager = model_manager(&settings);
+    let engine = load_engine(&manager, &settings, &args.engine)?;
+
+    let device = args.device.clone().or_else(|| settings.microphone_id.clone());
+    let mut capture = AudioCapture::new(device.as_deref())?;
+    capture.start(device.as_deref())?;
+
+    let runtime = runtime()?;
+    let stop = Arc::new(AtomicBool::new(false));
+    let on_interrupt = stop.clone();
+    runtime.spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            on_interrupt.store(true, Ordering::SeqCst);
+        }
+    });
+    eprintln!("Listening on {}, press Ctrl+C to stop", device.as_deref().unwrap_or("default input"));
+
+    let started = Instant::now();
+    let limit = args.duration.map(Duration::from_secs);
+    let mut transcriber = StreamingTranscriber::new(Some(engine.as_ref()));
+    let mut stdout = std::io::stdout();
+    let mut separator = "";
+    let mut print = |text: &str| {
+        let _ = write!(stdout, "{}{}", separator, text);
+        let _ = stdout.flush();
+        separator = " ";
+    };
+
+    while !stop.load(Ordering::SeqCst) && !limit.is_some_and(|limit| started.elapsed() >= limit) {
+        std::thread::sleep(transcriber.poll_interval());
+        let (audio, sample_rate) = capture.get_audio_snapshot(transcriber.received_samples());
+        transcriber.push_audio(&audio, sample_rate);
+        match transcriber.step(engine.as_ref()) {
+            Ok(Some(progress)) if !progress.newly_committed.is_empty() => print(&progress.newly_committed),
+            Ok(_) => {}
+            Err(e) => log::warn!("Streaming step failed: {}", e),
+        }
+    }
+
+    // Fin du flux : reconnaît ce qui a été enregistré depuis la dernière étape
+    let (audio, sample_rate) = capture.stop()?;
+    let received = transcriber.received_samples().min(audio.len());
+    transcriber.push_audio(&audio[received..], sample_rate);
+    let progress = transcriber.finish(engine.as_ref())?;
+    if !progress.newly_committed.is_empty() {
+        print(&progress.newly_committed);
+    }
+    println!();
+    Ok(())
+}
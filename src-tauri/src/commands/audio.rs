@@ -49,7 +49,7 @@ pub fn start_mic_preview(app: AppHandle, device_id: Option<String>) -> Result<()
         while MIC_PREVIEW_ACTIVE.load(Ordering::SeqCst) {
             std::thread::sleep(std::time::Duration::from_millis(50));
 
-            let (buffer, _sample_rate) = capture.get_audio_snapshot();
+            let (buffer, _sample_rate) = capture.get_audio_snapshot(0);
             let len = buffer.len();
 
             // Take only the last ~2400 samples (~50ms at 48kHz)
@@ -1,33 +1,36 @@
-use crate::audio::AudioDecoder;
+use crate::audio::{diarization, AudioDecoder};
+use crate::engines::long_form;
+use crate::llm::translation;
 use crate::state::AppState;
 use crate::storage::history;
-use crate::types::TranscriptionResult;
-use serde::{Deserialize, Serialize};
+use crate::storage::export;
+use crate::types::{ExportFormat, FileTranscriptionResult, SubtitleOptions, TranscriptionResult, TranslationInfo};
+use serde::Serialize;
+use std::path::Path;
 use tauri::{AppHandle, Emitter, State};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct FileTranscriptionResult {
-    pub file_path: String,
-    pub file_name: String,
-    pub transcription: Option<TranscriptionResult>,
-    pub error: Option<String>,
-}
-
 #[derive(Debug, Clone, Serialize)]
 pub struct FileTranscriptionProgress {
     pub current: usize,
     pub total: usize,
     pub file_name: String,
     pub status: String,
+    /// Chunk being transcribed (long files are split at silences)
+    pub chunk: Option<usize>,
+    pub total_chunks: Option<usize>,
 }
 
-/// Transcribe multiple audio files using the currently configured engine
+/// Transcribe multiple audio files using the currently configured engine.
+/// With `translate_to`, the text is translated: straight from speech by
+/// Whisper for English, through the configured LLM otherwise.
 #[tauri::command]
 pub async fn transcribe_files(
     app: AppHandle,
     state: State<'_, AppState>,
     paths: Vec<String>,
+    translate_to: Option<String>,
 ) -> Result<Vec<FileTranscriptionResult>, String> {
+    let translate_to = translate_to.filter(|language| !language.is_empty());
     let mut results = Vec::new();
     let total = paths.len();
 
@@ -47,6 +50,8 @@ pub async fn transcribe_files(
                 total,
                 file_name: file_name.clone(),
                 status: "decoding".to_string(),
+                chunk: None,
+                total_chunks: None,
             },
         );
 
@@ -57,6 +62,7 @@ pub async fn transcribe_files(
                 file_name,
                 transcription: None,
                 error: Some("Unsupported audio format".to_string()),
+                history_id: None,
             });
             continue;
         }
@@ -70,50 +76,106 @@ pub async fn transcribe_files(
                     file_name,
                     transcription: None,
                     error: Some(format!("Failed to decode: {}", e)),
+                    history_id: None,
                 });
                 continue;
             }
         };
 
-        // Emit progress: transcribing
-        let _ = app.emit(
-            "file-transcription-progress",
-            FileTranscriptionProgress {
-                current: index + 1,
-                total,
-                file_name: file_name.clone(),
-                status: "transcribing".to_string(),
-            },
-        );
-
-        // Transcribe using current engine
+        // Transcribe using current engine, chunk by chunk for long files
+        let mut needs_text_translation = translate_to.is_some();
         let transcription = {
             let engine_guard = state.engine.read().map_err(|e| e.to_string())?;
             if let Some(ref engine) = *engine_guard {
-                engine.transcribe(&audio, sample_rate)
+                // Fast path: Whisper translates speech to English itself
+                let speech_to_english = translate_to.as_deref() == Some("en") && engine.supports_translation();
+                let on_progress = |done, chunks| {
+                    // Emit progress: transcribing
+                    let _ = app.emit(
+                        "file-transcription-progress",
+                        FileTranscriptionProgress {
+                            current: index + 1,
+                            total,
+                            file_name: file_name.clone(),
+                            status: "transcribing".to_string(),
+                            chunk: Some(done),
+                            total_chunks: Some(chunks),
+                        },
+                    );
+                };
+                if speech_to_english {
+                    needs_text_translation = false;
+                    long_form::translate_long(&**engine, &audio, sample_rate, on_progress)
+                } else {
+                    long_form::transcribe_long(&**engine, &audio, sample_rate, on_progress)
+                }
             } else {
                 Err("No engine initialized".to_string())
             }
         };
 
+        let settings = state.settings.read().map_err(|e| e.to_string())?.clone();
+        let transcription = match (transcription, translate_to.as_deref()) {
+            (Ok(mut result), Some(language)) if needs_text_translation => {
+                let _ = app.emit(
+                    "file-transcription-progress",
+                    FileTranscriptionProgress {
+                        current: index + 1,
+                        total,
+                        file_name: file_name.clone(),
+                        status: "translating".to_string(),
+                        chunk: None,
+                        total_chunks: None,
+                    },
+                );
+                match translation::translate(&settings, &result.text, language).await {
+                    Ok(text) => {
+                        // Segment timings no longer match the translated text
+                        result.text = text;
+                        result.segments.clear();
+                        result.translation = Some(TranslationInfo {
+                            source_language: result.detected_language.clone(),
+                            target_language: language.to_string(),
+                        });
+                        Ok(result)
+                    }
+                    Err(e) => Err(format!("Translation failed: {}", e)),
+                }
+            }
+            (transcription, _) => transcription,
+        };
+
         match transcription {
-            Ok(result) => {
-                // Save to history
-                if let Err(e) = history::add_transcription(result.clone()) {
-                    log::warn!("Failed to save transcription to history: {}", e);
+            Ok(mut result) => {
+                // Label speakers when diarization is enabled
+                if settings.diarization_enabled {
+                    let _ = app.emit(
+                        "file-transcription-progress",
+                        FileTranscriptionProgress {
+                            current: index + 1,
+                            total,
+                            file_name: file_name.clone(),
+                            status: "diarizing".to_string(),
+                            chunk: None,
+                            total_chunks: None,
+                        },
+                    );
+                    diarization::diarize_if_enabled(&mut result, &audio, sample_rate, &settings);
                 }
 
+                // Save to history
+                let history_id = history::add_transcription(result.clone(), None)
+                    .map_err(|e| log::warn!("Failed to save transcription to history: {}", e))
+                    .ok();
+
                 // Record stats
-                {
-                    let settings = state.settings.read().map_err(|e| e.to_string())?;
-                    if settings.stats_tracking_enabled {
-                        let word_count = result.text.split_whitespace().count() as u64;
-                        let _ = crate::storage::stats::record_transcription(
-                            word_count,
-                            result.duration_seconds as f64,
-                            result.detected_language.as_deref(),
-                        );
-                    }
+                if settings.stats_tracking_enabled {
+                    let word_count = result.text.split_whitespace().count() as u64;
+                    let _ = crate::storage::stats::record_transcription(
+                        word_count,
+                        result.duration_seconds as f64,
+                        result.detected_language.as_deref(),
+                    );
                 }
 
                 results.push(FileTranscriptionResult {
@@ -121,6 +183,7 @@ pub async fn transcribe_files(
                     file_name,
                     transcription: Some(result),
                     error: None,
+                    history_id,
                 });
             }
             Err(e) => {
@@ -129,6 +192,7 @@ pub async fn transcribe_files(
                     file_name,
                     transcription: None,
                     error: Some(e),
+                    history_id: None,
                 });
             }
         }
@@ -142,6 +206,8 @@ pub async fn transcribe_files(
             total,
             file_name: String::new(),
             status: "completed".to_string(),
+            chunk: None,
+            total_chunks: None,
         },
     );
 
@@ -156,3 +222,36 @@ pub fn get_supported_audio_formats() -> Vec<String> {
         .map(|s| s.to_string())
         .collect()
 }
+
+/// Rename a speaker found by diarization in every segment of a transcription,
+/// and in its history entry when there is one
+#[tauri::command]
+pub fn rename_speaker(
+    mut result: TranscriptionResult,
+    from: String,
+    to: String,
+    history_id: Option<i64>,
+) -> Result<TranscriptionResult, String> {
+    if to.trim().is_empty() {
+        return Err("Speaker name cannot be empty".to_string());
+    }
+    diarization::rename_speaker(&mut result, &from, &to);
+    if let Some(id) = history_id {
+        history::update_transcription(id, &result)?;
+    }
+    Ok(result)
+}
+
+/// Export a file transcription as subtitles (SRT, WebVTT, TTML), JSON or plain text
+#[tauri::command]
+pub fn export_transcription(
+    result: FileTranscriptionResult,
+    format: ExportFormat,
+    path: String,
+    options: Option<SubtitleOptions>,
+) -> Result<(), String> {
+    let options = options.unwrap_or_default();
+    export::export_to_file(&result, format, &options, Path::new(&path))?;
+    log::info!("Transcription exported as {:?} to {}", format, path);
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use tauri::ipc::Response;
+use tauri::{AppHandle, Manager};
+
+use crate::engines::long_form;
+use crate::state::AppState;
+use crate::storage::{history, recordings};
+use crate::types::{EngineType, HistoryEntry, HistoryFacets, HistoryPage, HistoryQuery};
+
+#[tauri::command]
+pub fn search_history(query: HistoryQuery) -> Result<HistoryPage, String> {
+    history::search(&query)
+}
+
+#[tauri::command]
+pub fn get_history_page(offset: u32, limit: u32) -> Result<HistoryPage, String> {
+    history::get_page(offset, limit)
+}
+
+#[tauri::command]
+pub fn delete_history_entry(id: i64) -> Result<(), String> {
+    history::delete_entry(id)
+}
+
+#[tauri::command]
+pub fn set_history_favorite(id: i64, favorite: bool) -> Result<(), String> {
+    history::set_favorite(id, favorite)
+}
+
+#[tauri::command]
+pub fn set_history_tags(id: i64, tags: Vec<String>) -> Result<(), String> {
+    history::set_tags(id, &tags)
+}
+
+#[tauri::command]
+pub fn get_history_facets() -> Result<HistoryFacets, String> {
+    history::facets()
+}
+
+fn retained_audio(id: i64) -> Result<std::path::PathBuf, String> {
+    history::audio_path(id)?.ok_or_else(|| format!("No audio retained for history entry {}", id))
+}
+
+/// Audio FLAC conservé pour l'entrée (déchiffré), lu tel quel par l'interface
+#[tauri::command]
+pub fn get_history_audio(id: i64) -> Result<Response, String> {
+    Ok(Response::new(recordings::read(&retained_audio(id)?)?))
+}
+
+/// Transcrit à nouveau l'audio conservé avec un autre moteur ou modèle (sans
+/// post-traitement) et remplace le texte de l'entrée
+#[tauri::command]
+pub async fn retranscribe_history_entry(
+    app: AppHandle,
+    id: i64,
+    engine: EngineType,
+    model: Option<String>,
+) -> Result<HistoryEntry, String> {
+    // Chargement du modèle et transcription bloquent : hors du runtime async
+    tokio::task::spawn_blocking(move || retranscribe(&app.state::<AppState>(), id, engine, model.as_deref()))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+fn retranscribe(state: &AppState, id: i64, engine: EngineType, model: Option<&str>) -> Result<HistoryEntry, String> {
+    let previous = history::get_entry(id)?;
+    let (audio, sample_rate) = recordings::load(&retained_audio(id)?)?;
+
+    let engine = state.load_engine(engine, model)?;
+    let mut result = long_form::transcribe_long(engine.as_ref(), &audio, sample_rate, |_, _| {})?;
+    if result.text.is_empty() {
+        return Err("Transcription returned empty text".to_string());
+    }
+    result.timestamp = previous.transcription.timestamp;
+    log::info!("History entry {} re-transcribed with {:?}", id, result.model_used);
+
+    history::update_transcription(id, &result)
+}
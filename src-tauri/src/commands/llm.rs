@@ -1,52 +1,34 @@
+use serde::Serialize;
 use std::sync::Arc;
-use keyring::Entry;
 use tauri::{Emitter, State};
-use tokio::sync::RwLock;
 
+use crate::engines::catalog::{self, ModelFamily};
 use crate::engines::ModelManager;
-use crate::llm::{groq_client, LocalLlmEngine};
-use crate::storage::config;
-use crate::types::{LlmProvider, LocalLlmModel};
+use crate::llm::{self, groq_client, CompletionParams, LlmClient, OpenAiCompatibleClient, SharedLocalEngine};
+use crate::storage::{config, secrets};
+use crate::types::{LlmProvider, OpenAiCompatibleConfig};
 
-const SERVICE_NAME: &str = "wakascribe";
-const ACCOUNT_NAME: &str = "groq_api_key";
+/// Protection des clés API et de la clé de chiffrement du stockage
+#[tauri::command]
+pub fn get_secrets_protection() -> secrets::Protection {
+    secrets::protection()
+}
 
-/// Stocke la clé API Groq dans les settings de l'application (et keyring en backup)
+/// Stocke la clé API Groq dans le trousseau (ou le coffre chiffré)
 #[tauri::command]
 pub fn set_groq_api_key(key: String) -> Result<(), String> {
-    // Stocker dans les settings (méthode principale)
-    let mut settings = config::load_settings();
-    settings.groq_api_key = Some(key.clone());
-    config::save_settings(&settings)?;
-
-    // Essayer aussi le keyring en backup (mais ne pas échouer si ça marche pas)
-    if let Ok(entry) = Entry::new(SERVICE_NAME, ACCOUNT_NAME) {
-        let _ = entry.set_password(&key);
-    }
-
-    Ok(())
+    secrets::set(secrets::GROQ_API_KEY, &key)
 }
 
-/// Récupère la clé API Groq (settings en priorité, puis keyring)
+/// Récupère la clé API Groq
 #[tauri::command]
 pub fn get_groq_api_key() -> Option<String> {
     get_groq_api_key_internal()
 }
 
 /// Récupère la clé API Groq (usage interne sans attribut tauri::command)
-/// Vérifie d'abord dans les settings, puis dans le keyring
 pub fn get_groq_api_key_internal() -> Option<String> {
-    // 1. Vérifier dans les settings
-    let settings = config::load_settings();
-    if let Some(ref key) = settings.groq_api_key {
-        if !key.is_empty() {
-            return Some(key.clone());
-        }
-    }
-
-    // 2. Fallback sur le keyring
-    let entry = Entry::new(SERVICE_NAME, ACCOUNT_NAME).ok()?;
-    entry.get_password().ok()
+    secrets::get(secrets::GROQ_API_KEY)
 }
 
 /// Vérifie si une clé API Groq existe
@@ -82,20 +64,57 @@ pub async fn validate_groq_api_key(key: String) -> bool {
     }
 }
 
-/// Supprime la clé API Groq (des settings et du keyring)
+/// Supprime la clé API Groq
 #[tauri::command]
 pub fn delete_groq_api_key() -> Result<(), String> {
-    // Supprimer des settings
-    let mut settings = config::load_settings();
-    settings.groq_api_key = None;
-    config::save_settings(&settings)?;
-
-    // Essayer de supprimer du keyring aussi
-    if let Ok(entry) = Entry::new(SERVICE_NAME, ACCOUNT_NAME) {
-        let _ = entry.delete_credential();
-    }
+    secrets::delete(secrets::GROQ_API_KEY)
+}
 
-    Ok(())
+/// Stocke la clé API du serveur compatible OpenAI dans le trousseau
+#[tauri::command]
+pub fn set_openai_compatible_api_key(key: String) -> Result<(), String> {
+    secrets::set(secrets::OPENAI_COMPATIBLE_API_KEY, &key)
+}
+
+/// Récupère la clé API du serveur compatible OpenAI (usage interne)
+pub fn get_openai_compatible_api_key_internal() -> Option<String> {
+    secrets::get(secrets::OPENAI_COMPATIBLE_API_KEY)
+}
+
+/// Vérifie si une clé API est enregistrée pour le serveur compatible OpenAI
+#[tauri::command]
+pub fn has_openai_compatible_api_key() -> bool {
+    get_openai_compatible_api_key_internal().is_some()
+}
+
+/// Supprime la clé API du serveur compatible OpenAI
+#[tauri::command]
+pub fn delete_openai_compatible_api_key() -> Result<(), String> {
+    secrets::delete(secrets::OPENAI_COMPATIBLE_API_KEY)
+}
+
+/// Teste la connexion à un serveur compatible OpenAI avec une requête minimale
+#[tauri::command]
+pub async fn test_openai_compatible_provider(config: OpenAiCompatibleConfig) -> Result<String, String> {
+    let headers = config
+        .headers
+        .iter()
+        .filter(|h| !h.name.trim().is_empty())
+        .map(|h| (h.name.clone(), h.value.clone()))
+        .collect();
+    let client = OpenAiCompatibleClient::new(&config.base_url, &config.model)
+        .with_api_key(get_openai_compatible_api_key_internal())
+        .with_headers(headers);
+
+    let params = CompletionParams {
+        temperature: 0.0,
+        max_tokens: 16,
+    };
+    client
+        .complete("Reply with OK", "test", &params)
+        .await
+        .map(|reply| reply.trim().to_string())
+        .map_err(|e| format!("{} ({})", e, client.endpoint()))
 }
 
 /// Récupère les informations de quota Groq
@@ -104,11 +123,17 @@ pub fn get_groq_quota() -> Option<groq_client::GroqQuota> {
     groq_client::get_last_quota()
 }
 
-/// Résume un texte transcrit via Groq
+/// Résume un texte transcrit via le provider distant configuré
 #[tauri::command]
 pub async fn summarize_text(text: String) -> Result<String, String> {
-    let api_key = get_groq_api_key_internal()
-        .ok_or_else(|| "Clé API Groq non configurée. Configurez-la dans les paramètres.".to_string())?;
+    let settings = config::load_settings();
+    summarize_text_remote(text, settings.llm_provider).await
+}
+
+async fn summarize_text_remote(text: String, provider: LlmProvider) -> Result<String, String> {
+    let settings = config::load_settings();
+    let client = llm::client_for_settings(&settings, provider)
+        .map_err(|e| format!("{}. Configurez-le dans les paramètres.", e))?;
 
     let system_prompt = r#"Tu es un assistant expert en analyse de transcriptions audio.
 
@@ -167,7 +192,7 @@ comme si tu racontais à quelqu'un qui n'a pas écouté l'audio.
 
     let user_message = format!("Voici la transcription à analyser :\n\n{}", text);
 
-    match groq_client::send_completion(&api_key, system_prompt, &user_message).await {
+    match client.complete(system_prompt, &user_message, &CompletionParams::default()).await {
         Ok(summary) => {
             log::info!("Summarization successful: {} chars -> {} chars", text.len(), summary.len());
             Ok(summary.trim().to_string())
@@ -179,45 +204,16 @@ comme si tu racontais à quelqu'un qui n'a pas écouté l'audio.
     }
 }
 
-/// Traduit un texte vers une langue cible via Groq
+/// Traduit un texte vers une langue cible via le provider configuré
 #[tauri::command]
 pub async fn translate_text(text: String, target_language: String) -> Result<String, String> {
-    let api_key = get_groq_api_key_internal()
-        .ok_or_else(|| "No Groq API key configured".to_string())?;
-
-    let language_name = match target_language.as_str() {
-        "fr" => "French",
-        "en" => "English",
-        "de" => "German",
-        "es" => "Spanish",
-        "it" => "Italian",
-        "pt" => "Portuguese",
-        "nl" => "Dutch",
-        "ru" => "Russian",
-        "zh" => "Chinese",
-        "ja" => "Japanese",
-        "ko" => "Korean",
-        "ar" => "Arabic",
-        _ => &target_language,
-    };
-
-    let system_prompt = format!(
-        "You are a professional translator. Translate the following text to {}. \
-         Only output the translation, nothing else. Preserve the original formatting, \
-         punctuation and tone. If the text is already in {}, return it unchanged.",
-        language_name, language_name
-    );
-
-    match groq_client::send_completion(&api_key, &system_prompt, &text).await {
-        Ok(translated) => {
-            log::info!("Translation successful: {} -> {}", text.len(), translated.len());
-            Ok(translated.trim().to_string())
-        }
-        Err(e) => {
+    let settings = config::load_settings();
+    llm::translation::translate(&settings, &text, &target_language)
+        .await
+        .map_err(|e| {
             log::error!("Translation failed: {}", e);
-            Err(format!("Translation failed: {}", e))
-        }
-    }
+            format!("Translation failed: {}", e)
+        })
 }
 
 // === LLM LOCAL (MISTRAL) ===
@@ -226,17 +222,48 @@ pub async fn translate_text(text: String, target_language: String) -> Result<Str
 #[tauri::command]
 pub fn is_llm_model_available(
     model_manager: State<'_, Arc<ModelManager>>,
-    model_size: LocalLlmModel,
+    model_size: String,
 ) -> bool {
-    model_manager.is_llm_model_available(model_size)
+    model_manager.installed_entry(ModelFamily::Llm, &model_size).is_some()
 }
 
-/// Liste les modèles LLM disponibles
+/// Modèle LLM du catalogue avec son état d'installation
+#[derive(Debug, Clone, Serialize)]
+pub struct LocalLlmModelInfo {
+    /// Identifiant LLM du catalogue
+    pub size: String,
+    pub display_name: String,
+    pub available: bool,
+    pub size_bytes: u64,
+}
+
+/// Liste les modèles LLM du catalogue (modèles importés compris)
+#[tauri::command]
+pub fn get_llm_models(
+    model_manager: State<'_, Arc<ModelManager>>,
+) -> Vec<LocalLlmModelInfo> {
+    model_manager
+        .catalog()
+        .family(ModelFamily::Llm)
+        .map(|entry| LocalLlmModelInfo {
+            size: entry.id.clone(),
+            display_name: entry.display_name.clone(),
+            available: model_manager.is_installed(entry),
+            size_bytes: entry.size_bytes(),
+        })
+        .collect()
+}
+
+/// Liste les modèles LLM installés
 #[tauri::command]
 pub fn get_available_llm_models(
     model_manager: State<'_, Arc<ModelManager>>,
-) -> Vec<LocalLlmModel> {
-    model_manager.available_llm_models()
+) -> Vec<String> {
+    model_manager
+        .installed_entries(ModelFamily::Llm)
+        .into_iter()
+        .map(|entry| entry.id)
+        .collect()
 }
 
 /// Télécharge un modèle LLM
@@ -244,16 +271,18 @@ pub fn get_available_llm_models(
 pub async fn download_llm_model(
     app: tauri::AppHandle,
     model_manager: State<'_, Arc<ModelManager>>,
-    model_size: LocalLlmModel,
+    model_size: String,
 ) -> Result<String, String> {
-    log::info!("download_llm_model called with model_size: {:?}", model_size);
-    println!("[LLM] download_llm_model called with model_size: {:?}", model_size);
+    log::info!("download_llm_model called with model_size: {}", model_size);
+    println!("[LLM] download_llm_model called with model_size: {}", model_size);
 
     let manager = model_manager.inner().clone();
+    let entry = manager.entry(ModelFamily::Llm, &model_size)?;
     let app_clone = app.clone();
 
+    let active = crate::engines::download::register(&crate::engines::download::download_id("llm", &model_size))?;
     let result = manager
-        .download_llm_model(model_size, move |downloaded, total| {
+        .download_entry(&entry, active.token(), move |downloaded, total| {
             let progress = (downloaded as f64 / total as f64 * 100.0) as u32;
             if downloaded % (10 * 1024 * 1024) < 1024 * 1024 {
                 println!("[LLM] Download progress: {}% ({}/{})", progress, downloaded, total);
@@ -285,42 +314,38 @@ pub async fn download_llm_model(
 #[tauri::command]
 pub async fn delete_llm_model(
     model_manager: State<'_, Arc<ModelManager>>,
-    model_size: LocalLlmModel,
+    model_size: String,
 ) -> Result<(), String> {
-    model_manager.delete_llm_model(model_size).await
+    let entry = model_manager.entry(ModelFamily::Llm, &model_size)?;
+    model_manager.delete_entry(&entry).await?;
+    // Un modèle importé n'a pas de source : on le retire aussi du catalogue
+    if !entry.is_downloadable() {
+        catalog::remove_user_entry(ModelFamily::Llm, &model_size)?;
+    }
+    Ok(())
 }
 
 /// Résume un texte avec le modèle local Mistral
 #[tauri::command]
 pub async fn summarize_text_local(
     model_manager: State<'_, Arc<ModelManager>>,
-    llm_engine: State<'_, Arc<RwLock<Option<LocalLlmEngine>>>>,
+    llm_engine: State<'_, SharedLocalEngine>,
     text: String,
 ) -> Result<String, String> {
     let settings = config::load_settings();
 
     // Vérifier que le modèle est disponible
-    let model_path = model_manager
-        .get_llm_model_path(settings.local_llm_model)
-        .ok_or_else(|| format!(
+    let entry = model_manager.entry(ModelFamily::Llm, &settings.local_llm_model)?;
+    if !model_manager.is_installed(&entry) {
+        return Err(format!(
             "Modèle LLM {} non installé. Téléchargez-le dans les paramètres.",
-            settings.local_llm_model.display_name()
-        ))?;
-
-    // Charger le moteur si nécessaire
-    {
-        let engine_read = llm_engine.read().await;
-        if engine_read.is_none() {
-            drop(engine_read);
-            let mut engine_write = llm_engine.write().await;
-            if engine_write.is_none() {
-                log::info!("Initializing Local LLM engine...");
-                let engine = LocalLlmEngine::new(&model_path, settings.local_llm_model)?;
-                *engine_write = Some(engine);
-            }
-        }
+            entry.display_name
+        ));
     }
 
+    // Charger le moteur si nécessaire (ou changer de modèle)
+    llm::local_engine::ensure_loaded(llm_engine.inner(), &entry.id).await?;
+
     // Effectuer le résumé
     let engine_read = llm_engine.read().await;
     let engine = engine_read.as_ref().ok_or("LLM engine not initialized")?;
@@ -336,7 +361,7 @@ pub async fn summarize_text_local(
 #[tauri::command]
 pub async fn summarize_text_smart(
     model_manager: State<'_, Arc<ModelManager>>,
-    llm_engine: State<'_, Arc<RwLock<Option<LocalLlmEngine>>>>,
+    llm_engine: State<'_, SharedLocalEngine>,
     text: String,
     provider: Option<LlmProvider>,
 ) -> Result<String, String> {
@@ -347,8 +372,8 @@ pub async fn summarize_text_smart(
         LlmProvider::Local => {
             summarize_text_local(model_manager, llm_engine, text).await
         }
-        LlmProvider::Groq => {
-            summarize_text(text).await
+        LlmProvider::Groq | LlmProvider::OpenAiCompatible => {
+            summarize_text_remote(text, use_provider).await
         }
     }
 }
@@ -1,8 +1,11 @@
 pub mod audio;
 pub mod file_transcription;
+pub mod history;
 pub mod integrations;
 pub mod llm;
 pub mod models;
+pub mod prompts;
+pub mod server;
 pub mod settings;
 pub mod snippets;
 pub mod stats;
@@ -10,9 +13,12 @@ pub mod system_integration;
 pub mod transcription;
 
 pub use audio::*;
+pub use history::*;
 pub use integrations::*;
 pub use llm::*;
 pub use models::*;
+pub use prompts::*;
+pub use server::*;
 pub use settings::*;
 pub use snippets::*;
 pub use stats::*;
@@ -1,16 +1,30 @@
 use tauri::{AppHandle, Emitter, State};
+use crate::engines::catalog::{self, CatalogEntry, ModelCatalog, ModelFamily};
+use crate::engines::download;
 use crate::state::AppState;
-use crate::types::{EngineType, ModelSize, ParakeetModelSize, VoskLanguage};
+use crate::types::EngineType;
 use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelInfo {
-    pub size: ModelSize,
+    /// Identifiant Whisper du catalogue
+    pub size: String,
     pub display_name: String,
     pub available: bool,
     pub size_bytes: u64,
+    pub languages: Vec<String>,
+    pub license: Option<String>,
+}
+
+/// Entrée du catalogue avec son état d'installation
+#[derive(Debug, Clone, Serialize)]
+pub struct CatalogModelInfo {
+    #[serde(flatten)]
+    pub entry: CatalogEntry,
+    pub size_bytes: u64,
+    pub available: bool,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -22,33 +36,73 @@ pub struct DownloadProgress {
 
 #[tauri::command]
 pub fn get_available_models(state: State<'_, AppState>) -> Vec<ModelInfo> {
-    [ModelSize::Tiny, ModelSize::Small, ModelSize::Medium]
-        .into_iter()
-        .map(|size| ModelInfo {
-            size,
-            display_name: size.display_name().to_string(),
-            available: state.model_manager.is_model_available(size),
-            size_bytes: size.size_bytes(),
+    state
+        .model_manager
+        .catalog()
+        .family(ModelFamily::Whisper)
+        .map(|entry| ModelInfo {
+            size: entry.id.clone(),
+            display_name: entry.display_name.clone(),
+            available: state.model_manager.is_installed(entry),
+            size_bytes: entry.size_bytes(),
+            languages: entry.languages.clone(),
+            license: entry.license.clone(),
         })
         .collect()
 }
 
 #[tauri::command]
-pub fn get_current_model(state: State<'_, AppState>) -> Result<ModelSize, String> {
+pub fn get_current_model(state: State<'_, AppState>) -> Result<String, String> {
     let settings = state.settings.read().map_err(|e| e.to_string())?;
-    Ok(settings.whisper_model)
+    Ok(settings.whisper_model.clone())
+}
+
+/// Catalogue complet (toutes familles) avec l'état d'installation
+#[tauri::command]
+pub fn get_model_catalog(state: State<'_, AppState>) -> Vec<CatalogModelInfo> {
+    state
+        .model_manager
+        .catalog()
+        .models
+        .iter()
+        .map(|entry| CatalogModelInfo {
+            entry: entry.clone(),
+            size_bytes: entry.size_bytes(),
+            available: state.model_manager.is_installed(entry),
+        })
+        .collect()
+}
+
+/// Télécharge le manifeste distant configuré (ou relit les manifestes locaux)
+#[tauri::command]
+pub async fn refresh_model_catalog(state: State<'_, AppState>) -> Result<usize, String> {
+    let url = state
+        .settings
+        .read()
+        .map_err(|e| e.to_string())?
+        .model_catalog_url
+        .clone()
+        .filter(|url| !url.trim().is_empty());
+
+    let catalog: std::sync::Arc<ModelCatalog> = match url {
+        Some(url) => catalog::refresh_remote(url.trim()).await?,
+        None => catalog::reload(),
+    };
+    log::info!("Model catalog refreshed: {} entries", catalog.models.len());
+    Ok(catalog.models.len())
 }
 
 #[tauri::command]
 pub async fn download_model(
     app: AppHandle,
     state: State<'_, AppState>,
-    size: ModelSize,
+    size: String,
 ) -> Result<(), String> {
     let model_manager = state.model_manager.clone();
+    let entry = model_manager.entry(ModelFamily::Whisper, &size)?;
 
     let downloaded = Arc::new(AtomicU64::new(0));
-    let total = Arc::new(AtomicU64::new(size.size_bytes()));
+    let total = Arc::new(AtomicU64::new(entry.size_bytes()));
     let app_clone = app.clone();
     let downloaded_clone = downloaded.clone();
     let total_clone = total.clone();
@@ -66,8 +120,9 @@ pub async fn download_model(
         let _ = app_clone.emit("model-download-progress", progress);
     };
 
+    let active = download::register(&download::download_id("whisper", &size))?;
     model_manager
-        .download_model(size, progress_callback)
+        .download_entry(&entry, active.token(), progress_callback)
         .await?;
 
     let _ = app.emit("model-download-complete", size);
@@ -75,15 +130,78 @@ pub async fn download_model(
     Ok(())
 }
 
+/// Annule un téléchargement de modèle (`whisper:<taille>`, `vosk:<langue>`,
+/// `parakeet:<taille>` ou `llm:<modèle>`). La reprise se fera au prochain essai.
 #[tauri::command]
-pub async fn delete_model(state: State<'_, AppState>, size: ModelSize) -> Result<(), String> {
-    state.model_manager.delete_model(size).await
+pub fn cancel_model_download(download_id: String) -> bool {
+    log::info!("Cancelling download {}", download_id);
+    download::cancel(&download_id)
 }
 
 #[tauri::command]
-pub fn switch_model(state: State<'_, AppState>, size: ModelSize) -> Result<(), String> {
-    if !state.model_manager.is_model_available(size) {
-        return Err(format!("Model {:?} is not available. Please download it first.", size));
+pub async fn delete_model(state: State<'_, AppState>, size: String) -> Result<(), String> {
+    state.model_manager.delete_model(&size).await?;
+    // Un modèle importé n'a pas de source : on le retire aussi du catalogue
+    let entry = state.model_manager.entry(ModelFamily::Whisper, &size)?;
+    if !entry.is_downloadable() {
+        catalog::remove_user_entry(ModelFamily::Whisper, &size)?;
+    }
+    Ok(())
+}
+
+/// Importe un modèle présent sur disque (fichier ggml/gguf, dossier ONNX ou Vosk).
+/// `id` cible une entrée du catalogue (ex. `fr` pour Vosk) ; un `id` inconnu
+/// ou absent enregistre la source comme nouveau modèle.
+#[tauri::command]
+pub async fn import_model(
+    state: State<'_, AppState>,
+    path: String,
+    engine: ModelFamily,
+    id: Option<String>,
+    link: Option<bool>,
+) -> Result<CatalogModelInfo, String> {
+    let entry = state
+        .model_manager
+        .import_model(engine, std::path::Path::new(&path), id.as_deref(), link.unwrap_or(false))
+        .await?;
+    log::info!("Imported {:?} model {} from {}", engine, entry.id, path);
+
+    // Recharger le moteur si le modèle importé est celui en cours d'utilisation
+    let engine_type = {
+        let settings = state.settings.read().map_err(|e| e.to_string())?;
+        let in_use = match (settings.engine_type, engine) {
+            (EngineType::Whisper, ModelFamily::Whisper) => settings.whisper_model == entry.id,
+            (EngineType::Vosk, ModelFamily::Vosk) => settings.vosk_language.as_deref() == Some(entry.id.as_str()),
+            (EngineType::Parakeet, ModelFamily::Parakeet) => settings.parakeet_model == entry.id,
+            _ => false,
+        };
+        in_use.then_some(settings.engine_type)
+    };
+    if let Some(engine_type) = engine_type {
+        if let Err(e) = state.switch_engine_type(engine_type) {
+            log::warn!("Failed to reload engine after import: {}", e);
+        }
+    }
+    // Le LLM local se recharge à la prochaine requête
+    if engine == ModelFamily::Llm {
+        let llm_engine = crate::llm::local_engine::shared_engine();
+        let mut loaded = llm_engine.write().await;
+        if loaded.as_ref().is_some_and(|e| e.model_id() == entry.id) {
+            *loaded = None;
+        }
+    }
+
+    Ok(CatalogModelInfo {
+        size_bytes: entry.size_bytes(),
+        available: state.model_manager.is_installed(&entry),
+        entry,
+    })
+}
+
+#[tauri::command]
+pub fn switch_model(state: State<'_, AppState>, size: String) -> Result<(), String> {
+    if !state.model_manager.is_model_available(&size) {
+        return Err(format!("Model {} is not available. Please download it first.", size));
     }
 
     let settings = state.settings.read().map_err(|e| e.to_string())?;
@@ -94,7 +212,7 @@ pub fn switch_model(state: State<'_, AppState>, size: ModelSize) -> Result<(), S
     };
     drop(settings);
 
-    state.reload_engine(size, language)?;
+    state.reload_engine(&size, language)?;
 
     let mut settings = state.settings.write().map_err(|e| e.to_string())?;
     settings.whisper_model = size;
@@ -115,21 +233,22 @@ pub fn is_engine_ready(state: State<'_, AppState>) -> bool {
 
 #[derive(Debug, Clone, Serialize)]
 pub struct VoskModelInfo {
-    pub language: VoskLanguage,
+    /// Identifiant Vosk du catalogue
+    pub language: String,
     pub display_name: String,
     pub available: bool,
 }
 
 #[tauri::command]
 pub fn get_vosk_models(state: State<'_, AppState>) -> Vec<VoskModelInfo> {
-    use VoskLanguage::*;
-
-    [En, Fr, De, Es, It, Ru, Zh, Ja, Ko, Pt, Nl, Pl, Uk, Tr, Vi, Ar, Hi, Fa, Ca, Cs]
-        .into_iter()
-        .map(|lang| VoskModelInfo {
-            language: lang,
-            display_name: lang.display_name().to_string(),
-            available: state.model_manager.get_vosk_model_path(lang).is_some(),
+    state
+        .model_manager
+        .catalog()
+        .family(ModelFamily::Vosk)
+        .map(|entry| VoskModelInfo {
+            language: entry.id.clone(),
+            display_name: entry.display_name.clone(),
+            available: state.model_manager.is_installed(entry),
         })
         .collect()
 }
@@ -138,9 +257,10 @@ pub fn get_vosk_models(state: State<'_, AppState>) -> Vec<VoskModelInfo> {
 pub async fn download_vosk_model(
     app: AppHandle,
     state: State<'_, AppState>,
-    language: VoskLanguage,
+    language: String,
 ) -> Result<(), String> {
     let model_manager = state.model_manager.clone();
+    let entry = model_manager.entry(ModelFamily::Vosk, &language)?;
     let app_clone = app.clone();
 
     let downloaded = Arc::new(AtomicU64::new(0));
@@ -161,22 +281,23 @@ pub async fn download_vosk_model(
         let _ = app_clone.emit("vosk-download-progress", progress);
     };
 
+    let active = download::register(&download::download_id("vosk", &language))?;
     model_manager
-        .download_vosk_model(language, progress_callback)
+        .download_entry(&entry, active.token(), progress_callback)
         .await?;
 
-    let _ = app.emit("vosk-download-complete", language);
+    let _ = app.emit("vosk-download-complete", &language);
 
     Ok(())
 }
 
 #[tauri::command]
-pub fn select_vosk_language(state: State<'_, AppState>, language: VoskLanguage) -> Result<(), String> {
-    if state.model_manager.get_vosk_model_path(language).is_none() {
-        return Err(format!("Vosk model for {:?} is not available. Please download it first.", language));
+pub fn select_vosk_language(state: State<'_, AppState>, language: String) -> Result<(), String> {
+    if state.model_manager.installed_entry(ModelFamily::Vosk, &language).is_none() {
+        return Err(format!("Vosk model {} is not available. Please download it first.", language));
     }
 
-    state.reload_vosk_engine(language)?;
+    state.reload_vosk_engine(&language)?;
 
     let mut settings = state.settings.write().map_err(|e| e.to_string())?;
     settings.vosk_language = Some(language);
@@ -223,7 +344,8 @@ pub fn is_parakeet_available(_state: State<'_, AppState>) -> bool {
 
 #[derive(Debug, Clone, Serialize)]
 pub struct ParakeetModelInfo {
-    pub size: ParakeetModelSize,
+    /// Identifiant Parakeet du catalogue
+    pub size: String,
     pub display_name: String,
     pub available: bool,
     pub size_bytes: u64,
@@ -231,24 +353,28 @@ pub struct ParakeetModelInfo {
 
 #[tauri::command]
 pub fn get_parakeet_models(state: State<'_, AppState>) -> Vec<ParakeetModelInfo> {
-    vec![
-        ParakeetModelInfo {
-            size: ParakeetModelSize::Tdt06bV3,
-            display_name: "Parakeet TDT 0.6B v3".to_string(),
-            available: state.model_manager.get_parakeet_model_path(ParakeetModelSize::Tdt06bV3).is_some(),
-            size_bytes: 1_200_000_000, // ~1.2 GB
-        },
-    ]
+    state
+        .model_manager
+        .catalog()
+        .family(ModelFamily::Parakeet)
+        .map(|entry| ParakeetModelInfo {
+            size: entry.id.clone(),
+            display_name: entry.display_name.clone(),
+            available: state.model_manager.is_installed(entry),
+            size_bytes: entry.size_bytes(),
+        })
+        .collect()
 }
 
 #[tauri::command]
 pub async fn download_parakeet_model(
     app: AppHandle,
     state: State<'_, AppState>,
-    size: ParakeetModelSize,
+    size: String,
 ) -> Result<(), String> {
-    log::info!("download_parakeet_model called with size: {:?}", size);
+    log::info!("download_parakeet_model called with size: {}", size);
     let model_manager = state.model_manager.clone();
+    let entry = model_manager.entry(ModelFamily::Parakeet, &size)?;
     let app_clone = app.clone();
 
     let downloaded = Arc::new(AtomicU64::new(0));
@@ -269,17 +395,19 @@ pub async fn download_parakeet_model(
         let _ = app_clone.emit("parakeet-download-progress", progress);
     };
 
+    let active = download::register(&download::download_id("parakeet", &size))?;
     model_manager
-        .download_parakeet_model(size, progress_callback)
+        .download_entry(&entry, active.token(), progress_callback)
         .await?;
+    drop(active);
 
-    let _ = app.emit("parakeet-download-complete", size);
+    let _ = app.emit("parakeet-download-complete", &size);
 
     // Si Parakeet est le moteur sélectionné, charger l'engine automatiquement
     let settings = state.settings.read().map_err(|e| e.to_string())?;
     if settings.engine_type == EngineType::Parakeet {
         drop(settings);
-        if let Err(e) = state.reload_parakeet_engine(size) {
+        if let Err(e) = state.reload_parakeet_engine(&size) {
             log::warn!("Failed to load Parakeet engine after download: {}", e);
         } else {
             log::info!("Parakeet engine loaded automatically after download");
@@ -290,17 +418,22 @@ pub async fn download_parakeet_model(
 }
 
 #[tauri::command]
-pub async fn delete_parakeet_model(state: State<'_, AppState>, size: ParakeetModelSize) -> Result<(), String> {
-    state.model_manager.delete_parakeet_model(size).await
+pub async fn delete_parakeet_model(state: State<'_, AppState>, size: String) -> Result<(), String> {
+    let entry = state.model_manager.entry(ModelFamily::Parakeet, &size)?;
+    state.model_manager.delete_entry(&entry).await?;
+    if !entry.is_downloadable() {
+        catalog::remove_user_entry(ModelFamily::Parakeet, &size)?;
+    }
+    Ok(())
 }
 
 #[tauri::command]
-pub fn select_parakeet_model(state: State<'_, AppState>, size: ParakeetModelSize) -> Result<(), String> {
-    if state.model_manager.get_parakeet_model_path(size).is_none() {
-        return Err(format!("Parakeet model {:?} is not available. Please download it first.", size));
+pub fn select_parakeet_model(state: State<'_, AppState>, size: String) -> Result<(), String> {
+    if state.model_manager.installed_entry(ModelFamily::Parakeet, &size).is_none() {
+        return Err(format!("Parakeet model {} is not available. Please download it first.", size));
     }
 
-    state.reload_parakeet_engine(size)?;
+    state.reload_parakeet_engine(&size)?;
 
     let mut settings = state.settings.write().map_err(|e| e.to_string())?;
     settings.parakeet_model = size;
@@ -0,0 +1,77 @@
+use tauri::{AppHandle, State};
+
+use crate::hotkeys::parse_hotkey;
+use crate::state::AppState;
+use crate::storage::{config, prompts};
+use crate::types::PromptProfile;
+
+/// Raccourci reconnu et libre parmi les raccourcis des réglages
+fn check_hotkey(state: &AppState, profile: &PromptProfile) -> Result<(), String> {
+    let Some(hotkey) = profile.hotkey.as_deref().map(str::trim).filter(|h| !h.is_empty()) else {
+        return Ok(());
+    };
+    if parse_hotkey(hotkey).is_none() {
+        return Err(format!("Invalid hotkey format: {}", hotkey));
+    }
+    let settings = state.settings.read().map_err(|e| e.to_string())?;
+    match config::hotkey_owner(&settings, hotkey) {
+        Some(field) => Err(format!("Hotkey {} is already used by {}", hotkey, field)),
+        None => Ok(()),
+    }
+}
+
+/// Reporte dans les réglages (profil choisi, règles par application) le
+/// renommage d'un profil, ou sa suppression quand `to` est `None`
+fn update_profile_references(state: &AppState, from: &str, to: Option<&str>) -> Result<(), String> {
+    let mut guard = state.settings.write().map_err(|e| e.to_string())?;
+    let settings = &mut *guard;
+    let mut changed = false;
+    let references = std::iter::once(&mut settings.llm_profile)
+        .chain(settings.app_rules.iter_mut().map(|rule| &mut rule.llm_profile));
+    for reference in references.filter(|r| r.as_deref() == Some(from)) {
+        *reference = to.map(str::to_string);
+        changed = true;
+    }
+    if changed {
+        config::save_settings(settings)?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_prompt_profiles() -> Result<Vec<PromptProfile>, String> {
+    Ok(prompts::load_profiles().profiles)
+}
+
+#[tauri::command]
+pub fn add_prompt_profile(app: AppHandle, state: State<'_, AppState>, profile: PromptProfile) -> Result<(), String> {
+    check_hotkey(&state, &profile)?;
+    prompts::add_profile(profile)?;
+    crate::ptt::register_profile_shortcuts(&app);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn update_prompt_profile(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    id: String,
+    profile: PromptProfile,
+) -> Result<(), String> {
+    check_hotkey(&state, &profile)?;
+    let new_id = profile.id.clone();
+    prompts::update_profile(&id, profile)?;
+    if new_id != id {
+        update_profile_references(&state, &id, Some(&new_id))?;
+    }
+    crate::ptt::register_profile_shortcuts(&app);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn remove_prompt_profile(app: AppHandle, state: State<'_, AppState>, id: String) -> Result<(), String> {
+    prompts::remove_profile(&id)?;
+    update_profile_references(&state, &id, None)?;
+    crate::ptt::register_profile_shortcuts(&app);
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use crate::server::{self, LocalServerStatus};
+
+/// État du serveur local (adresse, dernière erreur de démarrage)
+#[tauri::command]
+pub fn get_local_server_status() -> LocalServerStatus {
+    server::status()
+}
+
+/// Jeton d'accès au serveur local, créé s'il n'existe pas encore
+#[tauri::command]
+pub fn get_local_server_token() -> Result<String, String> {
+    server::token()
+}
+
+/// Invalide l'ancien jeton et en génère un nouveau
+#[tauri::command]
+pub fn regenerate_local_server_token() -> Result<String, String> {
+    server::regenerate_token()
+}
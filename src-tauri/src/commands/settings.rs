@@ -1,9 +1,10 @@
+use std::path::Path;
 use tauri::{AppHandle, State};
 use tauri_plugin_global_shortcut::GlobalShortcutExt;
 use crate::hotkeys::parse_hotkey;
 use crate::state::AppState;
-use crate::storage::{config, dictionary};
-use crate::types::AppSettings;
+use crate::storage::{config, dictionary, prompts, settings_archive, snippets};
+use crate::types::{AppSettings, SettingsError, SettingsImportReport, SettingsLoadReport};
 
 #[tauri::command]
 pub fn get_settings(state: State<'_, AppState>) -> Result<AppSettings, String> {
@@ -18,11 +19,57 @@ pub fn update_settings(
     new_settings: AppSettings
 ) -> Result<(), String> {
     let old_settings = state.settings.read().map_err(|e| e.to_string())?.clone();
+
+    // Refuser les erreurs introduites par cette modification (celles déjà
+    // présentes ne bloquent pas les autres réglages)
+    let previous_errors = validate(&old_settings);
+    let errors: Vec<SettingsError> = validate(&new_settings)
+        .into_iter()
+        .filter(|error| !previous_errors.contains(error))
+        .collect();
+    if !errors.is_empty() {
+        let messages: Vec<String> = errors.iter().map(|e| format!("{}: {}", e.field, e.message)).collect();
+        return Err(messages.join("; "));
+    }
+
+    apply_settings(&app, &state, old_settings, new_settings)
+}
+
+/// `config::validate`, plus les raccourcis déjà pris par un profil de prompts
+fn validate(settings: &AppSettings) -> Vec<SettingsError> {
+    let mut errors = config::validate(settings);
+    for profile in prompts::load_profiles().profiles {
+        let Some(hotkey) = profile.hotkey else {
+            continue;
+        };
+        if let Some(field) = config::hotkey_owner(settings, &hotkey) {
+            errors.push(SettingsError::new(
+                field,
+                format!("Hotkey {} is already used by prompt profile {}", hotkey, profile.name),
+            ));
+        }
+    }
+    errors
+}
+
+/// Enregistre les réglages et applique ce qui a changé (raccourcis, moteur,
+/// serveur local, chiffrement)
+fn apply_settings(
+    app: &AppHandle,
+    state: &AppState,
+    old_settings: AppSettings,
+    new_settings: AppSettings,
+) -> Result<(), String> {
     let ptt_hotkey_changed = old_settings.hotkey_push_to_talk != new_settings.hotkey_push_to_talk;
     let translate_hotkey_changed = old_settings.hotkey_translate != new_settings.hotkey_translate;
     let translation_enabled_changed = old_settings.translation_enabled != new_settings.translation_enabled;
     let engine_type_changed = old_settings.engine_type != new_settings.engine_type;
 
+    // Chiffrer ou déchiffrer le stockage avant d'enregistrer le réglage
+    if old_settings.encrypt_storage != new_settings.encrypt_storage {
+        crate::storage::set_encryption(new_settings.encrypt_storage)?;
+    }
+
     config::save_settings(&new_settings)?;
 
     {
@@ -30,6 +77,14 @@ pub fn update_settings(
         *settings = new_settings.clone();
     }
 
+    if old_settings.model_mirror_url != new_settings.model_mirror_url {
+        state.model_manager.set_mirror_url(new_settings.model_mirror_url.clone());
+    }
+
+    if old_settings.local_server != new_settings.local_server {
+        crate::server::apply_config(state, &new_settings.local_server);
+    }
+
     if engine_type_changed {
         if let Err(e) = state.switch_engine_type(new_settings.engine_type) {
             log::warn!("Failed to switch engine type: {}. Model may need to be downloaded first.", e);
@@ -37,11 +92,15 @@ pub fn update_settings(
     }
 
     if ptt_hotkey_changed {
-        if let Err(e) = update_shortcut(&app, &old_settings.hotkey_push_to_talk, &new_settings.hotkey_push_to_talk) {
+        if let Err(e) = update_shortcut(app, &old_settings.hotkey_push_to_talk, &new_settings.hotkey_push_to_talk) {
             log::warn!("Failed to update PTT shortcut: {}. Restart may be required.", e);
         }
     }
 
+    if old_settings.hotkey_speech_translate != new_settings.hotkey_speech_translate {
+        crate::ptt::register_speech_translate_shortcut(app, &new_settings.hotkey_speech_translate);
+    }
+
     if translation_enabled_changed || translate_hotkey_changed {
         if old_settings.translation_enabled {
             if let Some(old_shortcut) = parse_hotkey(&old_settings.hotkey_translate) {
@@ -62,6 +121,87 @@ pub fn update_settings(
     Ok(())
 }
 
+/// Champs de config.json illisibles au démarrage, remis à leur valeur par défaut
+#[tauri::command]
+pub fn get_settings_load_report() -> Option<SettingsLoadReport> {
+    config::take_load_report()
+}
+
+/// Exporte réglages, dictionnaire, snippets et profils de prompts dans une archive zip
+#[tauri::command]
+pub fn export_settings(path: String) -> Result<(), String> {
+    settings_archive::export_to_file(Path::new(&path))?;
+    log::info!("Settings exported to {}", path);
+    Ok(())
+}
+
+/// Importe une archive de `export_settings`. Les réglages passent par les
+/// migrations ; un champ illisible ou invalide garde sa valeur actuelle.
+#[tauri::command]
+pub fn import_settings(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    path: String,
+) -> Result<SettingsImportReport, String> {
+    let archive = settings_archive::read_file(Path::new(&path))?;
+    let current = state.settings.read().map_err(|e| e.to_string())?.clone();
+
+    // Tout est vérifié avant la première écriture : une archive refusée ne
+    // laisse rien à moitié importé
+    let (imported, mut errors) = config::parse_external(&archive.settings);
+    match archive.prompts {
+        Some(ref data) => {
+            prompts::validate_profiles(data)?;
+            errors.extend(config::validate(&imported));
+        }
+        // Les profils actuels restent : leurs raccourcis comptent
+        None => errors.extend(validate(&imported)),
+    }
+    let imported = config::restore_fields(&imported, &current, &errors);
+    let mut report = SettingsImportReport::default();
+
+    let mut profiles = archive.prompts;
+    if let Some(ref mut data) = profiles {
+        for profile in &mut data.profiles {
+            let hotkey = profile.hotkey.as_deref().map(str::trim).unwrap_or_default();
+            if !hotkey.is_empty() && parse_hotkey(hotkey).is_none() {
+                errors.push(SettingsError::new(
+                    &format!("prompt_profiles.{}.hotkey", profile.id),
+                    format!("Invalid hotkey format: {}", hotkey),
+                ));
+                profile.hotkey = None;
+            } else if let Some(field) = config::hotkey_owner(&imported, hotkey) {
+                errors.push(SettingsError::new(
+                    &format!("prompt_profiles.{}.hotkey", profile.id),
+                    format!("Hotkey {} is already used by {}", hotkey, field),
+                ));
+                profile.hotkey = None;
+            }
+        }
+    }
+
+    // Les réglages d'abord : leur application peut encore échouer (chiffrement)
+    apply_settings(&app, &state, current, imported)?;
+    if let Some(data) = archive.dictionary {
+        report.dictionary_words = data.words.len();
+        dictionary::save_dictionary(&data)?;
+    }
+    if let Some(data) = archive.snippets {
+        report.snippets = data.snippets.len();
+        snippets::save_snippets(&data)?;
+    }
+    if let Some(data) = profiles {
+        report.prompt_profiles = data.profiles.len();
+        prompts::save_profiles(&data)?;
+    }
+    state.refresh_vocabulary()?;
+    crate::ptt::register_profile_shortcuts(&app);
+
+    log::info!("Settings imported from {} ({} fields kept)", path, errors.len());
+    report.errors = errors;
+    Ok(report)
+}
+
 /// Met à jour un raccourci dynamiquement
 fn update_shortcut(app: &AppHandle, old_hotkey: &str, new_hotkey: &str) -> Result<(), String> {
     if let Some(old_shortcut) = parse_hotkey(old_hotkey) {
@@ -86,11 +226,13 @@ pub fn get_dictionary() -> Result<Vec<String>, String> {
 }
 
 #[tauri::command]
-pub fn add_dictionary_word(word: String) -> Result<(), String> {
-    dictionary::add_word(word)
+pub fn add_dictionary_word(state: State<'_, AppState>, word: String) -> Result<(), String> {
+    dictionary::add_word(word)?;
+    state.refresh_vocabulary()
 }
 
 #[tauri::command]
-pub fn remove_dictionary_word(word: String) -> Result<(), String> {
-    dictionary::remove_word(&word)
+pub fn remove_dictionary_word(state: State<'_, AppState>, word: String) -> Result<(), String> {
+    dictionary::remove_word(&word)?;
+    state.refresh_vocabulary()
 }
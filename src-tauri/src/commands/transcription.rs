@@ -1,24 +1,26 @@
 use std::sync::{mpsc, Arc, Mutex, RwLock};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use tauri::{AppHandle, Emitter, State};
 use serde::Serialize;
 use crate::engines::SpeechEngine;
 use crate::state::AppState;
-use crate::storage::history;
+use crate::storage::{history, recordings};
 use crate::types::TranscriptionResult;
-use crate::audio::AudioCapture;
-use crate::voice_commands;
-use crate::llm;
+use crate::audio::{diarization, vad, AudioCapture, StreamingTranscriber};
+use crate::dictation::{self, DictationContext};
 
 /// Taux d'échantillonnage requis par Whisper
 const TARGET_SAMPLE_RATE: u32 = 16000;
 
-/// Durée d'un chunk en secondes pour le streaming
-const STREAMING_CHUNK_DURATION_SECS: f32 = 2.5;
-
 /// État global pour le streaming
 static STREAMING_ACTIVE: AtomicBool = AtomicBool::new(false);
 
+/// Mode mains libres : arrêt automatique après un silence prolongé
+static HANDS_FREE_ACTIVE: AtomicBool = AtomicBool::new(false);
+static TRAILING_SILENCE_MS: AtomicU32 = AtomicU32::new(0);
+/// Moniteur de silence actif : un seul à la fois, même après un arrêt et une reprise rapides
+static SILENCE_MONITOR_SESSION: AtomicU32 = AtomicU32::new(0);
+
 /// Buffer audio partagé pour le streaming (clone du buffer interne pendant l'enregistrement)
 static STREAMING_BUFFER: Mutex<Option<Arc<RwLock<Vec<f32>>>>> = Mutex::new(None);
 static STREAMING_SAMPLE_RATE: Mutex<u32> = Mutex::new(16000);
@@ -26,7 +28,8 @@ static STREAMING_SAMPLE_RATE: Mutex<u32> = Mutex::new(16000);
 /// Channels pour communiquer avec le thread audio
 static AUDIO_CMD_SENDER: Mutex<Option<mpsc::Sender<AudioCommand>>> = Mutex::new(None);
 static AUDIO_RESULT_RECEIVER: Mutex<Option<mpsc::Receiver<AudioResult>>> = Mutex::new(None);
-static AUDIO_SNAPSHOT_SENDER: Mutex<Option<mpsc::Sender<()>>> = Mutex::new(None);
+/// Demande de snapshot : premier échantillon voulu
+static AUDIO_SNAPSHOT_SENDER: Mutex<Option<mpsc::Sender<usize>>> = Mutex::new(None);
 static AUDIO_SNAPSHOT_RECEIVER: Mutex<Option<mpsc::Receiver<(Vec<f32>, u32)>>> = Mutex::new(None);
 
 /// Commandes pour le thread audio
@@ -66,7 +69,7 @@ fn emit_streaming_chunk(app: &AppHandle, chunk: StreamingChunkEvent) {
 pub fn init_gui_audio_thread() {
     let (cmd_tx, cmd_rx) = mpsc::channel::<AudioCommand>();
     let (result_tx, result_rx) = mpsc::channel::<AudioResult>();
-    let (snapshot_req_tx, snapshot_req_rx) = mpsc::channel::<()>();
+    let (snapshot_req_tx, snapshot_req_rx) = mpsc::channel::<usize>();
     let (snapshot_res_tx, snapshot_res_rx) = mpsc::channel::<(Vec<f32>, u32)>();
 
     // Stocker les channels
@@ -89,10 +92,16 @@ pub fn init_gui_audio_thread() {
         let mut capture: Option<AudioCapture> = None;
 
         loop {
+            // Publier la durée du silence courant pour le mode mains libres
+            TRAILING_SILENCE_MS.store(
+                capture.as_mut().map(|cap| cap.trailing_silence_ms()).unwrap_or(0),
+                Ordering::Relaxed,
+            );
+
             // Vérifier les demandes de snapshot (non-bloquant)
-            if let Ok(()) = snapshot_req_rx.try_recv() {
+            if let Ok(from) = snapshot_req_rx.try_recv() {
                 if let Some(ref cap) = capture {
-                    let (audio, sample_rate) = cap.get_audio_snapshot();
+                    let (audio, sample_rate) = cap.get_audio_snapshot(from);
                     let _ = snapshot_res_tx.send((audio, sample_rate));
                 } else {
                     let _ = snapshot_res_tx.send((vec![], 16000));
@@ -139,7 +148,7 @@ pub fn init_gui_audio_thread() {
                 }
                 Ok(AudioCommand::GetSnapshot) => {
                     if let Some(ref cap) = capture {
-                        let (audio, sample_rate) = cap.get_audio_snapshot();
+                        let (audio, sample_rate) = cap.get_audio_snapshot(0);
                         let _ = snapshot_res_tx.send((audio, sample_rate));
                     } else {
                         let _ = snapshot_res_tx.send((vec![], 16000));
@@ -167,6 +176,7 @@ pub fn start_recording(app: AppHandle, state: State<'_, AppState>) -> Result<(),
     let settings = state.settings.read().map_err(|e| e.to_string())?;
     let device_id = settings.microphone_id.clone();
     let streaming_enabled = settings.streaming_enabled;
+    let hands_free = settings.hands_free_enabled.then_some(settings.hands_free_silence_ms);
     drop(settings);
 
     // Envoyer la commande de démarrage au thread audio
@@ -181,6 +191,8 @@ pub fn start_recording(app: AppHandle, state: State<'_, AppState>) -> Result<(),
 
     *is_recording = true;
     STREAMING_ACTIVE.store(streaming_enabled, Ordering::SeqCst);
+    TRAILING_SILENCE_MS.store(0, Ordering::Relaxed);
+    HANDS_FREE_ACTIVE.store(hands_free.is_some(), Ordering::SeqCst);
 
     // Émettre le statut d'enregistrement
     emit_recording_status(&app, "recording");
@@ -194,101 +206,109 @@ pub fn start_recording(app: AppHandle, state: State<'_, AppState>) -> Result<(),
         });
     }
 
-    log::info!("Recording started (streaming: {})", streaming_enabled);
+    // Surveiller le silence en mode mains libres
+    if let Some(silence_ms) = hands_free {
+        let app_clone = app.clone();
+        let session = SILENCE_MONITOR_SESSION.fetch_add(1, Ordering::SeqCst) + 1;
+        std::thread::spawn(move || {
+            run_silence_monitor(app_clone, silence_ms, session);
+        });
+    }
+
+    log::info!("Recording started (streaming: {}, hands-free: {:?})", streaming_enabled, hands_free);
     Ok(())
 }
 
+/// Prévient l'interface quand le silence dépasse `silence_ms` après avoir
+/// entendu de la parole ; le frontend arrête alors l'enregistrement.
+/// S'arrête dès qu'un enregistrement plus récent lance son propre moniteur.
+fn run_silence_monitor(app: AppHandle, silence_ms: u32, session: u32) {
+    while HANDS_FREE_ACTIVE.load(Ordering::SeqCst) && SILENCE_MONITOR_SESSION.load(Ordering::SeqCst) == session {
+        std::thread::sleep(std::time::Duration::from_millis(100));
+
+        if TRAILING_SILENCE_MS.load(Ordering::Relaxed) >= silence_ms {
+            log::info!("Hands-free: {}ms of silence, stopping", silence_ms);
+            HANDS_FREE_ACTIVE.store(false, Ordering::SeqCst);
+            let _ = app.emit("recording-silence-detected", silence_ms);
+        }
+    }
+}
+
+/// Demande au thread audio une copie de l'audio enregistré à partir de l'échantillon `from`
+fn request_audio_snapshot(from: usize) -> Option<(Vec<f32>, u32)> {
+    let guard = AUDIO_SNAPSHOT_SENDER.lock().ok();
+    let receiver_guard = AUDIO_SNAPSHOT_RECEIVER.lock().ok();
+
+    if let (Some(ref sender), Some(ref receiver)) = (guard.as_ref().and_then(|g| g.as_ref()), receiver_guard.as_ref().and_then(|g| g.as_ref())) {
+        if sender.send(from).is_ok() {
+            receiver.recv_timeout(std::time::Duration::from_millis(500)).ok()
+        } else {
+            None
+        }
+    } else {
+        None
+    }
+}
+
 /// Tâche de streaming qui transcrit l'audio en temps réel
 fn run_streaming_task(app: AppHandle, state: Arc<RwLock<Option<Box<dyn SpeechEngine>>>>) {
-    log::info!("Streaming task started with real-time transcription");
-
-    let start_time = std::time::Instant::now();
-    let mut last_processed_samples: usize = 0;
-    let chunk_samples = (STREAMING_CHUNK_DURATION_SECS * TARGET_SAMPLE_RATE as f32) as usize;
+    let mut transcriber = match state.read() {
+        Ok(guard) => StreamingTranscriber::new(guard.as_deref()),
+        Err(_) => StreamingTranscriber::new(None),
+    };
+    log::info!("Streaming task started");
 
     while STREAMING_ACTIVE.load(Ordering::SeqCst) {
-        std::thread::sleep(std::time::Duration::from_millis(500));
+        std::thread::sleep(transcriber.poll_interval());
 
         if !STREAMING_ACTIVE.load(Ordering::SeqCst) {
             break;
         }
 
-        // Demander un snapshot audio
-        let snapshot = {
-            let guard = AUDIO_SNAPSHOT_SENDER.lock().ok();
-            let receiver_guard = AUDIO_SNAPSHOT_RECEIVER.lock().ok();
-
-            if let (Some(ref sender), Some(ref receiver)) = (guard.as_ref().and_then(|g| g.as_ref()), receiver_guard.as_ref().and_then(|g| g.as_ref())) {
-                if sender.send(()).is_ok() {
-                    receiver.recv_timeout(std::time::Duration::from_millis(500)).ok()
-                } else {
-                    None
-                }
-            } else {
-                None
-            }
+        let Some((audio, sample_rate)) = request_audio_snapshot(transcriber.received_samples()) else {
+            continue;
+        };
+        transcriber.push_audio(&audio, sample_rate);
+
+        let progress = match state.read() {
+            Ok(guard) => match guard.as_ref() {
+                Some(engine) => transcriber.step(engine.as_ref()),
+                None => continue,
+            },
+            Err(_) => continue,
         };
 
-        if let Some((audio, sample_rate)) = snapshot {
-            let elapsed = start_time.elapsed().as_secs_f32();
-
-            // Calculer combien de nouveaux échantillons nous avons
-            let current_samples = audio.len();
-            let new_samples = current_samples.saturating_sub(last_processed_samples);
-
-            // Si nous avons assez de nouveaux échantillons pour un chunk
-            if new_samples >= chunk_samples && current_samples >= chunk_samples {
-                // Prendre les derniers samples pour la transcription partielle
-                let chunk_start = current_samples.saturating_sub(chunk_samples);
-                let chunk_audio = &audio[chunk_start..];
-
-                // Resampling si nécessaire
-                let resampled = if sample_rate != TARGET_SAMPLE_RATE {
-                    resample_audio(chunk_audio, sample_rate, TARGET_SAMPLE_RATE)
-                } else {
-                    chunk_audio.to_vec()
-                };
-
-                // Transcrire le chunk
-                if let Ok(engine_guard) = state.read() {
-                    if let Some(ref engine) = *engine_guard {
-                        match engine.transcribe(&resampled, TARGET_SAMPLE_RATE) {
-                            Ok(result) => {
-                                if !result.text.trim().is_empty() {
-                                    log::info!("Streaming chunk: '{}'", result.text);
-                                    emit_streaming_chunk(&app, StreamingChunkEvent {
-                                        text: result.text,
-                                        is_final: false,
-                                        duration_seconds: elapsed,
-                                    });
-                                }
-                            }
-                            Err(e) => {
-                                log::warn!("Streaming transcription error: {}", e);
-                            }
-                        }
-                    }
+        match progress {
+            Ok(Some(progress)) => {
+                if !progress.newly_committed.is_empty() {
+                    log::info!("Streaming committed: '{}'", progress.newly_committed);
                 }
-
-                last_processed_samples = current_samples;
-            } else {
+                emit_streaming_chunk(&app, StreamingChunkEvent {
+                    text: progress.display_text(),
+                    is_final: false,
+                    duration_seconds: transcriber.duration_secs(),
+                });
+            }
+            Ok(None) => {
                 // Émettre juste la durée pour indiquer que le streaming est actif
                 emit_streaming_chunk(&app, StreamingChunkEvent {
-                    text: String::new(),
+                    text: transcriber.committed_text(),
                     is_final: false,
-                    duration_seconds: elapsed,
+                    duration_seconds: transcriber.duration_secs(),
                 });
             }
+            Err(e) => log::warn!("Streaming transcription error: {}", e),
         }
     }
 
-    log::info!("Streaming task ended after {:.1}s", start_time.elapsed().as_secs_f32());
+    log::info!("Streaming task ended after {:.1}s", transcriber.duration_secs());
 }
 
 #[tauri::command]
 pub async fn stop_recording(app: AppHandle, state: State<'_, AppState>) -> Result<TranscriptionResult, String> {
     // Arrêter la tâche de streaming
     STREAMING_ACTIVE.store(false, Ordering::SeqCst);
+    HANDS_FREE_ACTIVE.store(false, Ordering::SeqCst);
 
     // Émettre le statut "processing"
     emit_recording_status(&app, "processing");
@@ -356,8 +376,13 @@ pub async fn stop_recording(app: AppHandle, state: State<'_, AppState>) -> Resul
         audio_buffer
     };
 
+    // Supprimer le silence en début et fin d'enregistrement
+    let settings_snapshot = state.settings.read().map_err(|e| e.to_string())?.clone();
+    let retained_audio = settings_snapshot.audio_retention.enabled.then(|| resampled_audio.clone());
+    let resampled_audio = vad::trim_if_enabled(resampled_audio, TARGET_SAMPLE_RATE, &settings_snapshot);
+
     // Transcription
-    let result = {
+    let mut result = {
         let engine_guard = state.engine.read().map_err(|e| e.to_string())?;
         let engine = engine_guard
             .as_ref()
@@ -365,48 +390,14 @@ pub async fn stop_recording(app: AppHandle, state: State<'_, AppState>) -> Resul
         engine.transcribe(&resampled_audio, TARGET_SAMPLE_RATE)?
     };
 
-    // Lire les settings pour le post-processing
-    let (voice_commands_enabled, dictation_mode, llm_enabled, llm_mode, system_commands_enabled) = {
-        let settings = state.settings.read().map_err(|e| e.to_string())?;
-        (
-            settings.voice_commands_enabled,
-            settings.dictation_mode,
-            settings.llm_enabled,
-            settings.llm_mode,
-            settings.system_commands_enabled,
-        )
-    };
+    // Identifier les intervenants (réunions, appels)
+    diarization::diarize_if_enabled(&mut result, &resampled_audio, TARGET_SAMPLE_RATE, &settings_snapshot);
 
-    // Post-traitement
-    let mut final_text = result.text.clone();
-
-    // Voice commands
-    if voice_commands_enabled {
-        let snippets = crate::storage::snippets::load_snippets().snippets;
-        let parse_result = voice_commands::parse(&final_text, dictation_mode, &snippets, system_commands_enabled);
-        final_text = parse_result.text;
-        if !parse_result.actions.is_empty() {
-            log::info!("Voice commands detected: {:?}", parse_result.actions);
-            voice_commands::execute_actions(&parse_result.actions, &snippets);
-        }
-    }
-
-    // LLM post-processing
-    if llm_enabled {
-        if let Some(api_key) = super::llm::get_groq_api_key_internal() {
-            match llm::process(&final_text, llm_mode, dictation_mode, &api_key).await {
-                Ok(processed) => {
-                    log::info!("LLM processing successful");
-                    final_text = processed;
-                }
-                Err(e) => {
-                    log::warn!("LLM processing failed: {}", e);
-                }
-            }
-        } else {
-            log::warn!("LLM enabled but no API key found");
-        }
-    }
+    // Post-traitement (commandes vocales, LLM)
+    let retention = settings_snapshot.audio_retention.clone();
+    let encrypt_storage = settings_snapshot.encrypt_storage;
+    let context = DictationContext::from_settings(settings_snapshot);
+    let final_text = dictation::post_process(&result.text, &context).await;
 
     // Créer le résultat final
     let final_result = TranscriptionResult {
@@ -417,6 +408,8 @@ pub async fn stop_recording(app: AppHandle, state: State<'_, AppState>) -> Resul
         detected_language: result.detected_language,
         timestamp: result.timestamp,
         model_used: result.model_used,
+        segments: result.segments,
+        translation: result.translation,
     };
 
     // Émettre le chunk final
@@ -429,7 +422,10 @@ pub async fn stop_recording(app: AppHandle, state: State<'_, AppState>) -> Resul
     // Émettre le statut "idle"
     emit_recording_status(&app, "idle");
 
-    history::add_transcription(final_result.clone())?;
+    let history_id = history::add_transcription(final_result.clone(), None)?;
+    if let Some(audio) = retained_audio {
+        recordings::retain(history_id, audio, TARGET_SAMPLE_RATE, retention, encrypt_storage);
+    }
 
     // Record stats
     if state.settings.read().map(|s| s.stats_tracking_enabled).unwrap_or(true) {
@@ -445,16 +441,6 @@ pub async fn stop_recording(app: AppHandle, state: State<'_, AppState>) -> Resul
     Ok(final_result)
 }
 
-#[tauri::command]
-pub fn get_history() -> Result<Vec<TranscriptionResult>, String> {
-    Ok(history::load_history().transcriptions)
-}
-
-#[tauri::command]
-pub fn clear_history() -> Result<(), String> {
-    history::clear_history()
-}
-
 #[tauri::command]
 pub fn get_recording_status(state: State<'_, AppState>) -> Result<bool, String> {
     let is_recording = state.is_recording.read().map_err(|e| e.to_string())?;
@@ -468,6 +454,7 @@ pub fn reset_recording_state(app: AppHandle, state: State<'_, AppState>) -> Resu
 
     // Arrêter le streaming
     STREAMING_ACTIVE.store(false, Ordering::SeqCst);
+    HANDS_FREE_ACTIVE.store(false, Ordering::SeqCst);
 
     // Réinitialiser l'état
     let mut is_recording = state.is_recording.write().map_err(|e| e.to_string())?;
@@ -0,0 +1,175 @@
+//! Contexte d'une dictée : règles par application appliquées au démarrage du
+//! push-to-talk, puis post-traitement commun (commandes vocales, LLM)
+
+use crate::app_context::AppContext;
+use crate::llm;
+use crate::platform::FocusedApp;
+use crate::types::{AppRule, AppSettings, DictationMode, LlmMode};
+use crate::voice_commands;
+
+/// Réglages effectifs d'une dictée
+#[derive(Debug, Clone)]
+pub struct DictationContext {
+    /// Réglages après application de la règle éventuelle
+    pub settings: AppSettings,
+    /// Jeu de commandes vocales reconnu, `None` si désactivé
+    pub voice_commands: Option<DictationMode>,
+    pub app: Option<FocusedApp>,
+    pub rule: Option<AppRule>,
+    /// Contexte transmis au LLM (voir `AppSettings::context_capture`)
+    pub app_context: Option<AppContext>,
+}
+
+impl DictationContext {
+    /// Contexte sans application (dictée lancée depuis la fenêtre principale)
+    pub fn from_settings(settings: AppSettings) -> Self {
+        Self::new(settings, None)
+    }
+
+    pub fn new(settings: AppSettings, app: Option<FocusedApp>) -> Self {
+        let rule = app.as_ref().and_then(|app| match_rule(&settings.app_rules, app)).cloned();
+        let mut settings = settings;
+        let mut voice_commands = settings.voice_commands_enabled.then_some(settings.dictation_mode);
+
+        if let Some(ref rule) = rule {
+            if let Some(mode) = rule.dictation_mode {
+                settings.dictation_mode = mode;
+                voice_commands = voice_commands.map(|_| mode);
+            }
+            if let Some(ref profile) = rule.llm_profile {
+                settings.llm_enabled = true;
+                settings.llm_mode = LlmMode::Profile;
+                settings.llm_profile = Some(profile.clone());
+            }
+            if let Some(ref language) = rule.language {
+                settings.auto_detect_language = language == "auto";
+                if language != "auto" {
+                    settings.transcription_language = language.clone();
+                }
+            }
+            if let Some(set) = rule.voice_commands {
+                voice_commands = set.mode();
+            }
+        }
+
+        Self {
+            settings,
+            voice_commands,
+            app,
+            rule,
+            app_context: None,
+        }
+    }
+
+    /// Le texte final sera réécrit (commandes vocales, LLM) : il ne peut pas
+    /// être tapé au fil de la dictée
+    pub fn rewrites_text(&self) -> bool {
+        self.voice_commands.is_some() || self.settings.llm_enabled
+    }
+
+    /// Langue imposée au moteur, `None` pour la détection automatique
+    pub fn engine_language(&self) -> Option<String> {
+        (!self.settings.auto_detect_language).then(|| self.settings.transcription_language.clone())
+    }
+}
+
+/// Première règle dont le motif apparaît dans l'identifiant de l'application
+pub fn match_rule<'a>(rules: &'a [AppRule], app: &FocusedApp) -> Option<&'a AppRule> {
+    let app_id = app.app_id.to_lowercase();
+    rules.iter().find(|rule| {
+        let pattern = rule.app.trim().to_lowercase();
+        !pattern.is_empty() && app_id.contains(&pattern)
+    })
+}
+
+/// Commandes vocales puis réécriture LLM ; en cas d'échec le texte est conservé
+pub async fn post_process(text: &str, context: &DictationContext) -> String {
+    let settings = &context.settings;
+    let mut final_text = text.to_string();
+
+    if let Some(mode) = context.voice_commands {
+        let snippets = crate::storage::snippets::load_snippets().snippets;
+        let parse_result = voice_commands::parse(&final_text, mode, &snippets, settings.system_commands_enabled);
+        final_text = parse_result.text;
+        if !parse_result.actions.is_empty() {
+            log::info!("Voice commands detected: {:?}", parse_result.actions);
+            voice_commands::execute_actions(&parse_result.actions, &snippets);
+        }
+    }
+
+    if !settings.llm_enabled {
+        return final_text;
+    }
+    let profiles = crate::storage::prompts::load_profiles().profiles;
+    let profile = llm::post_processor::resolve_profile(
+        &profiles,
+        settings.llm_mode,
+        settings.dictation_mode,
+        settings.llm_profile.as_deref(),
+    );
+    let Some(profile) = profile else {
+        return final_text;
+    };
+
+    let provider = profile.provider.unwrap_or(settings.llm_provider);
+    match llm::client_for_settings(settings, provider) {
+        Ok(client) => match llm::process(&final_text, profile, context.app_context.as_ref(), client.as_ref()).await {
+            Ok(processed) => {
+                log::info!("LLM processing successful ({}, {})", profile.name, client.name());
+                processed
+            }
+            Err(e) => {
+                log::warn!("LLM processing failed: {}", e);
+                final_text
+            }
+        },
+        Err(e) => {
+            log::warn!("LLM enabled but provider unavailable: {}", e);
+            final_text
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::VoiceCommandSet;
+
+    fn app(app_id: &str) -> FocusedApp {
+        FocusedApp { app_id: app_id.to_string(), ..Default::default() }
+    }
+
+    #[test]
+    fn test_rules_override_settings() {
+        let mut settings = AppSettings {
+            voice_commands_enabled: true,
+            ..Default::default()
+        };
+        settings.app_rules.push(AppRule {
+            app: "Terminal".to_string(),
+            dictation_mode: None,
+            llm_profile: Some("commit".to_string()),
+            language: Some("en".to_string()),
+            voice_commands: Some(VoiceCommandSet::Off),
+        });
+
+        let code = DictationContext::new(settings.clone(), Some(app("code")));
+        assert_eq!(code.settings.dictation_mode, DictationMode::Code);
+        assert_eq!(code.voice_commands, Some(DictationMode::Code));
+
+        let terminal = DictationContext::new(settings.clone(), Some(app("gnome-terminal-server")));
+        assert_eq!(terminal.settings.llm_mode, LlmMode::Profile);
+        assert_eq!(terminal.settings.llm_profile.as_deref(), Some("commit"));
+        assert_eq!(terminal.engine_language().as_deref(), Some("en"));
+        assert_eq!(terminal.voice_commands, None);
+        // Réécrit par le LLM : pas de frappe au fil de la dictée
+        assert!(terminal.rewrites_text());
+
+        let other = DictationContext::new(settings.clone(), Some(app("firefox")));
+        assert!(other.rule.is_none());
+        assert_eq!(other.settings.dictation_mode, settings.dictation_mode);
+        let plain = AppSettings { voice_commands_enabled: false, llm_enabled: false, ..settings.clone() };
+        assert!(!DictationContext::from_settings(plain).rewrites_text());
+        assert_eq!(DictationContext::from_settings(settings).voice_commands, Some(DictationMode::General));
+    }
+}
@@ -0,0 +1,382 @@
+//! Catalogue des modèles téléchargeables.
+//!
+//! Le manifeste embarqué (`model_catalog.json`) peut être complété ou remplacé
+//! entrée par entrée par un manifeste distant mis en cache
+//! (`models/catalog.remote.json`) puis par un manifeste utilisateur
+//! (`models/catalog.json`).
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, OnceLock, RwLock};
+
+use crate::storage::json_store::write_atomic;
+use crate::types::ChatTemplate;
+
+const BUNDLED_CATALOG: &str = include_str!("model_catalog.json");
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum ModelFamily {
+    Whisper,
+    Vosk,
+    Parakeet,
+    Llm,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogFile {
+    pub name: String,
+    /// Vide pour un modèle importé localement
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+    #[serde(default)]
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogEntry {
+    #[serde(rename = "engine")]
+    pub family: ModelFamily,
+    pub id: String,
+    pub display_name: String,
+    /// Dossier d'installation pour les modèles multi-fichiers ou archivés
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub directory: Option<String>,
+    pub files: Vec<CatalogFile>,
+    /// L'unique fichier est une archive zip à extraire dans `directory`
+    #[serde(default)]
+    pub archive: bool,
+    /// Codes ISO des langues supportées (vide : multilingue)
+    #[serde(default)]
+    pub languages: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    #[serde(default)]
+    pub prefer_gpu: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chat_template: Option<ChatTemplate>,
+}
+
+/// Nom utilisable tel quel sous le dossier des modèles : un seul composant,
+/// ni caché, ni absolu, sans remontée (`..`)
+fn is_path_component(name: &str) -> bool {
+    !name.trim().is_empty()
+        && !name.starts_with('.')
+        && !name.contains(['/', '\\', ':'])
+        && matches!(
+            Path::new(name).components().collect::<Vec<_>>().as_slice(),
+            [Component::Normal(_)]
+        )
+}
+
+impl CatalogEntry {
+    pub fn size_bytes(&self) -> u64 {
+        self.files.iter().map(|f| f.size_bytes).sum()
+    }
+
+    /// Nom du dossier d'installation (l'identifiant à défaut)
+    pub fn directory_name(&self) -> &str {
+        self.directory.as_deref().unwrap_or(&self.id)
+    }
+
+    pub fn primary_file(&self) -> Option<&CatalogFile> {
+        self.files.first()
+    }
+
+    /// Faux pour les modèles importés, qui n'ont pas de source distante
+    pub fn is_downloadable(&self) -> bool {
+        self.files.iter().all(|f| !f.url.is_empty())
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !is_path_component(&self.id) {
+            return Err(format!("invalid entry id {:?}", self.id));
+        }
+        if let Some(ref directory) = self.directory {
+            if !is_path_component(directory) {
+                return Err(format!("{:?}/{}: invalid directory {:?}", self.family, self.id, directory));
+            }
+        }
+        if self.files.is_empty() {
+            return Err(format!("{:?}/{} has no files", self.family, self.id));
+        }
+        if self.archive && self.files.len() != 1 {
+            return Err(format!("{:?}/{}: an archive entry must have exactly one file", self.family, self.id));
+        }
+        for file in &self.files {
+            if !is_path_component(&file.name) {
+                return Err(format!("{:?}/{}: invalid file name {}", self.family, self.id, file.name));
+            }
+            if let Some(ref hash) = file.sha256 {
+                if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return Err(format!("{:?}/{}: invalid sha256 for {}", self.family, self.id, file.name));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ModelCatalog {
+    #[serde(default)]
+    pub version: u32,
+    #[serde(default)]
+    pub models: Vec<CatalogEntry>,
+}
+
+impl ModelCatalog {
+    pub fn parse(json: &str) -> Result<Self, String> {
+        let catalog: ModelCatalog =
+            serde_json::from_str(json).map_err(|e| format!("Invalid model catalog: {}", e))?;
+        for entry in &catalog.models {
+            entry.validate().map_err(|e| format!("Invalid model catalog: {}", e))?;
+        }
+        Ok(catalog)
+    }
+
+    pub fn bundled() -> Self {
+        Self::parse(BUNDLED_CATALOG).expect("bundled model catalog must be valid")
+    }
+
+    /// Ajoute les entrées de `other`, en remplaçant celles de même famille et identifiant
+    pub fn merge(&mut self, other: ModelCatalog) {
+        for entry in other.models {
+            match self
+                .models
+                .iter_mut()
+                .find(|e| e.family == entry.family && e.id == entry.id)
+            {
+                Some(existing) => *existing = entry,
+                None => self.models.push(entry),
+            }
+        }
+        self.version = self.version.max(other.version);
+    }
+
+    pub fn get(&self, family: ModelFamily, id: &str) -> Option<&CatalogEntry> {
+        self.models.iter().find(|e| e.family == family && e.id == id)
+    }
+
+    pub fn family(&self, family: ModelFamily) -> impl Iterator<Item = &CatalogEntry> {
+        self.models.iter().filter(move |e| e.family == family)
+    }
+
+    /// Premier modèle de la famille déclarant la langue (code ISO)
+    pub fn for_language(&self, family: ModelFamily, code: &str) -> Option<&CatalogEntry> {
+        let code = code.to_lowercase();
+        self.family(family).find(|e| e.languages.contains(&code))
+    }
+
+    /// Manifeste embarqué, puis distant (cache), puis utilisateur
+    pub fn load() -> Self {
+        let mut catalog = Self::bundled();
+        for path in [remote_catalog_path(), user_catalog_path()] {
+            if !path.exists() {
+                continue;
+            }
+            match std::fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|json| Self::parse(&json))
+            {
+                Ok(overrides) => {
+                    log::info!("Loaded {} catalog entries from {:?}", overrides.models.len(), path);
+                    catalog.merge(overrides);
+                }
+                Err(e) => log::warn!("Ignoring model catalog {:?}: {}", path, e),
+            }
+        }
+        catalog
+    }
+}
+
+/// Nom sérialisé d'une valeur d'enum (`ModelFamily::Vosk` -> `vosk`)
+pub fn id_of<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// Valeur d'enum correspondant à un nom sérialisé (inverse de `id_of`)
+pub fn from_id<T: DeserializeOwned>(id: &str) -> Option<T> {
+    serde_json::from_value(serde_json::Value::String(id.to_string())).ok()
+}
+
+/// Manifeste fourni par l'utilisateur
+pub fn user_catalog_path() -> PathBuf {
+    crate::storage::get_app_data_dir().join("models").join("catalog.json")
+}
+
+/// Dernier manifeste distant téléchargé
+pub fn remote_catalog_path() -> PathBuf {
+    crate::storage::get_app_data_dir().join("models").join("catalog.remote.json")
+}
+
+fn read_user_catalog() -> Result<ModelCatalog, String> {
+    let path = user_catalog_path();
+    if !path.exists() {
+        return Ok(ModelCatalog { version: 1, models: Vec::new() });
+    }
+    let json = std::fs::read_to_string(&path).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+    ModelCatalog::parse(&json)
+}
+
+fn write_user_catalog(catalog: &ModelCatalog) -> Result<(), String> {
+    let path = user_catalog_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(catalog).map_err(|e| e.to_string())?;
+    write_atomic(&path, json.as_bytes()).map_err(|e| format!("Failed to save model catalog: {}", e))
+}
+
+/// Ajoute (ou remplace) une entrée dans le manifeste utilisateur
+pub fn register_user_entry(entry: CatalogEntry) -> Result<Arc<ModelCatalog>, String> {
+    entry.validate()?;
+    let mut user = read_user_catalog()?;
+    user.merge(ModelCatalog { version: 0, models: vec![entry] });
+    write_user_catalog(&user)?;
+    Ok(reload())
+}
+
+/// Retire une entrée du manifeste utilisateur (sans effet si elle n'y figure pas)
+pub fn remove_user_entry(family: ModelFamily, id: &str) -> Result<Arc<ModelCatalog>, String> {
+    let mut user = read_user_catalog()?;
+    let before = user.models.len();
+    user.models.retain(|e| !(e.family == family && e.id == id));
+    if user.models.len() != before {
+        write_user_catalog(&user)?;
+    }
+    Ok(reload())
+}
+
+fn cache() -> &'static RwLock<Option<Arc<ModelCatalog>>> {
+    static CATALOG: OnceLock<RwLock<Option<Arc<ModelCatalog>>>> = OnceLock::new();
+    CATALOG.get_or_init(|| RwLock::new(None))
+}
+
+/// Catalogue courant (chargé au premier accès)
+pub fn current() -> Arc<ModelCatalog> {
+    if let Some(catalog) = cache().read().ok().and_then(|c| c.clone()) {
+        return catalog;
+    }
+    reload()
+}
+
+/// Relit les manifestes depuis le disque
+pub fn reload() -> Arc<ModelCatalog> {
+    let catalog = Arc::new(ModelCatalog::load());
+    if let Ok(mut cached) = cache().write() {
+        *cached = Some(catalog.clone());
+    }
+    catalog
+}
+
+/// Télécharge un manifeste distant, le valide et le met en cache
+pub async fn refresh_remote(url: &str) -> Result<Arc<ModelCatalog>, String> {
+    let response = reqwest::get(url)
+        .await
+        .map_err(|e| format!("Failed to fetch model catalog: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to fetch model catalog: HTTP {}", response.status()));
+    }
+    let json = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read model catalog: {}", e))?;
+    ModelCatalog::parse(&json)?;
+
+    let path = remote_catalog_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    write_atomic(&path, json.as_bytes()).map_err(|e| format!("Failed to save model catalog: {}", e))?;
+    Ok(reload())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_catalog_covers_all_families() {
+        let catalog = ModelCatalog::bundled();
+        for family in [ModelFamily::Whisper, ModelFamily::Vosk, ModelFamily::Parakeet, ModelFamily::Llm] {
+            assert!(catalog.family(family).next().is_some(), "{:?}", family);
+        }
+        let turbo = catalog.get(ModelFamily::Whisper, "large-v3-turbo").unwrap();
+        assert!(turbo.prefer_gpu);
+        let fr = catalog.get(ModelFamily::Vosk, "fr").unwrap();
+        assert!(fr.archive);
+        assert_eq!(fr.directory_name(), "vosk-model-small-fr-0.22");
+        let phi = catalog.get(ModelFamily::Llm, "phi3_mini").unwrap();
+        assert_eq!(phi.chat_template, Some(ChatTemplate::Phi3));
+        assert_eq!(catalog.for_language(ModelFamily::Vosk, "FR").map(|e| e.id.as_str()), Some("fr"));
+        assert!(catalog.for_language(ModelFamily::Vosk, "xx").is_none());
+
+        // Les modèles par défaut des réglages existent au catalogue
+        let settings = crate::types::AppSettings::default();
+        assert!(catalog.get(ModelFamily::Whisper, &settings.whisper_model).is_some());
+        assert!(catalog.get(ModelFamily::Parakeet, &settings.parakeet_model).is_some());
+        assert!(catalog.get(ModelFamily::Llm, &settings.local_llm_model).is_some());
+    }
+
+    #[test]
+    fn test_bundled_files_have_checksums() {
+        let catalog = ModelCatalog::bundled();
+        let missing: Vec<String> = catalog
+            .models
+            .iter()
+            .flat_map(|entry| entry.files.iter().map(move |file| (entry, file)))
+            .filter(|(_, file)| {
+                !file.sha256.as_ref().is_some_and(|h| h.len() == 64 && h.chars().all(|c| c.is_ascii_hexdigit()))
+            })
+            .map(|(entry, file)| format!("{}/{}", entry.id, file.name))
+            .collect();
+        assert!(missing.is_empty(), "No sha256 (run scripts/fill-catalog-sha256.cjs): {:?}", missing);
+    }
+
+    #[test]
+    fn test_override_replaces_and_extends() {
+        let mut catalog = ModelCatalog::bundled();
+        let count = catalog.models.len();
+        let overrides = ModelCatalog::parse(
+            r#"{"version": 2, "models": [
+                {"engine": "whisper", "id": "tiny", "display_name": "Tiny (mirror)",
+                 "files": [{"name": "ggml-tiny.bin", "url": "https://mirror.example/ggml-tiny.bin", "size_bytes": 1}]},
+                {"engine": "whisper", "id": "large-v3", "display_name": "Large v3",
+                 "files": [{"name": "ggml-large-v3.bin", "url": "https://mirror.example/ggml-large-v3.bin"}]}
+            ]}"#,
+        )
+        .unwrap();
+        catalog.merge(overrides);
+
+        assert_eq!(catalog.models.len(), count + 1);
+        assert_eq!(catalog.version, 2);
+        let tiny = catalog.get(ModelFamily::Whisper, "tiny").unwrap();
+        assert_eq!(tiny.display_name, "Tiny (mirror)");
+        assert!(catalog.get(ModelFamily::Whisper, "large-v3").is_some());
+    }
+
+    #[test]
+    fn test_rejects_unsafe_file_names() {
+        let result = ModelCatalog::parse(
+            r#"{"models": [{"engine": "llm", "id": "x", "display_name": "x",
+                "files": [{"name": "../escape.gguf", "url": "https://example.com/x"}]}]}"#,
+        );
+        assert!(result.is_err());
+
+        for (id, directory) in [("../x", "x"), ("x", "../.."), ("x", "/tmp"), ("x", ".hidden"), ("x", "")] {
+            let json = format!(
+                r#"{{"models": [{{"engine": "vosk", "id": "{}", "directory": "{}", "display_name": "x",
+                    "archive": true, "files": [{{"name": "x.zip", "url": "https://example.com/x"}}]}}]}}"#,
+                id, directory
+            );
+            assert!(ModelCatalog::parse(&json).is_err(), "{} / {}", id, directory);
+        }
+    }
+}
@@ -0,0 +1,444 @@
+//! Moteur de téléchargement commun à toutes les familles de modèles :
+//! fichier `.part`, reprise par requête Range, vérification SHA-256,
+//! annulation et renommage atomique.
+
+use futures_util::StreamExt;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use tokio::fs;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::Notify;
+
+pub const CANCELLED_MESSAGE: &str = "Téléchargement annulé";
+
+/// Jeton d'annulation partagé entre la commande de téléchargement et `cancel_model_download`
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+    notify: Arc<Notify>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.notify.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Se termine dès que le jeton est annulé
+    pub async fn cancelled(&self) {
+        let notified = self.notify.notified();
+        if self.is_cancelled() {
+            return;
+        }
+        notified.await;
+    }
+}
+
+fn active_downloads() -> &'static Mutex<HashMap<String, CancellationToken>> {
+    static ACTIVE: OnceLock<Mutex<HashMap<String, CancellationToken>>> = OnceLock::new();
+    ACTIVE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Identifiant d'un téléchargement : `<famille>:<modèle>` (ex. `llm:phi3_mini`)
+pub fn download_id(family: &str, id: &str) -> String {
+    format!("{}:{}", family, id)
+}
+
+/// Téléchargement en cours ; se désinscrit automatiquement à la fin
+pub struct ActiveDownload {
+    id: String,
+    token: CancellationToken,
+}
+
+impl ActiveDownload {
+    pub fn token(&self) -> &CancellationToken {
+        &self.token
+    }
+}
+
+impl Drop for ActiveDownload {
+    fn drop(&mut self) {
+        if let Ok(mut active) = active_downloads().lock() {
+            active.remove(&self.id);
+        }
+    }
+}
+
+/// Enregistre un téléchargement annulable (refuse un doublon en cours)
+pub fn register(id: &str) -> Result<ActiveDownload, String> {
+    let mut active = active_downloads().lock().map_err(|e| e.to_string())?;
+    if active.contains_key(id) {
+        return Err(format!("Téléchargement déjà en cours: {}", id));
+    }
+    let token = CancellationToken::new();
+    active.insert(id.to_string(), token.clone());
+    Ok(ActiveDownload {
+        id: id.to_string(),
+        token,
+    })
+}
+
+/// Annule un téléchargement en cours. Le fichier `.part` est conservé pour reprise.
+pub fn cancel(id: &str) -> bool {
+    match active_downloads().lock() {
+        Ok(active) => match active.get(id) {
+            Some(token) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        },
+        Err(_) => false,
+    }
+}
+
+/// Fichier temporaire associé à une destination
+pub fn part_path(dest: &Path) -> PathBuf {
+    let mut name = dest.file_name().unwrap_or_default().to_os_string();
+    name.push(".part");
+    dest.with_file_name(name)
+}
+
+pub struct DownloadRequest<'a> {
+    pub url: &'a str,
+    pub dest: &'a Path,
+    pub sha256: Option<String>,
+    /// Taille utilisée pour la progression si le serveur ne l'annonce pas
+    pub size_hint: u64,
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Ajoute au hash le contenu déjà présent dans un fichier
+async fn hash_existing(path: &Path, hasher: &mut Sha256) -> Result<(), String> {
+    let mut file = fs::File::open(path)
+        .await
+        .map_err(|e| format!("Failed to open partial file: {}", e))?;
+    let mut buf = vec![0u8; 1024 * 1024];
+    loop {
+        let n = file
+            .read(&mut buf)
+            .await
+            .map_err(|e| format!("Failed to read partial file: {}", e))?;
+        if n == 0 {
+            return Ok(());
+        }
+        hasher.update(&buf[..n]);
+    }
+}
+
+/// Empreinte SHA-256 (hexadécimale) d'un fichier complet
+pub async fn file_sha256(path: &Path) -> Result<String, String> {
+    let mut hasher = Sha256::new();
+    hash_existing(path, &mut hasher).await?;
+    Ok(to_hex(&hasher.finalize()))
+}
+
+/// Réécrit une URL amont vers un miroir privé, selon la disposition de
+/// `wget --force-directories` : `https://hote/chemin` -> `{miroir}/hote/chemin`
+pub fn mirrored_url(url: &str, mirror: Option<&str>) -> String {
+    let Some(mirror) = mirror.map(str::trim).filter(|m| !m.is_empty()) else {
+        return url.to_string();
+    };
+    let Ok(parsed) = reqwest::Url::parse(url) else {
+        return url.to_string();
+    };
+    let Some(host) = parsed.host_str() else {
+        return url.to_string();
+    };
+    let mut mirrored = format!("{}/{}{}", mirror.trim_end_matches('/'), host, parsed.path());
+    if let Some(query) = parsed.query() {
+        mirrored.push('?');
+        mirrored.push_str(query);
+    }
+    mirrored
+}
+
+/// Vérifie l'empreinte puis renomme le `.part` vers sa destination finale
+async fn finalize(part: &Path, dest: &Path, expected: Option<&str>, actual: String) -> Result<(), String> {
+    if let Some(expected) = expected {
+        if actual != expected {
+            let _ = fs::remove_file(part).await;
+            return Err(format!(
+                "Checksum mismatch for {}: expected {}, got {}",
+                dest.display(),
+                expected,
+                actual
+            ));
+        }
+    } else {
+        // Sans empreinte au catalogue, le fichier n'est pas vérifié : on trace la
+        // sienne pour pouvoir compléter le manifeste
+        log::warn!(
+            "No sha256 in catalog for {}, download not verified (sha256: {})",
+            dest.display(),
+            actual
+        );
+    }
+    fs::rename(part, dest)
+        .await
+        .map_err(|e| format!("Failed to move downloaded file into place: {}", e))
+}
+
+/// Télécharge `request.url` vers `request.dest`, en reprenant un éventuel `.part`
+pub async fn download_file(
+    client: &reqwest::Client,
+    request: &DownloadRequest<'_>,
+    cancel: &CancellationToken,
+    progress: &mut (dyn FnMut(u64, u64) + Send),
+) -> Result<(), String> {
+    let part = part_path(request.dest);
+    let expected = request.sha256.as_deref();
+    let existing = fs::metadata(&part).await.map(|m| m.len()).unwrap_or(0);
+
+    let mut http_request = client.get(request.url);
+    if existing > 0 {
+        log::info!("Resuming {} from byte {}", request.url, existing);
+        http_request = http_request.header(reqwest::header::RANGE, format!("bytes={}-", existing));
+    }
+
+    let response = tokio::select! {
+        response = http_request.send() => response.map_err(|e| format!("Failed to start download: {}", e))?,
+        _ = cancel.cancelled() => return Err(CANCELLED_MESSAGE.to_string()),
+    };
+    let status = response.status();
+
+    // Le `.part` contient déjà tout le fichier (interruption avant le renommage)
+    if status == reqwest::StatusCode::RANGE_NOT_SATISFIABLE && existing > 0 {
+        let mut hasher = Sha256::new();
+        hash_existing(&part, &mut hasher).await?;
+        progress(existing, existing);
+        return finalize(&part, request.dest, expected, to_hex(&hasher.finalize())).await;
+    }
+
+    if !status.is_success() {
+        return Err(format!("Download failed with status: {}", status));
+    }
+
+    let mut hasher = Sha256::new();
+    let resumed = status == reqwest::StatusCode::PARTIAL_CONTENT && existing > 0;
+    let mut file = if resumed {
+        hash_existing(&part, &mut hasher).await?;
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&part)
+            .await
+            .map_err(|e| format!("Failed to open partial file: {}", e))?
+    } else {
+        // Le serveur ignore Range : on repart de zéro
+        fs::File::create(&part)
+            .await
+            .map_err(|e| format!("Failed to create file: {}", e))?
+    };
+
+    let start = if resumed { existing } else { 0 };
+    let expected_len = response.content_length().map(|len| start + len);
+    let total = expected_len.unwrap_or(request.size_hint.max(start));
+    let mut downloaded = start;
+    progress(downloaded, total);
+
+    let mut stream = response.bytes_stream();
+    loop {
+        let chunk = tokio::select! {
+            chunk = stream.next() => chunk,
+            _ = cancel.cancelled() => {
+                let _ = file.flush().await;
+                log::info!("Download of {} cancelled at {} bytes", request.url, downloaded);
+                return Err(CANCELLED_MESSAGE.to_string());
+            }
+        };
+        let Some(chunk) = chunk else { break };
+        let chunk = chunk.map_err(|e| format!("Download error: {}", e))?;
+        file.write_all(&chunk)
+            .await
+            .map_err(|e| format!("Write error: {}", e))?;
+        hasher.update(&chunk);
+        downloaded += chunk.len() as u64;
+        progress(downloaded, total);
+    }
+
+    file.flush().await.map_err(|e| format!("Flush error: {}", e))?;
+    file.sync_all().await.map_err(|e| format!("Flush error: {}", e))?;
+    drop(file);
+
+    if let Some(expected_len) = expected_len {
+        if downloaded < expected_len {
+            return Err(format!(
+                "Download interrupted ({} / {} bytes), it will resume on the next attempt",
+                downloaded, expected_len
+            ));
+        }
+    }
+
+    finalize(&part, request.dest, expected, to_hex(&hasher.finalize())).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    const BODY: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+    fn sha256_hex(data: &[u8]) -> String {
+        to_hex(&Sha256::digest(data))
+    }
+
+    #[test]
+    fn test_mirrored_url_keeps_host_and_path() {
+        let url = "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-tiny.bin";
+        assert_eq!(mirrored_url(url, None), url);
+        assert_eq!(mirrored_url(url, Some("  ")), url);
+        assert_eq!(
+            mirrored_url(url, Some("http://mirror.lan/models/")),
+            "http://mirror.lan/models/huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-tiny.bin"
+        );
+        assert_eq!(
+            mirrored_url("https://alphacephei.com/vosk/models/x.zip?download=true", Some("http://m")),
+            "http://m/alphacephei.com/vosk/models/x.zip?download=true"
+        );
+    }
+
+    /// Serveur HTTP minimal qui honore `Range: bytes=N-` ; renvoie les requêtes reçues
+    async fn file_server(body: &'static [u8]) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let seen = requests.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = [0u8; 4096];
+                let n = socket.read(&mut buf).await.unwrap();
+                let request = String::from_utf8_lossy(&buf[..n]).to_string();
+                let range_start = request
+                    .lines()
+                    .find_map(|l| l.to_ascii_lowercase().strip_prefix("range: bytes=").map(str::to_string))
+                    .and_then(|r| r.trim_end_matches('-').parse::<usize>().ok());
+                seen.lock().unwrap().push(request);
+
+                let (status, payload) = match range_start {
+                    Some(start) if start >= body.len() => ("416 Range Not Satisfiable", &body[..0]),
+                    Some(start) => ("206 Partial Content", &body[start..]),
+                    None => ("200 OK", body),
+                };
+                let header = format!(
+                    "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    status,
+                    payload.len()
+                );
+                socket.write_all(header.as_bytes()).await.unwrap();
+                socket.write_all(payload).await.unwrap();
+            }
+        });
+
+        (format!("http://{}/model.bin", address), requests)
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("scribe-download-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_download_verifies_and_renames() {
+        let (url, _) = file_server(BODY).await;
+        let dir = temp_dir("fresh");
+        let dest = dir.join("model.bin");
+        let request = DownloadRequest {
+            url: &url,
+            dest: &dest,
+            sha256: Some(sha256_hex(BODY)),
+            size_hint: 0,
+        };
+
+        let mut last = (0, 0);
+        download_file(&reqwest::Client::new(), &request, &CancellationToken::new(), &mut |d, t| last = (d, t))
+            .await
+            .unwrap();
+
+        assert_eq!(std::fs::read(&dest).unwrap(), BODY);
+        assert!(!part_path(&dest).exists());
+        assert_eq!(last, (BODY.len() as u64, BODY.len() as u64));
+    }
+
+    #[tokio::test]
+    async fn test_download_resumes_partial_file() {
+        let (url, requests) = file_server(BODY).await;
+        let dir = temp_dir("resume");
+        let dest = dir.join("model.bin");
+        std::fs::write(part_path(&dest), &BODY[..10]).unwrap();
+
+        let request = DownloadRequest {
+            url: &url,
+            dest: &dest,
+            sha256: Some(sha256_hex(BODY)),
+            size_hint: 0,
+        };
+        download_file(&reqwest::Client::new(), &request, &CancellationToken::new(), &mut |_, _| {})
+            .await
+            .unwrap();
+
+        assert_eq!(std::fs::read(&dest).unwrap(), BODY);
+        let requests = requests.lock().unwrap();
+        assert!(requests[0].to_ascii_lowercase().contains("range: bytes=10-"));
+    }
+
+    #[tokio::test]
+    async fn test_checksum_mismatch_discards_download() {
+        let (url, _) = file_server(BODY).await;
+        let dir = temp_dir("mismatch");
+        let dest = dir.join("model.bin");
+        let request = DownloadRequest {
+            url: &url,
+            dest: &dest,
+            sha256: Some("00".repeat(32)),
+            size_hint: 0,
+        };
+
+        let result = download_file(&reqwest::Client::new(), &request, &CancellationToken::new(), &mut |_, _| {}).await;
+        assert!(result.unwrap_err().contains("Checksum mismatch"));
+        assert!(!dest.exists());
+        assert!(!part_path(&dest).exists());
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_download_is_not_installed() {
+        let (url, _) = file_server(BODY).await;
+        let dir = temp_dir("cancel");
+        let dest = dir.join("model.bin");
+        let request = DownloadRequest {
+            url: &url,
+            dest: &dest,
+            sha256: None,
+            size_hint: 0,
+        };
+
+        let download = register("test:cancel").unwrap();
+        assert!(register("test:cancel").is_err());
+        assert!(cancel("test:cancel"));
+
+        let result = download_file(&reqwest::Client::new(), &request, download.token(), &mut |_, _| {}).await;
+        assert_eq!(result.unwrap_err(), CANCELLED_MESSAGE);
+        assert!(!dest.exists());
+
+        drop(download);
+        assert!(!cancel("test:cancel"));
+    }
+}
@@ -0,0 +1,246 @@
+//! Transcription des fichiers longs : découpe aux silences, transcription de
+//! chaque morceau puis assemblage avec des horodatages absolus
+
+use crate::audio::vad::Vad;
+use crate::engines::traits::SpeechEngine;
+use crate::types::{Segment, TranscriptionResult};
+use chrono::Utc;
+
+/// Plus long morceau envoyé en un appel : reste sous la fenêtre de 30 s de
+/// Whisper et limite la taille du tenseur de l'encodeur Parakeet
+pub const MAX_CHUNK_SECS: f32 = 28.0;
+
+/// Transcrit un audio de toute durée. `on_progress(faits, total)` est appelé
+/// avant chaque morceau puis une fois tous les morceaux traités
+pub fn transcribe_long(
+    engine: &dyn SpeechEngine,
+    audio: &[f32],
+    sample_rate: u32,
+    on_progress: impl FnMut(usize, usize),
+) -> Result<TranscriptionResult, String> {
+    run_long(engine, audio, sample_rate, on_progress, |engine, chunk, rate| {
+        engine.transcribe(chunk, rate)
+    })
+}
+
+/// Comme [`transcribe_long`], avec la tâche de traduction vers l'anglais du moteur
+pub fn translate_long(
+    engine: &dyn SpeechEngine,
+    audio: &[f32],
+    sample_rate: u32,
+    on_progress: impl FnMut(usize, usize),
+) -> Result<TranscriptionResult, String> {
+    run_long(engine, audio, sample_rate, on_progress, |engine, chunk, rate| {
+        engine.translate_to_english(chunk, rate)
+    })
+}
+
+fn run_long(
+    engine: &dyn SpeechEngine,
+    audio: &[f32],
+    sample_rate: u32,
+    mut on_progress: impl FnMut(usize, usize),
+    run_chunk: impl Fn(&dyn SpeechEngine, &[f32], u32) -> Result<TranscriptionResult, String>,
+) -> Result<TranscriptionResult, String> {
+    let duration_seconds = audio.len() as f32 / sample_rate as f32;
+    if duration_seconds <= MAX_CHUNK_SECS {
+        on_progress(0, 1);
+        let result = run_chunk(engine, audio, sample_rate);
+        on_progress(1, 1);
+        return result;
+    }
+
+    let chunks = Vad::default().plan_chunks(audio, sample_rate, MAX_CHUNK_SECS);
+    log::info!(
+        "Long-form transcription: {:.1}s split into {} chunks",
+        duration_seconds,
+        chunks.len()
+    );
+
+    let mut results = Vec::with_capacity(chunks.len());
+    for (index, range) in chunks.iter().enumerate() {
+        on_progress(index, chunks.len());
+        let offset = range.start as f32 / sample_rate as f32;
+        let result = run_chunk(engine, &audio[range.clone()], sample_rate)?;
+        results.push((offset, result));
+    }
+    on_progress(chunks.len(), chunks.len());
+
+    Ok(stitch(results, duration_seconds, engine))
+}
+
+/// Fusionne les résultats en décalant leurs horodatages du début du morceau
+fn stitch(
+    results: Vec<(f32, TranscriptionResult)>,
+    duration_seconds: f32,
+    engine: &dyn SpeechEngine,
+) -> TranscriptionResult {
+    let mut texts = Vec::new();
+    let mut segments = Vec::new();
+    let mut weighted_confidence = 0.0f32;
+    let mut weight = 0.0f32;
+    let mut processing_time_ms = 0;
+    let mut detected_language = None;
+    let mut model_used = None;
+    let mut translation = None;
+
+    for (offset, result) in results {
+        let text = result.text.trim();
+        processing_time_ms += result.processing_time_ms;
+        detected_language = detected_language.or(result.detected_language);
+        model_used = model_used.or(result.model_used);
+        translation = translation.or(result.translation);
+        if text.is_empty() {
+            continue;
+        }
+
+        weighted_confidence += result.confidence * result.duration_seconds;
+        weight += result.duration_seconds;
+        texts.push(text.to_string());
+
+        if result.segments.is_empty() {
+            // Moteur sans horodatage : le morceau devient un segment
+            segments.push(Segment {
+                start: offset,
+                end: offset + result.duration_seconds,
+                text: text.to_string(),
+                words: Vec::new(),
+                confidence: result.confidence,
+                speaker: None,
+            });
+            continue;
+        }
+
+        for mut segment in result.segments {
+            segment.start += offset;
+            segment.end += offset;
+            for word in &mut segment.words {
+                word.start += offset;
+                word.end += offset;
+            }
+            segments.push(segment);
+        }
+    }
+
+    TranscriptionResult {
+        text: texts.join(" "),
+        confidence: if weight > 0.0 { weighted_confidence / weight } else { 0.0 },
+        duration_seconds,
+        processing_time_ms,
+        detected_language,
+        timestamp: Utc::now().timestamp(),
+        model_used: model_used.or_else(|| Some(engine.model_display_name())),
+        segments,
+        translation,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::vocabulary::Vocabulary;
+    use crate::types::TranslationInfo;
+    use std::sync::Mutex;
+
+    /// Faux moteur qui renvoie un segment horodaté par appel
+    struct EchoEngine {
+        calls: Mutex<Vec<usize>>,
+    }
+
+    impl SpeechEngine for EchoEngine {
+        fn transcribe(&self, audio: &[f32], sample_rate: u32) -> Result<TranscriptionResult, String> {
+            let mut calls = self.calls.lock().unwrap();
+            calls.push(audio.len());
+            let duration_seconds = audio.len() as f32 / sample_rate as f32;
+            let text = format!("chunk{}", calls.len());
+            Ok(TranscriptionResult {
+                text: text.clone(),
+                confidence: 0.8,
+                duration_seconds,
+                processing_time_ms: 10,
+                detected_language: Some("fr".to_string()),
+                timestamp: 0,
+                model_used: None,
+                segments: vec![Segment {
+                    start: 0.5,
+                    end: 1.0,
+                    text,
+                    words: Vec::new(),
+                    confidence: 0.8,
+                    speaker: None,
+                }],
+                translation: None,
+            })
+        }
+
+        fn translate_to_english(&self, audio: &[f32], sample_rate: u32) -> Result<TranscriptionResult, String> {
+            let mut result = self.transcribe(audio, sample_rate)?;
+            result.translation = Some(TranslationInfo {
+                source_language: result.detected_language.clone(),
+                target_language: "en".to_string(),
+            });
+            Ok(result)
+        }
+
+        fn name(&self) -> &str {
+            "Echo"
+        }
+
+        fn model_display_name(&self) -> String {
+            "Echo".to_string()
+        }
+
+        fn set_vocabulary(&mut self, _vocabulary: Vocabulary) {}
+    }
+
+    /// Trois salves de 20 s séparées par 2 s de silence
+    fn long_audio(sample_rate: u32) -> Vec<f32> {
+        let burst = |secs: f32| -> Vec<f32> {
+            (0..(secs * sample_rate as f32) as usize)
+                .map(|i| 0.5 * (i as f32 * 0.1).sin())
+                .collect()
+        };
+        let mut audio = Vec::new();
+        for _ in 0..3 {
+            audio.extend(burst(20.0));
+            audio.extend(vec![0.0; 2 * sample_rate as usize]);
+        }
+        audio
+    }
+
+    #[test]
+    fn test_long_audio_is_chunked_and_offsets_applied() {
+        let sample_rate = 16000;
+        let audio = long_audio(sample_rate);
+
+        let engine = EchoEngine { calls: Mutex::new(Vec::new()) };
+        let mut progress = Vec::new();
+        let result = transcribe_long(&engine, &audio, sample_rate, |done, total| {
+            progress.push((done, total))
+        })
+        .unwrap();
+
+        assert_eq!(result.text, "chunk1 chunk2 chunk3");
+        assert_eq!(progress, vec![(0, 3), (1, 3), (2, 3), (3, 3)]);
+        assert_eq!(result.segments.len(), 3);
+        // La deuxième salve commence à 22 s, morceau élargi de 0,2 s
+        assert!((result.segments[1].start - 22.3).abs() < 0.1);
+        assert!((result.duration_seconds - 66.0).abs() < 1e-3);
+        assert_eq!(result.processing_time_ms, 30);
+    }
+
+    #[test]
+    fn test_translate_long_keeps_languages() {
+        let sample_rate = 16000;
+        let engine = EchoEngine { calls: Mutex::new(Vec::new()) };
+        let result = translate_long(&engine, &long_audio(sample_rate), sample_rate, |_, _| {}).unwrap();
+
+        assert_eq!(result.text, "chunk1 chunk2 chunk3");
+        let translation = result.translation.unwrap();
+        assert_eq!(translation.source_language.as_deref(), Some("fr"));
+        assert_eq!(translation.target_language, "en");
+
+        let plain = transcribe_long(&engine, &long_audio(sample_rate), sample_rate, |_, _| {}).unwrap();
+        assert!(plain.translation.is_none());
+    }
+}
@@ -1,15 +1,22 @@
+pub mod catalog;
+pub mod download;
 pub mod error;
+pub mod long_form;
+pub mod model_import;
 pub mod model_manager;
 pub mod parakeet;
 pub mod parakeet_coreml;
+pub mod segments;
 pub mod traits;
+pub mod vocabulary;
 pub mod vosk;
 pub mod whisper;
 
 pub use error::EngineError;
 pub use model_manager::ModelManager;
-pub use parakeet::{ParakeetEngine, ParakeetModelSize};
+pub use parakeet::ParakeetEngine;
 pub use parakeet_coreml::ParakeetCoreMLEngine;
-pub use traits::SpeechEngine;
+pub use traits::{SpeechEngine, SpeechStream, StreamingSpeechEngine, StreamingUpdate};
+pub use vocabulary::Vocabulary;
 pub use vosk::VoskEngine;
 pub use whisper::WhisperEngine;
@@ -0,0 +1,295 @@
+//! Import de modèles fournis localement (machines sans accès réseau) :
+//! validation du format, puis copie ou lien symbolique dans `models_dir`.
+
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+use super::catalog::{CatalogEntry, CatalogFile, ModelFamily};
+
+/// En-tête des fichiers ggml de whisper.cpp (0x67676d6c en little-endian)
+const GGML_MAGIC: &[u8; 4] = b"lmgg";
+const GGUF_MAGIC: &[u8; 4] = b"GGUF";
+
+fn read_magic(path: &Path) -> Result<[u8; 4], String> {
+    let mut magic = [0u8; 4];
+    fs::File::open(path)
+        .and_then(|mut f| f.read_exact(&mut magic))
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    Ok(magic)
+}
+
+fn require_file(path: &Path) -> Result<(), String> {
+    if path.is_file() {
+        Ok(())
+    } else {
+        Err(format!("{} is not a file", path.display()))
+    }
+}
+
+fn require_dir(path: &Path) -> Result<(), String> {
+    if path.is_dir() {
+        Ok(())
+    } else {
+        Err(format!("{} is not a directory", path.display()))
+    }
+}
+
+/// Vérifie que `source` a la forme attendue par la famille. `entry` est
+/// l'entrée du catalogue remplacée, dont les fichiers doivent être présents.
+pub fn validate_source(family: ModelFamily, source: &Path, entry: Option<&CatalogEntry>) -> Result<(), String> {
+    match family {
+        ModelFamily::Whisper => {
+            require_file(source)?;
+            let magic = read_magic(source)?;
+            if &magic != GGML_MAGIC && &magic != GGUF_MAGIC {
+                return Err(format!("{} is not a ggml Whisper model", source.display()));
+            }
+        }
+        ModelFamily::Llm => {
+            require_file(source)?;
+            if &read_magic(source)? != GGUF_MAGIC {
+                return Err(format!("{} is not a GGUF model", source.display()));
+            }
+        }
+        ModelFamily::Parakeet => {
+            require_dir(source)?;
+            match entry {
+                Some(entry) => {
+                    for file in &entry.files {
+                        let path = source.join(&file.name);
+                        if fs::metadata(&path).map(|m| m.len() == 0).unwrap_or(true) {
+                            return Err(format!("Missing or empty file: {}", file.name));
+                        }
+                    }
+                }
+                None => {
+                    let has_onnx = fs::read_dir(source)
+                        .map_err(|e| e.to_string())?
+                        .flatten()
+                        .any(|f| f.path().extension().is_some_and(|ext| ext == "onnx"));
+                    if !has_onnx {
+                        return Err(format!("No ONNX model found in {}", source.display()));
+                    }
+                }
+            }
+        }
+        ModelFamily::Vosk => {
+            require_dir(source)?;
+            if !source.join("am").join("final.mdl").is_file() || !source.join("conf").is_dir() {
+                return Err(format!(
+                    "{} is not a Vosk model (expected am/final.mdl and conf/)",
+                    source.display()
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn file_size(path: &Path) -> u64 {
+    fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+}
+
+/// Entrée de catalogue pour un modèle importé sans équivalent au catalogue.
+/// L'identifiant vient par défaut du nom de la source ; l'entrée a la même
+/// forme qu'une entrée téléchargée pour que chemin et suppression s'appliquent.
+pub fn custom_entry(family: ModelFamily, source: &Path, id: Option<&str>) -> Result<CatalogEntry, String> {
+    let name = source
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or("Invalid model path")?;
+    let stem = Path::new(&name)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let default_id = match family {
+        ModelFamily::Whisper => stem.trim_start_matches("ggml-").to_string(),
+        ModelFamily::Llm => stem,
+        ModelFamily::Vosk | ModelFamily::Parakeet => name.clone(),
+    };
+    let id = id.map(str::to_string).unwrap_or(default_id);
+
+    let (directory, files, archive) = match family {
+        ModelFamily::Whisper | ModelFamily::Llm => {
+            let file = CatalogFile { name, url: String::new(), sha256: None, size_bytes: file_size(source) };
+            (None, vec![file], false)
+        }
+        // Dossier `vosk/<id>`, comme une archive extraite
+        ModelFamily::Vosk => {
+            let file = CatalogFile { name: format!("{}.zip", id), url: String::new(), sha256: None, size_bytes: 0 };
+            (Some(id.clone()), vec![file], true)
+        }
+        // Dossier `parakeet/<id>` avec les fichiers présents dans la source
+        ModelFamily::Parakeet => {
+            let mut files: Vec<CatalogFile> = fs::read_dir(source)
+                .map_err(|e| format!("Failed to read {}: {}", source.display(), e))?
+                .flatten()
+                .filter(|f| f.path().is_file())
+                .map(|f| f.file_name().to_string_lossy().to_string())
+                .filter(|name| !name.starts_with('.'))
+                .map(|name| CatalogFile {
+                    size_bytes: file_size(&source.join(&name)),
+                    name,
+                    url: String::new(),
+                    sha256: None,
+                })
+                .collect();
+            files.sort_by(|a, b| a.name.cmp(&b.name));
+            (Some(id.clone()), files, false)
+        }
+    };
+
+    let entry = CatalogEntry {
+        family,
+        id: id.clone(),
+        display_name: id,
+        directory,
+        files,
+        archive,
+        languages: Vec::new(),
+        license: None,
+        prefer_gpu: false,
+        chat_template: None,
+    };
+    entry.validate()?;
+    Ok(entry)
+}
+
+fn copy_dir(source: &Path, dest: &Path) -> io::Result<()> {
+    fs::create_dir_all(dest)?;
+    for item in fs::read_dir(source)? {
+        let item = item?;
+        let target = dest.join(item.file_name());
+        if item.file_type()?.is_dir() {
+            copy_dir(&item.path(), &target)?;
+        } else {
+            fs::copy(item.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn symlink(source: &Path, dest: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(source, dest)
+}
+
+#[cfg(windows)]
+fn symlink(source: &Path, dest: &Path) -> io::Result<()> {
+    if source.is_dir() {
+        std::os::windows::fs::symlink_dir(source, dest)
+    } else {
+        std::os::windows::fs::symlink_file(source, dest)
+    }
+}
+
+/// Supprime un fichier, un dossier ou un lien (sans suivre le lien)
+fn remove_existing(path: &Path) -> io::Result<()> {
+    match fs::symlink_metadata(path) {
+        Ok(meta) if meta.is_dir() => fs::remove_dir_all(path),
+        Ok(_) => fs::remove_file(path).or_else(|_| fs::remove_dir(path)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Installe `source` à `dest`, par lien symbolique ou par copie dans un
+/// emplacement temporaire renommé ensuite (jamais de modèle à moitié copié)
+pub fn install(source: &Path, dest: &Path, link: bool) -> Result<(), String> {
+    let source = fs::canonicalize(source).map_err(|e| format!("Failed to resolve {}: {}", source.display(), e))?;
+    if fs::canonicalize(dest).is_ok_and(|existing| existing == source) {
+        return Ok(());
+    }
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create models directory: {}", e))?;
+    }
+
+    if link {
+        remove_existing(dest).map_err(|e| format!("Failed to replace model: {}", e))?;
+        return symlink(&source, dest).map_err(|e| format!("Failed to link model: {}", e));
+    }
+
+    let name = dest.file_name().unwrap_or_default().to_string_lossy();
+    let staging = dest.with_file_name(format!(".{}.importing", name));
+    let _ = remove_existing(&staging);
+    let copied = if source.is_dir() {
+        copy_dir(&source, &staging)
+    } else {
+        fs::copy(&source, &staging).map(|_| ())
+    };
+    if let Err(e) = copied {
+        let _ = remove_existing(&staging);
+        return Err(format!("Failed to copy model: {}", e));
+    }
+    remove_existing(dest).map_err(|e| format!("Failed to replace model: {}", e))?;
+    fs::rename(&staging, dest).map_err(|e| format!("Failed to install model: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("scribe-import-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_validate_checks_file_magic() {
+        let dir = temp_dir("magic");
+        let ggml = dir.join("ggml-custom.bin");
+        fs::write(&ggml, b"lmgg\x00\x01").unwrap();
+        let gguf = dir.join("model.gguf");
+        fs::write(&gguf, b"GGUF\x03\x00").unwrap();
+
+        assert!(validate_source(ModelFamily::Whisper, &ggml, None).is_ok());
+        assert!(validate_source(ModelFamily::Llm, &gguf, None).is_ok());
+        assert!(validate_source(ModelFamily::Llm, &ggml, None).is_err());
+        assert!(validate_source(ModelFamily::Whisper, &dir, None).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_copy_install_of_vosk_directory() {
+        let dir = temp_dir("vosk");
+        let source = dir.join("vosk-model-small-fr");
+        fs::create_dir_all(source.join("am")).unwrap();
+        fs::create_dir_all(source.join("conf")).unwrap();
+        fs::write(source.join("am").join("final.mdl"), b"model").unwrap();
+        assert!(validate_source(ModelFamily::Vosk, &source, None).is_ok());
+        assert!(validate_source(ModelFamily::Vosk, &source.join("am"), None).is_err());
+
+        let dest = dir.join("models").join("vosk-model-small-fr-0.22");
+        install(&source, &dest, false).unwrap();
+        assert_eq!(fs::read(dest.join("am").join("final.mdl")).unwrap(), b"model");
+        assert!(!dir.join("models").join(".vosk-model-small-fr-0.22.importing").exists());
+
+        // Réimporter remplace le modèle existant
+        fs::write(source.join("am").join("final.mdl"), b"updated").unwrap();
+        install(&source, &dest, false).unwrap();
+        assert_eq!(fs::read(dest.join("am").join("final.mdl")).unwrap(), b"updated");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_custom_entry_for_parakeet_directory() {
+        let dir = temp_dir("parakeet");
+        let source = dir.join("my-parakeet");
+        fs::create_dir_all(&source).unwrap();
+        fs::write(source.join("encoder.onnx"), b"onnx").unwrap();
+        fs::write(source.join("vocab.txt"), b"a").unwrap();
+        assert!(validate_source(ModelFamily::Parakeet, &source, None).is_ok());
+
+        let entry = custom_entry(ModelFamily::Parakeet, &source, None).unwrap();
+        assert_eq!(entry.id, "my-parakeet");
+        assert_eq!(entry.directory_name(), "my-parakeet");
+        let names: Vec<&str> = entry.files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["encoder.onnx", "vocab.txt"]);
+        assert!(custom_entry(ModelFamily::Vosk, &source, Some("../x")).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
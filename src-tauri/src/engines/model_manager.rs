@@ -1,12 +1,23 @@
-use crate::types::{LocalLlmModel, ModelSize, ParakeetModelSize, VoskLanguage};
-use futures_util::StreamExt;
-use std::path::PathBuf;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, RwLock};
 use tokio::fs;
-use tokio::io::AsyncWriteExt;
+
+use super::catalog::{self, CatalogEntry, ModelCatalog, ModelFamily};
+use super::download::{self, CancellationToken, DownloadRequest};
+use super::model_import;
+
+fn http_client() -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::limited(10))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
 
 pub struct ModelManager {
     models_dir: PathBuf,
     bundled_model_path: Option<PathBuf>,
+    /// Miroir privé remplaçant les hôtes amont pour tous les téléchargements
+    mirror_url: RwLock<Option<String>>,
 }
 
 impl ModelManager {
@@ -15,430 +26,377 @@ impl ModelManager {
         Self {
             models_dir,
             bundled_model_path,
+            mirror_url: RwLock::new(None),
         }
     }
 
-    /// Retourne le chemin du modèle s'il existe
-    pub fn get_model_path(&self, size: ModelSize) -> Option<PathBuf> {
-        // Pour tiny, vérifier d'abord le bundled
-        if size == ModelSize::Tiny {
-            if let Some(ref bundled) = self.bundled_model_path {
-                let bundled_model = bundled.join(size.file_name());
-                if bundled_model.exists() {
-                    return Some(bundled_model);
-                }
-            }
-        }
-
-        // Sinon, vérifier dans le dossier utilisateur
-        let user_model = self.models_dir.join(size.file_name());
-        if user_model.exists() {
-            Some(user_model)
-        } else {
-            None
+    pub fn set_mirror_url(&self, mirror_url: Option<String>) {
+        if let Ok(mut current) = self.mirror_url.write() {
+            *current = mirror_url.filter(|url| !url.trim().is_empty());
         }
     }
 
-    /// Vérifie si un modèle est disponible
-    pub fn is_model_available(&self, size: ModelSize) -> bool {
-        self.get_model_path(size).is_some()
+    pub fn catalog(&self) -> Arc<ModelCatalog> {
+        catalog::current()
     }
 
-    /// Liste les modèles disponibles
-    pub fn available_models(&self) -> Vec<ModelSize> {
-        [ModelSize::Tiny, ModelSize::Small, ModelSize::Medium]
-            .into_iter()
-            .filter(|&size| self.is_model_available(size))
-            .collect()
+    /// Entrée du catalogue, ou erreur explicite si elle n'existe pas
+    pub fn entry(&self, family: ModelFamily, id: &str) -> Result<CatalogEntry, String> {
+        self.catalog()
+            .get(family, id)
+            .cloned()
+            .ok_or_else(|| format!("Unknown {:?} model: {}", family, id))
     }
 
-    /// Télécharge un modèle depuis Hugging Face
-    pub async fn download_model<F>(
-        &self,
-        size: ModelSize,
-        progress_callback: F,
-    ) -> Result<PathBuf, String>
-    where
-        F: Fn(u64, u64) + Send + 'static,
-    {
-        // Créer le dossier models si nécessaire
-        fs::create_dir_all(&self.models_dir)
-            .await
-            .map_err(|e| format!("Failed to create models directory: {}", e))?;
-
-        let dest_path = self.models_dir.join(size.file_name());
-        let url = size.download_url();
-
-        log::info!("Downloading model {} from {}", size.file_name(), url);
-
-        let client = reqwest::Client::new();
-        let response = client
-            .get(url)
-            .send()
-            .await
-            .map_err(|e| format!("Failed to start download: {}", e))?;
-
-        if !response.status().is_success() {
-            return Err(format!("Download failed with status: {}", response.status()));
-        }
-
-        let total_size = response.content_length().unwrap_or(size.size_bytes());
-        let mut downloaded: u64 = 0;
-
-        let mut file = fs::File::create(&dest_path)
-            .await
-            .map_err(|e| format!("Failed to create file: {}", e))?;
+    // === CATALOG-DRIVEN OPERATIONS ===
 
-        let mut stream = response.bytes_stream();
-
-        while let Some(chunk) = stream.next().await {
-            let chunk = chunk.map_err(|e| format!("Download error: {}", e))?;
-            file.write_all(&chunk)
-                .await
-                .map_err(|e| format!("Write error: {}", e))?;
-            downloaded += chunk.len() as u64;
-            progress_callback(downloaded, total_size);
+    /// Dossier où sont rangés les fichiers d'une entrée
+    fn install_dir(&self, entry: &CatalogEntry) -> PathBuf {
+        match entry.family {
+            ModelFamily::Whisper => self.models_dir.clone(),
+            ModelFamily::Llm => self.models_dir.join("llm"),
+            ModelFamily::Vosk => self.models_dir.join("vosk"),
+            ModelFamily::Parakeet => self.models_dir.join("parakeet").join(entry.directory_name()),
         }
-
-        file.flush()
-            .await
-            .map_err(|e| format!("Flush error: {}", e))?;
-
-        log::info!("Model {} downloaded successfully", size.file_name());
-        Ok(dest_path)
     }
 
-    /// Supprime un modèle téléchargé
-    pub async fn delete_model(&self, size: ModelSize) -> Result<(), String> {
-        let path = self.models_dir.join(size.file_name());
-        if path.exists() {
-            fs::remove_file(&path)
-                .await
-                .map_err(|e| format!("Failed to delete model: {}", e))?;
-        }
-        Ok(())
-    }
-
-    // === VOSK MODELS ===
-
-    /// Get path to a Vosk model if installed
-    pub fn get_vosk_model_path(&self, language: VoskLanguage) -> Option<PathBuf> {
-        let vosk_dir = self.models_dir.join("vosk").join(language.model_name());
-        if vosk_dir.exists() {
-            Some(vosk_dir)
+    /// Garde-fou avant toute suppression récursive : le dossier doit se trouver
+    /// sous le dossier des modèles, sans remontée
+    fn ensure_inside_models_dir(&self, path: &Path) -> Result<(), String> {
+        let inside = path != self.models_dir
+            && path.starts_with(&self.models_dir)
+            && path.components().all(|c| c != Component::ParentDir);
+        if inside {
+            Ok(())
         } else {
-            None
+            Err(format!("Refusing to touch {} outside the models directory", path.display()))
         }
     }
 
-    /// Check if a Vosk model is available
-    pub fn is_vosk_model_available(&self, language: VoskLanguage) -> bool {
-        self.get_vosk_model_path(language).is_some()
+    /// Chemin du modèle installé (fichier ou dossier selon la famille)
+    pub fn installed_path(&self, entry: &CatalogEntry) -> Option<PathBuf> {
+        let dir = self.install_dir(entry);
+        let path = if entry.archive {
+            dir.join(entry.directory_name())
+        } else if entry.files.len() > 1 {
+            // Tous les fichiers doivent être présents (un téléchargement interrompu laisse le dossier)
+            if !entry.files.iter().all(|f| dir.join(&f.name).exists()) {
+                return None;
+            }
+            dir
+        } else {
+            let file = &entry.primary_file()?.name;
+            // Modèles Whisper livrés avec l'application
+            if entry.family == ModelFamily::Whisper {
+                if let Some(ref bundled) = self.bundled_model_path {
+                    let bundled_model = bundled.join(file);
+                    if bundled_model.exists() {
+                        return Some(bundled_model);
+                    }
+                }
+            }
+            dir.join(file)
+        };
+        path.exists().then_some(path)
     }
 
-    /// List available Vosk models
-    pub fn available_vosk_models(&self) -> Vec<VoskLanguage> {
-        use VoskLanguage::*;
-        [En, Fr, De, Es, It, Ru, Zh, Ja, Ko, Pt, Nl, Pl, Uk, Tr, Vi, Ar, Hi, Fa, Ca, Cs]
-            .into_iter()
-            .filter(|&lang| self.is_vosk_model_available(lang))
-            .collect()
+    pub fn is_installed(&self, entry: &CatalogEntry) -> bool {
+        self.installed_path(entry).is_some()
     }
 
-    /// Download a Vosk model
-    pub async fn download_vosk_model<F>(
+    /// Télécharge tous les fichiers d'une entrée (reprise, vérification, annulation)
+    pub async fn download_entry<F>(
         &self,
-        language: VoskLanguage,
-        progress_callback: F,
+        entry: &CatalogEntry,
+        cancel: &CancellationToken,
+        mut progress_callback: F,
     ) -> Result<PathBuf, String>
     where
-        F: Fn(u64, u64) + Send + 'static,
+        F: FnMut(u64, u64) + Send + 'static,
     {
-        let vosk_dir = self.models_dir.join("vosk");
-        fs::create_dir_all(&vosk_dir)
+        let dir = self.install_dir(entry);
+        fs::create_dir_all(&dir)
             .await
-            .map_err(|e| format!("Failed to create vosk directory: {}", e))?;
-
-        let zip_path = vosk_dir.join(format!("{}.zip", language.model_name()));
-        let extract_path = vosk_dir.join(language.model_name());
-        let url = language.download_url();
+            .map_err(|e| format!("Failed to create models directory: {}", e))?;
 
-        log::info!("Downloading Vosk model {} from {}", language.model_name(), url);
+        log::info!("Downloading {:?} model {}", entry.family, entry.id);
 
-        // Download
-        let client = reqwest::Client::new();
-        let response = client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| format!("Failed to start download: {}", e))?;
+        let client = http_client()?;
+        let mirror = self.mirror_url.read().ok().and_then(|m| m.clone());
+        let total_size = entry.size_bytes();
+        let multi_file = entry.files.len() > 1;
+        let mut total_downloaded: u64 = 0;
 
-        if !response.status().is_success() {
-            return Err(format!("Download failed with status: {}", response.status()));
-        }
+        for file in &entry.files {
+            let dest_path = dir.join(&file.name);
 
-        let total_size = response.content_length().unwrap_or(50_000_000);
-        let mut downloaded: u64 = 0;
+            // Les fichiers complets ont déjà été vérifiés et renommés
+            if dest_path.exists() && !entry.archive {
+                log::info!("File {} already exists, skipping", file.name);
+                total_downloaded += file.size_bytes;
+                progress_callback(total_downloaded, total_size);
+                continue;
+            }
 
-        let mut file = fs::File::create(&zip_path)
+            if file.url.is_empty() {
+                return Err(format!("{} has no download URL, import it instead", file.name));
+            }
+            let url = download::mirrored_url(&file.url, mirror.as_deref());
+            log::info!("Downloading {} from {}", file.name, url);
+
+            let request = DownloadRequest {
+                url: &url,
+                dest: &dest_path,
+                sha256: file.sha256.as_ref().map(|h| h.to_ascii_lowercase()),
+                size_hint: file.size_bytes,
+            };
+            let offset = total_downloaded;
+            let mut file_size = file.size_bytes;
+            download::download_file(&client, &request, cancel, &mut |downloaded, total| {
+                file_size = total;
+                if multi_file {
+                    progress_callback(offset + downloaded, total_size);
+                } else {
+                    progress_callback(downloaded, total);
+                }
+            })
             .await
-            .map_err(|e| format!("Failed to create file: {}", e))?;
+            .map_err(|e| if multi_file { format!("{}: {}", file.name, e) } else { e })?;
 
-        let mut stream = response.bytes_stream();
-
-        while let Some(chunk) = stream.next().await {
-            let chunk = chunk.map_err(|e| format!("Download error: {}", e))?;
-            file.write_all(&chunk)
-                .await
-                .map_err(|e| format!("Write error: {}", e))?;
-            downloaded += chunk.len() as u64;
-            progress_callback(downloaded, total_size);
+            total_downloaded += file_size;
         }
 
-        file.flush().await.map_err(|e| format!("Flush error: {}", e))?;
-        drop(file);
+        if entry.archive {
+            self.extract_archive(entry).await?;
+        }
 
-        // Extract zip
-        log::info!("Extracting Vosk model...");
-        let zip_path_clone = zip_path.clone();
-        let vosk_dir_clone = vosk_dir.clone();
+        let path = self
+            .installed_path(entry)
+            .ok_or_else(|| format!("Model {} was downloaded but could not be found", entry.id))?;
+        log::info!("{:?} model {} installed at {:?}", entry.family, entry.id, path);
+        Ok(path)
+    }
 
+    /// Extrait une archive dans un dossier temporaire puis le renomme : un modèle
+    /// à moitié extrait ne doit jamais apparaître comme installé
+    async fn extract_archive(&self, entry: &CatalogEntry) -> Result<(), String> {
+        let dir = self.install_dir(entry);
+        let file = entry.primary_file().ok_or("Archive entry without file")?;
+        let zip_path = dir.join(&file.name);
+        let staging_dir = dir.join(format!(".{}.extracting", entry.directory_name()));
+        let extract_path = dir.join(entry.directory_name());
+        let model_name = entry.directory_name().to_string();
+        self.ensure_inside_models_dir(&staging_dir)?;
+        self.ensure_inside_models_dir(&extract_path)?;
+
+        log::info!("Extracting {}...", file.name);
         tokio::task::spawn_blocking(move || {
-            let file = std::fs::File::open(&zip_path_clone)
+            let _ = std::fs::remove_dir_all(&staging_dir);
+            let file = std::fs::File::open(&zip_path)
                 .map_err(|e| format!("Failed to open zip: {}", e))?;
             let mut archive = zip::ZipArchive::new(file)
                 .map_err(|e| format!("Failed to read zip: {}", e))?;
-            archive.extract(&vosk_dir_clone)
+            archive.extract(&staging_dir)
                 .map_err(|e| format!("Failed to extract: {}", e))?;
-            std::fs::remove_file(&zip_path_clone).ok();
+
+            // L'archive contient normalement un dossier racine au nom du modèle
+            let nested = staging_dir.join(&model_name);
+            let source = if nested.is_dir() { nested } else { staging_dir.clone() };
+            let _ = std::fs::remove_dir_all(&extract_path);
+            std::fs::rename(&source, &extract_path)
+                .map_err(|e| format!("Failed to install model: {}", e))?;
+            let _ = std::fs::remove_dir_all(&staging_dir);
+            std::fs::remove_file(&zip_path).ok();
             Ok::<(), String>(())
         })
         .await
-        .map_err(|e| format!("Task error: {}", e))??;
-
-        log::info!("Vosk model {} installed successfully", language.model_name());
-        Ok(extract_path)
-    }
-
-    // === PARAKEET MODELS ===
-
-    /// Get path to Parakeet model if installed
-    pub fn get_parakeet_model_path(&self, model_size: ParakeetModelSize) -> Option<PathBuf> {
-        let parakeet_dir = self.models_dir.join("parakeet").join(model_size.model_name());
-        if parakeet_dir.exists() {
-            Some(parakeet_dir)
-        } else {
-            None
-        }
+        .map_err(|e| format!("Task error: {}", e))?
     }
 
-    /// Check if Parakeet model is available
-    pub fn is_parakeet_available(&self, model_size: ParakeetModelSize) -> bool {
-        self.get_parakeet_model_path(model_size).is_some()
-    }
-
-    /// List available Parakeet models
-    pub fn available_parakeet_models(&self) -> Vec<ParakeetModelSize> {
-        [ParakeetModelSize::Tdt06bV3]
-            .into_iter()
-            .filter(|&size| self.is_parakeet_available(size))
-            .collect()
-    }
-
-    /// Download a Parakeet model from HuggingFace (istupakov/parakeet-tdt-0.6b-v3-onnx)
-    pub async fn download_parakeet_model<F>(
+    /// Importe un modèle local. `id` désigne l'entrée du catalogue à remplacer ;
+    /// un identifiant inconnu (ou absent) enregistre un nouveau modèle, quelle
+    /// que soit la famille, dans le manifeste utilisateur.
+    pub async fn import_model(
         &self,
-        model_size: ParakeetModelSize,
-        progress_callback: F,
-    ) -> Result<PathBuf, String>
-    where
-        F: Fn(u64, u64) + Send + 'static,
-    {
-        let parakeet_dir = self.models_dir.join("parakeet");
-        let model_dir = parakeet_dir.join(model_size.model_name());
-
-        fs::create_dir_all(&model_dir)
-            .await
-            .map_err(|e| format!("Failed to create parakeet directory: {}", e))?;
-
-        // Files to download from HuggingFace (non-quantized for tract-onnx compatibility)
-        let base_url = "https://huggingface.co/istupakov/parakeet-tdt-0.6b-v3-onnx/resolve/main";
-        let files = [
-            ("encoder-model.onnx", 41_800_000u64),
-            ("encoder-model.onnx.data", 2_440_000_000u64),
-            ("decoder_joint-model.onnx", 72_500_000u64),
-            ("vocab.txt", 94_000u64),
-        ];
-
-        let total_size: u64 = files.iter().map(|(_, s)| s).sum();
-        let mut total_downloaded: u64 = 0;
-
-        let client = reqwest::Client::builder()
-            .redirect(reqwest::redirect::Policy::limited(10))
-            .build()
-            .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
-
-        for (filename, estimated_size) in files {
-            let url = format!("{}/{}", base_url, filename);
-            let dest_path = model_dir.join(filename);
-
-            // Skip if file already exists with reasonable size
-            if dest_path.exists() {
-                if let Ok(meta) = std::fs::metadata(&dest_path) {
-                    if meta.len() > estimated_size / 2 {
-                        log::info!("File {} already exists, skipping", filename);
-                        total_downloaded += estimated_size;
-                        progress_callback(total_downloaded, total_size);
-                        continue;
-                    }
-                }
+        family: ModelFamily,
+        source: &Path,
+        id: Option<&str>,
+        link: bool,
+    ) -> Result<CatalogEntry, String> {
+        let existing = id.and_then(|id| self.catalog().get(family, id).cloned());
+        model_import::validate_source(family, source, existing.as_ref())?;
+
+        let entry = match existing {
+            Some(entry) => {
+                self.verify_import(&entry, source).await?;
+                entry
             }
+            None => model_import::custom_entry(family, source, id)?,
+        };
 
-            log::info!("Downloading {} from {}", filename, url);
+        let dest = self.import_dest(&entry)?;
+        self.ensure_inside_models_dir(&dest)?;
 
-            let response = client
-                .get(&url)
-                .send()
-                .await
-                .map_err(|e| format!("Failed to download {}: {}", filename, e))?;
-
-            if !response.status().is_success() {
-                return Err(format!("Download of {} failed with status: {}", filename, response.status()));
-            }
-
-            let file_size = response.content_length().unwrap_or(estimated_size);
-            let mut file_downloaded: u64 = 0;
-
-            let mut file = fs::File::create(&dest_path)
-                .await
-                .map_err(|e| format!("Failed to create file {}: {}", filename, e))?;
-
-            let mut stream = response.bytes_stream();
-
-            while let Some(chunk) = stream.next().await {
-                let chunk = chunk.map_err(|e| format!("Download error for {}: {}", filename, e))?;
-                file.write_all(&chunk)
-                    .await
-                    .map_err(|e| format!("Write error for {}: {}", filename, e))?;
-                file_downloaded += chunk.len() as u64;
-                progress_callback(total_downloaded + file_downloaded, total_size);
-            }
+        log::info!("Importing {:?} model {} from {:?} (link: {})", family, entry.id, source, link);
+        let source_path = source.to_path_buf();
+        tokio::task::spawn_blocking(move || model_import::install(&source_path, &dest, link))
+            .await
+            .map_err(|e| format!("Task error: {}", e))??;
 
-            file.flush().await.map_err(|e| format!("Flush error: {}", e))?;
-            total_downloaded += file_size;
-            log::info!("Downloaded {} successfully", filename);
+        if !entry.is_downloadable() {
+            catalog::register_user_entry(entry.clone())?;
         }
+        Ok(entry)
+    }
 
-        log::info!("Parakeet model {} downloaded successfully", model_size.model_name());
-        Ok(model_dir)
+    /// Emplacement d'un modèle importé : celui où `installed_path` le cherche
+    fn import_dest(&self, entry: &CatalogEntry) -> Result<PathBuf, String> {
+        let dir = self.install_dir(entry);
+        Ok(match entry.family {
+            ModelFamily::Whisper | ModelFamily::Llm => {
+                dir.join(&entry.primary_file().ok_or("Catalog entry without file")?.name)
+            }
+            ModelFamily::Vosk => dir.join(entry.directory_name()),
+            ModelFamily::Parakeet => dir,
+        })
     }
 
-    /// Delete a Parakeet model
-    pub async fn delete_parakeet_model(&self, model_size: ParakeetModelSize) -> Result<(), String> {
-        let model_dir = self.models_dir.join("parakeet").join(model_size.model_name());
-        if model_dir.exists() {
-            fs::remove_dir_all(&model_dir)
-                .await
-                .map_err(|e| format!("Failed to delete model: {}", e))?;
+    /// Contrôle les empreintes connues des fichiers importés
+    async fn verify_import(&self, entry: &CatalogEntry, source: &Path) -> Result<(), String> {
+        // L'empreinte d'une archive porte sur le zip, pas sur le dossier extrait
+        if entry.archive {
+            return Ok(());
+        }
+        for file in &entry.files {
+            let Some(ref expected) = file.sha256 else {
+                continue;
+            };
+            let path = if source.is_dir() { source.join(&file.name) } else { source.to_path_buf() };
+            let actual = download::file_sha256(&path).await?;
+            if !actual.eq_ignore_ascii_case(expected) {
+                return Err(format!(
+                    "Checksum mismatch for {}: expected {}, got {}",
+                    file.name, expected, actual
+                ));
+            }
         }
         Ok(())
     }
 
-    // === LLM MODELS (Mistral) ===
-
-    /// Get path to a LLM model if installed
-    pub fn get_llm_model_path(&self, model_size: LocalLlmModel) -> Option<PathBuf> {
-        let llm_dir = self.models_dir.join("llm");
-        let model_path = llm_dir.join(model_size.file_name());
-        if model_path.exists() {
-            Some(model_path)
+    /// Supprime un modèle et ses éventuels fichiers partiels
+    pub async fn delete_entry(&self, entry: &CatalogEntry) -> Result<(), String> {
+        let dir = self.install_dir(entry);
+        let model_dir = if entry.archive {
+            Some(dir.join(entry.directory_name()))
+        } else if entry.family == ModelFamily::Parakeet {
+            Some(dir.clone())
         } else {
             None
+        };
+        if let Some(model_dir) = model_dir {
+            self.ensure_inside_models_dir(&model_dir)?;
+            if model_dir.exists() {
+                fs::remove_dir_all(&model_dir)
+                    .await
+                    .map_err(|e| format!("Failed to delete model: {}", e))?;
+            }
+        }
+        for file in &entry.files {
+            let path = dir.join(&file.name);
+            if path.exists() {
+                fs::remove_file(&path)
+                    .await
+                    .map_err(|e| format!("Failed to delete model: {}", e))?;
+            }
+            let _ = fs::remove_file(download::part_path(&path)).await;
         }
+        Ok(())
     }
 
-    /// Check if a LLM model is available
-    pub fn is_llm_model_available(&self, model_size: LocalLlmModel) -> bool {
-        self.get_llm_model_path(model_size).is_some()
+    // === WHISPER MODELS ===
+
+    /// Retourne le chemin du modèle s'il existe
+    pub fn get_model_path(&self, id: &str) -> Option<PathBuf> {
+        let entry = self.entry(ModelFamily::Whisper, id).ok()?;
+        self.installed_path(&entry)
     }
 
-    /// List available LLM models
-    pub fn available_llm_models(&self) -> Vec<LocalLlmModel> {
-        [LocalLlmModel::SmolLM2_360M, LocalLlmModel::Phi3Mini, LocalLlmModel::Qwen2_5_3B]
-            .into_iter()
-            .filter(|&size| self.is_llm_model_available(size))
-            .collect()
+    /// Vérifie si un modèle est disponible
+    pub fn is_model_available(&self, id: &str) -> bool {
+        self.get_model_path(id).is_some()
     }
 
-    /// Download a LLM model from HuggingFace
-    pub async fn download_llm_model<F>(
+    /// Télécharge un modèle Whisper
+    pub async fn download_model<F>(
         &self,
-        model_size: LocalLlmModel,
+        id: &str,
+        cancel: &CancellationToken,
         progress_callback: F,
     ) -> Result<PathBuf, String>
     where
-        F: Fn(u64, u64) + Send + 'static,
+        F: FnMut(u64, u64) + Send + 'static,
     {
-        let llm_dir = self.models_dir.join("llm");
-        fs::create_dir_all(&llm_dir)
-            .await
-            .map_err(|e| format!("Failed to create llm directory: {}", e))?;
-
-        let dest_path = llm_dir.join(model_size.file_name());
-        let url = model_size.download_url();
-
-        log::info!("Downloading LLM model {} from {}", model_size.file_name(), url);
-
-        let client = reqwest::Client::builder()
-            .redirect(reqwest::redirect::Policy::limited(10))
-            .build()
-            .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
-
-        let response = client
-            .get(url)
-            .send()
-            .await
-            .map_err(|e| format!("Failed to start download: {}", e))?;
-
-        if !response.status().is_success() {
-            return Err(format!("Download failed with status: {}", response.status()));
-        }
-
-        let total_size = response.content_length().unwrap_or(model_size.size_bytes());
-        let mut downloaded: u64 = 0;
-
-        let mut file = fs::File::create(&dest_path)
-            .await
-            .map_err(|e| format!("Failed to create file: {}", e))?;
+        let entry = self.entry(ModelFamily::Whisper, id)?;
+        self.download_entry(&entry, cancel, progress_callback).await
+    }
 
-        let mut stream = response.bytes_stream();
+    /// Supprime un modèle téléchargé
+    pub async fn delete_model(&self, id: &str) -> Result<(), String> {
+        let entry = self.entry(ModelFamily::Whisper, id)?;
+        self.delete_entry(&entry).await
+    }
 
-        while let Some(chunk) = stream.next().await {
-            let chunk = chunk.map_err(|e| format!("Download error: {}", e))?;
-            file.write_all(&chunk)
-                .await
-                .map_err(|e| format!("Write error: {}", e))?;
-            downloaded += chunk.len() as u64;
-            progress_callback(downloaded, total_size);
-        }
+    // === VOSK, PARAKEET ET LLM ===
 
-        file.flush()
-            .await
-            .map_err(|e| format!("Flush error: {}", e))?;
+    /// Entrée et chemin d'un modèle installé, d'après son identifiant catalogue
+    pub fn installed_entry(&self, family: ModelFamily, id: &str) -> Option<(CatalogEntry, PathBuf)> {
+        let entry = self.entry(family, id).ok()?;
+        let path = self.installed_path(&entry)?;
+        Some((entry, path))
+    }
 
-        log::info!("LLM model {} downloaded successfully", model_size.file_name());
-        Ok(dest_path)
+    /// Modèles installés d'une famille
+    pub fn installed_entries(&self, family: ModelFamily) -> Vec<CatalogEntry> {
+        self.catalog()
+            .family(family)
+            .filter(|entry| self.is_installed(entry))
+            .cloned()
+            .collect()
     }
+}
 
-    /// Delete a LLM model
-    pub async fn delete_llm_model(&self, model_size: LocalLlmModel) -> Result<(), String> {
-        let llm_dir = self.models_dir.join("llm");
-        let path = llm_dir.join(model_size.file_name());
-        if path.exists() {
-            fs::remove_file(&path)
-                .await
-                .map_err(|e| format!("Failed to delete LLM model: {}", e))?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_imported_models_resolve_for_every_family() {
+        let dir = std::env::temp_dir().join(format!("scribe-manager-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let sources = dir.join("sources");
+        std::fs::create_dir_all(sources.join("my-vosk").join("am")).unwrap();
+        std::fs::create_dir_all(sources.join("my-parakeet")).unwrap();
+        for name in ["encoder-model.onnx", "decoder_joint-model.onnx", "vocab.txt"] {
+            std::fs::write(sources.join("my-parakeet").join(name), b"onnx").unwrap();
         }
-        Ok(())
+        std::fs::write(sources.join("my-llm.gguf"), b"GGUF").unwrap();
+
+        let manager = ModelManager::new(dir.clone(), None);
+        for (family, source) in [
+            (ModelFamily::Vosk, "my-vosk"),
+            (ModelFamily::Parakeet, "my-parakeet"),
+            (ModelFamily::Llm, "my-llm.gguf"),
+        ] {
+            let source = sources.join(source);
+            let entry = model_import::custom_entry(family, &source, None).unwrap();
+            assert!(!manager.is_installed(&entry), "{:?}", family);
+
+            let dest = manager.import_dest(&entry).unwrap();
+            manager.ensure_inside_models_dir(&dest).unwrap();
+            model_import::install(&source, &dest, false).unwrap();
+            // Le chemin attendu par les moteurs : dossier Vosk/Parakeet, fichier GGUF
+            assert_eq!(manager.installed_path(&entry), Some(dest), "{:?}", family);
+        }
+        let _ = std::fs::remove_dir_all(&dir);
     }
 }
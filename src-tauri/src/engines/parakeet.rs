@@ -1,5 +1,9 @@
+use crate::audio::fft::{fft, mel_filterbank};
+use crate::engines::catalog::CatalogEntry;
+use crate::engines::segments;
 use crate::engines::traits::SpeechEngine;
-use crate::types::TranscriptionResult;
+use crate::engines::vocabulary::Vocabulary;
+use crate::types::{TranscriptionResult, WordTiming};
 use chrono::Utc;
 use std::collections::HashMap;
 use std::fs;
@@ -9,45 +13,17 @@ use tract_onnx::prelude::*;
 
 type TractModel = SimplePlan<TypedFact, Box<dyn TypedOp>, Graph<TypedFact, Box<dyn TypedOp>>>;
 
-/// Parakeet model size options
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub enum ParakeetModelSize {
-    #[default]
-    Tdt06bV3,
-}
-
-impl ParakeetModelSize {
-    pub fn model_name(&self) -> &'static str {
-        match self {
-            ParakeetModelSize::Tdt06bV3 => "parakeet-tdt-0.6b-v3",
-        }
-    }
-
-    pub fn display_name(&self) -> &'static str {
-        match self {
-            ParakeetModelSize::Tdt06bV3 => "Parakeet TDT 0.6B v3",
-        }
-    }
-}
-
-impl From<crate::types::ParakeetModelSize> for ParakeetModelSize {
-    fn from(value: crate::types::ParakeetModelSize) -> Self {
-        match value {
-            crate::types::ParakeetModelSize::Tdt06bV3 => ParakeetModelSize::Tdt06bV3,
-        }
-    }
-}
-
 pub struct ParakeetEngine {
     encoder: Mutex<TractModel>,
     decoder_joint: Mutex<TractModel>,
     vocab: HashMap<i64, String>,
-    model_size: ParakeetModelSize,
+    model: CatalogEntry,
     blank_id: i64,
+    vocabulary: Vocabulary,
 }
 
 impl ParakeetEngine {
-    pub fn new(model_path: &Path, model_size: ParakeetModelSize) -> Result<Self, String> {
+    pub fn new(model_path: &Path, model: CatalogEntry) -> Result<Self, String> {
         log::info!("Loading Parakeet model from {:?}", model_path);
 
         if !model_path.exists() {
@@ -111,53 +87,12 @@ impl ParakeetEngine {
             encoder: Mutex::new(encoder),
             decoder_joint: Mutex::new(decoder_joint),
             vocab,
-            model_size,
+            model,
             blank_id,
+            vocabulary: Vocabulary::default(),
         })
     }
 
-    /// Radix-2 Cooley-Tukey FFT (in-place, n must be power of 2)
-    fn fft(buf: &mut [(f32, f32)]) {
-        let n = buf.len();
-        if n <= 1 {
-            return;
-        }
-
-        // Bit-reversal permutation
-        let mut j = 0usize;
-        for i in 1..n {
-            let mut bit = n >> 1;
-            while j & bit != 0 {
-                j ^= bit;
-                bit >>= 1;
-            }
-            j ^= bit;
-            if i < j {
-                buf.swap(i, j);
-            }
-        }
-
-        // Butterfly passes
-        let mut len = 2;
-        while len <= n {
-            let half = len / 2;
-            let angle = -2.0 * std::f32::consts::PI / len as f32;
-            let wn = (angle.cos(), angle.sin());
-            for i in (0..n).step_by(len) {
-                let mut w = (1.0f32, 0.0f32);
-                for k in 0..half {
-                    let u = buf[i + k];
-                    let t = buf[i + k + half];
-                    let v = (t.0 * w.0 - t.1 * w.1, t.0 * w.1 + t.1 * w.0);
-                    buf[i + k] = (u.0 + v.0, u.1 + v.1);
-                    buf[i + k + half] = (u.0 - v.0, u.1 - v.1);
-                    w = (w.0 * wn.0 - w.1 * wn.1, w.0 * wn.1 + w.1 * wn.0);
-                }
-            }
-            len <<= 1;
-        }
-    }
-
     /// Compute mel-spectrogram features (80 mel bins)
     fn compute_features(&self, audio: &[f32], sample_rate: u32) -> Vec<f32> {
         let n_fft = 512;
@@ -173,7 +108,7 @@ impl ParakeetEngine {
         };
 
         let mut mel_spec = vec![0.0f32; n_mels * num_frames];
-        let mel_filters = Self::create_mel_filterbank(n_fft, sample_rate, n_mels, fmin, fmax);
+        let mel_filters = mel_filterbank(n_fft, sample_rate, n_mels, fmin, fmax);
 
         // Pre-compute Hann window
         let window: Vec<f32> = (0..n_fft)
@@ -195,7 +130,7 @@ impl ParakeetEngine {
                 };
             }
 
-            Self::fft(&mut fft_buf);
+            fft(&mut fft_buf);
 
             // Compute magnitudes and apply mel filterbank
             for mel_idx in 0..n_mels {
@@ -222,53 +157,6 @@ impl ParakeetEngine {
         mel_spec
     }
 
-    fn create_mel_filterbank(
-        n_fft: usize,
-        sample_rate: u32,
-        n_mels: usize,
-        fmin: f32,
-        fmax: f32,
-    ) -> Vec<f32> {
-        let n_freqs = n_fft / 2 + 1;
-        let mut filterbank = vec![0.0f32; n_mels * n_freqs];
-
-        let hz_to_mel = |hz: f32| 2595.0 * (1.0 + hz / 700.0).log10();
-        let mel_to_hz = |mel: f32| 700.0 * (10.0f32.powf(mel / 2595.0) - 1.0);
-
-        let mel_min = hz_to_mel(fmin);
-        let mel_max = hz_to_mel(fmax);
-
-        let mel_points: Vec<f32> = (0..=n_mels + 1)
-            .map(|i| mel_min + (mel_max - mel_min) * i as f32 / (n_mels + 1) as f32)
-            .collect();
-
-        let hz_points: Vec<f32> = mel_points.iter().map(|&m| mel_to_hz(m)).collect();
-        let bin_points: Vec<usize> = hz_points
-            .iter()
-            .map(|&hz| ((n_fft as f32 + 1.0) * hz / sample_rate as f32).floor() as usize)
-            .collect();
-
-        for m in 0..n_mels {
-            let start = bin_points[m];
-            let center = bin_points[m + 1];
-            let end = bin_points[m + 2];
-
-            for k in start..center {
-                if k < n_freqs && center > start {
-                    filterbank[m * n_freqs + k] = (k - start) as f32 / (center - start) as f32;
-                }
-            }
-
-            for k in center..end {
-                if k < n_freqs && end > center {
-                    filterbank[m * n_freqs + k] = (end - k) as f32 / (end - center) as f32;
-                }
-            }
-        }
-
-        filterbank
-    }
-
     fn decode_tokens(&self, token_ids: &[i64]) -> String {
         let mut text = String::new();
 
@@ -286,15 +174,51 @@ impl ParakeetEngine {
         text.trim().to_string()
     }
 
+    /// Regroupe les tokens SentencePiece en mots horodatés à partir de la
+    /// trame d'encodeur à laquelle chaque token a été émis
+    fn word_timings(&self, tokens: &[(i64, usize)], frame_secs: f32) -> Vec<WordTiming> {
+        let mut words: Vec<WordTiming> = Vec::new();
+
+        for &(id, frame) in tokens {
+            let Some(token) = self.vocab.get(&id) else {
+                continue;
+            };
+            let start = frame as f32 * frame_secs;
+            let end = start + frame_secs;
+
+            match (token.strip_prefix('▁'), words.last_mut()) {
+                (None, Some(word)) => {
+                    word.text.push_str(token);
+                    word.end = end;
+                }
+                (piece, _) => {
+                    let piece = piece.unwrap_or(token);
+                    if piece.is_empty() {
+                        continue;
+                    }
+                    words.push(WordTiming {
+                        start,
+                        end,
+                        text: piece.to_string(),
+                        confidence: None,
+                    });
+                }
+            }
+        }
+
+        words
+    }
+
+    /// Décodage glouton ; renvoie chaque token avec l'index de sa trame d'encodeur
     fn greedy_decode(
         &self,
         encoder_out: &tract_ndarray::ArrayD<f32>,
-    ) -> Result<Vec<i64>, String> {
+    ) -> Result<Vec<(i64, usize)>, String> {
         let shape = encoder_out.shape();
         let time_steps = shape[1];
         let encoder_dim = shape[2];
 
-        let mut decoded_tokens: Vec<i64> = Vec::new();
+        let mut decoded_tokens: Vec<(i64, usize)> = Vec::new();
         let decoder_joint = self.decoder_joint.lock().map_err(|e| e.to_string())?;
 
         // Initial decoder state
@@ -344,7 +268,7 @@ impl ParakeetEngine {
             }
 
             if max_idx != self.blank_id {
-                decoded_tokens.push(max_idx);
+                decoded_tokens.push((max_idx, t));
                 last_token = max_idx;
             }
         }
@@ -406,8 +330,17 @@ impl SpeechEngine for ParakeetEngine {
         drop(encoder);
 
         // Greedy decode
-        let token_ids = self.greedy_decode(&encoder_out_owned)?;
-        let text = self.decode_tokens(&token_ids);
+        let time_steps = encoder_out_owned.shape()[1].max(1);
+        let tokens = self.greedy_decode(&encoder_out_owned)?;
+        let token_ids: Vec<i64> = tokens.iter().map(|&(id, _)| id).collect();
+        let text = self.vocabulary.correct(&self.decode_tokens(&token_ids));
+
+        // Horodatage : chaque trame d'encodeur couvre une durée fixe de l'audio
+        let frame_secs = duration_seconds / time_steps as f32;
+        let mut segments = segments::group_words(self.word_timings(&tokens, frame_secs), 0.9);
+        for segment in &mut segments {
+            segment.text = self.vocabulary.correct(&segment.text);
+        }
 
         let processing_time_ms = start_time.elapsed().as_millis() as u64;
 
@@ -425,6 +358,8 @@ impl SpeechEngine for ParakeetEngine {
             detected_language: Some("auto".to_string()),
             timestamp: Utc::now().timestamp(),
             model_used: Some(self.model_display_name()),
+            segments,
+            translation: None,
         })
     }
 
@@ -433,7 +368,11 @@ impl SpeechEngine for ParakeetEngine {
     }
 
     fn model_display_name(&self) -> String {
-        format!("Parakeet {}", self.model_size.display_name())
+        self.model.display_name.clone()
+    }
+
+    fn set_vocabulary(&mut self, vocabulary: Vocabulary) {
+        self.vocabulary = vocabulary;
     }
 }
 
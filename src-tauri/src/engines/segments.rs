@@ -0,0 +1,108 @@
+//! Construction de segments horodatés à partir des mots horodatés
+
+use crate::types::{Segment, WordTiming};
+
+/// Silence entre deux mots qui ouvre un nouveau segment (secondes)
+const MAX_WORD_GAP_SECS: f32 = 1.0;
+
+/// Durée de segment au-delà de laquelle la coupure est forcée (secondes)
+const MAX_SEGMENT_SECS: f32 = 15.0;
+
+/// Segment unique couvrant tous les mots donnés
+pub fn segment_from_words(words: Vec<WordTiming>, default_confidence: f32) -> Option<Segment> {
+    let first = words.first()?;
+    let last = words.last()?;
+
+    let text = words
+        .iter()
+        .map(|w| w.text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let scores: Vec<f32> = words.iter().filter_map(|w| w.confidence).collect();
+    let confidence = if scores.is_empty() {
+        default_confidence
+    } else {
+        scores.iter().sum::<f32>() / scores.len() as f32
+    };
+
+    Some(Segment {
+        start: first.start,
+        end: last.end,
+        text,
+        confidence,
+        speaker: None,
+        words,
+    })
+}
+
+/// Regroupe les mots en segments proches de la phrase : coupure sur la
+/// ponctuation de fin de phrase, les longues pauses et les suites trop longues
+pub fn group_words(words: Vec<WordTiming>, default_confidence: f32) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut current: Vec<WordTiming> = Vec::new();
+
+    for word in words {
+        if let Some(prev) = current.last() {
+            let gap = word.start - prev.end;
+            let span = word.end - current[0].start;
+            if gap > MAX_WORD_GAP_SECS || span > MAX_SEGMENT_SECS {
+                segments.extend(segment_from_words(std::mem::take(&mut current), default_confidence));
+            }
+        }
+
+        let ends_sentence = word.text.ends_with(['.', '?', '!', '…']);
+        current.push(word);
+
+        if ends_sentence {
+            segments.extend(segment_from_words(std::mem::take(&mut current), default_confidence));
+        }
+    }
+
+    segments.extend(segment_from_words(current, default_confidence));
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(text: &str, start: f32, end: f32) -> WordTiming {
+        WordTiming {
+            start,
+            end,
+            text: text.to_string(),
+            confidence: None,
+        }
+    }
+
+    #[test]
+    fn test_group_words_splits_on_punctuation_and_pauses() {
+        let words = vec![
+            word("Bonjour", 0.0, 0.4),
+            word("à", 0.5, 0.6),
+            word("tous.", 0.6, 0.9),
+            word("On", 1.0, 1.1),
+            word("commence", 1.1, 1.6),
+            word("maintenant", 3.0, 3.5),
+        ];
+
+        let segments = group_words(words, 0.9);
+        let texts: Vec<&str> = segments.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["Bonjour à tous.", "On commence", "maintenant"]);
+        assert_eq!(segments[1].start, 1.0);
+        assert_eq!(segments[1].end, 1.6);
+        assert_eq!(segments[1].words.len(), 2);
+    }
+
+    #[test]
+    fn test_segment_confidence_is_word_average() {
+        let mut words = vec![word("a", 0.0, 0.1), word("b", 0.1, 0.2)];
+        words[0].confidence = Some(0.5);
+        words[1].confidence = Some(1.0);
+
+        let segment = segment_from_words(words, 0.9).unwrap();
+        assert!((segment.confidence - 0.75).abs() < 1e-6);
+        assert!(segment_from_words(Vec::new(), 0.9).is_none());
+    }
+}
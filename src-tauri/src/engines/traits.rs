@@ -1,7 +1,47 @@
+use crate::engines::vocabulary::Vocabulary;
 use crate::types::TranscriptionResult;
 
 pub trait SpeechEngine: Send + Sync {
     fn transcribe(&self, audio: &[f32], sample_rate: u32) -> Result<TranscriptionResult, String>;
     fn name(&self) -> &str;
     fn model_display_name(&self) -> String;
+    /// Met à jour le vocabulaire personnalisé (dictionnaire utilisateur)
+    fn set_vocabulary(&mut self, vocabulary: Vocabulary);
+    /// Langue imposée (`None` : détection automatique) pour les moteurs multilingues
+    fn set_language(&mut self, _language: Option<String>) {}
+    /// Le moteur sait traduire directement la parole vers l'anglais
+    fn supports_translation(&self) -> bool {
+        false
+    }
+    /// Transcription traduite en anglais, sans passer par un LLM
+    fn translate_to_english(&self, _audio: &[f32], _sample_rate: u32) -> Result<TranscriptionResult, String> {
+        Err(format!("{} cannot translate speech", self.name()))
+    }
+    /// Reconnaissance incrémentale native, si le moteur la supporte
+    fn as_streaming(&self) -> Option<&dyn StreamingSpeechEngine> {
+        None
+    }
+}
+
+/// Moteur capable de transcrire l'audio au fil de l'eau
+pub trait StreamingSpeechEngine {
+    /// Ouvre un flux de reconnaissance pour un enregistrement
+    fn start_stream(&self, sample_rate: u32) -> Result<Box<dyn SpeechStream>, String>;
+}
+
+/// Flux de reconnaissance : reçoit l'audio par morceaux
+pub trait SpeechStream: Send {
+    /// Ajoute des échantillons et renvoie l'état courant de la reconnaissance
+    fn accept(&mut self, samples: &[f32]) -> Result<StreamingUpdate, String>;
+    /// Termine le flux et renvoie le texte final restant
+    fn finish(&mut self) -> Result<String, String>;
+}
+
+/// Résultat incrémental d'un flux de reconnaissance
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StreamingUpdate {
+    /// Texte définitivement reconnu depuis la mise à jour précédente
+    pub final_text: Option<String>,
+    /// Hypothèse en cours, susceptible de changer
+    pub partial: String,
 }
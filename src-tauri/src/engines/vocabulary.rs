@@ -0,0 +1,426 @@
+//! Vocabulaire personnalisé des moteurs de reconnaissance
+//!
+//! Construit les indices de reconnaissance à partir du dictionnaire
+//! utilisateur (prompt initial pour Whisper, reclassement N-best pour Vosk)
+//! et applique après reconnaissance une passe qui remplace les mots proches
+//! par leur orthographe du dictionnaire (clé phonétique et distance
+//! d'édition). Cette passe vaut pour tous les moteurs.
+
+/// Longueur maximale du prompt initial Whisper construit depuis le dictionnaire
+const MAX_PROMPT_CHARS: usize = 800;
+
+/// Mots plus courts (normalisés) corrigés seulement s'ils sont identiques
+const MIN_FUZZY_LEN: usize = 4;
+
+/// Distance d'édition normalisée maximale sur la seule orthographe
+const MAX_SPELLING_RATIO: f32 = 0.2;
+
+/// Distance d'édition normalisée maximale quand les clés phonétiques concordent
+const MAX_PHONETIC_RATIO: f32 = 0.4;
+
+#[derive(Debug, Clone)]
+struct VocabularyEntry {
+    /// Orthographe saisie par l'utilisateur
+    word: String,
+    /// Forme normalisée sans espaces (ASCII minuscule)
+    normalized: String,
+    /// Forme normalisée, sons proches fusionnés
+    sounds: String,
+    /// Clé phonétique tirée de `sounds`
+    phonetic: String,
+    /// Nombre de mots (séparés par des espaces) de l'entrée
+    token_count: usize,
+}
+
+/// Vocabulaire utilisateur qui oriente et corrige les transcriptions
+#[derive(Debug, Clone, Default)]
+pub struct Vocabulary {
+    entries: Vec<VocabularyEntry>,
+}
+
+impl Vocabulary {
+    pub fn new(words: &[String]) -> Self {
+        let entries = words
+            .iter()
+            .map(|w| w.trim())
+            .filter(|w| !w.is_empty())
+            .filter_map(|w| {
+                let normalized: String = w.split_whitespace().map(normalize).collect();
+                if normalized.is_empty() {
+                    return None;
+                }
+                let sounds = sound_spelling(&normalized);
+                Some(VocabularyEntry {
+                    word: w.to_string(),
+                    phonetic: phonetic_key(&sounds),
+                    sounds,
+                    normalized,
+                    token_count: w.split_whitespace().count(),
+                })
+            })
+            .collect();
+
+        Self { entries }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Prompt initial listant les mots du dictionnaire (Whisper)
+    pub fn prompt(&self) -> Option<String> {
+        if self.entries.is_empty() {
+            return None;
+        }
+
+        let mut prompt = String::new();
+        for entry in &self.entries {
+            if prompt.len() + entry.word.len() + 2 > MAX_PROMPT_CHARS {
+                break;
+            }
+            if !prompt.is_empty() {
+                prompt.push_str(", ");
+            }
+            prompt.push_str(&entry.word);
+        }
+        prompt.push('.');
+        Some(prompt)
+    }
+
+    /// Nombre d'entrées du dictionnaire présentes telles quelles (après normalisation) dans un texte
+    pub fn count_matches(&self, text: &str) -> usize {
+        let tokens: Vec<String> = text.split_whitespace().map(normalize).collect();
+        self.entries
+            .iter()
+            .filter(|entry| {
+                let n = entry.token_count;
+                n <= tokens.len()
+                    && tokens.windows(n).any(|w| w.concat() == entry.normalized)
+            })
+            .count()
+    }
+
+    /// Remplace les mots proches par leur orthographe du dictionnaire
+    pub fn correct(&self, text: &str) -> String {
+        if self.entries.is_empty() || text.is_empty() {
+            return text.to_string();
+        }
+
+        let tokens = tokenize(text);
+        let max_window = self
+            .entries
+            .iter()
+            .map(|e| e.token_count + 1)
+            .max()
+            .unwrap_or(1);
+
+        let mut output = String::with_capacity(text.len());
+        let mut cursor = 0;
+        let mut i = 0;
+
+        while i < tokens.len() {
+            let mut replaced = false;
+
+            for n in (1..=max_window.min(tokens.len() - i)).rev() {
+                let window = &tokens[i..i + n];
+                if let Some(entry) = self.best_match(window) {
+                    let first = &window[0];
+                    let last = &window[n - 1];
+
+                    output.push_str(&text[cursor..first.start]);
+                    output.push_str(&text[first.start..first.core_start]);
+                    output.push_str(&entry.word);
+                    output.push_str(&text[last.core_end..last.end]);
+                    cursor = last.end;
+
+                    i += n;
+                    replaced = true;
+                    break;
+                }
+            }
+
+            if !replaced {
+                i += 1;
+            }
+        }
+
+        output.push_str(&text[cursor..]);
+        output
+    }
+
+    fn best_match(&self, window: &[Token]) -> Option<&VocabularyEntry> {
+        if window.iter().any(|t| t.normalized.is_empty()) {
+            return None;
+        }
+        // Seuls les bords de la fenêtre peuvent porter de la ponctuation
+        if window.len() > 1
+            && window[..window.len() - 1].iter().any(|t| t.core_end != t.end)
+        {
+            return None;
+        }
+
+        let candidate: String = window.iter().map(|t| t.normalized.as_str()).collect();
+        let candidate_sounds = sound_spelling(&candidate);
+        let candidate_phonetic = phonetic_key(&candidate_sounds);
+        let n = window.len();
+
+        let mut best: Option<(&VocabularyEntry, f32)> = None;
+
+        for entry in &self.entries {
+            // Le moteur peut couper un mot du dictionnaire en un mot de plus
+            if entry.token_count != n && entry.token_count + 1 != n {
+                continue;
+            }
+
+            let ratio = if candidate == entry.normalized {
+                0.0
+            } else {
+                let max_len = candidate.len().max(entry.normalized.len());
+                let min_len = candidate.len().min(entry.normalized.len());
+                if min_len < MIN_FUZZY_LEN || (max_len - min_len) * 3 > max_len {
+                    continue;
+                }
+
+                let ratio = levenshtein(&candidate, &entry.normalized) as f32 / max_len as f32;
+                if ratio <= MAX_SPELLING_RATIO {
+                    ratio
+                } else if candidate_phonetic == entry.phonetic {
+                    let sounds_len = candidate_sounds.len().max(entry.sounds.len());
+                    let sounds_ratio =
+                        levenshtein(&candidate_sounds, &entry.sounds) as f32 / sounds_len as f32;
+                    if sounds_ratio > MAX_PHONETIC_RATIO {
+                        continue;
+                    }
+                    sounds_ratio.max(MAX_SPELLING_RATIO)
+                } else {
+                    continue;
+                }
+            };
+
+            if best.is_none_or(|(_, r)| ratio < r) {
+                best = Some((entry, ratio));
+            }
+        }
+
+        best.map(|(entry, _)| entry)
+    }
+}
+
+/// Mot (séparé par des espaces) et ses positions en octets dans le texte source
+#[derive(Debug)]
+struct Token {
+    start: usize,
+    end: usize,
+    /// Plage d'octets du mot sans la ponctuation qui l'entoure
+    core_start: usize,
+    core_end: usize,
+    normalized: String,
+}
+
+fn tokenize(text: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut start: Option<usize> = None;
+
+    for (idx, c) in text.char_indices().chain(std::iter::once((text.len(), ' '))) {
+        match (c.is_whitespace(), start) {
+            (true, Some(s)) => {
+                tokens.push(make_token(text, s, idx));
+                start = None;
+            }
+            (false, None) => start = Some(idx),
+            _ => {}
+        }
+    }
+
+    tokens
+}
+
+fn make_token(text: &str, start: usize, end: usize) -> Token {
+    let raw = &text[start..end];
+    let trimmed_start = raw.trim_start_matches(|c: char| !c.is_alphanumeric());
+    let core_start = start + (raw.len() - trimmed_start.len());
+    let core = trimmed_start.trim_end_matches(|c: char| !c.is_alphanumeric());
+    let core_end = core_start + core.len();
+
+    Token {
+        start,
+        end,
+        core_start,
+        core_end,
+        normalized: normalize(core),
+    }
+}
+
+/// Met en minuscules, retire les accents et ne garde que lettres et chiffres ASCII
+fn normalize(word: &str) -> String {
+    let mut out = String::with_capacity(word.len());
+    for c in word.chars().flat_map(|c| c.to_lowercase()) {
+        match c {
+            'à' | 'â' | 'ä' | 'á' | 'ã' | 'å' => out.push('a'),
+            'é' | 'è' | 'ê' | 'ë' => out.push('e'),
+            'î' | 'ï' | 'í' | 'ì' => out.push('i'),
+            'ô' | 'ö' | 'ó' | 'ò' | 'õ' => out.push('o'),
+            'ù' | 'û' | 'ü' | 'ú' => out.push('u'),
+            'ç' => out.push('c'),
+            'ñ' => out.push('n'),
+            'ÿ' | 'ý' => out.push('y'),
+            'œ' => out.push_str("oe"),
+            'æ' => out.push_str("ae"),
+            c if c.is_ascii_alphanumeric() => out.push(c),
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Ramène à une seule graphie les lettres qui sonnent pareil en français et en anglais
+fn sound_spelling(normalized: &str) -> String {
+    let mut s = normalized.to_string();
+    for (from, to) in [
+        ("ph", "f"),
+        ("qu", "k"),
+        ("ck", "k"),
+        ("ch", "x"),
+        ("sh", "x"),
+        ("th", "t"),
+        ("gh", "g"),
+        ("ou", "u"),
+        ("oo", "u"),
+        ("ee", "i"),
+        ("ce", "se"),
+        ("ci", "si"),
+    ] {
+        s = s.replace(from, to);
+    }
+
+    s.chars()
+        .filter_map(|c| match c {
+            'c' | 'q' => Some('k'),
+            'z' => Some('s'),
+            'w' => Some('v'),
+            'y' => Some('i'),
+            'h' => None,
+            c => Some(c),
+        })
+        .collect()
+}
+
+/// Clé phonétique grossière : sons répétés fusionnés, voyelles internes retirées
+fn phonetic_key(sounds: &str) -> String {
+    let mut key = String::with_capacity(sounds.len());
+    let mut last: Option<char> = None;
+
+    for (idx, c) in sounds.chars().enumerate() {
+        if Some(c) == last {
+            continue;
+        }
+        last = Some(c);
+        if idx > 0 && matches!(c, 'a' | 'e' | 'i' | 'o' | 'u') {
+            continue;
+        }
+        key.push(c);
+    }
+
+    key
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        curr[0] = i;
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            curr[j] = (prev[j] + 1).min(curr[j - 1] + 1).min(prev[j - 1] + cost);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vocab(words: &[&str]) -> Vocabulary {
+        Vocabulary::new(&words.iter().map(|w| w.to_string()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_exact_match_fixes_case() {
+        let v = vocab(&["iPhone"]);
+        assert_eq!(v.correct("mon iphone est cassé"), "mon iPhone est cassé");
+    }
+
+    #[test]
+    fn test_spelling_near_miss() {
+        let v = vocab(&["Kubernetes"]);
+        assert_eq!(v.correct("déployer sur cubernetes."), "déployer sur Kubernetes.");
+    }
+
+    #[test]
+    fn test_phonetic_near_miss() {
+        let v = vocab(&["Phonon"]);
+        assert_eq!(v.correct("ouvre fonone"), "ouvre Phonon");
+    }
+
+    #[test]
+    fn test_split_word_is_merged() {
+        let v = vocab(&["TensorFlow"]);
+        assert_eq!(v.correct("un modèle tensor flow, entraîné"), "un modèle TensorFlow, entraîné");
+    }
+
+    #[test]
+    fn test_multi_word_entry() {
+        let v = vocab(&["Jean-Baptiste Durand"]);
+        assert_eq!(
+            v.correct("merci jean baptiste durant pour le retour"),
+            "merci Jean-Baptiste Durand pour le retour"
+        );
+    }
+
+    #[test]
+    fn test_accents_are_ignored_for_matching() {
+        let v = vocab(&["Hélène"]);
+        assert_eq!(v.correct("bonjour helene"), "bonjour Hélène");
+    }
+
+    #[test]
+    fn test_unrelated_words_untouched() {
+        let v = vocab(&["Bonjovi", "Kubernetes", "Scribe"]);
+        let text = "bonjour tout le monde, je décris le script";
+        assert_eq!(v.correct(text), text);
+    }
+
+    #[test]
+    fn test_short_words_require_exact_match() {
+        let v = vocab(&["Tom"]);
+        assert_eq!(v.correct("un ton grave"), "un ton grave");
+        assert_eq!(v.correct("merci tom"), "merci Tom");
+    }
+
+    #[test]
+    fn test_punctuation_and_spacing_preserved() {
+        let v = vocab(&["Anthropic"]);
+        assert_eq!(v.correct("(anthropik)  et\nla suite !"), "(Anthropic)  et\nla suite !");
+    }
+
+    #[test]
+    fn test_empty_vocabulary_is_noop() {
+        let v = Vocabulary::default();
+        assert_eq!(v.correct("texte inchangé"), "texte inchangé");
+        assert!(v.prompt().is_none());
+    }
+
+    #[test]
+    fn test_prompt_and_count_matches() {
+        let v = vocab(&["Scribe", "Jean Dupont"]);
+        assert_eq!(v.prompt().as_deref(), Some("Scribe, Jean Dupont."));
+        assert_eq!(v.count_matches("scribe et jean dupont"), 2);
+        assert_eq!(v.count_matches("script"), 0);
+    }
+}
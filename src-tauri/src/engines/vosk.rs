@@ -1,17 +1,34 @@
-use crate::engines::traits::SpeechEngine;
-use crate::types::{TranscriptionResult, VoskLanguage};
+use crate::engines::segments;
+use crate::engines::traits::{SpeechEngine, SpeechStream, StreamingSpeechEngine, StreamingUpdate};
+use crate::engines::vocabulary::Vocabulary;
+use crate::engines::catalog::CatalogEntry;
+use crate::types::{Segment, TranscriptionResult, WordTiming};
 use chrono::Utc;
 use std::path::Path;
 use std::sync::Mutex;
-use vosk::{Model, Recognizer};
+use vosk::{Alternative, CompleteResult, DecodingState, Model, Recognizer};
+
+/// Confiance par défaut (Vosk ne fournit pas de score par mot en mode N-best)
+const DEFAULT_CONFIDENCE: f32 = 0.9;
+
+/// Nombre d'hypothèses N-best demandées quand un dictionnaire est défini.
+/// La grammaire Vosk (`new_with_grammar`) limiterait la reconnaissance à une
+/// liste fermée de phrases : elle ne convient pas à la dictée libre, le
+/// dictionnaire sert donc à reclasser les hypothèses après décodage.
+const MAX_ALTERNATIVES: u16 = 5;
+
+/// Écart de confiance relatif toléré pour préférer une hypothèse contenant
+/// des mots du dictionnaire
+const RESCORE_MARGIN: f32 = 0.1;
 
 pub struct VoskEngine {
     model: Mutex<Model>,
-    language: VoskLanguage,
+    entry: CatalogEntry,
+    vocabulary: Vocabulary,
 }
 
 impl VoskEngine {
-    pub fn new(model_path: &Path, language: VoskLanguage) -> Result<Self, String> {
+    pub fn new(model_path: &Path, entry: CatalogEntry) -> Result<Self, String> {
         log::info!("Loading Vosk model from {:?}", model_path);
 
         if !model_path.exists() {
@@ -25,12 +42,78 @@ impl VoskEngine {
 
         Ok(Self {
             model: Mutex::new(model),
-            language,
+            entry,
+            vocabulary: Vocabulary::default(),
         })
     }
 
-    pub fn language(&self) -> VoskLanguage {
-        self.language
+    /// Langue du modèle (code ISO déclaré au catalogue, l'identifiant à défaut)
+    pub fn language(&self) -> &str {
+        self.entry.languages.first().unwrap_or(&self.entry.id)
+    }
+
+    /// Reclassement N-best : garde l'hypothèse qui contient le plus de mots du
+    /// dictionnaire, tant que sa confiance reste proche de la meilleure
+    fn rescore_alternatives<'a, 'b>(&self, alternatives: &'b [Alternative<'a>]) -> Option<&'b Alternative<'a>> {
+        let top = alternatives.first()?;
+        let floor = top.confidence - top.confidence.abs() * RESCORE_MARGIN;
+
+        alternatives
+            .iter()
+            .filter(|alt| alt.confidence >= floor)
+            .enumerate()
+            .max_by_key(|(rank, alt)| (self.vocabulary.count_matches(alt.text), std::cmp::Reverse(*rank)))
+            .map(|(_, alt)| alt)
+    }
+
+    /// Convertit le résultat d'un énoncé Vosk en segment horodaté
+    fn utterance_segment(&self, result: CompleteResult) -> Option<Segment> {
+        let (text, words): (String, Vec<WordTiming>) = match result {
+            CompleteResult::Single(single) => (
+                single.text.to_string(),
+                single
+                    .result
+                    .iter()
+                    .map(|w| WordTiming {
+                        start: w.start,
+                        end: w.end,
+                        text: w.word.to_string(),
+                        confidence: Some(w.conf),
+                    })
+                    .collect(),
+            ),
+            CompleteResult::Multiple(multiple) => {
+                let alt = self.rescore_alternatives(&multiple.alternatives)?;
+                (
+                    alt.text.to_string(),
+                    alt.result
+                        .iter()
+                        .map(|w| WordTiming {
+                            start: w.start,
+                            end: w.end,
+                            text: w.word.to_string(),
+                            confidence: None,
+                        })
+                        .collect(),
+                )
+            }
+        };
+
+        let text = self.vocabulary.correct(text.trim());
+        if text.is_empty() {
+            return None;
+        }
+
+        let mut segment = segments::segment_from_words(words, DEFAULT_CONFIDENCE).unwrap_or(Segment {
+            start: 0.0,
+            end: 0.0,
+            text: String::new(),
+            words: Vec::new(),
+            confidence: DEFAULT_CONFIDENCE,
+            speaker: None,
+        });
+        segment.text = text;
+        Some(segment)
     }
 }
 
@@ -53,19 +136,27 @@ impl SpeechEngine for VoskEngine {
             .ok_or("Failed to create recognizer")?;
 
         recognizer.set_words(true);
+        if !self.vocabulary.is_empty() {
+            recognizer.set_max_alternatives(MAX_ALTERNATIVES);
+        }
 
-        // Process audio in chunks to avoid full i16 buffer allocation
+        // Process audio in chunks to avoid full i16 buffer allocation.
+        // Each finalized utterance must be collected before feeding more audio,
+        // otherwise Vosk discards it when the next utterance starts.
         const CHUNK_SIZE: usize = 4096;
+        let mut segments = Vec::new();
         for chunk in audio.chunks(CHUNK_SIZE) {
-            let chunk_i16: Vec<i16> = chunk
-                .iter()
-                .map(|&s| (s * 32767.0).clamp(-32768.0, 32767.0) as i16)
-                .collect();
-            recognizer.accept_waveform(&chunk_i16);
+            if let DecodingState::Finalized = recognizer.accept_waveform(&to_i16(chunk)) {
+                segments.extend(self.utterance_segment(recognizer.result()));
+            }
         }
+        segments.extend(self.utterance_segment(recognizer.final_result()));
 
-        let result = recognizer.final_result();
-        let text = result.single().map(|r| r.text.to_string()).unwrap_or_default();
+        let text = segments
+            .iter()
+            .map(|s| s.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
 
         let processing_time_ms = start_time.elapsed().as_millis() as u64;
 
@@ -76,13 +167,15 @@ impl SpeechEngine for VoskEngine {
         );
 
         Ok(TranscriptionResult {
-            text: text.trim().to_string(),
-            confidence: 0.9,
+            text,
+            confidence: DEFAULT_CONFIDENCE,
             duration_seconds,
             processing_time_ms,
-            detected_language: Some(format!("{:?}", self.language).to_lowercase()),
+            detected_language: Some(self.language().to_string()),
             timestamp: Utc::now().timestamp(),
             model_used: Some(self.model_display_name()),
+            segments,
+            translation: None,
         })
     }
 
@@ -91,9 +184,79 @@ impl SpeechEngine for VoskEngine {
     }
 
     fn model_display_name(&self) -> String {
-        format!("Vosk {}", self.language.display_name())
+        format!("Vosk {}", self.entry.display_name)
+    }
+
+    fn set_vocabulary(&mut self, vocabulary: Vocabulary) {
+        self.vocabulary = vocabulary;
+    }
+
+    fn as_streaming(&self) -> Option<&dyn StreamingSpeechEngine> {
+        Some(self)
     }
 }
 
+impl StreamingSpeechEngine for VoskEngine {
+    fn start_stream(&self, sample_rate: u32) -> Result<Box<dyn SpeechStream>, String> {
+        let model = self.model.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let recognizer = Recognizer::new(&model, sample_rate as f32)
+            .ok_or("Failed to create recognizer")?;
+
+        Ok(Box::new(VoskStream {
+            recognizer,
+            vocabulary: self.vocabulary.clone(),
+        }))
+    }
+}
+
+/// Flux Vosk : la reconnaissance avance à chaque `accept_waveform`,
+/// sans retranscrire l'audio déjà traité
+struct VoskStream {
+    recognizer: Recognizer,
+    vocabulary: Vocabulary,
+}
+
+impl SpeechStream for VoskStream {
+    fn accept(&mut self, samples: &[f32]) -> Result<StreamingUpdate, String> {
+        match self.recognizer.accept_waveform(&to_i16(samples)) {
+            DecodingState::Finalized => {
+                let text = self
+                    .recognizer
+                    .result()
+                    .single()
+                    .map(|r| r.text.trim().to_string())
+                    .unwrap_or_default();
+                Ok(StreamingUpdate {
+                    final_text: Some(self.vocabulary.correct(&text)).filter(|t| !t.is_empty()),
+                    partial: String::new(),
+                })
+            }
+            DecodingState::Running => Ok(StreamingUpdate {
+                final_text: None,
+                partial: self.recognizer.partial_result().partial.trim().to_string(),
+            }),
+            DecodingState::Failed => Err("Vosk decoding failed".to_string()),
+        }
+    }
+
+    fn finish(&mut self) -> Result<String, String> {
+        let text = self
+            .recognizer
+            .final_result()
+            .single()
+            .map(|r| r.text.trim().to_string())
+            .unwrap_or_default();
+        Ok(self.vocabulary.correct(&text))
+    }
+}
+
+fn to_i16(samples: &[f32]) -> Vec<i16> {
+    samples
+        .iter()
+        .map(|&s| (s * 32767.0).clamp(-32768.0, 32767.0) as i16)
+        .collect()
+}
+
 unsafe impl Send for VoskEngine {}
 unsafe impl Sync for VoskEngine {}
+unsafe impl Send for VoskStream {}
@@ -1,18 +1,31 @@
 use crate::engines::traits::SpeechEngine;
-use crate::types::{ModelSize, TranscriptionResult};
+use crate::engines::vocabulary::Vocabulary;
+use crate::types::{Segment, TranscriptionResult, TranslationInfo, WordTiming};
+
+use super::catalog::CatalogEntry;
 use chrono::Utc;
 use std::path::Path;
 use std::sync::Mutex;
-use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+use whisper_rs::{
+    FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters, WhisperState,
+    WhisperToken,
+};
+
+/// Au-delà de cette durée, Whisper découpe librement l'audio en segments
+const SINGLE_SEGMENT_MAX_SECS: f32 = 30.0;
+
+/// Confiance par défaut quand Whisper ne fournit pas de probabilités
+const DEFAULT_CONFIDENCE: f32 = 0.95;
 
 pub struct WhisperEngine {
     ctx: Mutex<WhisperContext>,
     language: Option<String>,
-    model_size: ModelSize,
+    model: CatalogEntry,
+    vocabulary: Vocabulary,
 }
 
 impl WhisperEngine {
-    pub fn new(model_path: &Path, language: Option<String>, model_size: ModelSize) -> Result<Self, String> {
+    pub fn new(model_path: &Path, language: Option<String>, model: CatalogEntry) -> Result<Self, String> {
         log::info!("Loading Whisper model from {:?}", model_path);
 
         if !model_path.exists() {
@@ -21,7 +34,7 @@ impl WhisperEngine {
 
         let mut ctx_params = WhisperContextParameters::default();
         // GPU (Metal/CUDA) only benefits medium+ models; tiny/small are faster on CPU
-        ctx_params.use_gpu = matches!(model_size, ModelSize::Medium);
+        ctx_params.use_gpu = model.prefer_gpu;
 
         let ctx = WhisperContext::new_with_params(
             model_path.to_str().ok_or("Invalid model path")?,
@@ -34,21 +47,74 @@ impl WhisperEngine {
         Ok(Self {
             ctx: Mutex::new(ctx),
             language,
-            model_size,
+            model,
+            vocabulary: Vocabulary::default(),
         })
     }
 
-    pub fn model_size(&self) -> ModelSize {
-        self.model_size
+    pub fn model_id(&self) -> &str {
+        &self.model.id
     }
 
-    pub fn set_language(&mut self, language: Option<String>) {
-        self.language = language;
+    /// Regroupe les tokens d'un segment en mots horodatés
+    fn segment_words(state: &WhisperState, segment: i32, token_eot: WhisperToken) -> Vec<WordTiming> {
+        let n_tokens = state.full_n_tokens(segment).unwrap_or(0);
+        let mut words = Vec::new();
+
+        let mut bytes: Vec<u8> = Vec::new();
+        let mut probs: Vec<f32> = Vec::new();
+        let mut start = 0.0f32;
+        let mut end = 0.0f32;
+
+        let mut flush = |bytes: &mut Vec<u8>, probs: &mut Vec<f32>, start: f32, end: f32| {
+            let text = String::from_utf8_lossy(bytes).trim().to_string();
+            if !text.is_empty() {
+                let confidence = probs.iter().sum::<f32>() / probs.len().max(1) as f32;
+                words.push(WordTiming {
+                    start,
+                    end,
+                    text,
+                    confidence: Some(confidence),
+                });
+            }
+            bytes.clear();
+            probs.clear();
+        };
+
+        for t in 0..n_tokens {
+            let data = match state.full_get_token_data(segment, t) {
+                Ok(data) => data,
+                Err(_) => continue,
+            };
+            // Tokens spéciaux (timestamps, fin de texte, etc.)
+            if data.id >= token_eot {
+                continue;
+            }
+            let piece = match state.full_get_token_bytes(segment, t) {
+                Ok(piece) => piece,
+                Err(_) => continue,
+            };
+
+            // Un espace en tête de token marque le début d'un nouveau mot
+            if piece.first() == Some(&b' ') && !bytes.is_empty() {
+                flush(&mut bytes, &mut probs, start, end);
+            }
+            if bytes.is_empty() {
+                start = data.t0 as f32 / 100.0;
+            }
+            bytes.extend_from_slice(&piece);
+            probs.push(data.p);
+            end = data.t1 as f32 / 100.0;
+        }
+        flush(&mut bytes, &mut probs, start, end);
+
+        words
     }
-}
 
-impl SpeechEngine for WhisperEngine {
-    fn transcribe(&self, audio: &[f32], sample_rate: u32) -> Result<TranscriptionResult, String> {
+    /// Transcrit l'audio ; avec `translate`, Whisper produit directement le
+    /// texte en anglais (tâche `translate`). `detected_language` reste la
+    /// langue parlée.
+    fn run(&self, audio: &[f32], sample_rate: u32, translate: bool) -> Result<TranscriptionResult, String> {
         let start_time = std::time::Instant::now();
 
         if sample_rate != 16000 {
@@ -65,6 +131,7 @@ impl SpeechEngine for WhisperEngine {
 
         let ctx = self.ctx.lock().map_err(|e| format!("Lock error: {}", e))?;
 
+        let initial_prompt = self.vocabulary.prompt();
         let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
 
         // Utiliser les coeurs physiques pour l'inférence (min 1, max 8)
@@ -80,14 +147,24 @@ impl SpeechEngine for WhisperEngine {
             }
         }
 
+        params.set_translate(translate);
+
+        // Biaiser la reconnaissance vers les mots du dictionnaire
+        if let Some(ref prompt) = initial_prompt {
+            params.set_initial_prompt(prompt);
+        }
+
         // Optimisations
         params.set_print_special(false);
         params.set_print_progress(false);
         params.set_print_realtime(false);
         params.set_print_timestamps(false);
-        params.set_single_segment(true);
+        params.set_single_segment(duration_seconds <= SINGLE_SEGMENT_MAX_SECS);
         params.set_no_context(true);
 
+        // Horodatage au niveau des tokens pour construire les mots
+        params.set_token_timestamps(true);
+
         // Éviter les hallucinations (musique, sous-titres, etc.)
         params.set_suppress_nst(true);
 
@@ -103,14 +180,40 @@ impl SpeechEngine for WhisperEngine {
 
         // Récupérer le résultat
         let num_segments = state.full_n_segments().map_err(|e| format!("Error: {}", e))?;
+        let token_eot = ctx.token_eot();
         let mut text = String::new();
+        let mut segments = Vec::with_capacity(num_segments.max(0) as usize);
 
         for i in 0..num_segments {
-            if let Ok(segment) = state.full_get_segment_text(i) {
-                text.push_str(&segment);
-            }
+            let segment_text = match state.full_get_segment_text(i) {
+                Ok(segment) => segment,
+                Err(_) => continue,
+            };
+            text.push_str(&segment_text);
+
+            let words = Self::segment_words(&state, i, token_eot);
+            let confidence = if words.is_empty() {
+                DEFAULT_CONFIDENCE
+            } else {
+                words.iter().filter_map(|w| w.confidence).sum::<f32>() / words.len() as f32
+            };
+
+            segments.push(Segment {
+                start: state.full_get_segment_t0(i).unwrap_or(0) as f32 / 100.0,
+                end: state.full_get_segment_t1(i).unwrap_or(0) as f32 / 100.0,
+                text: self.vocabulary.correct(segment_text.trim()),
+                words,
+                confidence,
+                speaker: None,
+            });
         }
 
+        let confidence = if segments.is_empty() {
+            DEFAULT_CONFIDENCE
+        } else {
+            segments.iter().map(|s| s.confidence).sum::<f32>() / segments.len() as f32
+        };
+
         let detected_language = state
             .full_lang_id_from_state()
             .ok()
@@ -119,28 +222,59 @@ impl SpeechEngine for WhisperEngine {
         let processing_time_ms = start_time.elapsed().as_millis() as u64;
 
         log::info!(
-            "Transcription completed in {}ms: {} chars",
+            "{} completed in {}ms: {} chars",
+            if translate { "Translation" } else { "Transcription" },
             processing_time_ms,
             text.len()
         );
 
+        let translation = translate.then(|| TranslationInfo {
+            source_language: detected_language.clone(),
+            target_language: "en".to_string(),
+        });
+
         Ok(TranscriptionResult {
-            text: text.trim().to_string(),
-            confidence: 0.95,
+            text: self.vocabulary.correct(text.trim()),
+            confidence,
             duration_seconds,
             processing_time_ms,
             detected_language,
             timestamp: Utc::now().timestamp(),
             model_used: Some(self.model_display_name()),
+            segments,
+            translation,
         })
     }
+}
+
+impl SpeechEngine for WhisperEngine {
+    fn transcribe(&self, audio: &[f32], sample_rate: u32) -> Result<TranscriptionResult, String> {
+        self.run(audio, sample_rate, false)
+    }
+
+    /// Les modèles `.en` ne connaissent que l'anglais
+    fn supports_translation(&self) -> bool {
+        !self.model.id.ends_with(".en")
+    }
+
+    fn translate_to_english(&self, audio: &[f32], sample_rate: u32) -> Result<TranscriptionResult, String> {
+        self.run(audio, sample_rate, true)
+    }
 
     fn name(&self) -> &str {
         "Whisper"
     }
 
     fn model_display_name(&self) -> String {
-        format!("Whisper {}", self.model_size.display_name())
+        format!("Whisper {}", self.model.display_name)
+    }
+
+    fn set_vocabulary(&mut self, vocabulary: Vocabulary) {
+        self.vocabulary = vocabulary;
+    }
+
+    fn set_language(&mut self, language: Option<String>) {
+        self.language = language;
     }
 }
 
@@ -1,10 +1,14 @@
+mod app_context;
 mod audio;
+pub mod cli;
 mod commands;
+mod dictation;
 mod engines;
 mod hotkeys;
 mod llm;
 mod platform;
 mod ptt;
+mod server;
 mod state;
 mod storage;
 mod tray;
@@ -14,11 +18,8 @@ mod voice_commands;
 pub use audio::AudioCapture;
 pub use types::*;
 
-use llm::LocalLlmEngine;
 use state::AppState;
-use std::sync::Arc;
-use tauri::Manager;
-use tokio::sync::RwLock;
+use tauri::{Emitter, Manager};
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -45,18 +46,31 @@ pub fn run() {
             commands::stop_mic_preview,
             commands::get_settings,
             commands::update_settings,
+            commands::get_settings_load_report,
+            commands::export_settings,
+            commands::import_settings,
             commands::get_dictionary,
             commands::add_dictionary_word,
             commands::remove_dictionary_word,
             commands::start_recording,
             commands::stop_recording,
-            commands::get_history,
-            commands::clear_history,
+            commands::search_history,
+            commands::get_history_page,
+            commands::delete_history_entry,
+            commands::set_history_favorite,
+            commands::set_history_tags,
+            commands::get_history_facets,
+            commands::get_history_audio,
+            commands::retranscribe_history_entry,
             commands::get_recording_status,
             commands::reset_recording_state,
             commands::get_available_models,
             commands::get_current_model,
+            commands::get_model_catalog,
+            commands::refresh_model_catalog,
+            commands::import_model,
             commands::download_model,
+            commands::cancel_model_download,
             commands::delete_model,
             commands::switch_model,
             commands::is_engine_ready,
@@ -69,15 +83,21 @@ pub fn run() {
             commands::download_parakeet_model,
             commands::delete_parakeet_model,
             commands::select_parakeet_model,
+            commands::get_secrets_protection,
             commands::set_groq_api_key,
             commands::get_groq_api_key,
             commands::has_groq_api_key,
             commands::validate_groq_api_key,
             commands::delete_groq_api_key,
             commands::get_groq_quota,
+            commands::set_openai_compatible_api_key,
+            commands::has_openai_compatible_api_key,
+            commands::delete_openai_compatible_api_key,
+            commands::test_openai_compatible_provider,
             commands::translate_text,
             commands::summarize_text,
             commands::is_llm_model_available,
+            commands::get_llm_models,
             commands::get_available_llm_models,
             commands::download_llm_model,
             commands::delete_llm_model,
@@ -95,14 +115,23 @@ pub fn run() {
             commands::toggle_subtitles,
             commands::file_transcription::transcribe_files,
             commands::file_transcription::get_supported_audio_formats,
+            commands::file_transcription::export_transcription,
+            commands::file_transcription::rename_speaker,
             commands::get_snippets,
             commands::add_snippet,
             commands::update_snippet,
             commands::remove_snippet,
+            commands::get_prompt_profiles,
+            commands::add_prompt_profile,
+            commands::update_prompt_profile,
+            commands::remove_prompt_profile,
             commands::get_usage_stats,
             commands::reset_stats,
             commands::send_to_apple_notes,
             commands::send_to_obsidian,
+            commands::get_local_server_status,
+            commands::get_local_server_token,
+            commands::regenerate_local_server_token,
         ])
         .setup(|app| {
             // Initialiser l'état
@@ -115,11 +144,21 @@ pub fn run() {
             };
 
             let model_manager = app_state.model_manager.clone();
+            // Serveur local de transcription (opt-in)
+            let server_config = app_state.settings.read().map(|s| s.local_server.clone()).unwrap_or_default();
+            if server_config.enabled {
+                server::apply_config(&app_state, &server_config);
+            }
             app.manage(app_state);
             app.manage(model_manager);
 
-            let llm_engine: Arc<RwLock<Option<LocalLlmEngine>>> = Arc::new(RwLock::new(None));
-            app.manage(llm_engine);
+            app.manage(llm::local_engine::shared_engine());
+
+            // Statistiques à jour dans la fenêtre ouverte
+            let handle = app.handle().clone();
+            storage::stats::subscribe(move |stats| {
+                let _ = handle.emit("stats-updated", stats);
+            });
 
             // Initialiser les threads audio
             ptt::init_ptt_audio_thread();
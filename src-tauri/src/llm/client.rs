@@ -0,0 +1,79 @@
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::types::{AppSettings, LlmProvider};
+
+use super::groq_client;
+use super::local_engine::{self, LocalLlmClient};
+use super::openai_compatible::OpenAiCompatibleClient;
+
+pub type CompletionFuture<'a> = Pin<Box<dyn Future<Output = Result<String, String>> + Send + 'a>>;
+
+/// Paramètres de génération
+#[derive(Debug, Clone, Copy)]
+pub struct CompletionParams {
+    pub temperature: f32,
+    pub max_tokens: u32,
+}
+
+impl Default for CompletionParams {
+    fn default() -> Self {
+        Self {
+            temperature: 0.3,
+            max_tokens: 2048,
+        }
+    }
+}
+
+/// Fournisseur de complétions utilisé par le post-traitement, la traduction,
+/// le résumé et les actions vocales
+pub trait LlmClient: Send + Sync {
+    fn complete<'a>(
+        &'a self,
+        system: &'a str,
+        user: &'a str,
+        params: &'a CompletionParams,
+    ) -> CompletionFuture<'a>;
+
+    /// Nom affiché dans les logs
+    fn name(&self) -> String;
+}
+
+/// Client correspondant au provider choisi (Groq, serveur compatible OpenAI
+/// ou modèle GGUF local, entièrement hors ligne)
+pub fn client_for_settings(
+    settings: &AppSettings,
+    provider: LlmProvider,
+) -> Result<Box<dyn LlmClient>, String> {
+    match provider {
+        LlmProvider::OpenAiCompatible => {
+            let config = &settings.openai_compatible;
+            if config.base_url.trim().is_empty() || config.model.trim().is_empty() {
+                return Err("Serveur compatible OpenAI non configuré (URL et modèle requis)".to_string());
+            }
+            let headers = config
+                .headers
+                .iter()
+                .filter(|h| !h.name.trim().is_empty())
+                .map(|h| (h.name.clone(), h.value.clone()))
+                .collect();
+            Ok(Box::new(
+                OpenAiCompatibleClient::new(&config.base_url, &config.model)
+                    .with_api_key(crate::commands::llm::get_openai_compatible_api_key_internal())
+                    .with_headers(headers),
+            ))
+        }
+        LlmProvider::Local => {
+            let entry = local_engine::model_entry(&settings.local_llm_model)?;
+            if !local_engine::model_path(&entry).is_some_and(|path| path.exists()) {
+                return Err(format!("Modèle LLM {} non installé", entry.display_name));
+            }
+            Ok(Box::new(LocalLlmClient::new(local_engine::shared_engine(), entry.id)))
+        }
+        LlmProvider::Groq => {
+            let api_key = crate::commands::llm::get_groq_api_key_internal()
+                .ok_or_else(|| "Clé API Groq non configurée".to_string())?;
+            Ok(Box::new(groq_client::client(&api_key)))
+        }
+    }
+}
@@ -1,11 +1,11 @@
-use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::sync::Mutex;
-use std::time::Duration;
 
-const GROQ_API_ENDPOINT: &str = "https://api.groq.com/openai/v1/chat/completions";
+use super::client::CompletionParams;
+use super::openai_compatible::{LlmError, OpenAiCompatibleClient};
+
+const GROQ_API_BASE_URL: &str = "https://api.groq.com/openai/v1";
 const GROQ_MODEL: &str = "llama-3.3-70b-versatile";
-const TIMEOUT_SECONDS: u64 = 30;
 
 /// Informations de quota Groq (mises à jour après chaque requête)
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -60,56 +60,14 @@ fn update_quota_from_headers(headers: &reqwest::header::HeaderMap) {
     }
 }
 
-#[derive(Debug)]
-pub enum GroqError {
-    InvalidApiKey,
-    RateLimit,
-    Timeout,
-    NetworkError(String),
-    ParseError(String),
-}
-
-impl std::fmt::Display for GroqError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            GroqError::InvalidApiKey => write!(f, "Invalid API key"),
-            GroqError::RateLimit => write!(f, "Rate limit exceeded"),
-            GroqError::Timeout => write!(f, "Request timeout"),
-            GroqError::NetworkError(msg) => write!(f, "Network error: {}", msg),
-            GroqError::ParseError(msg) => write!(f, "Parse error: {}", msg),
-        }
-    }
-}
-
-impl std::error::Error for GroqError {}
-
-#[derive(Serialize)]
-struct ChatMessage {
-    role: String,
-    content: String,
-}
-
-#[derive(Serialize)]
-struct ChatCompletionRequest {
-    model: String,
-    messages: Vec<ChatMessage>,
-    temperature: f32,
-    max_tokens: u32,
-}
-
-#[derive(Deserialize)]
-struct ChatCompletionResponse {
-    choices: Vec<Choice>,
-}
-
-#[derive(Deserialize)]
-struct Choice {
-    message: ResponseMessage,
-}
+/// Erreurs Groq : mêmes cas que tout fournisseur compatible OpenAI
+pub type GroqError = LlmError;
 
-#[derive(Deserialize)]
-struct ResponseMessage {
-    content: String,
+/// Client Groq : API compatible OpenAI avec suivi des quotas
+pub fn client(api_key: &str) -> OpenAiCompatibleClient {
+    OpenAiCompatibleClient::new(GROQ_API_BASE_URL, GROQ_MODEL)
+        .with_api_key(Some(api_key.to_string()))
+        .with_response_hook(update_quota_from_headers)
 }
 
 pub async fn send_completion(
@@ -117,75 +75,7 @@ pub async fn send_completion(
     system_prompt: &str,
     text: &str,
 ) -> Result<String, GroqError> {
-    let client = Client::builder()
-        .timeout(Duration::from_secs(TIMEOUT_SECONDS))
-        .build()
-        .map_err(|e| GroqError::NetworkError(e.to_string()))?;
-
-    let request_body = ChatCompletionRequest {
-        model: GROQ_MODEL.to_string(),
-        messages: vec![
-            ChatMessage {
-                role: "system".to_string(),
-                content: system_prompt.to_string(),
-            },
-            ChatMessage {
-                role: "user".to_string(),
-                content: text.to_string(),
-            },
-        ],
-        temperature: 0.3,
-        max_tokens: 2048,
-    };
-
-    let response = client
-        .post(GROQ_API_ENDPOINT)
-        .header("Content-Type", "application/json")
-        .header("Authorization", format!("Bearer {}", api_key))
-        .json(&request_body)
-        .send()
-        .await
-        .map_err(|e| {
-            if e.is_timeout() {
-                GroqError::Timeout
-            } else {
-                GroqError::NetworkError(e.to_string())
-            }
-        })?;
-
-    let status = response.status();
-
-    // Capturer les informations de quota depuis les headers
-    update_quota_from_headers(response.headers());
-
-    if status == reqwest::StatusCode::UNAUTHORIZED {
-        return Err(GroqError::InvalidApiKey);
-    }
-
-    if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-        return Err(GroqError::RateLimit);
-    }
-
-    if !status.is_success() {
-        let error_text = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(GroqError::NetworkError(format!(
-            "HTTP {}: {}",
-            status, error_text
-        )));
-    }
-
-    let response_body: ChatCompletionResponse = response
-        .json()
+    client(api_key)
+        .send_completion(system_prompt, text, &CompletionParams::default())
         .await
-        .map_err(|e| GroqError::ParseError(e.to_string()))?;
-
-    response_body
-        .choices
-        .into_iter()
-        .next()
-        .map(|choice| choice.message.content)
-        .ok_or_else(|| GroqError::ParseError("No choices in response".to_string()))
 }
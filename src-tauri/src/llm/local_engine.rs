@@ -1,5 +1,6 @@
 use std::num::NonZeroU32;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
 
 use llama_cpp_2::context::params::LlamaContextParams;
 use llama_cpp_2::llama_backend::LlamaBackend;
@@ -7,21 +8,57 @@ use llama_cpp_2::llama_batch::LlamaBatch;
 use llama_cpp_2::model::params::LlamaModelParams;
 use llama_cpp_2::model::{AddBos, LlamaModel};
 use llama_cpp_2::sampling::LlamaSampler;
+use tokio::sync::RwLock;
 
-use crate::types::LocalLlmModel;
+use crate::engines::catalog::{self, CatalogEntry, ModelFamily};
+use crate::types::ChatTemplate;
+
+use super::client::{CompletionFuture, CompletionParams, LlmClient};
+
+const CONTEXT_SIZE: u32 = 4096;
+
+const SUMMARY_INSTRUCTION: &str = "Resume ce texte en 2-3 phrases concises, dans la langue du texte.";
+
+/// Moteur partagé entre les commandes et le push-to-talk (chargé à la demande)
+pub type SharedLocalEngine = Arc<RwLock<Option<LocalLlmEngine>>>;
+
+pub fn shared_engine() -> SharedLocalEngine {
+    static ENGINE: OnceLock<SharedLocalEngine> = OnceLock::new();
+    ENGINE.get_or_init(|| Arc::new(RwLock::new(None))).clone()
+}
+
+/// Entrée LLM du catalogue (modèles importés compris)
+pub fn model_entry(id: &str) -> Result<CatalogEntry, String> {
+    catalog::current()
+        .get(ModelFamily::Llm, id)
+        .cloned()
+        .ok_or_else(|| format!("Modèle LLM inconnu : {}", id))
+}
+
+/// Emplacement du fichier GGUF d'un modèle local (d'après le catalogue)
+pub fn model_path(entry: &CatalogEntry) -> Option<PathBuf> {
+    let file = entry.primary_file()?;
+    Some(
+        crate::storage::get_app_data_dir()
+            .join("models")
+            .join("llm")
+            .join(&file.name),
+    )
+}
 
 /// Moteur LLM local via llama.cpp (GGUF)
 pub struct LocalLlmEngine {
     backend: LlamaBackend,
     model: LlamaModel,
-    model_type: LocalLlmModel,
+    entry: CatalogEntry,
+    chat_template: ChatTemplate,
 }
 
 impl LocalLlmEngine {
-    pub fn new(model_path: &Path, model_type: LocalLlmModel) -> Result<Self, String> {
+    pub fn new(model_path: &Path, entry: CatalogEntry) -> Result<Self, String> {
         log::info!(
-            "Initializing Local LLM engine: {:?} from {:?}",
-            model_type,
+            "Initializing Local LLM engine: {} from {:?}",
+            entry.id,
             model_path
         );
 
@@ -29,7 +66,7 @@ impl LocalLlmEngine {
             .map_err(|e| format!("Failed to initialize llama backend: {}", e))?;
 
         let model_params = LlamaModelParams::default()
-            .with_n_gpu_layers(1000); // Offload all layers to GPU (Metal on macOS)
+            .with_n_gpu_layers(1000); // Toutes les couches sur le GPU (Metal sur macOS)
 
         let model = LlamaModel::load_from_file(&backend, model_path, &model_params)
             .map_err(|e| format!("Failed to load model from {:?}: {}", model_path, e))?;
@@ -43,24 +80,40 @@ impl LocalLlmEngine {
         Ok(Self {
             backend,
             model,
-            model_type,
+            // Template déclaré au catalogue, ChatML par défaut (modèles importés)
+            chat_template: entry.chat_template.unwrap_or(ChatTemplate::ChatMl),
+            entry,
         })
     }
 
-    /// Generates a summary of the given text
+    /// Résume le texte donné
     pub fn summarize(&self, text: &str) -> Result<String, String> {
-        let prompt = self.model_type.format_prompt("", text);
+        let params = CompletionParams {
+            temperature: 0.3,
+            max_tokens: 512,
+        };
+        self.complete_blocking(SUMMARY_INSTRUCTION, text, &params)
+    }
 
-        // Create a fresh context for this inference
+    /// Complétion de conversation avec le template du modèle
+    pub fn complete_blocking(
+        &self,
+        system: &str,
+        user: &str,
+        params: &CompletionParams,
+    ) -> Result<String, String> {
+        let prompt = self.chat_template.format(system, user);
+
+        // Nouveau contexte pour cette inférence
         let ctx_params = LlamaContextParams::default()
-            .with_n_ctx(Some(NonZeroU32::new(2048).unwrap()));
+            .with_n_ctx(Some(NonZeroU32::new(CONTEXT_SIZE).unwrap()));
 
         let mut ctx = self
             .model
             .new_context(&self.backend, ctx_params)
             .map_err(|e| format!("Failed to create inference context: {}", e))?;
 
-        // Tokenize the prompt
+        // Tokenisation du prompt
         let tokens = self
             .model
             .str_to_token(&prompt, AddBos::Always)
@@ -72,7 +125,17 @@ impl LocalLlmEngine {
 
         log::info!("Prompt tokenized to {} tokens", tokens.len());
 
-        // Create batch large enough for the prompt
+        let max_output_tokens = (params.max_tokens as usize)
+            .min((CONTEXT_SIZE as usize).saturating_sub(tokens.len()));
+        if max_output_tokens == 0 {
+            return Err(format!(
+                "Texte trop long pour le modèle local ({} tokens, maximum {})",
+                tokens.len(),
+                CONTEXT_SIZE
+            ));
+        }
+
+        // Batch assez grand pour le prompt
         let batch_capacity = (tokens.len() + 1).max(512);
         let mut batch = LlamaBatch::new(batch_capacity, 1);
 
@@ -83,21 +146,23 @@ impl LocalLlmEngine {
                 .map_err(|e| format!("Failed to add token to batch: {}", e))?;
         }
 
-        // Evaluate the prompt
+        // Évaluation du prompt
         ctx.decode(&mut batch)
             .map_err(|e| format!("Failed to decode prompt: {}", e))?;
 
-        // Set up sampling: temperature 0.3 for focused output
-        let mut sampler = LlamaSampler::chain_simple([
-            LlamaSampler::temp(0.3),
-            LlamaSampler::dist(42),
-        ]);
+        // Décodage glouton à température 0, sinon échantillonnage à la température demandée
+        let mut sampler = if params.temperature <= 0.0 {
+            LlamaSampler::greedy()
+        } else {
+            LlamaSampler::chain_simple([
+                LlamaSampler::temp(params.temperature),
+                LlamaSampler::dist(42),
+            ])
+        };
 
-        // UTF-8 decoder for token-to-text conversion
+        // Décodeur UTF-8 pour convertir les tokens en texte
         let mut decoder = encoding_rs::UTF_8.new_decoder();
 
-        // Generate up to 512 output tokens
-        let max_output_tokens = 512;
         let mut output = String::new();
         let mut n_cur = tokens.len() as i32;
 
@@ -105,18 +170,18 @@ impl LocalLlmEngine {
             let new_token = sampler.sample(&ctx, batch.n_tokens() - 1);
             sampler.accept(new_token);
 
-            // Stop at end-of-generation
+            // Arrêt en fin de génération
             if self.model.is_eog_token(new_token) {
                 break;
             }
 
-            // Decode token to text
+            // Token vers texte
             match self.model.token_to_piece(new_token, &mut decoder, true, None) {
                 Ok(piece) => output.push_str(&piece),
-                Err(_) => {} // Skip invalid tokens
+                Err(_) => {} // Tokens invalides ignorés
             }
 
-            // Prepare next iteration
+            // Préparer l'itération suivante
             batch.clear();
             batch
                 .add(new_token, n_cur, &[0], true)
@@ -136,14 +201,81 @@ impl LocalLlmEngine {
         Ok(result)
     }
 
-    pub fn model_type(&self) -> LocalLlmModel {
-        self.model_type
+    pub fn model_id(&self) -> &str {
+        &self.entry.id
     }
 
     pub fn display_name(&self) -> String {
-        format!("Local LLM ({})", self.model_type.display_name())
+        format!("Local LLM ({})", self.entry.display_name)
     }
 }
 
 unsafe impl Send for LocalLlmEngine {}
 unsafe impl Sync for LocalLlmEngine {}
+
+/// Charge (ou recharge si un autre modèle est actif) le moteur partagé
+pub async fn ensure_loaded(engine: &SharedLocalEngine, model: &str) -> Result<(), String> {
+    {
+        let current = engine.read().await;
+        if current.as_ref().map(|e| e.model_id()) == Some(model) {
+            return Ok(());
+        }
+    }
+
+    let entry = model_entry(model)?;
+    let path = model_path(&entry).filter(|path| path.exists()).ok_or_else(|| {
+        format!(
+            "Modèle LLM {} non installé. Téléchargez-le dans les paramètres.",
+            entry.display_name
+        )
+    })?;
+
+    let mut current = engine.write().await;
+    if current.as_ref().map(|e| e.model_id()) != Some(model) {
+        log::info!("Initializing Local LLM engine...");
+        // Libérer l'ancien modèle avant d'en charger un nouveau
+        *current = None;
+        *current = Some(LocalLlmEngine::new(&path, entry)?);
+    }
+    Ok(())
+}
+
+/// Client hors ligne : le modèle GGUF sélectionné derrière l'interface `LlmClient`
+pub struct LocalLlmClient {
+    engine: SharedLocalEngine,
+    model: String,
+}
+
+impl LocalLlmClient {
+    pub fn new(engine: SharedLocalEngine, model: String) -> Self {
+        Self { engine, model }
+    }
+}
+
+impl LlmClient for LocalLlmClient {
+    fn complete<'a>(
+        &'a self,
+        system: &'a str,
+        user: &'a str,
+        params: &'a CompletionParams,
+    ) -> CompletionFuture<'a> {
+        Box::pin(async move {
+            ensure_loaded(&self.engine, &self.model).await?;
+
+            // L'inférence est bloquante : la sortir du runtime async
+            let engine = self.engine.clone();
+            let (system, user, params) = (system.to_string(), user.to_string(), *params);
+            tokio::task::spawn_blocking(move || {
+                let guard = engine.blocking_read();
+                let engine = guard.as_ref().ok_or("LLM engine not initialized")?;
+                engine.complete_blocking(&system, &user, &params)
+            })
+            .await
+            .map_err(|e| format!("Local LLM task failed: {}", e))?
+        })
+    }
+
+    fn name(&self) -> String {
+        format!("Local LLM ({})", self.model.display_name())
+    }
+}
@@ -1,7 +1,12 @@
+pub mod client;
 pub mod groq_client;
 pub mod local_engine;
+pub mod openai_compatible;
 pub mod post_processor;
+pub mod translation;
 
+pub use client::{client_for_settings, CompletionParams, LlmClient};
 pub use groq_client::GroqError;
-pub use local_engine::LocalLlmEngine;
+pub use local_engine::{LocalLlmEngine, SharedLocalEngine};
+pub use openai_compatible::{LlmError, OpenAiCompatibleClient};
 pub use post_processor::process;
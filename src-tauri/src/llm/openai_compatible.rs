@@ -0,0 +1,305 @@
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use super::client::{CompletionFuture, CompletionParams, LlmClient};
+
+const TIMEOUT_SECONDS: u64 = 30;
+
+#[derive(Debug)]
+pub enum LlmError {
+    InvalidApiKey,
+    RateLimit,
+    Timeout,
+    NetworkError(String),
+    ParseError(String),
+}
+
+impl std::fmt::Display for LlmError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LlmError::InvalidApiKey => write!(f, "Invalid API key"),
+            LlmError::RateLimit => write!(f, "Rate limit exceeded"),
+            LlmError::Timeout => write!(f, "Request timeout"),
+            LlmError::NetworkError(msg) => write!(f, "Network error: {}", msg),
+            LlmError::ParseError(msg) => write!(f, "Parse error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for LlmError {}
+
+#[derive(Serialize)]
+struct ChatMessage {
+    role: String,
+    content: String,
+}
+
+#[derive(Serialize)]
+struct ChatCompletionRequest {
+    model: String,
+    messages: Vec<ChatMessage>,
+    temperature: f32,
+    max_tokens: u32,
+}
+
+#[derive(Deserialize)]
+struct ChatCompletionResponse {
+    choices: Vec<Choice>,
+}
+
+#[derive(Deserialize)]
+struct Choice {
+    message: ResponseMessage,
+}
+
+#[derive(Deserialize)]
+struct ResponseMessage {
+    content: String,
+}
+
+/// Client pour toute API compatible OpenAI (`/chat/completions`) :
+/// Groq, Ollama, serveur llama.cpp, LM Studio, passerelles d'entreprise…
+#[derive(Clone)]
+pub struct OpenAiCompatibleClient {
+    base_url: String,
+    model: String,
+    api_key: Option<String>,
+    headers: Vec<(String, String)>,
+    /// Appelé avec les en-têtes de chaque réponse (quotas Groq…)
+    on_response_headers: Option<fn(&HeaderMap)>,
+}
+
+impl OpenAiCompatibleClient {
+    pub fn new(base_url: &str, model: &str) -> Self {
+        Self {
+            base_url: base_url.trim().trim_end_matches('/').to_string(),
+            model: model.trim().to_string(),
+            api_key: None,
+            headers: Vec::new(),
+            on_response_headers: None,
+        }
+    }
+
+    pub fn with_api_key(mut self, api_key: Option<String>) -> Self {
+        self.api_key = api_key.filter(|key| !key.is_empty());
+        self
+    }
+
+    pub fn with_headers(mut self, headers: Vec<(String, String)>) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    pub fn with_response_hook(mut self, hook: fn(&HeaderMap)) -> Self {
+        self.on_response_headers = Some(hook);
+        self
+    }
+
+    pub fn endpoint(&self) -> String {
+        format!("{}/chat/completions", self.base_url)
+    }
+
+    fn request_headers(&self) -> Result<HeaderMap, LlmError> {
+        let mut headers = HeaderMap::new();
+        if let Some(ref key) = self.api_key {
+            let value = HeaderValue::from_str(&format!("Bearer {}", key))
+                .map_err(|_| LlmError::InvalidApiKey)?;
+            headers.insert(reqwest::header::AUTHORIZATION, value);
+        }
+        for (name, value) in &self.headers {
+            let name = HeaderName::from_bytes(name.trim().as_bytes())
+                .map_err(|_| LlmError::NetworkError(format!("Invalid header name: {}", name)))?;
+            let value = HeaderValue::from_str(value.trim())
+                .map_err(|_| LlmError::NetworkError(format!("Invalid value for header {}", name)))?;
+            headers.insert(name, value);
+        }
+        Ok(headers)
+    }
+
+    pub async fn send_completion(
+        &self,
+        system_prompt: &str,
+        text: &str,
+        params: &CompletionParams,
+    ) -> Result<String, LlmError> {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(TIMEOUT_SECONDS))
+            .build()
+            .map_err(|e| LlmError::NetworkError(e.to_string()))?;
+
+        let request_body = ChatCompletionRequest {
+            model: self.model.clone(),
+            messages: vec![
+                ChatMessage {
+                    role: "system".to_string(),
+                    content: system_prompt.to_string(),
+                },
+                ChatMessage {
+                    role: "user".to_string(),
+                    content: text.to_string(),
+                },
+            ],
+            temperature: params.temperature,
+            max_tokens: params.max_tokens,
+        };
+
+        let response = client
+            .post(self.endpoint())
+            .headers(self.request_headers()?)
+            .json(&request_body)
+            .send()
+            .await
+            .map_err(|e| {
+                if e.is_timeout() {
+                    LlmError::Timeout
+                } else {
+                    LlmError::NetworkError(e.to_string())
+                }
+            })?;
+
+        let status = response.status();
+
+        if let Some(hook) = self.on_response_headers {
+            hook(response.headers());
+        }
+
+        if status == reqwest::StatusCode::UNAUTHORIZED {
+            return Err(LlmError::InvalidApiKey);
+        }
+
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(LlmError::RateLimit);
+        }
+
+        if !status.is_success() {
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(LlmError::NetworkError(format!(
+                "HTTP {}: {}",
+                status, error_text
+            )));
+        }
+
+        let response_body: ChatCompletionResponse = response
+            .json()
+            .await
+            .map_err(|e| LlmError::ParseError(e.to_string()))?;
+
+        response_body
+            .choices
+            .into_iter()
+            .next()
+            .map(|choice| choice.message.content)
+            .ok_or_else(|| LlmError::ParseError("No choices in response".to_string()))
+    }
+}
+
+impl LlmClient for OpenAiCompatibleClient {
+    fn complete<'a>(
+        &'a self,
+        system: &'a str,
+        user: &'a str,
+        params: &'a CompletionParams,
+    ) -> CompletionFuture<'a> {
+        Box::pin(async move {
+            self.send_completion(system, user, params)
+                .await
+                .map_err(|e| e.to_string())
+        })
+    }
+
+    fn name(&self) -> String {
+        self.model.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serveur HTTP minimal : renvoie `status`/`body` et la requête reçue
+    async fn mock_server(status: &'static str, body: &'static str) -> (String, tokio::task::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let handle = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            loop {
+                let n = socket.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request);
+                if let Some(header_end) = text.find("\r\n\r\n") {
+                    let length = text[..header_end]
+                        .lines()
+                        .find_map(|l| l.to_ascii_lowercase().strip_prefix("content-length:").map(|v| v.trim().parse::<usize>().unwrap()))
+                        .unwrap_or(0);
+                    if request.len() >= header_end + 4 + length {
+                        break;
+                    }
+                }
+                if n == 0 {
+                    break;
+                }
+            }
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8_lossy(&request).to_string()
+        });
+
+        (format!("http://{}/v1/", address), handle)
+    }
+
+    #[tokio::test]
+    async fn test_completion_against_mock_server() {
+        let (base_url, server) = mock_server(
+            "200 OK",
+            r#"{"choices":[{"message":{"role":"assistant","content":"Bonjour !"}}]}"#,
+        )
+        .await;
+
+        let client = OpenAiCompatibleClient::new(&base_url, "llama3.2")
+            .with_api_key(Some("secret".to_string()))
+            .with_headers(vec![("X-Team".to_string(), "scribe".to_string())]);
+        let answer = client
+            .complete("Sois poli", "Salut", &CompletionParams::default())
+            .await
+            .unwrap();
+        assert_eq!(answer, "Bonjour !");
+
+        let request = server.await.unwrap();
+        let lower = request.to_ascii_lowercase();
+        assert!(request.starts_with("POST /v1/chat/completions HTTP/1.1"));
+        assert!(lower.contains("authorization: bearer secret"));
+        assert!(lower.contains("x-team: scribe"));
+        assert!(request.contains(r#""model":"llama3.2""#));
+        assert!(request.contains(r#""content":"Sois poli""#));
+    }
+
+    #[tokio::test]
+    async fn test_unauthorized_maps_to_invalid_key() {
+        let (base_url, server) = mock_server("401 Unauthorized", "{}").await;
+
+        let client = OpenAiCompatibleClient::new(&base_url, "model");
+        let result = client
+            .send_completion("system", "user", &CompletionParams::default())
+            .await;
+        assert!(matches!(result, Err(LlmError::InvalidApiKey)));
+
+        // Sans clé, aucun en-tête Authorization n'est envoyé
+        let request = server.await.unwrap();
+        assert!(!request.to_ascii_lowercase().contains("authorization:"));
+    }
+}
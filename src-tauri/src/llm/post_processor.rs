@@ -1,51 +1,114 @@
-use crate::types::{DictationMode, LlmMode};
+use crate::app_context::{AppContext, SYSTEM_NOTICE};
+use crate::types::{DictationMode, LlmMode, PromptProfile};
 
-use super::groq_client;
+use super::client::{CompletionParams, LlmClient};
 
-const PROMPT_BASIC: &str = "Tu es un correcteur de texte. Corrige uniquement la ponctuation, les majuscules et les fautes de grammaire évidentes. Ne modifie pas le sens ni le style. Retourne uniquement le texte corrigé, sans explication.";
-
-const PROMPT_SMART: &str = "Tu es un assistant d'écriture. Corrige la ponctuation et la grammaire, supprime les hésitations (euh, hum, ben) et les répétitions inutiles. Reformule légèrement pour plus de clarté si nécessaire. Retourne uniquement le texte amélioré.";
-
-const PROMPT_EMAIL: &str = "Tu es un assistant d'écriture professionnelle. Transforme ce texte dicté en email professionnel. Ajoute les formules de politesse appropriées si absentes. Garde un ton formel mais naturel. Retourne uniquement l'email formaté.";
-
-const PROMPT_CODE: &str = "Tu es un assistant technique. Formate ce texte en documentation de code ou commentaire technique. Utilise la terminologie appropriée. Structure clairement. Retourne uniquement le texte formaté.";
+/// Profil appliqué au texte dicté (voir `storage::prompts`)
+pub fn resolve_profile<'a>(
+    profiles: &'a [PromptProfile],
+    llm_mode: LlmMode,
+    dictation_mode: DictationMode,
+    selected_profile: Option<&str>,
+) -> Option<&'a PromptProfile> {
+    let by_id = |id: &str| profiles.iter().find(|p| p.id == id);
+    match llm_mode {
+        LlmMode::Off => None,
+        LlmMode::Basic => by_id("basic"),
+        LlmMode::Smart => by_id("smart"),
+        LlmMode::Contextual => profiles
+            .iter()
+            .find(|p| p.dictation_mode == Some(dictation_mode))
+            .or_else(|| by_id("smart")),
+        LlmMode::Profile => selected_profile.and_then(by_id),
+    }
+}
 
-const PROMPT_NOTES: &str = "Tu es un assistant de prise de notes. Organise ce texte en notes structurées avec puces si approprié. Garde les points essentiels, supprime le superflu. Retourne uniquement les notes formatées.";
+/// Invite système du profil ; l'exemple éventuel y est ajouté dans le même
+/// format que le message utilisateur. Le contexte de l'application n'y figure
+/// pas : seule la consigne de le traiter comme une donnée y est ajoutée.
+pub fn system_prompt(profile: &PromptProfile, context: Option<&AppContext>) -> String {
+    let mut prompt = match profile.example {
+        Some(ref example) => format!(
+            "{}\n\nExemple :\nTexte: {}\nRésultat : {}",
+            profile.system_prompt.trim(),
+            example.input.trim(),
+            example.output.trim()
+        ),
+        None => profile.system_prompt.clone(),
+    };
+    if context.is_some() {
+        prompt.push_str("\n\n");
+        prompt.push_str(SYSTEM_NOTICE);
+    }
+    prompt
+}
 
-fn get_prompt(llm_mode: LlmMode, dictation_mode: DictationMode) -> &'static str {
-    match llm_mode {
-        LlmMode::Off => "",
-        LlmMode::Basic => PROMPT_BASIC,
-        LlmMode::Smart => PROMPT_SMART,
-        LlmMode::Contextual => match dictation_mode {
-            DictationMode::Email => PROMPT_EMAIL,
-            DictationMode::Code => PROMPT_CODE,
-            DictationMode::Notes => PROMPT_NOTES,
-            DictationMode::General => PROMPT_SMART,
-        },
+/// Texte à réécrire, précédé du contexte de l'application entre balises
+pub fn user_message(text: &str, context: Option<&AppContext>) -> String {
+    match context {
+        Some(context) => format!("{}\n\nTexte: {}", context.prompt_section(), text),
+        None => format!("Texte: {}", text),
     }
 }
 
 pub async fn process(
     text: &str,
-    llm_mode: LlmMode,
-    dictation_mode: DictationMode,
-    api_key: &str,
+    profile: &PromptProfile,
+    context: Option<&AppContext>,
+    client: &dyn LlmClient,
 ) -> Result<String, String> {
-    // If LLM mode is Off, return text as-is
-    if llm_mode == LlmMode::Off {
-        return Ok(text.to_string());
-    }
+    let prompt = system_prompt(profile, context);
+    let user_message = user_message(text, context);
+    let params = CompletionParams {
+        temperature: profile.temperature,
+        max_tokens: profile.max_tokens,
+    };
 
-    let prompt = get_prompt(llm_mode, dictation_mode);
-    let user_message = format!("Texte: {}", text);
-
-    match groq_client::send_completion(api_key, prompt, &user_message).await {
+    match client.complete(&prompt, &user_message, &params).await {
         Ok(processed_text) => Ok(processed_text),
         Err(e) => {
             // Log the error and return original text (graceful fallback)
-            log::error!("LLM post-processing failed: {}. Returning original text.", e);
+            log::error!("LLM post-processing failed ({}): {}. Returning original text.", profile.name, e);
             Ok(text.to_string())
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::prompts::builtin_profiles;
+    use crate::types::PromptExample;
+
+    #[test]
+    fn test_resolve_profile() {
+        let mut profiles = builtin_profiles();
+        let id = |p: Option<&PromptProfile>| p.map(|p| p.id.clone());
+
+        assert_eq!(id(resolve_profile(&profiles, LlmMode::Off, DictationMode::Email, None)), None);
+        assert_eq!(id(resolve_profile(&profiles, LlmMode::Basic, DictationMode::Email, None)).as_deref(), Some("basic"));
+        assert_eq!(id(resolve_profile(&profiles, LlmMode::Contextual, DictationMode::Email, None)).as_deref(), Some("email"));
+        assert_eq!(id(resolve_profile(&profiles, LlmMode::Contextual, DictationMode::General, None)).as_deref(), Some("smart"));
+        assert_eq!(id(resolve_profile(&profiles, LlmMode::Profile, DictationMode::General, Some("notes"))).as_deref(), Some("notes"));
+        assert_eq!(id(resolve_profile(&profiles, LlmMode::Profile, DictationMode::General, None)), None);
+
+        profiles[1].example = Some(PromptExample {
+            input: "euh bonjour".to_string(),
+            output: "Bonjour.".to_string(),
+        });
+        let prompt = system_prompt(&profiles[1], None);
+        assert!(prompt.ends_with("Exemple :\nTexte: euh bonjour\nRésultat : Bonjour."));
+
+        let context = AppContext {
+            app_name: Some("thunderbird".to_string()),
+            ..Default::default()
+        };
+        let prompt = system_prompt(&profiles[1], Some(&context));
+        assert!(prompt.ends_with(SYSTEM_NOTICE));
+        assert!(!prompt.contains("thunderbird"));
+        assert_eq!(
+            user_message("euh merci", Some(&context)),
+            "<contexte>\nApplication : thunderbird\n</contexte>\n\nTexte: euh merci"
+        );
+    }
+}
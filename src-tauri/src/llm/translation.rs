@@ -0,0 +1,134 @@
+//! Traduction de texte par le provider LLM configuré (Groq, serveur compatible
+//! OpenAI ou modèle GGUF local, entièrement hors ligne)
+
+use super::client::{client_for_settings, CompletionParams, LlmClient};
+use crate::types::{AppSettings, LlmProvider};
+
+/// Nom anglais d'une langue (les modèles suivent mieux « French » que « fr »)
+pub fn language_name(code: &str) -> &str {
+    match code {
+        "fr" => "French",
+        "en" => "English",
+        "de" => "German",
+        "es" => "Spanish",
+        "it" => "Italian",
+        "pt" => "Portuguese",
+        "nl" => "Dutch",
+        "ru" => "Russian",
+        "zh" => "Chinese",
+        "ja" => "Japanese",
+        "ko" => "Korean",
+        "ar" => "Arabic",
+        _ => code,
+    }
+}
+
+/// Invite système et message utilisateur. Les petits modèles locaux suivent
+/// mieux une consigne courte rappelée juste avant un texte délimité ; le
+/// modèle de chat (ChatML, Phi-3) place ensuite la consigne au bon endroit.
+pub fn prompt(text: &str, target_language: &str, local: bool) -> (String, String) {
+    let language = language_name(target_language);
+    if local {
+        let system = format!(
+            "You translate text to {}. Reply with the translation only, without notes or quotes.",
+            language
+        );
+        let user = format!("Translate to {}:\n\n{}", language, text);
+        return (system, user);
+    }
+
+    let system = format!(
+        "You are a professional translator. Translate the following text to {}. \
+         Only output the translation, nothing else. Preserve the original formatting, \
+         punctuation and tone. If the text is already in {}, return it unchanged.",
+        language, language
+    );
+    (system, text.to_string())
+}
+
+/// Décodage glouton et longueur bornée pour les modèles locaux
+pub fn params(text: &str, local: bool) -> CompletionParams {
+    if !local {
+        return CompletionParams::default();
+    }
+    CompletionParams {
+        temperature: 0.0,
+        max_tokens: (text.chars().count() as u32 / 2 + 64).min(2048),
+    }
+}
+
+/// Retire les préambules que les petits modèles ajoutent malgré la consigne
+pub fn clean_output(output: &str) -> String {
+    let mut text = output.trim();
+    if let Some((first_line, rest)) = text.split_once('\n') {
+        let lower = first_line.to_lowercase();
+        if first_line.trim_end().ends_with(':')
+            && (lower.contains("translation") || lower.contains("traduction"))
+        {
+            text = rest.trim();
+        }
+    }
+    for prefix in ["Translation:", "Traduction :", "Traduction:"] {
+        if let Some(rest) = text.strip_prefix(prefix) {
+            text = rest.trim();
+        }
+    }
+    let unquoted = text
+        .strip_prefix('"')
+        .and_then(|t| t.strip_suffix('"'))
+        .filter(|t| !t.contains('"'));
+    unquoted.unwrap_or(text).trim().to_string()
+}
+
+/// Traduit `text` avec un client déjà construit
+pub async fn translate_with(
+    client: &dyn LlmClient,
+    text: &str,
+    target_language: &str,
+    local: bool,
+) -> Result<String, String> {
+    let (system, user) = prompt(text, target_language, local);
+    let translated = client.complete(&system, &user, &params(text, local)).await?;
+    log::info!(
+        "Translation to {} via {}: {} -> {} chars",
+        target_language,
+        client.name(),
+        text.len(),
+        translated.len()
+    );
+    Ok(clean_output(&translated))
+}
+
+/// Traduit `text` avec le provider choisi dans les réglages
+pub async fn translate(settings: &AppSettings, text: &str, target_language: &str) -> Result<String, String> {
+    let client = client_for_settings(settings, settings.llm_provider)?;
+    let local = settings.llm_provider == LlmProvider::Local;
+    translate_with(client.as_ref(), text, target_language, local).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_prompt_is_compact_and_greedy() {
+        let (system, user) = prompt("Bonjour", "en", true);
+        assert!(system.contains("English"));
+        assert_eq!(user, "Translate to English:\n\nBonjour");
+        assert_eq!(params("Bonjour", true).temperature, 0.0);
+        assert_eq!(params(&"a".repeat(10_000), true).max_tokens, 2048);
+
+        let (system, user) = prompt("Bonjour", "xx", false);
+        assert!(system.contains("to xx"));
+        assert_eq!(user, "Bonjour");
+    }
+
+    #[test]
+    fn test_clean_output_strips_preambles() {
+        assert_eq!(clean_output("Translation: Hello"), "Hello");
+        assert_eq!(clean_output("Here is the translation:\nHello there"), "Hello there");
+        assert_eq!(clean_output("\"Hello\"\n"), "Hello");
+        assert_eq!(clean_output("He said \"hi\" to me"), "He said \"hi\" to me");
+        assert_eq!(clean_output("Line one:\nLine two"), "Line one:\nLine two");
+    }
+}
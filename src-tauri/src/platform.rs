@@ -715,3 +715,155 @@ pub fn obsidian_create(vault_path: &str, title: &str, body: &str) -> Result<(),
     log::info!("[OBSIDIAN] Note created at: {:?}", file_path);
     Ok(())
 }
+
+/// Application au premier plan
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+pub struct FocusedApp {
+    /// Identifiant stable de l'application (classe de fenêtre X11 en minuscules)
+    pub app_id: String,
+    /// Titre de la fenêtre active
+    pub title: String,
+    pub pid: Option<u32>,
+}
+
+/// Durée pendant laquelle la dernière lecture est réutilisée : un appui bref
+/// interroge le focus au début puis pour l'historique, sans relancer `xprop`
+const FOCUS_CACHE: std::time::Duration = std::time::Duration::from_secs(1);
+
+static LAST_FOCUS: std::sync::Mutex<Option<(std::time::Instant, Option<FocusedApp>)>> =
+    std::sync::Mutex::new(None);
+
+/// Application qui a le focus. Sous Linux, lu via `_NET_ACTIVE_WINDOW` (X11 ou
+/// XWayland, avec `xprop`) ; pas encore disponible sur les autres systèmes.
+pub fn focused_app() -> Option<FocusedApp> {
+    let mut last = LAST_FOCUS.lock().ok()?;
+    if let Some((at, ref app)) = *last {
+        if at.elapsed() < FOCUS_CACHE {
+            return app.clone();
+        }
+    }
+    let app = read_focused_app();
+    *last = Some((std::time::Instant::now(), app.clone()));
+    app
+}
+
+/// `xprop` introuvable : signalé une fois, plus appelé ensuite
+#[cfg(target_os = "linux")]
+static XPROP_MISSING: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+#[cfg(target_os = "linux")]
+fn xprop(args: &[&str]) -> Option<String> {
+    use std::sync::atomic::Ordering;
+
+    if XPROP_MISSING.load(Ordering::Relaxed) {
+        return None;
+    }
+    match Command::new("xprop").args(args).stderr(Stdio::null()).output() {
+        Ok(output) if output.status.success() => Some(String::from_utf8_lossy(&output.stdout).into_owned()),
+        Ok(_) => None,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            XPROP_MISSING.store(true, Ordering::Relaxed);
+            log::info!("[FOCUS] xprop not found, per-application rules are disabled");
+            None
+        }
+        Err(e) => {
+            log::debug!("[FOCUS] xprop failed: {}", e);
+            None
+        }
+    }
+}
+
+fn read_focused_app() -> Option<FocusedApp> {
+    #[cfg(target_os = "linux")]
+    {
+        if std::env::var("DISPLAY").is_err() {
+            return None;
+        }
+        let window = parse_active_window(&xprop(&["-root", "-notype", "_NET_ACTIVE_WINDOW"])?)?;
+        let props = xprop(&["-id", &window, "-notype", "WM_CLASS", "_NET_WM_NAME", "_NET_WM_PID"])?;
+        let app = parse_window_props(&props);
+        log::debug!("[FOCUS] Active window {}: {:?}", window, app);
+        (!app.app_id.is_empty()).then_some(app)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// `_NET_ACTIVE_WINDOW: window id # 0x3a00007` -> `0x3a00007`
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_active_window(output: &str) -> Option<String> {
+    let id = output.rsplit('#').next()?.trim();
+    let id = id.split(',').next()?.trim();
+    (id.starts_with("0x") && id != "0x0").then(|| id.to_string())
+}
+
+/// Chaînes entre guillemets d'une valeur `xprop`
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn quoted_values(value: &str) -> Vec<String> {
+    let mut values = Vec::new();
+    let mut current = None::<String>;
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match (c, current.as_mut()) {
+            ('"', None) => current = Some(String::new()),
+            ('"', Some(_)) => values.extend(current.take()),
+            ('\\', Some(text)) => text.extend(chars.next()),
+            (c, Some(text)) => text.push(c),
+            _ => {}
+        }
+    }
+    values
+}
+
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_window_props(output: &str) -> FocusedApp {
+    let mut app = FocusedApp::default();
+    for line in output.lines() {
+        let Some((name, value)) = line.split_once(" = ") else {
+            continue;
+        };
+        // Sans `-notype`, le nom est suivi du type : `WM_CLASS(STRING)`
+        match name.split('(').next().unwrap_or(name).trim() {
+            "WM_CLASS" => {
+                // Instance puis classe : "code", "Code"
+                let values = quoted_values(value);
+                if let Some(class) = values.last() {
+                    app.app_id = class.to_lowercase();
+                }
+            }
+            "_NET_WM_NAME" => app.title = quoted_values(value).into_iter().next().unwrap_or_default(),
+            "_NET_WM_PID" => app.pid = value.trim().parse().ok(),
+            _ => {}
+        }
+    }
+    app
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_xprop_output() {
+        assert_eq!(
+            parse_active_window("_NET_ACTIVE_WINDOW: window id # 0x3a00007\n").as_deref(),
+            Some("0x3a00007")
+        );
+        assert_eq!(parse_active_window("_NET_ACTIVE_WINDOW: window id # 0x0"), None);
+        assert_eq!(parse_active_window("_NET_ACTIVE_WINDOW:  not found."), None);
+
+        let app = parse_window_props(
+            "WM_CLASS = \"code\", \"Code\"\n_NET_WM_NAME = \"main.rs - \\\"crate\\\" - Visual Studio Code\"\n_NET_WM_PID = 4242\n",
+        );
+        assert_eq!(app.app_id, "code");
+        assert_eq!(app.title, "main.rs - \"crate\" - Visual Studio Code");
+        assert_eq!(app.pid, Some(4242));
+
+        let app = parse_window_props("WM_CLASS(STRING) = \"Mail\", \"thunderbird\"\n_NET_WM_PID(CARDINAL) = 7");
+        assert_eq!(app.app_id, "thunderbird");
+        assert_eq!(app.pid, Some(7));
+    }
+}
@@ -4,10 +4,14 @@ use tauri::{Emitter, Manager};
 use tauri_plugin_clipboard_manager::ClipboardExt;
 use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, Shortcut, ShortcutState};
 
-use crate::audio::AudioCapture;
+use crate::app_context::AppContext;
+use crate::audio::{vad, AudioCapture, StreamingTranscriber};
+use crate::engines::long_form;
 use crate::hotkeys::parse_hotkey;
-use crate::platform::{copy_selected_text, paste_text, type_text_incremental};
+use crate::dictation::{self, DictationContext};
+use crate::platform::{copy_selected_text, focused_app, paste_text, type_text_incremental};
 use crate::state::AppState;
+use crate::types::{AppSettings, LlmMode};
 use crate::storage;
 use crate::tray::{set_tray_recording, set_tray_state, TrayState};
 
@@ -18,24 +22,33 @@ const TARGET_SAMPLE_RATE: u32 = 16000;
 static PTT_SHORTCUT: Mutex<Option<Shortcut>> = Mutex::new(None);
 static TRANSLATE_SHORTCUT: Mutex<Option<Shortcut>> = Mutex::new(None);
 static VOICE_ACTION_SHORTCUT: Mutex<Option<Shortcut>> = Mutex::new(None);
+static SPEECH_TRANSLATE_SHORTCUT: Mutex<Option<Shortcut>> = Mutex::new(None);
+/// Raccourci -> identifiant du profil de prompts
+static PROFILE_SHORTCUTS: Mutex<Vec<(Shortcut, String)>> = Mutex::new(Vec::new());
 
 // État global pour le push-to-talk
 static IS_PTT_ACTIVE: AtomicBool = AtomicBool::new(false);
 static IS_VOICE_ACTION_ACTIVE: AtomicBool = AtomicBool::new(false);
+static IS_SPEECH_TRANSLATE_ACTIVE: AtomicBool = AtomicBool::new(false);
+static IS_PROFILE_DICTATION_ACTIVE: AtomicBool = AtomicBool::new(false);
 static SELECTED_TEXT_FOR_ACTION: Mutex<String> = Mutex::new(String::new());
 
 // Channel pour envoyer les données audio du thread d'enregistrement
 static PTT_AUDIO_SENDER: Mutex<Option<mpsc::Sender<PttCommand>>> = Mutex::new(None);
 static PTT_AUDIO_RECEIVER: Mutex<Option<mpsc::Receiver<PttResult>>> = Mutex::new(None);
 
-// Pour le streaming temps réel
-static STREAMING_TEXT: Mutex<String> = Mutex::new(String::new());
+// Thread de la dictée en cours : rend le streaming à terminer au relâchement
+static DICTATION_THREAD: Mutex<Option<std::thread::JoinHandle<Option<StreamingTranscriber>>>> = Mutex::new(None);
+
+// Réglages de la dictée en cours, selon l'application active au démarrage
+static DICTATION_CONTEXT: Mutex<Option<DictationContext>> = Mutex::new(None);
 
 #[derive(Debug)]
 enum PttCommand {
     Start,
     Stop,
-    GetSnapshot,
+    /// Audio enregistré à partir de l'échantillon `from`
+    GetSnapshot { from: usize },
 }
 
 #[derive(Debug)]
@@ -77,9 +90,9 @@ pub fn init_ptt_audio_thread() {
                         }
                     }
                 }
-                Ok(PttCommand::GetSnapshot) => {
+                Ok(PttCommand::GetSnapshot { from }) => {
                     if let Some(ref cap) = capture {
-                        let (audio, sample_rate) = cap.get_audio_snapshot();
+                        let (audio, sample_rate) = cap.get_audio_snapshot(from);
                         let _ = result_tx.send(PttResult::AudioSnapshot { audio, sample_rate });
                     }
                 }
@@ -117,120 +130,196 @@ fn start_ptt_recording() {
     }
 }
 
-/// Streaming temps réel : transcrit et tape le texte pendant l'enregistrement
-fn start_streaming_transcription(app: &tauri::AppHandle) {
+#[derive(serde::Serialize, Clone)]
+struct StreamingChunk {
+    text: String,
+    is_final: bool,
+    duration_seconds: f32,
+}
+
+/// Demande au thread audio PTT une copie de l'audio enregistré à partir de l'échantillon `from`
+fn request_ptt_snapshot(from: usize) -> Option<(Vec<f32>, u32)> {
+    if let Ok(guard) = PTT_AUDIO_SENDER.lock() {
+        if let Some(ref sender) = *guard {
+            let _ = sender.send(PttCommand::GetSnapshot { from });
+        }
+    }
+
+    if let Ok(guard) = PTT_AUDIO_RECEIVER.lock() {
+        if let Some(ref receiver) = *guard {
+            match receiver.recv_timeout(std::time::Duration::from_millis(500)) {
+                Ok(PttResult::AudioSnapshot { audio, sample_rate }) => Some((audio, sample_rate)),
+                _ => None,
+            }
+        } else {
+            None
+        }
+    } else {
+        None
+    }
+}
+
+/// Streaming temps réel : tape le texte validé pendant l'enregistrement.
+/// Rend le flux au relâchement pour que stop_ptt_and_paste le termine.
+fn start_streaming_transcription(app: &tauri::AppHandle) -> Option<StreamingTranscriber> {
     log::info!("[STREAMING] Starting streaming transcription");
 
     let settings = storage::config::load_settings();
     if !settings.streaming_enabled {
         log::info!("[STREAMING] Streaming disabled in settings");
-        return;
+        return None;
     }
 
-    const STREAMING_INTERVAL_MS: u64 = 1000;
-    let mut last_text_len = 0;
+    // Texte réécrit à la fin : le streaming ne sert qu'à l'aperçu, le texte
+    // post-traité est collé au relâchement
+    let type_committed = DICTATION_CONTEXT
+        .lock()
+        .ok()
+        .and_then(|context| context.as_ref().map(|context| !context.rewrites_text()))
+        .unwrap_or(true);
+
+    let state: tauri::State<'_, AppState> = app.state();
+    let mut transcriber = match state.engine.read() {
+        Ok(guard) => StreamingTranscriber::new(guard.as_deref()),
+        Err(_) => StreamingTranscriber::new(None),
+    };
 
     while IS_PTT_ACTIVE.load(Ordering::SeqCst) {
-        std::thread::sleep(std::time::Duration::from_millis(STREAMING_INTERVAL_MS));
+        std::thread::sleep(transcriber.poll_interval());
 
         if !IS_PTT_ACTIVE.load(Ordering::SeqCst) {
             break;
         }
 
-        if let Ok(guard) = PTT_AUDIO_SENDER.lock() {
-            if let Some(ref sender) = *guard {
-                let _ = sender.send(PttCommand::GetSnapshot);
-            }
-        }
-
-        let snapshot = if let Ok(guard) = PTT_AUDIO_RECEIVER.lock() {
-            if let Some(ref receiver) = *guard {
-                match receiver.recv_timeout(std::time::Duration::from_millis(500)) {
-                    Ok(PttResult::AudioSnapshot { audio, sample_rate }) => Some((audio, sample_rate)),
-                    _ => None,
+        let (audio_data, sample_rate) = match request_ptt_snapshot(transcriber.received_samples()) {
+            Some(data) => data,
+            None => continue,
+        };
+        transcriber.push_audio(&audio_data, sample_rate);
+
+        let progress = {
+            let engine_guard = match state.engine.read() {
+                Ok(guard) => guard,
+                Err(_) => continue,
+            };
+            let engine = match engine_guard.as_ref() {
+                Some(e) => e,
+                None => continue,
+            };
+            match transcriber.step(engine.as_ref()) {
+                Ok(Some(progress)) => progress,
+                Ok(None) => continue,
+                Err(e) => {
+                    log::warn!("[STREAMING] Transcription error: {}", e);
+                    continue;
                 }
-            } else {
-                None
             }
-        } else {
-            None
         };
 
-        let (audio_data, sample_rate) = match snapshot {
-            Some(data) => data,
-            None => continue,
+        let chunk = StreamingChunk {
+            text: progress.display_text(),
+            is_final: false,
+            duration_seconds: transcriber.duration_secs(),
         };
+        let _ = app.emit("transcription-chunk", chunk);
 
-        let duration = audio_data.len() as f32 / sample_rate as f32;
-        if duration < 1.0 {
+        // Seul le texte validé est tapé : il ne changera plus
+        if !type_committed || progress.newly_committed.is_empty() {
             continue;
         }
+        log::info!("[STREAMING] Committed: '{}'", progress.newly_committed);
 
-        let resampled = if sample_rate != TARGET_SAMPLE_RATE {
-            crate::audio::resampling::resample_audio(&audio_data, sample_rate, TARGET_SAMPLE_RATE)
+        if progress.committed == progress.newly_committed {
+            type_text_incremental(&progress.newly_committed);
         } else {
-            audio_data
-        };
+            type_text_incremental(&format!(" {}", progress.newly_committed));
+        }
+    }
 
-        let state: tauri::State<'_, AppState> = app.state();
-        let engine_guard = match state.engine.read() {
-            Ok(guard) => guard,
-            Err(_) => continue,
-        };
-        let engine = match engine_guard.as_ref() {
-            Some(e) => e,
-            None => continue,
-        };
-        let result = match engine.transcribe(&resampled, TARGET_SAMPLE_RATE) {
-            Ok(r) => r,
-            Err(e) => {
-                log::warn!("[STREAMING] Transcription error: {}", e);
-                continue;
-            }
-        };
+    log::info!("[STREAMING] Streaming transcription ended");
+    type_committed.then_some(transcriber)
+}
 
-        if result.text.is_empty() {
-            continue;
+/// Capture l'application active et applique sa règle à la dictée qui commence
+fn begin_dictation(app: &tauri::AppHandle) {
+    let mut context = DictationContext::new(storage::config::load_settings(), focused_app());
+    if let (Some(focused), Some(rule)) = (&context.app, &context.rule) {
+        log::info!("[PTT] Rule '{}' applied for '{}'", rule.app, focused.app_id);
+    }
+
+    // Rien n'est copié ni capturé si aucune réécriture LLM ne suivra
+    let capture = &context.settings.context_capture;
+    if capture.enabled && context.settings.llm_enabled && context.settings.llm_mode != LlmMode::Off {
+        let selection = if capture.selected_text { capture_selection(app) } else { None };
+        context.app_context = AppContext::from_parts(capture, context.app.as_ref(), selection);
+        if let Some(ref captured) = context.app_context {
+            log::info!(
+                "[PTT] Context captured (app: {}, title: {}, selection: {} chars)",
+                captured.app_name.is_some(),
+                captured.window_title.is_some(),
+                captured.selected_text.as_ref().map_or(0, |t| t.chars().count())
+            );
         }
+    }
 
-        log::info!("[STREAMING] Transcribed: '{}'", result.text);
+    // Le streaming et le relâchement attendent la fin de ce thread : la langue
+    // est en place avant la première transcription
+    set_engine_language(app, context.engine_language());
+    if let Ok(mut current) = DICTATION_CONTEXT.lock() {
+        *current = Some(context);
+    }
+}
 
-        #[derive(serde::Serialize, Clone)]
-        struct StreamingChunk {
-            text: String,
-            is_final: bool,
-            duration_seconds: f32,
+fn set_engine_language(app: &tauri::AppHandle, language: Option<String>) {
+    let state: tauri::State<'_, AppState> = app.state();
+    if let Ok(mut engine) = state.engine.write() {
+        if let Some(engine) = engine.as_mut() {
+            engine.set_language(language);
         }
-        let chunk = StreamingChunk {
-            text: result.text.clone(),
-            is_final: false,
-            duration_seconds: duration,
-        };
-        let _ = app.emit("transcription-chunk", chunk);
+    }
+}
 
-        let current_text = result.text.trim();
-        if current_text.len() > last_text_len {
-            let new_text = &current_text[last_text_len..];
-            if !new_text.trim().is_empty() {
-                type_text_incremental(new_text);
-            }
-            last_text_len = current_text.len();
+/// Remet le moteur partagé dans la langue des réglages à la fin de la dictée,
+/// quelle que soit la sortie de `stop_ptt_and_paste`
+struct RestoreEngineLanguage<'a>(&'a tauri::AppHandle);
 
-            if let Ok(mut streaming_text) = STREAMING_TEXT.lock() {
-                *streaming_text = current_text.to_string();
-            }
-        }
+impl Drop for RestoreEngineLanguage<'_> {
+    fn drop(&mut self) {
+        let settings = storage::config::load_settings();
+        set_engine_language(self.0, DictationContext::from_settings(settings).engine_language());
     }
+}
 
-    log::info!("[STREAMING] Streaming transcription ended");
+/// Texte sélectionné dans l'application active, copié comme pour le Voice
+/// Action ; le presse-papiers précédent est restauré
+fn capture_selection(app: &tauri::AppHandle) -> Option<String> {
+    let previous = app.clipboard().read_text().ok();
+    copy_selected_text();
+    let copied = app.clipboard().read_text().ok();
+
+    if let Some(ref previous) = previous {
+        if let Err(e) = app.clipboard().write_text(previous.as_str()) {
+            log::warn!("[PTT] Failed to restore clipboard: {}", e);
+        }
+    }
+    // Presse-papiers inchangé : rien n'était sélectionné
+    copied.filter(|text| !text.trim().is_empty() && Some(text) != previous.as_ref())
 }
 
 /// Arrête l'enregistrement et colle le texte transcrit
 fn stop_ptt_and_paste(app: &tauri::AppHandle) {
     log::info!("[PTT] stop_ptt_and_paste() called");
+    let _restore_language = RestoreEngineLanguage(app);
 
-    let streaming_text = STREAMING_TEXT.lock().ok().map(|t| t.clone()).unwrap_or_default();
-    let had_streaming = !streaming_text.is_empty();
+    // Attendre la fin du streaming avant d'arrêter la capture : il ne lit plus
+    // le canal audio et tout le texte validé est déjà tapé
+    let transcriber = DICTATION_THREAD
+        .lock()
+        .ok()
+        .and_then(|mut thread| thread.take())
+        .and_then(|thread| thread.join().ok())
+        .flatten()
+        .filter(|transcriber| !transcriber.committed_text().is_empty());
 
     if let Ok(guard) = PTT_AUDIO_SENDER.lock() {
         if let Some(ref sender) = *guard {
@@ -272,12 +361,40 @@ fn stop_ptt_and_paste(app: &tauri::AppHandle) {
         return;
     }
 
+    // Fin du flux : seuls les mots jamais validés restent à taper
+    let streamed_tail = transcriber.map(|mut transcriber| {
+        let received = transcriber.received_samples().min(audio_data.len());
+        transcriber.push_audio(&audio_data[received..], sample_rate);
+        let state: tauri::State<'_, AppState> = app.state();
+        let finished = match state.engine.read() {
+            Ok(guard) => match guard.as_ref() {
+                Some(engine) => transcriber.finish(engine.as_ref()),
+                None => Err("engine not initialized".to_string()),
+            },
+            Err(e) => Err(e.to_string()),
+        };
+        finished
+            .map(|progress| progress.newly_committed)
+            .unwrap_or_else(|e| {
+                log::error!("[STREAMING] Failed to finish the stream: {}", e);
+                String::new()
+            })
+    });
+
     let resampled_audio = if sample_rate != TARGET_SAMPLE_RATE {
         crate::audio::resampling::resample_audio(&audio_data, sample_rate, TARGET_SAMPLE_RATE)
     } else {
         audio_data
     };
 
+    let context = DICTATION_CONTEXT
+        .lock()
+        .ok()
+        .and_then(|mut context| context.take())
+        .unwrap_or_else(|| DictationContext::from_settings(storage::config::load_settings()));
+    let retained_audio = context.settings.audio_retention.enabled.then(|| resampled_audio.clone());
+    let resampled_audio = vad::trim_if_enabled(resampled_audio, TARGET_SAMPLE_RATE, &context.settings);
+
     let state: tauri::State<'_, AppState> = app.state();
     let engine_guard = match state.engine.read() {
         Ok(guard) => guard,
@@ -293,13 +410,14 @@ fn stop_ptt_and_paste(app: &tauri::AppHandle) {
             return;
         }
     };
-    let result = match engine.transcribe(&resampled_audio, TARGET_SAMPLE_RATE) {
+    let mut result = match engine.transcribe(&resampled_audio, TARGET_SAMPLE_RATE) {
         Ok(r) => r,
         Err(e) => {
             log::error!("Transcription failed: {}", e);
             return;
         }
     };
+    drop(engine_guard);
 
     if result.text.is_empty() {
         log::warn!("Transcription returned empty text");
@@ -308,6 +426,14 @@ fn stop_ptt_and_paste(app: &tauri::AppHandle) {
 
     log::info!("Transcribed: '{}'", result.text);
 
+    // Sans réécriture prévue, le texte tapé en streaming reste inchangé
+    if context.rewrites_text() {
+        match tokio::runtime::Builder::new_current_thread().enable_all().build() {
+            Ok(rt) => result.text = rt.block_on(dictation::post_process(&result.text, &context)),
+            Err(e) => log::error!("[PTT] Failed to create runtime for post-processing: {}", e),
+        }
+    }
+
     #[derive(serde::Serialize, Clone)]
     struct TranscriptionChunk {
         text: String,
@@ -321,20 +447,21 @@ fn stop_ptt_and_paste(app: &tauri::AppHandle) {
     };
     let _ = app.emit("transcription-chunk", chunk);
 
-    let _ = storage::history::add_transcription(result.clone());
-
-    let final_text = result.text.trim();
-    if had_streaming && final_text.len() > streaming_text.len() {
-        let remaining = &final_text[streaming_text.len()..];
-        if !remaining.trim().is_empty() {
-            type_text_incremental(remaining.trim());
+    let app_id = context.app.as_ref().map(|focused| focused.app_id.as_str());
+    match storage::history::add_transcription(result.clone(), app_id) {
+        Ok(id) => {
+            if let Some(audio) = retained_audio {
+                let retention = context.settings.audio_retention.clone();
+                storage::recordings::retain(id, audio, TARGET_SAMPLE_RATE, retention, context.settings.encrypt_storage);
+            }
         }
-    } else if !had_streaming {
-        paste_text(&result.text);
+        Err(e) => log::warn!("Failed to save transcription to history: {}", e),
     }
 
-    if let Ok(mut text) = STREAMING_TEXT.lock() {
-        text.clear();
+    match streamed_tail {
+        Some(tail) if !tail.is_empty() => type_text_incremental(&format!(" {}", tail)),
+        Some(_) => {}
+        None => paste_text(&result.text),
     }
 }
 
@@ -366,35 +493,9 @@ fn translate_clipboard_and_paste(app: &tauri::AppHandle) {
     };
 
     let settings = storage::config::load_settings();
-    let target_language = settings.translation_target_language;
-
-    let api_key = match crate::commands::llm::get_groq_api_key_internal() {
-        Some(key) => key,
-        None => {
-            log::warn!("[TRANSLATE] No Groq API key configured");
-            set_tray_state(TrayState::Idle);
-            let _ = app.emit("translation_error", "Clé API Groq non configurée");
-            let _ = app.emit("translation-status", "idle");
-            return;
-        }
-    };
-
-    let language_name = match target_language.as_str() {
-        "fr" => "French", "en" => "English", "de" => "German",
-        "es" => "Spanish", "it" => "Italian", "pt" => "Portuguese",
-        "nl" => "Dutch", "ru" => "Russian", "zh" => "Chinese",
-        "ja" => "Japanese", "ko" => "Korean", "ar" => "Arabic",
-        _ => &target_language,
-    };
-
-    let system_prompt = format!(
-        "You are a professional translator. Translate the following text to {}. \
-         Only output the translation, nothing else. Preserve the original formatting, \
-         punctuation and tone. If the text is already in {}, return it unchanged.",
-        language_name, language_name
-    );
+    let target_language = settings.translation_target_language.clone();
 
-    log::info!("[TRANSLATE] Calling Groq API for translation to {}...", language_name);
+    log::info!("[TRANSLATE] Translating to {} with {:?}...", target_language, settings.llm_provider);
 
     let rt = match tokio::runtime::Builder::new_current_thread()
         .enable_all()
@@ -409,16 +510,13 @@ fn translate_clipboard_and_paste(app: &tauri::AppHandle) {
         }
     };
 
-    let translated = rt.block_on(async {
-        crate::llm::groq_client::send_completion(&api_key, &system_prompt, &clipboard_text).await
-    });
+    let translated = rt.block_on(crate::llm::translation::translate(&settings, &clipboard_text, &target_language));
 
     match translated {
         Ok(text) => {
-            let trimmed = text.trim().to_string();
             log::info!("[TRANSLATE] Translation successful");
-            paste_text(&trimmed);
-            let _ = app.emit("translation_complete", &trimmed);
+            paste_text(&text);
+            let _ = app.emit("translation_complete", &text);
         }
         Err(e) => {
             log::error!("[TRANSLATE] Translation failed: {}", e);
@@ -430,6 +528,127 @@ fn translate_clipboard_and_paste(app: &tauri::AppHandle) {
     let _ = app.emit("translation-status", "idle");
 }
 
+/// Arrête l'enregistrement et attend l'audio complet du thread PTT
+fn stop_ptt_recording() -> Result<(Vec<f32>, u32), String> {
+    if let Ok(guard) = PTT_AUDIO_SENDER.lock() {
+        if let Some(ref sender) = *guard {
+            let _ = sender.send(PttCommand::Stop);
+        }
+    }
+
+    let guard = PTT_AUDIO_RECEIVER.lock().map_err(|e| e.to_string())?;
+    let receiver = guard.as_ref().ok_or("PTT audio receiver not initialized")?;
+    loop {
+        match receiver.recv_timeout(std::time::Duration::from_secs(2)) {
+            Ok(PttResult::AudioComplete { audio, sample_rate }) => return Ok((audio, sample_rate)),
+            Ok(PttResult::AudioSnapshot { .. }) => continue,
+            Err(e) => return Err(format!("Failed to receive audio data: {}", e)),
+        }
+    }
+}
+
+/// Audio enregistré, rééchantillonné à 16 kHz et débarrassé des silences
+fn recorded_speech(settings: &AppSettings) -> Result<Vec<f32>, String> {
+    let (audio_data, sample_rate) = stop_ptt_recording()?;
+
+    let duration = audio_data.len() as f32 / sample_rate as f32;
+    log::info!("Captured {:.2}s of audio", duration);
+    if duration < 0.5 {
+        return Err("Enregistrement trop court".to_string());
+    }
+
+    let resampled = if sample_rate != TARGET_SAMPLE_RATE {
+        crate::audio::resampling::resample_audio(&audio_data, sample_rate, TARGET_SAMPLE_RATE)
+    } else {
+        audio_data
+    };
+    Ok(vad::trim_if_enabled(resampled, TARGET_SAMPLE_RATE, settings))
+}
+
+/// Dictée traduite : Whisper transcrit la parole directement en anglais
+/// (tâche `translate`), sans passer par un LLM, puis le texte est collé
+fn stop_speech_translate_and_paste(app: &tauri::AppHandle) -> Result<(), String> {
+    let settings = storage::config::load_settings();
+    let resampled = recorded_speech(&settings)?;
+
+    let state: tauri::State<'_, AppState> = app.state();
+    let result = {
+        let engine_guard = state.engine.read().map_err(|e| e.to_string())?;
+        let engine = engine_guard.as_deref().ok_or("Aucun moteur chargé")?;
+        if !engine.supports_translation() {
+            return Err(format!(
+                "{} ne traduit pas la parole : choisissez un modèle Whisper multilingue",
+                engine.model_display_name()
+            ));
+        }
+        long_form::translate_long(engine, &resampled, TARGET_SAMPLE_RATE, |_, _| {})?
+    };
+
+    if result.text.is_empty() {
+        return Err("La traduction est vide".to_string());
+    }
+    log::info!(
+        "[SPEECH_TRANSLATE] {} -> en: '{}'",
+        result.detected_language.as_deref().unwrap_or("?"),
+        result.text
+    );
+
+    let _ = app.emit(
+        "transcription-chunk",
+        StreamingChunk {
+            text: result.text.clone(),
+            is_final: true,
+            duration_seconds: result.duration_seconds,
+        },
+    );
+    let focused = focused_app();
+    let _ = storage::history::add_transcription(result.clone(), focused.as_ref().map(|f| f.app_id.as_str()));
+
+    paste_text(&result.text);
+    let _ = app.emit("translation_complete", &result.text);
+    Ok(())
+}
+
+/// Dictée réécrite : transcrit puis applique le profil de prompts lié au raccourci
+fn stop_profile_dictation_and_paste(app: &tauri::AppHandle, profile_id: &str) -> Result<(), String> {
+    let settings = storage::config::load_settings();
+    let audio = recorded_speech(&settings)?;
+    let profile = storage::prompts::get_profile(profile_id)
+        .ok_or_else(|| format!("Profil de prompts introuvable : {}", profile_id))?;
+
+    let state: tauri::State<'_, AppState> = app.state();
+    let mut result = {
+        let engine_guard = state.engine.read().map_err(|e| e.to_string())?;
+        let engine = engine_guard.as_deref().ok_or("Aucun moteur chargé")?;
+        long_form::transcribe_long(engine, &audio, TARGET_SAMPLE_RATE, |_, _| {})?
+    };
+    if result.text.is_empty() {
+        return Err("Transcription returned empty text".to_string());
+    }
+
+    let client = crate::llm::client_for_settings(&settings, profile.provider.unwrap_or(settings.llm_provider))?;
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| format!("Failed to create runtime: {}", e))?;
+    result.text = rt.block_on(crate::llm::process(&result.text, &profile, None, client.as_ref()))?;
+    log::info!("[PROFILE] '{}' via {}: '{}'", profile.name, client.name(), result.text);
+
+    let _ = app.emit(
+        "transcription-chunk",
+        StreamingChunk {
+            text: result.text.clone(),
+            is_final: true,
+            duration_seconds: result.duration_seconds,
+        },
+    );
+    let focused = focused_app();
+    let _ = storage::history::add_transcription(result.clone(), focused.as_ref().map(|f| f.app_id.as_str()));
+
+    paste_text(&result.text);
+    Ok(())
+}
+
 /// Démarre le Voice Action: copie le texte sélectionné et démarre l'enregistrement
 fn start_voice_action(app: &tauri::AppHandle) {
     log::info!("[VOICE_ACTION] Starting voice action...");
@@ -461,7 +680,7 @@ fn start_voice_action(app: &tauri::AppHandle) {
     let _ = app.emit("voice-action-status", "recording");
 }
 
-/// Arrête le Voice Action: transcrit l'instruction et exécute via Groq
+/// Arrête le Voice Action: transcrit l'instruction et exécute via le LLM configuré
 fn stop_voice_action_and_execute(app: &tauri::AppHandle) {
     log::info!("[VOICE_ACTION] Stopping and executing...");
 
@@ -522,6 +741,9 @@ fn stop_voice_action_and_execute(app: &tauri::AppHandle) {
         audio_data
     };
 
+    let settings = storage::config::load_settings();
+    let resampled = vad::trim_if_enabled(resampled, TARGET_SAMPLE_RATE, &settings);
+
     let state: tauri::State<'_, crate::state::AppState> = app.state();
     let engine_guard = match state.engine.read() {
         Ok(guard) => guard,
@@ -561,10 +783,10 @@ fn stop_voice_action_and_execute(app: &tauri::AppHandle) {
 
     log::info!("[VOICE_ACTION] Instruction: '{}'", transcription);
 
-    let api_key = match crate::commands::llm::get_groq_api_key_internal() {
-        Some(key) => key,
-        None => {
-            let _ = app.emit("voice-action-error", "Clé API Groq non configurée");
+    let client = match crate::llm::client_for_settings(&settings, settings.llm_provider) {
+        Ok(client) => client,
+        Err(e) => {
+            let _ = app.emit("voice-action-error", e);
             set_tray_state(TrayState::Idle);
             let _ = app.emit("voice-action-status", "idle");
             return;
@@ -595,9 +817,8 @@ Retourne UNIQUEMENT le résultat, sans explication ni commentaire."#;
         }
     };
 
-    let result = rt.block_on(async {
-        crate::llm::groq_client::send_completion(&api_key, system_prompt, &user_prompt).await
-    });
+    let params = crate::llm::CompletionParams::default();
+    let result = rt.block_on(client.complete(system_prompt, &user_prompt, &params));
 
     match result {
         Ok(response) => {
@@ -607,7 +828,7 @@ Retourne UNIQUEMENT le résultat, sans explication ni commentaire."#;
             let _ = app.emit("voice-action-complete", &trimmed);
         }
         Err(e) => {
-            log::error!("[VOICE_ACTION] Groq error: {}", e);
+            log::error!("[VOICE_ACTION] LLM error: {}", e);
             let _ = app.emit("voice-action-error", format!("Erreur: {}", e));
         }
     }
@@ -647,6 +868,12 @@ pub fn setup_shortcuts(app: &mut tauri::App) -> Result<(), Box<dyn std::error::E
         }
     }
 
+    // Raccourci dictée traduite en anglais
+    register_speech_translate_shortcut(app.handle(), &settings.hotkey_speech_translate);
+
+    // Raccourcis des profils de prompts
+    register_profile_shortcuts(app.handle());
+
     // Raccourci Voice Action
     let voice_action_hotkey = settings.hotkey_voice_action.clone();
     if let Some(voice_action_shortcut) = parse_hotkey(&voice_action_hotkey) {
@@ -662,6 +889,58 @@ pub fn setup_shortcuts(app: &mut tauri::App) -> Result<(), Box<dyn std::error::E
     Ok(())
 }
 
+/// (Ré)enregistre le raccourci de dictée traduite ; vide, il est désactivé
+pub fn register_speech_translate_shortcut(app: &tauri::AppHandle, hotkey: &str) {
+    let Ok(mut current) = SPEECH_TRANSLATE_SHORTCUT.lock() else {
+        return;
+    };
+    if let Some(shortcut) = current.take() {
+        let _ = app.global_shortcut().unregister(shortcut);
+    }
+    if hotkey.trim().is_empty() {
+        log::info!("[SPEECH_TRANSLATE] Shortcut disabled");
+        return;
+    }
+    let Some(shortcut) = parse_hotkey(hotkey) else {
+        log::warn!("[SPEECH_TRANSLATE] Invalid hotkey '{}'", hotkey);
+        return;
+    };
+    match app.global_shortcut().register(shortcut) {
+        Ok(_) => {
+            log::info!("[SPEECH_TRANSLATE] Shortcut '{}' registered!", hotkey);
+            *current = Some(shortcut);
+        }
+        Err(e) => log::error!("[SPEECH_TRANSLATE] ERROR registering shortcut: {:?}", e),
+    }
+}
+
+/// (Ré)enregistre les raccourcis des profils de prompts
+pub fn register_profile_shortcuts(app: &tauri::AppHandle) {
+    let Ok(mut registered) = PROFILE_SHORTCUTS.lock() else {
+        return;
+    };
+    for (shortcut, _) in registered.drain(..) {
+        let _ = app.global_shortcut().unregister(shortcut);
+    }
+
+    for profile in storage::prompts::load_profiles().profiles {
+        let Some(hotkey) = profile.hotkey else {
+            continue;
+        };
+        let Some(shortcut) = parse_hotkey(&hotkey) else {
+            log::warn!("[PROFILE] Invalid hotkey '{}' for profile '{}'", hotkey, profile.name);
+            continue;
+        };
+        match app.global_shortcut().register(shortcut) {
+            Ok(_) => {
+                log::info!("[PROFILE] Shortcut '{}' registered for '{}'", hotkey, profile.name);
+                registered.push((shortcut, profile.id));
+            }
+            Err(e) => log::error!("[PROFILE] ERROR registering shortcut '{}': {:?}", hotkey, e),
+        }
+    }
+}
+
 /// Handler pour les événements de raccourcis globaux
 pub fn handle_shortcut(app: &tauri::AppHandle, shortcut: &Shortcut, event: &tauri_plugin_global_shortcut::ShortcutEvent) {
     let is_ptt = PTT_SHORTCUT.lock().ok()
@@ -673,22 +952,31 @@ pub fn handle_shortcut(app: &tauri::AppHandle, shortcut: &Shortcut, event: &taur
     let is_voice_action = VOICE_ACTION_SHORTCUT.lock().ok()
         .and_then(|guard| guard.as_ref().map(|s| *s == *shortcut))
         .unwrap_or(false);
+    let is_speech_translate = SPEECH_TRANSLATE_SHORTCUT.lock().ok()
+        .and_then(|guard| guard.as_ref().map(|s| *s == *shortcut))
+        .unwrap_or(false);
+    let profile_id = PROFILE_SHORTCUTS.lock().ok()
+        .and_then(|guard| guard.iter().find(|(s, _)| *s == *shortcut).map(|(_, id)| id.clone()));
 
     if is_ptt {
         match event.state() {
             ShortcutState::Pressed => {
+                if IS_SPEECH_TRANSLATE_ACTIVE.load(Ordering::SeqCst) || IS_PROFILE_DICTATION_ACTIVE.load(Ordering::SeqCst) {
+                    return;
+                }
                 if !IS_PTT_ACTIVE.swap(true, Ordering::SeqCst) {
-                    if let Ok(mut text) = STREAMING_TEXT.lock() {
-                        text.clear();
-                    }
                     set_tray_recording(true);
                     start_ptt_recording();
                     let _ = app.emit("recording-status", "recording");
 
                     let handle = app.clone();
-                    std::thread::spawn(move || {
-                        start_streaming_transcription(&handle);
+                    let thread = std::thread::spawn(move || {
+                        begin_dictation(&handle);
+                        start_streaming_transcription(&handle)
                     });
+                    if let Ok(mut current) = DICTATION_THREAD.lock() {
+                        *current = Some(thread);
+                    }
                 }
             }
             ShortcutState::Released => {
@@ -710,6 +998,60 @@ pub fn handle_shortcut(app: &tauri::AppHandle, shortcut: &Shortcut, event: &taur
                 translate_clipboard_and_paste(&handle);
             });
         }
+    } else if is_speech_translate {
+        match event.state() {
+            ShortcutState::Pressed => {
+                // Partage le thread audio du push-to-talk
+                if IS_PTT_ACTIVE.load(Ordering::SeqCst) || IS_PROFILE_DICTATION_ACTIVE.load(Ordering::SeqCst) {
+                    return;
+                }
+                if !IS_SPEECH_TRANSLATE_ACTIVE.swap(true, Ordering::SeqCst) {
+                    set_tray_recording(true);
+                    start_ptt_recording();
+                    let _ = app.emit("recording-status", "recording");
+                }
+            }
+            ShortcutState::Released => {
+                if IS_SPEECH_TRANSLATE_ACTIVE.swap(false, Ordering::SeqCst) {
+                    set_tray_recording(false);
+                    let _ = app.emit("recording-status", "processing");
+                    let handle = app.clone();
+                    std::thread::spawn(move || {
+                        if let Err(e) = stop_speech_translate_and_paste(&handle) {
+                            log::error!("[SPEECH_TRANSLATE] {}", e);
+                            let _ = handle.emit("translation_error", format!("Erreur de traduction: {}", e));
+                        }
+                        let _ = handle.emit("recording-status", "idle");
+                    });
+                }
+            }
+        }
+    } else if let Some(profile_id) = profile_id {
+        match event.state() {
+            ShortcutState::Pressed => {
+                if IS_PTT_ACTIVE.load(Ordering::SeqCst) || IS_SPEECH_TRANSLATE_ACTIVE.load(Ordering::SeqCst) {
+                    return;
+                }
+                if !IS_PROFILE_DICTATION_ACTIVE.swap(true, Ordering::SeqCst) {
+                    set_tray_recording(true);
+                    start_ptt_recording();
+                    let _ = app.emit("recording-status", "recording");
+                }
+            }
+            ShortcutState::Released => {
+                if IS_PROFILE_DICTATION_ACTIVE.swap(false, Ordering::SeqCst) {
+                    set_tray_recording(false);
+                    let _ = app.emit("recording-status", "processing");
+                    let handle = app.clone();
+                    std::thread::spawn(move || {
+                        if let Err(e) = stop_profile_dictation_and_paste(&handle, &profile_id) {
+                            log::error!("[PROFILE] {}", e);
+                        }
+                        let _ = handle.emit("recording-status", "idle");
+                    });
+                }
+            }
+        }
     } else if is_voice_action {
         match event.state() {
             ShortcutState::Pressed => {
@@ -0,0 +1,213 @@
+//! Serveur local optionnel (127.0.0.1 uniquement) exposant le moteur déjà
+//! chargé par l'application :
+//! - `POST /v1/audio/transcriptions` compatible OpenAI (multipart)
+//! - `GET /v1/audio/stream` en WebSocket (PCM 16 bits -> résultats partiels et final)
+//!
+//! Toutes les routes exigent `Authorization: Bearer <jeton>` (ou `?token=` pour
+//! les clients WebSocket qui ne peuvent pas fixer d'en-tête). Le jeton est
+//! conservé dans le trousseau.
+
+pub mod protocol;
+mod stream;
+mod transcriptions;
+
+use axum::extract::{DefaultBodyLimit, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::Serialize;
+use serde_json::json;
+use std::sync::{Arc, Mutex, RwLock};
+use tokio::sync::oneshot;
+
+use crate::engines::SpeechEngine;
+use crate::state::AppState;
+use crate::storage::secrets;
+use crate::types::{AppSettings, LocalServerConfig};
+
+/// Taille maximale d'un fichier envoyé à `/v1/audio/transcriptions`
+const MAX_UPLOAD_BYTES: usize = 200 * 1024 * 1024;
+
+/// État partagé par les routes : le moteur et les réglages de `AppState`
+#[derive(Clone)]
+pub struct ServerState {
+    pub engine: Arc<RwLock<Option<Box<dyn SpeechEngine>>>>,
+    pub settings: Arc<RwLock<AppSettings>>,
+    token: Arc<RwLock<String>>,
+}
+
+struct RunningServer {
+    port: u16,
+    token: Arc<RwLock<String>>,
+    shutdown: oneshot::Sender<()>,
+}
+
+static SERVER: Mutex<Option<RunningServer>> = Mutex::new(None);
+static LAST_ERROR: Mutex<Option<String>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LocalServerStatus {
+    pub running: bool,
+    pub url: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Erreur au format OpenAI (`{"error": {"message", "type"}}`)
+pub struct ApiError {
+    status: StatusCode,
+    message: String,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self { status, message: message.into() }
+    }
+
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, message)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let kind = match self.status {
+            StatusCode::UNAUTHORIZED => "authentication_error",
+            s if s.is_client_error() => "invalid_request_error",
+            _ => "server_error",
+        };
+        let body = json!({ "error": { "message": self.message, "type": kind } });
+        (self.status, Json(body)).into_response()
+    }
+}
+
+// ===== Jeton =====
+
+/// Jeton d'accès, créé au premier appel
+pub fn token() -> Result<String, String> {
+    if let Some(token) = secrets::get(secrets::LOCAL_SERVER_TOKEN) {
+        return Ok(token);
+    }
+    let token = protocol::generate_token();
+    secrets::set(secrets::LOCAL_SERVER_TOKEN, &token)?;
+    Ok(token)
+}
+
+/// Remplace le jeton ; le serveur en cours l'applique immédiatement
+pub fn regenerate_token() -> Result<String, String> {
+    let token = protocol::generate_token();
+    secrets::set(secrets::LOCAL_SERVER_TOKEN, &token)?;
+
+    if let Some(ref server) = *SERVER.lock().map_err(|e| e.to_string())? {
+        if let Ok(mut current) = server.token.write() {
+            *current = token.clone();
+        }
+    }
+    Ok(token)
+}
+
+async fn require_token(State(state): State<ServerState>, request: Request, next: Next) -> Response {
+    let provided = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .or_else(|| protocol::query_param(request.uri().query(), "token"))
+        .map(str::to_string);
+
+    let authorized = match (provided, state.token.read()) {
+        (Some(provided), Ok(expected)) => protocol::tokens_match(provided.trim(), &expected),
+        _ => false,
+    };
+    if !authorized {
+        return ApiError::new(StatusCode::UNAUTHORIZED, "Missing or invalid bearer token").into_response();
+    }
+    next.run(request).await
+}
+
+fn router(state: ServerState) -> Router {
+    Router::new()
+        .route(
+            "/v1/audio/transcriptions",
+            post(transcriptions::create).layer(DefaultBodyLimit::max(MAX_UPLOAD_BYTES)),
+        )
+        .route("/v1/audio/stream", get(stream::upgrade))
+        .layer(middleware::from_fn_with_state(state.clone(), require_token))
+        .with_state(state)
+}
+
+// ===== Cycle de vie =====
+
+/// Démarre le serveur sur 127.0.0.1:`port` (en remplaçant celui en cours)
+pub fn start(app_state: &AppState, port: u16) -> Result<(), String> {
+    stop();
+
+    let listener = std::net::TcpListener::bind(("127.0.0.1", port))
+        .map_err(|e| format!("Failed to bind 127.0.0.1:{}: {}", port, e))?;
+    listener.set_nonblocking(true).map_err(|e| e.to_string())?;
+
+    let token = Arc::new(RwLock::new(token()?));
+    let state = ServerState {
+        engine: app_state.engine.clone(),
+        settings: app_state.settings.clone(),
+        token: token.clone(),
+    };
+    let (shutdown, shutdown_signal) = oneshot::channel::<()>();
+
+    tauri::async_runtime::spawn(async move {
+        let listener = match tokio::net::TcpListener::from_std(listener) {
+            Ok(listener) => listener,
+            Err(e) => {
+                log::error!("Local server failed to start: {}", e);
+                return;
+            }
+        };
+        let served = axum::serve(listener, router(state))
+            .with_graceful_shutdown(async {
+                let _ = shutdown_signal.await;
+            })
+            .await;
+        if let Err(e) = served {
+            log::error!("Local server stopped: {}", e);
+        }
+    });
+
+    log::info!("Local transcription server listening on 127.0.0.1:{}", port);
+    *SERVER.lock().map_err(|e| e.to_string())? = Some(RunningServer { port, token, shutdown });
+    Ok(())
+}
+
+pub fn stop() {
+    if let Ok(mut server) = SERVER.lock() {
+        if let Some(server) = server.take() {
+            let _ = server.shutdown.send(());
+            log::info!("Local transcription server on port {} stopped", server.port);
+        }
+    }
+}
+
+/// Démarre, redémarre ou arrête le serveur selon la configuration
+pub fn apply_config(app_state: &AppState, config: &LocalServerConfig) {
+    let result = if config.enabled {
+        start(app_state, config.port)
+    } else {
+        stop();
+        Ok(())
+    };
+    if let Err(ref e) = result {
+        log::error!("Local server: {}", e);
+    }
+    if let Ok(mut last_error) = LAST_ERROR.lock() {
+        *last_error = result.err();
+    }
+}
+
+pub fn status() -> LocalServerStatus {
+    let port = SERVER.lock().ok().and_then(|server| server.as_ref().map(|s| s.port));
+    LocalServerStatus {
+        running: port.is_some(),
+        url: port.map(|port| format!("http://127.0.0.1:{}", port)),
+        error: LAST_ERROR.lock().ok().and_then(|e| e.clone()),
+    }
+}
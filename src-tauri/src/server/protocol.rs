@@ -0,0 +1,189 @@
+//! Formats échangés par le serveur local : réponses compatibles OpenAI,
+//! messages du flux WebSocket et jeton d'accès
+
+use serde::Serialize;
+use serde_json::json;
+use std::str::FromStr;
+
+use crate::storage::{crypto, export};
+use crate::types::{SubtitleOptions, TranscriptionResult};
+
+/// `response_format` de `POST /v1/audio/transcriptions`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResponseFormat {
+    #[default]
+    Json,
+    VerboseJson,
+    Text,
+    Srt,
+    Vtt,
+}
+
+impl FromStr for ResponseFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim() {
+            "json" | "" => Ok(Self::Json),
+            "verbose_json" => Ok(Self::VerboseJson),
+            "text" => Ok(Self::Text),
+            "srt" => Ok(Self::Srt),
+            "vtt" => Ok(Self::Vtt),
+            other => Err(format!("Unsupported response_format: {}", other)),
+        }
+    }
+}
+
+/// Corps de la réponse et son type MIME
+pub fn render(result: &TranscriptionResult, format: ResponseFormat) -> (&'static str, String) {
+    let cues = || export::build_cues(result, &SubtitleOptions::default());
+    match format {
+        ResponseFormat::Json => ("application/json", json!({ "text": result.text }).to_string()),
+        ResponseFormat::VerboseJson => {
+            let segments: Vec<_> = result
+                .segments
+                .iter()
+                .enumerate()
+                .map(|(id, segment)| {
+                    json!({
+                        "id": id,
+                        "start": segment.start,
+                        "end": segment.end,
+                        "text": segment.text,
+                        "speaker": segment.speaker,
+                    })
+                })
+                .collect();
+            let body = json!({
+                "task": "transcribe",
+                "language": result.detected_language,
+                "duration": result.duration_seconds,
+                "text": result.text,
+                "segments": segments,
+            });
+            ("application/json", body.to_string())
+        }
+        ResponseFormat::Text => ("text/plain; charset=utf-8", result.text.clone()),
+        ResponseFormat::Srt => ("text/plain; charset=utf-8", export::to_srt(&cues())),
+        ResponseFormat::Vtt => ("text/vtt; charset=utf-8", export::to_vtt(&cues())),
+    }
+}
+
+/// Message envoyé au client WebSocket
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum StreamEvent {
+    /// `text` vient d'être validé ; `tentative` peut encore changer
+    Partial {
+        text: String,
+        committed: String,
+        tentative: String,
+    },
+    /// Transcription complète de l'audio reçu
+    Final { text: String, duration_seconds: f32 },
+    Error { message: String },
+}
+
+/// PCM 16 bits little-endian mono vers f32 (un octet impair final est ignoré)
+pub fn pcm16_to_f32(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(2)
+        .map(|pair| i16::from_le_bytes([pair[0], pair[1]]) as f32 / 32768.0)
+        .collect()
+}
+
+/// Valeur d'un paramètre de la query string (sans décodage : jetons hexadécimaux)
+pub fn query_param<'a>(query: Option<&'a str>, name: &str) -> Option<&'a str> {
+    query?
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+/// Comparaison en temps constant, pour ne pas laisser deviner le jeton
+pub fn tokens_match(provided: &str, expected: &str) -> bool {
+    if expected.is_empty() || provided.len() != expected.len() {
+        return false;
+    }
+    provided
+        .bytes()
+        .zip(expected.bytes())
+        .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+        == 0
+}
+
+/// Jeton de 256 bits tiré de l'aléa cryptographique du système
+pub fn generate_token() -> String {
+    crypto::to_hex(&crypto::random_bytes::<32>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Segment;
+
+    fn result() -> TranscriptionResult {
+        TranscriptionResult {
+            text: "Bonjour à tous.".to_string(),
+            confidence: 0.9,
+            duration_seconds: 2.0,
+            processing_time_ms: 10,
+            detected_language: Some("fr".to_string()),
+            timestamp: 0,
+            model_used: None,
+            segments: vec![Segment {
+                start: 0.0,
+                end: 2.0,
+                text: "Bonjour à tous.".to_string(),
+                words: Vec::new(),
+                confidence: 0.9,
+                speaker: None,
+            }],
+            translation: None,
+        }
+    }
+
+    #[test]
+    fn test_renders_openai_response_formats() {
+        let result = result();
+        let (mime, body) = render(&result, "json".parse().unwrap());
+        assert_eq!(mime, "application/json");
+        assert_eq!(body, r#"{"text":"Bonjour à tous."}"#);
+
+        let (_, body) = render(&result, ResponseFormat::VerboseJson);
+        let value: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(value["language"], "fr");
+        assert_eq!(value["segments"][0]["end"], 2.0);
+
+        let (_, srt) = render(&result, ResponseFormat::Srt);
+        assert!(srt.starts_with("1\n00:00:00,000 --> 00:00:02,000\n"));
+        let (mime, vtt) = render(&result, ResponseFormat::Vtt);
+        assert!(mime.starts_with("text/vtt"));
+        assert!(vtt.starts_with("WEBVTT"));
+        assert!("xml".parse::<ResponseFormat>().is_err());
+    }
+
+    #[test]
+    fn test_stream_helpers() {
+        assert_eq!(pcm16_to_f32(&[0x00, 0x40, 0x00, 0x80, 0x01]), vec![0.5, -1.0]);
+        assert_eq!(query_param(Some("sample_rate=48000&token=abc"), "token"), Some("abc"));
+        assert_eq!(query_param(None, "token"), None);
+
+        let event = StreamEvent::Final { text: "ok".to_string(), duration_seconds: 1.5 };
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"type":"final","text":"ok","duration_seconds":1.5}"#
+        );
+    }
+
+    #[test]
+    fn test_tokens() {
+        let token = generate_token();
+        assert_eq!(token.len(), 64);
+        assert_ne!(token, generate_token());
+        assert!(tokens_match(&token, &token));
+        assert!(!tokens_match(&token[..63], &token));
+        assert!(!tokens_match("", ""));
+    }
+}
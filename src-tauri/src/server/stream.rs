@@ -0,0 +1,106 @@
+//! `GET /v1/audio/stream` : transcription au fil de l'eau en WebSocket.
+//!
+//! Le client envoie des trames binaires PCM 16 bits mono little-endian
+//! (`?sample_rate=`, 16000 par défaut) puis `{"type":"stop"}`. Le serveur
+//! répond par des `StreamEvent` JSON : `partial` au fil de l'audio, puis
+//! `final` avec tout le texte une fois la fin du flux reconnue.
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Query, State};
+use axum::response::Response;
+use serde::Deserialize;
+
+use super::protocol::{self, StreamEvent};
+use super::ServerState;
+use crate::audio::{StreamingProgress, StreamingTranscriber};
+
+const TARGET_SAMPLE_RATE: u32 = 16000;
+/// Au-delà, le flux est refusé : tout l'audio reste en mémoire jusqu'à la fin
+const MAX_STREAM_SECONDS: f32 = 30.0 * 60.0;
+
+#[derive(Debug, Deserialize)]
+pub struct StreamParams {
+    sample_rate: Option<u32>,
+}
+
+pub async fn upgrade(
+    ws: WebSocketUpgrade,
+    Query(params): Query<StreamParams>,
+    State(state): State<ServerState>,
+) -> Response {
+    let sample_rate = params.sample_rate.filter(|&rate| rate > 0).unwrap_or(TARGET_SAMPLE_RATE);
+    ws.on_upgrade(move |socket| handle(socket, state, sample_rate))
+}
+
+async fn send(socket: &mut WebSocket, event: StreamEvent) -> bool {
+    match serde_json::to_string(&event) {
+        Ok(json) => socket.send(Message::Text(json)).await.is_ok(),
+        Err(_) => false,
+    }
+}
+
+fn is_stop(text: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(text)
+        .map(|value| value["type"] == "stop")
+        .unwrap_or(false)
+}
+
+fn step(state: &ServerState, transcriber: &mut StreamingTranscriber) -> Result<Option<StreamingProgress>, String> {
+    let engine = state.engine.read().map_err(|e| e.to_string())?;
+    let engine = engine.as_deref().ok_or("No engine initialized")?;
+    transcriber.step(engine)
+}
+
+/// Fin du flux : reconnaît l'audio restant et renvoie tout le texte
+fn finish(state: &ServerState, transcriber: &mut StreamingTranscriber) -> Result<StreamEvent, String> {
+    let engine = state.engine.read().map_err(|e| e.to_string())?;
+    let engine = engine.as_deref().ok_or("No engine initialized")?;
+    let progress = transcriber.finish(engine)?;
+    Ok(StreamEvent::Final {
+        text: progress.committed,
+        duration_seconds: transcriber.duration_secs(),
+    })
+}
+
+async fn handle(mut socket: WebSocket, state: ServerState, sample_rate: u32) {
+    let mut transcriber = match state.engine.read() {
+        Ok(guard) => StreamingTranscriber::new(guard.as_deref()),
+        Err(_) => StreamingTranscriber::new(None),
+    };
+
+    while let Some(Ok(message)) = socket.recv().await {
+        match message {
+            Message::Binary(data) => {
+                transcriber.push_audio(&protocol::pcm16_to_f32(&data), sample_rate);
+                if transcriber.duration_secs() > MAX_STREAM_SECONDS {
+                    let message = format!("Stream exceeds {} minutes", MAX_STREAM_SECONDS / 60.0);
+                    send(&mut socket, StreamEvent::Error { message }).await;
+                    let _ = socket.close().await;
+                    return;
+                }
+                let event = match tokio::task::block_in_place(|| step(&state, &mut transcriber)) {
+                    Ok(Some(progress)) => StreamEvent::Partial {
+                        text: progress.newly_committed,
+                        committed: progress.committed,
+                        tentative: progress.tentative,
+                    },
+                    Ok(None) => continue,
+                    Err(message) => StreamEvent::Error { message },
+                };
+                if !send(&mut socket, event).await {
+                    return;
+                }
+            }
+            Message::Text(text) if is_stop(&text) => break,
+            Message::Close(_) => return,
+            _ => {}
+        }
+    }
+
+    if transcriber.received_samples() > 0 {
+        let event = tokio::task::block_in_place(|| finish(&state, &mut transcriber))
+            .unwrap_or_else(|message| StreamEvent::Error { message });
+        send(&mut socket, event).await;
+    }
+    let _ = socket.close().await;
+}
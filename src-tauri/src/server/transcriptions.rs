@@ -0,0 +1,90 @@
+//! `POST /v1/audio/transcriptions`, compatible avec l'API OpenAI
+
+use axum::extract::{Multipart, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::protocol::{self, ResponseFormat};
+use super::{ApiError, ServerState};
+use crate::audio::{diarization, AudioDecoder};
+use crate::engines::long_form;
+use crate::types::TranscriptionResult;
+
+static UPLOAD_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Champs `file` (obligatoire) et `response_format` ; `model`, `language` et
+/// `prompt` sont acceptés mais c'est le moteur chargé dans l'application qui répond
+pub async fn create(State(state): State<ServerState>, mut multipart: Multipart) -> Result<Response, ApiError> {
+    let mut upload = None;
+    let mut format = ResponseFormat::default();
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| ApiError::bad_request(e.to_string()))?
+    {
+        match field.name() {
+            Some("file") => {
+                let file_name = field.file_name().unwrap_or("audio.wav").to_string();
+                let data = field.bytes().await.map_err(|e| ApiError::bad_request(e.to_string()))?;
+                upload = Some((file_name, data));
+            }
+            Some("response_format") => {
+                let value = field.text().await.map_err(|e| ApiError::bad_request(e.to_string()))?;
+                format = value.parse().map_err(ApiError::bad_request)?;
+            }
+            _ => {}
+        }
+    }
+
+    let (file_name, data) = upload.ok_or_else(|| ApiError::bad_request("Missing 'file' field"))?;
+    let result = tokio::task::spawn_blocking(move || transcribe_upload(&state, &file_name, &data))
+        .await
+        .map_err(|e| ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))??;
+
+    let (content_type, body) = protocol::render(&result, format);
+    Ok(([(header::CONTENT_TYPE, content_type)], body).into_response())
+}
+
+/// Le décodeur lit un fichier : l'envoi est écrit dans un fichier temporaire
+/// qui garde l'extension d'origine
+fn transcribe_upload(state: &ServerState, file_name: &str, data: &[u8]) -> Result<TranscriptionResult, ApiError> {
+    let extension = Path::new(file_name)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("wav");
+    let path = std::env::temp_dir().join(format!(
+        "scribe-upload-{}-{}.{}",
+        std::process::id(),
+        UPLOAD_COUNTER.fetch_add(1, Ordering::Relaxed),
+        extension
+    ));
+    if !AudioDecoder::is_supported(&path) {
+        return Err(ApiError::bad_request(format!("Unsupported audio format: {}", extension)));
+    }
+
+    std::fs::write(&path, data).map_err(|e| ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let decoded = AudioDecoder::decode_file(&path);
+    let _ = std::fs::remove_file(&path);
+    let (audio, sample_rate) = decoded.map_err(|e| ApiError::bad_request(format!("Failed to decode: {}", e)))?;
+
+    let settings = state
+        .settings
+        .read()
+        .map_err(|e| ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .clone();
+    let engine = state
+        .engine
+        .read()
+        .map_err(|e| ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let engine = engine
+        .as_deref()
+        .ok_or_else(|| ApiError::new(StatusCode::SERVICE_UNAVAILABLE, "No engine initialized"))?;
+
+    let mut result = long_form::transcribe_long(engine, &audio, sample_rate, |_, _| {})
+        .map_err(|e| ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    diarization::diarize_if_enabled(&mut result, &audio, sample_rate, &settings);
+    Ok(result)
+}
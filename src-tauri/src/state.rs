@@ -2,9 +2,10 @@ use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
 use tauri::{AppHandle, Manager};
 
-use crate::engines::{ModelManager, ParakeetCoreMLEngine, ParakeetEngine, SpeechEngine, VoskEngine, WhisperEngine};
-use crate::storage::config;
-use crate::types::{AppSettings, EngineType, ModelSize, ParakeetModelSize, VoskLanguage};
+use crate::engines::catalog::ModelFamily;
+use crate::engines::{ModelManager, ParakeetCoreMLEngine, ParakeetEngine, SpeechEngine, VoskEngine, Vocabulary, WhisperEngine};
+use crate::storage::{config, dictionary};
+use crate::types::{AppSettings, EngineType};
 
 pub struct AppState {
     pub is_recording: Arc<RwLock<bool>>,
@@ -71,19 +72,21 @@ impl AppState {
             app_data_dir,
             bundled_models_path,
         );
+        model_manager.set_mirror_url(settings.model_mirror_url.clone());
 
         // Helper to load Whisper engine
         let load_whisper = |model_manager: &ModelManager, settings: &AppSettings| -> Option<Box<dyn SpeechEngine>> {
-            if let Some(model_path) = model_manager.get_model_path(settings.whisper_model) {
+            let entry = model_manager.entry(ModelFamily::Whisper, &settings.whisper_model).ok();
+            if let Some((entry, model_path)) = entry.and_then(|e| model_manager.installed_path(&e).map(|p| (e, p))) {
                 let lang = if settings.auto_detect_language {
                     None
                 } else {
                     Some(settings.transcription_language.clone())
                 };
 
-                match WhisperEngine::new(&model_path, lang, settings.whisper_model) {
+                match WhisperEngine::new(&model_path, lang, entry) {
                     Ok(engine) => {
-                        log::info!("Whisper engine initialized with model {:?}", settings.whisper_model);
+                        log::info!("Whisper engine initialized with model {}", settings.whisper_model);
                         Some(Box::new(engine))
                     }
                     Err(e) => {
@@ -92,13 +95,13 @@ impl AppState {
                     }
                 }
             } else {
-                log::warn!("Whisper model {:?} not available", settings.whisper_model);
+                log::warn!("Whisper model {} not available", settings.whisper_model);
                 None
             }
         };
 
         // Load engine based on configured type
-        let engine: Option<Box<dyn SpeechEngine>> = match settings.engine_type {
+        let mut engine: Option<Box<dyn SpeechEngine>> = match settings.engine_type {
             EngineType::Whisper => load_whisper(&model_manager, &settings),
             EngineType::Parakeet => {
                 // On macOS, use CoreML-based Parakeet engine
@@ -125,10 +128,10 @@ impl AppState {
                 // On other platforms, use ONNX-based Parakeet engine
                 #[cfg(not(target_os = "macos"))]
                 {
-                    if let Some(model_path) = model_manager.get_parakeet_model_path(settings.parakeet_model) {
-                        match ParakeetEngine::new(&model_path, settings.parakeet_model.into()) {
+                    if let Some((entry, model_path)) = model_manager.installed_entry(ModelFamily::Parakeet, &settings.parakeet_model) {
+                        match ParakeetEngine::new(&model_path, entry) {
                             Ok(engine) => {
-                                log::info!("Parakeet engine initialized with model {:?}", settings.parakeet_model);
+                                log::info!("Parakeet engine initialized with model {}", settings.parakeet_model);
                                 Some(Box::new(engine))
                             }
                             Err(e) => {
@@ -137,21 +140,18 @@ impl AppState {
                             }
                         }
                     } else {
-                        log::warn!("Parakeet model {:?} not available, falling back to Whisper", settings.parakeet_model);
+                        log::warn!("Parakeet model {} not available, falling back to Whisper", settings.parakeet_model);
                         load_whisper(&model_manager, &settings)
                     }
                 }
             }
             EngineType::Vosk => {
-                // Find Vosk model matching configured language
-                let vosk_lang = settings.vosk_language
-                    .or_else(|| VoskLanguage::from_language_code(&settings.transcription_language));
-
-                if let Some(lang) = vosk_lang {
-                    if let Some(model_path) = model_manager.get_vosk_model_path(lang) {
-                        match VoskEngine::new(&model_path, lang) {
+                // Modèle Vosk configuré, ou celui de la langue de transcription
+                if let Some(id) = Self::vosk_model_id(&model_manager, &settings) {
+                    if let Some((entry, model_path)) = model_manager.installed_entry(ModelFamily::Vosk, &id) {
+                        match VoskEngine::new(&model_path, entry) {
                             Ok(engine) => {
-                                log::info!("Vosk engine initialized for language {:?}", lang);
+                                log::info!("Vosk engine initialized with model {}", id);
                                 Some(Box::new(engine))
                             }
                             Err(e) => {
@@ -160,7 +160,7 @@ impl AppState {
                             }
                         }
                     } else {
-                        log::warn!("Vosk model for {:?} not available", lang);
+                        log::warn!("Vosk model {} not available", id);
                         None
                     }
                 } else {
@@ -170,6 +170,10 @@ impl AppState {
             }
         };
 
+        if let Some(ref mut engine) = engine {
+            engine.set_vocabulary(Self::load_vocabulary());
+        }
+
         Ok(Self {
             is_recording: Arc::new(RwLock::new(false)),
             settings: Arc::new(RwLock::new(settings)),
@@ -229,73 +233,80 @@ impl AppState {
         None
     }
 
-    /// Recharge le moteur Whisper avec un nouveau modèle
-    pub fn reload_engine(&self, model_size: ModelSize, language: Option<String>) -> Result<(), String> {
-        let model_path = self.model_manager
-            .get_model_path(model_size)
-            .ok_or_else(|| format!("Model {:?} not available", model_size))?;
-
-        let new_engine = WhisperEngine::new(&model_path, language, model_size)?;
+    /// Construit le vocabulaire de reconnaissance depuis le dictionnaire utilisateur
+    fn load_vocabulary() -> Vocabulary {
+        Vocabulary::new(&dictionary::load_dictionary().words)
+    }
 
+    /// Propage le dictionnaire courant au moteur chargé
+    pub fn refresh_vocabulary(&self) -> Result<(), String> {
         let mut engine = self.engine.write().map_err(|e| e.to_string())?;
-        *engine = Some(Box::new(new_engine));
+        if let Some(ref mut engine) = *engine {
+            engine.set_vocabulary(Self::load_vocabulary());
+        }
+        Ok(())
+    }
 
-        log::info!("Whisper engine reloaded with model {:?}", model_size);
+    /// Installe le moteur comme moteur actif, avec le dictionnaire courant
+    fn activate(&self, mut new_engine: Box<dyn SpeechEngine>) -> Result<(), String> {
+        new_engine.set_vocabulary(Self::load_vocabulary());
+        let mut engine = self.engine.write().map_err(|e| e.to_string())?;
+        *engine = Some(new_engine);
         Ok(())
     }
 
-    /// Recharge le moteur Parakeet avec un nouveau modèle
-    pub fn reload_parakeet_engine(&self, _model_size: ParakeetModelSize) -> Result<(), String> {
-        // On macOS, use CoreML-based Parakeet engine
+    fn whisper_engine(&self, model_id: &str, language: Option<String>) -> Result<Box<dyn SpeechEngine>, String> {
+        let entry = self.model_manager.entry(ModelFamily::Whisper, model_id)?;
+        let model_path = self.model_manager
+            .installed_path(&entry)
+            .ok_or_else(|| format!("Model {} not available", model_id))?;
+        Ok(Box::new(WhisperEngine::new(&model_path, language, entry)?))
+    }
+
+    fn parakeet_engine(&self, model_id: &str) -> Result<Box<dyn SpeechEngine>, String> {
+        // Sur macOS, le sidecar CoreML n'embarque que le modèle par défaut
         #[cfg(target_os = "macos")]
         {
+            if model_id != crate::types::DEFAULT_PARAKEET_MODEL {
+                return Err(format!("Parakeet model {} is not available with CoreML", model_id));
+            }
             let sidecar_path = Self::find_parakeet_sidecar(&self.resource_path)
                 .ok_or_else(|| "Parakeet CoreML sidecar not found".to_string())?;
-
-            let new_engine = ParakeetCoreMLEngine::new(sidecar_path)?;
-
-            let mut engine = self.engine.write().map_err(|e| e.to_string())?;
-            *engine = Some(Box::new(new_engine));
-
-            log::info!("Parakeet CoreML engine reloaded");
-            Ok(())
+            Ok(Box::new(ParakeetCoreMLEngine::new(sidecar_path)?))
         }
-        // On other platforms, use ONNX-based Parakeet engine
+        // Ailleurs, moteur ONNX
         #[cfg(not(target_os = "macos"))]
         {
+            let entry = self.model_manager.entry(ModelFamily::Parakeet, model_id)?;
             let model_path = self.model_manager
-                .get_parakeet_model_path(_model_size)
-                .ok_or_else(|| format!("Parakeet model {:?} not available", _model_size))?;
-
-            let new_engine = ParakeetEngine::new(&model_path, _model_size.into())?;
-
-            let mut engine = self.engine.write().map_err(|e| e.to_string())?;
-            *engine = Some(Box::new(new_engine));
-
-            log::info!("Parakeet engine reloaded with model {:?}", _model_size);
-            Ok(())
+                .installed_path(&entry)
+                .ok_or_else(|| format!("Parakeet model {} not available", model_id))?;
+            Ok(Box::new(ParakeetEngine::new(&model_path, entry)?))
         }
     }
 
-    /// Recharge le moteur Vosk avec une nouvelle langue
-    pub fn reload_vosk_engine(&self, language: VoskLanguage) -> Result<(), String> {
+    fn vosk_engine(&self, model_id: &str) -> Result<Box<dyn SpeechEngine>, String> {
+        let entry = self.model_manager.entry(ModelFamily::Vosk, model_id)?;
         let model_path = self.model_manager
-            .get_vosk_model_path(language)
-            .ok_or_else(|| format!("Vosk model for {:?} not available", language))?;
-
-        let new_engine = VoskEngine::new(&model_path, language)?;
-
-        let mut engine = self.engine.write().map_err(|e| e.to_string())?;
-        *engine = Some(Box::new(new_engine));
-
-        log::info!("Vosk engine reloaded for language {:?}", language);
-        Ok(())
+            .installed_path(&entry)
+            .ok_or_else(|| format!("Vosk model {} not available", model_id))?;
+        Ok(Box::new(VoskEngine::new(&model_path, entry)?))
     }
 
-    /// Change le type de moteur (Whisper, Parakeet ou Vosk)
-    pub fn switch_engine_type(&self, engine_type: EngineType) -> Result<(), String> {
-        let settings = self.settings.read().map_err(|e| e.to_string())?;
+    /// Modèle Vosk des réglages, à défaut celui de la langue de transcription
+    fn vosk_model_id(model_manager: &ModelManager, settings: &AppSettings) -> Option<String> {
+        settings.vosk_language.clone().or_else(|| {
+            model_manager
+                .catalog()
+                .for_language(ModelFamily::Vosk, &settings.transcription_language)
+                .map(|entry| entry.id.clone())
+        })
+    }
 
+    /// Moteur du type demandé, sans dictionnaire ; sans `model`, celui des
+    /// réglages pour ce type de moteur
+    fn build_engine(&self, engine_type: EngineType, model: Option<&str>) -> Result<Box<dyn SpeechEngine>, String> {
+        let settings = self.settings.read().map_err(|e| e.to_string())?.clone();
         match engine_type {
             EngineType::Whisper => {
                 let language = if settings.auto_detect_language {
@@ -303,26 +314,53 @@ impl AppState {
                 } else {
                     Some(settings.transcription_language.clone())
                 };
-                let model_size = settings.whisper_model;
-                drop(settings);
-                self.reload_engine(model_size, language)
-            }
-            EngineType::Parakeet => {
-                let model_size = settings.parakeet_model;
-                drop(settings);
-                self.reload_parakeet_engine(model_size)
+                self.whisper_engine(model.unwrap_or(&settings.whisper_model), language)
             }
+            EngineType::Parakeet => self.parakeet_engine(model.unwrap_or(&settings.parakeet_model)),
             EngineType::Vosk => {
-                let vosk_lang = settings.vosk_language
-                    .or_else(|| VoskLanguage::from_language_code(&settings.transcription_language));
-                drop(settings);
-
-                if let Some(lang) = vosk_lang {
-                    self.reload_vosk_engine(lang)
-                } else {
-                    Err("No Vosk language configured and current language not supported by Vosk".to_string())
-                }
+                let model_id = match model {
+                    Some(id) => id.to_string(),
+                    None => Self::vosk_model_id(&self.model_manager, &settings)
+                        .ok_or("No Vosk language configured and current language not supported by Vosk")?,
+                };
+                self.vosk_engine(&model_id)
             }
         }
     }
+
+    /// Recharge le moteur Whisper avec un nouveau modèle
+    pub fn reload_engine(&self, model_id: &str, language: Option<String>) -> Result<(), String> {
+        self.activate(self.whisper_engine(model_id, language)?)?;
+        log::info!("Whisper engine reloaded with model {}", model_id);
+        Ok(())
+    }
+
+    /// Recharge le moteur Parakeet avec un nouveau modèle
+    pub fn reload_parakeet_engine(&self, model_id: &str) -> Result<(), String> {
+        self.activate(self.parakeet_engine(model_id)?)?;
+        log::info!("Parakeet engine reloaded with model {}", model_id);
+        Ok(())
+    }
+
+    /// Recharge le moteur Vosk avec un nouveau modèle
+    pub fn reload_vosk_engine(&self, model_id: &str) -> Result<(), String> {
+        self.activate(self.vosk_engine(model_id)?)?;
+        log::info!("Vosk engine reloaded with model {}", model_id);
+        Ok(())
+    }
+
+    /// Charge un moteur sans remplacer le moteur actif (re-transcription) ;
+    /// même résolution du modèle qu'au changement de moteur
+    pub fn load_engine(&self, engine_type: EngineType, model: Option<&str>) -> Result<Box<dyn SpeechEngine>, String> {
+        let mut engine = self.build_engine(engine_type, model)?;
+        engine.set_vocabulary(Self::load_vocabulary());
+        Ok(engine)
+    }
+
+    /// Change le type de moteur (Whisper, Parakeet ou Vosk)
+    pub fn switch_engine_type(&self, engine_type: EngineType) -> Result<(), String> {
+        self.activate(self.build_engine(engine_type, None)?)?;
+        log::info!("Engine switched to {:?}", engine_type);
+        Ok(())
+    }
 }
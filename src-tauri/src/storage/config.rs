@@ -1,24 +1,353 @@
-use crate::types::AppSettings;
+//! Réglages de l'application (config.json). Le fichier porte `schema_version` :
+//! les migrations manquantes sont appliquées au JSON brut à la lecture. Un
+//! champ illisible reprend sa valeur par défaut, après copie du fichier
+//! d'origine, au lieu de réinitialiser tous les réglages.
+
+use super::json_store::{write_private, JsonStore};
+use crate::hotkeys::parse_hotkey;
+use crate::types::{AppSettings, SettingsError, SettingsLoadReport, SETTINGS_SCHEMA_VERSION};
+use serde_json::{Map, Value};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Migration du JSON brut vers la version suivante
+type Migration = fn(&mut Map<String, Value>, Origin) -> Result<(), String>;
 
-fn config_path() -> PathBuf {
-    super::get_app_data_dir().join("config.json")
+/// Provenance du JSON : seules les migrations du config.json de cette
+/// installation agissent hors du fichier (trousseau)
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Origin {
+    Local,
+    /// Archive d'une autre installation, lue avant validation
+    External,
 }
 
-pub fn load_settings() -> AppSettings {
-    let path = config_path();
-    if path.exists() {
-        let content = fs::read_to_string(&path).unwrap_or_default();
-        serde_json::from_str(&content).unwrap_or_default()
-    } else {
-        AppSettings::default()
+/// Migrations dans l'ordre : la n-ième mène à la version n
+const MIGRATIONS: &[Migration] = &[move_groq_api_key];
+
+/// Problèmes rencontrés à la lecture, en attente d'affichage
+static LOAD_REPORT: Mutex<Option<SettingsLoadReport>> = Mutex::new(None);
+
+static STORE: JsonStore<AppSettings> = JsonStore::new("config.json", AppSettings::default, decode);
+
+/// Clé Groq en clair d'un config.json dont la migration a échoué. `AppSettings`
+/// ne la porte plus : toute réécriture du fichier l'effacerait.
+static PENDING_GROQ_KEY: Mutex<Option<String>> = Mutex::new(None);
+
+/// v1 : la clé Groq quitte config.json pour le trousseau. Elle n'est retirée
+/// du JSON qu'une fois rangée ; celle d'une archive importée est ignorée.
+fn move_groq_api_key(settings: &mut Map<String, Value>, origin: Origin) -> Result<(), String> {
+    if origin == Origin::Local {
+        if let Some(Value::String(key)) = settings.get("groq_api_key").filter(|key| key.as_str() != Some("")) {
+            if let Err(e) = super::secrets::set(super::secrets::GROQ_API_KEY, key) {
+                if let Ok(mut pending) = PENDING_GROQ_KEY.lock() {
+                    *pending = Some(key.clone());
+                }
+                return Err(e);
+            }
+        }
     }
+    settings.remove("groq_api_key");
+    Ok(())
+}
+
+/// Range la clé d'une migration en échec ; tant qu'elle ne l'est pas,
+/// config.json n'est pas réécrit
+fn store_pending_key() -> Result<(), String> {
+    let mut pending = PENDING_GROQ_KEY.lock().map_err(|e| e.to_string())?;
+    if let Some(ref key) = *pending {
+        super::secrets::set(super::secrets::GROQ_API_KEY, key).map_err(|e| {
+            format!("Settings not saved: the Groq API key in config.json could not be moved to the keyring ({})", e)
+        })?;
+        *pending = None;
+    }
+    Ok(())
+}
+
+/// Applique les migrations manquantes ; `true` si le JSON a changé
+fn migrate(settings: &mut Map<String, Value>, migrations: &[Migration], origin: Origin) -> Result<bool, String> {
+    let version = settings.get("schema_version").and_then(Value::as_u64).unwrap_or(0) as usize;
+    if version > migrations.len() {
+        log::warn!("config.json has schema version {}, newer than this build ({})", version, migrations.len());
+        return Ok(false);
+    }
+    for (index, migration) in migrations.iter().enumerate().skip(version) {
+        migration(settings, origin).map_err(|e| format!("Settings migration to version {} failed: {}", index + 1, e))?;
+        settings.insert("schema_version".to_string(), Value::from(index + 1));
+    }
+    Ok(version < migrations.len())
+}
+
+/// Réglages lus champ par champ : ceux qui ne se désérialisent pas gardent
+/// leur valeur par défaut et sont signalés
+fn deserialize_lenient(settings: Map<String, Value>) -> (AppSettings, Vec<SettingsError>) {
+    if let Ok(parsed) = serde_json::from_value(Value::Object(settings.clone())) {
+        return (parsed, Vec::new());
+    }
+    let mut accepted = Map::new();
+    let mut errors = Vec::new();
+    for (field, value) in settings {
+        let mut candidate = accepted.clone();
+        candidate.insert(field.clone(), value);
+        match serde_json::from_value::<AppSettings>(Value::Object(candidate.clone())) {
+            Ok(_) => accepted = candidate,
+            Err(e) => errors.push(SettingsError::new(&field, e.to_string())),
+        }
+    }
+    (serde_json::from_value(Value::Object(accepted)).unwrap_or_default(), errors)
+}
+
+/// Réglages d'un contenu JSON migré ; `true` si le fichier doit être réécrit.
+/// Une migration en échec est signalée (le fichier est copié) sans réécriture :
+/// elle est retentée au lancement suivant.
+fn parse(content: &str, migrations: &[Migration], origin: Origin) -> (AppSettings, Vec<SettingsError>, bool) {
+    let mut settings = match serde_json::from_str(content) {
+        Ok(Value::Object(settings)) => settings,
+        Ok(_) => return (AppSettings::default(), vec![SettingsError::new("", "Expected a JSON object")], true),
+        Err(e) => return (AppSettings::default(), vec![SettingsError::new("", e.to_string())], true),
+    };
+    let mut errors = Vec::new();
+    let migrated = migrate(&mut settings, migrations, origin).unwrap_or_else(|e| {
+        log::error!("{}", e);
+        errors.push(SettingsError::new("schema_version", e));
+        false
+    });
+    let (settings, field_errors) = deserialize_lenient(settings);
+    let rewrite = migrated || (errors.is_empty() && !field_errors.is_empty());
+    errors.extend(field_errors);
+    (settings, errors, rewrite)
+}
+
+/// Copie du fichier illisible à côté de l'original, sans la clé Groq en clair
+/// d'une ancienne version (rangée ou en attente, elle n'est pas perdue). Un
+/// JSON illisible est copié tel quel, lisible par le seul utilisateur.
+fn backup(path: &Path) -> Option<PathBuf> {
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let backup = path.with_file_name(format!("config.invalid-{}.json", stamp));
+    let copied = fs::read_to_string(path).map_err(|e| e.to_string()).and_then(|content| {
+        match serde_json::from_str::<Map<String, Value>>(&content) {
+            Ok(mut settings) => {
+                settings.remove("groq_api_key");
+                let content = serde_json::to_vec_pretty(&settings).map_err(|e| e.to_string())?;
+                write_private(&backup, &content)
+            }
+            Err(_) => write_private(&backup, content.as_bytes()),
+        }
+    });
+    match copied {
+        Ok(()) => Some(backup),
+        Err(e) => {
+            log::error!("Failed to back up {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+/// Décodage de config.json : un champ illisible est signalé et le fichier
+/// d'origine copié avant réécriture
+fn decode(content: &str) -> Result<(AppSettings, bool), String> {
+    let (settings, errors, rewrite) = parse(content, MIGRATIONS, Origin::Local);
+    if !errors.is_empty() {
+        let backup_path = backup(&STORE.path());
+        for error in &errors {
+            log::warn!("Invalid setting '{}' reset to default: {}", error.field, error.message);
+        }
+        if let Ok(mut report) = LOAD_REPORT.lock() {
+            *report = Some(SettingsLoadReport {
+                errors,
+                backup_path: backup_path.map(|p| p.to_string_lossy().to_string()),
+            });
+        }
+    }
+    Ok((settings, rewrite))
+}
+
+pub fn load_settings() -> AppSettings {
+    STORE.get()
 }
 
 pub fn save_settings(settings: &AppSettings) -> Result<(), String> {
-    super::ensure_app_data_dir().map_err(|e| e.to_string())?;
-    let path = config_path();
-    let content = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
-    fs::write(&path, content).map_err(|e| e.to_string())
+    store_pending_key()?;
+    STORE.save(AppSettings {
+        schema_version: SETTINGS_SCHEMA_VERSION,
+        ..settings.clone()
+    })
+}
+
+/// Problèmes de la dernière lecture, rendus une seule fois
+pub fn take_load_report() -> Option<SettingsLoadReport> {
+    LOAD_REPORT.lock().ok().and_then(|mut report| report.take())
+}
+
+/// Raccourcis globaux des réglages, avec le champ correspondant
+pub fn hotkeys(settings: &AppSettings) -> [(&'static str, &str); 5] {
+    [
+        ("hotkey_push_to_talk", &settings.hotkey_push_to_talk),
+        ("hotkey_toggle_record", &settings.hotkey_toggle_record),
+        ("hotkey_translate", &settings.hotkey_translate),
+        ("hotkey_voice_action", &settings.hotkey_voice_action),
+        ("hotkey_speech_translate", &settings.hotkey_speech_translate),
+    ]
+}
+
+/// Champ des réglages qui utilise déjà ce raccourci
+pub fn hotkey_owner(settings: &AppSettings, hotkey: &str) -> Option<&'static str> {
+    let shortcut = parse_hotkey(hotkey)?;
+    hotkeys(settings)
+        .into_iter()
+        .find(|(_, other)| parse_hotkey(other).as_ref() == Some(&shortcut))
+        .map(|(field, _)| field)
+}
+
+/// Erreurs champ par champ : raccourcis reconnus et distincts, coffre
+/// Obsidian existant. La dictée traduite peut être désactivée (champ vide).
+pub fn validate(settings: &AppSettings) -> Vec<SettingsError> {
+    let mut errors = Vec::new();
+    let mut used = Vec::new();
+    for (field, hotkey) in hotkeys(settings) {
+        if field == "hotkey_speech_translate" && hotkey.trim().is_empty() {
+            continue;
+        }
+        let Some(shortcut) = parse_hotkey(hotkey) else {
+            errors.push(SettingsError::new(field, format!("Invalid hotkey format: {}", hotkey)));
+            continue;
+        };
+        match used.iter().find(|(_, other)| *other == shortcut) {
+            Some((other, _)) => errors.push(SettingsError::new(
+                field,
+                format!("Hotkey {} is already used by {}", hotkey, other),
+            )),
+            None => used.push((field, shortcut)),
+        }
+    }
+
+    if let Some(vault) = settings.integrations.obsidian_vault_path.as_deref().filter(|p| !p.trim().is_empty()) {
+        if !Path::new(vault).is_dir() {
+            errors.push(SettingsError::new(
+                "integrations.obsidian_vault_path",
+                format!("Obsidian vault not found: {}", vault),
+            ));
+        }
+    }
+    errors
+}
+
+/// Remplace dans `settings` les champs en erreur par ceux de `fallback`
+pub fn restore_fields(settings: &AppSettings, fallback: &AppSettings, errors: &[SettingsError]) -> AppSettings {
+    let (Ok(mut value), Ok(fallback)) = (serde_json::to_value(settings), serde_json::to_value(fallback)) else {
+        return settings.clone();
+    };
+    for error in errors.iter().filter(|error| !error.field.is_empty()) {
+        let pointer = format!("/{}", error.field.replace('.', "/"));
+        if let (Some(target), Some(previous)) = (value.pointer_mut(&pointer), fallback.pointer(&pointer)) {
+            *target = previous.clone();
+        }
+    }
+    serde_json::from_value(value).unwrap_or_else(|_| settings.clone())
+}
+
+/// Réglages d'une autre installation (export), migrés sans effet hors du
+/// JSON et lus champ par champ
+pub fn parse_external(content: &str) -> (AppSettings, Vec<SettingsError>) {
+    let (settings, errors, _) = parse(content, MIGRATIONS, Origin::External);
+    (settings, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rename_theme(settings: &mut Map<String, Value>, _: Origin) -> Result<(), String> {
+        if let Some(theme) = settings.remove("color_theme") {
+            settings.insert("theme".to_string(), theme);
+        }
+        Ok(())
+    }
+
+    fn unreachable_migration(_: &mut Map<String, Value>, _: Origin) -> Result<(), String> {
+        Err("already applied".to_string())
+    }
+
+    #[test]
+    fn test_migrations_and_lenient_parse() {
+        assert_eq!(MIGRATIONS.len(), SETTINGS_SCHEMA_VERSION as usize);
+
+        let migrations: &[Migration] = &[unreachable_migration, rename_theme];
+        let (settings, errors, rewrite) = parse(
+            r#"{"schema_version": 1, "color_theme": "dark", "hotkey_translate": "Alt+T", "minimize_to_tray": "yes"}"#,
+            migrations,
+            Origin::Local,
+        );
+        assert_eq!(settings.theme, "dark");
+        assert_eq!(settings.hotkey_translate, "Alt+T");
+        // Seul le champ illisible revient à sa valeur par défaut
+        assert!(settings.minimize_to_tray);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].field, "minimize_to_tray");
+        assert!(rewrite);
+
+        let (settings, errors, rewrite) = parse(r#"{"theme": "dark""#, migrations, Origin::Local);
+        assert_eq!(settings.theme, AppSettings::default().theme);
+        assert_eq!(errors[0].field, "");
+        assert!(rewrite);
+
+        let (_, errors, rewrite) = parse(r#"{"schema_version": 2, "theme": "light"}"#, migrations, Origin::Local);
+        assert!(errors.is_empty());
+        assert!(!rewrite);
+
+        // Migration en échec : signalée, fichier laissé tel quel
+        let (settings, errors, rewrite) = parse(r#"{"theme": "light", "minimize_to_tray": "yes"}"#, migrations, Origin::Local);
+        assert_eq!(settings.theme, "light");
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["schema_version", "minimize_to_tray"]);
+        assert!(!rewrite);
+
+        // Archive importée : la clé est ignorée, sans toucher au trousseau
+        let (_, errors) = parse_external(r#"{"groq_api_key": "gsk_imported", "theme": "light"}"#);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_backup_drops_plaintext_key() {
+        let path = std::env::temp_dir().join(format!("wakascribe-config-{}", std::process::id())).join("config.json");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, r#"{"groq_api_key": "gsk_secret", "minimize_to_tray": "yes"}"#).unwrap();
+
+        let copy = backup(&path).unwrap();
+        let content = fs::read_to_string(&copy).unwrap();
+        assert!(!content.contains("gsk_secret"));
+        assert!(content.contains("minimize_to_tray"));
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_restore_fields() {
+        let current = AppSettings::default();
+        let mut imported = AppSettings {
+            theme: "dark".to_string(),
+            hotkey_translate: "Control+Nope".to_string(),
+            ..AppSettings::default()
+        };
+        imported.integrations.obsidian_vault_path = Some("/nonexistent/vault".to_string());
+
+        let errors = validate(&imported);
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["hotkey_translate", "integrations.obsidian_vault_path"]);
+
+        let restored = restore_fields(&imported, &current, &errors);
+        assert_eq!(restored.theme, "dark");
+        assert_eq!(restored.hotkey_translate, current.hotkey_translate);
+        assert_eq!(restored.integrations.obsidian_vault_path, None);
+        assert!(validate(&restored).is_empty());
+
+        // Raccourci déjà pris ; dictée traduite désactivée
+        let conflicting = AppSettings {
+            hotkey_voice_action: current.hotkey_push_to_talk.clone(),
+            hotkey_speech_translate: String::new(),
+            ..AppSettings::default()
+        };
+        let fields: Vec<String> = validate(&conflicting).into_iter().map(|e| e.field).collect();
+        assert_eq!(fields, vec!["hotkey_voice_action"]);
+    }
 }
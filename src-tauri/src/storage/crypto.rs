@@ -0,0 +1,92 @@
+//! Chiffrement authentifié (ChaCha20-Poly1305) des fichiers sensibles : coffre
+//! des secrets et audio conservé. Format : `MAGIC || nonce || chiffré`.
+
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+
+/// En-tête des données chiffrées
+const MAGIC: &[u8] = b"WKSENC1\0";
+const NONCE_LEN: usize = 12;
+
+pub const KEY_LEN: usize = 32;
+
+pub fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0u8; N];
+    OsRng.fill_bytes(&mut bytes);
+    bytes
+}
+
+pub fn generate_key() -> [u8; KEY_LEN] {
+    random_bytes()
+}
+
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
+
+pub fn encrypt(key: &[u8; KEY_LEN], plaintext: &[u8]) -> Result<Vec<u8>, String> {
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(key));
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher.encrypt(&nonce, plaintext).map_err(|_| "Encryption failed".to_string())?;
+
+    let mut data = Vec::with_capacity(MAGIC.len() + NONCE_LEN + ciphertext.len());
+    data.extend_from_slice(MAGIC);
+    data.extend_from_slice(&nonce);
+    data.extend_from_slice(&ciphertext);
+    Ok(data)
+}
+
+pub fn decrypt(key: &[u8; KEY_LEN], data: &[u8]) -> Result<Vec<u8>, String> {
+    let body = data.strip_prefix(MAGIC).ok_or("Data is not encrypted")?;
+    if body.len() < NONCE_LEN {
+        return Err("Encrypted data is truncated".to_string());
+    }
+    let (nonce, ciphertext) = body.split_at(NONCE_LEN);
+    ChaCha20Poly1305::new(Key::from_slice(key))
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| "Decryption failed: wrong key or corrupted data".to_string())
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub fn key_from_hex(hex: &str) -> Option<[u8; KEY_LEN]> {
+    if hex.len() != KEY_LEN * 2 || !hex.is_ascii() {
+        return None;
+    }
+    let mut key = [0u8; KEY_LEN];
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_roundtrip() {
+        let key = generate_key();
+        let data = encrypt(&key, b"secret").unwrap();
+        assert!(is_encrypted(&data));
+        assert_eq!(decrypt(&key, &data).unwrap(), b"secret");
+
+        // Mauvaise clé ou données altérées
+        assert!(decrypt(&generate_key(), &data).is_err());
+        let mut tampered = data.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(decrypt(&key, &tampered).is_err());
+        assert!(decrypt(&key, b"plain").is_err());
+    }
+
+    #[test]
+    fn test_key_hex() {
+        let key = generate_key();
+        assert_eq!(key_from_hex(&to_hex(&key)), Some(key));
+        assert_eq!(key_from_hex("abcd"), None);
+        assert_eq!(key_from_hex(&"zz".repeat(KEY_LEN)), None);
+    }
+}
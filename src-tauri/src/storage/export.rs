@@ -0,0 +1,515 @@
+//! Export des transcriptions : sous-titres (SRT, WebVTT, TTML), JSON et texte brut
+
+use crate::types::{
+    ExportFormat, FileTranscriptionResult, Segment, SubtitleOptions, TranscriptionResult,
+    WordTiming,
+};
+use std::fs;
+use std::path::Path;
+
+/// Sous-titre prêt à être écrit
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cue {
+    pub start: f32,
+    pub end: f32,
+    pub lines: Vec<String>,
+    /// Intervenant, indiqué quand il change par rapport au sous-titre précédent
+    pub speaker: Option<String>,
+}
+
+/// Écrit la transcription dans `path` au format demandé
+pub fn export_to_file(
+    result: &FileTranscriptionResult,
+    format: ExportFormat,
+    options: &SubtitleOptions,
+    path: &Path,
+) -> Result<(), String> {
+    let content = render(result, format, options)?;
+
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    fs::write(path, content).map_err(|e| format!("Failed to write export: {}", e))
+}
+
+/// Génère le contenu du fichier d'export
+pub fn render(
+    result: &FileTranscriptionResult,
+    format: ExportFormat,
+    options: &SubtitleOptions,
+) -> Result<String, String> {
+    let transcription = || {
+        result
+            .transcription
+            .as_ref()
+            .ok_or_else(|| "No transcription to export".to_string())
+    };
+
+    match format {
+        ExportFormat::Json => serde_json::to_string_pretty(result).map_err(|e| e.to_string()),
+        ExportFormat::Txt => Ok(to_txt(transcription()?)),
+        ExportFormat::Srt => Ok(to_srt(&build_cues(transcription()?, options))),
+        ExportFormat::Vtt => Ok(to_vtt(&build_cues(transcription()?, options))),
+        ExportFormat::Ttml => {
+            let transcription = transcription()?;
+            Ok(to_ttml(
+                &build_cues(transcription, options),
+                transcription.detected_language.as_deref(),
+            ))
+        }
+    }
+}
+
+/// Découpe une transcription en sous-titres respectant les limites de
+/// longueur de ligne, de nombre de lignes et de durée
+pub fn build_cues(result: &TranscriptionResult, options: &SubtitleOptions) -> Vec<Cue> {
+    let fallback;
+    let segments: &[Segment] = if result.segments.is_empty() {
+        if result.text.trim().is_empty() {
+            return Vec::new();
+        }
+        // Pas d'horodatage : un seul segment couvrant tout l'audio
+        fallback = [Segment {
+            start: 0.0,
+            end: result.duration_seconds,
+            text: result.text.trim().to_string(),
+            words: Vec::new(),
+            confidence: result.confidence,
+            speaker: None,
+        }];
+        &fallback
+    } else {
+        &result.segments
+    };
+
+    let max_line_chars = options.max_line_chars.max(1);
+    let max_lines = options.max_lines.max(1);
+    let mut cues = Vec::new();
+    // L'intervenant n'est rappelé que lorsqu'il change
+    let mut previous_speaker: Option<String> = None;
+
+    for segment in segments {
+        let mut current: Vec<WordTiming> = Vec::new();
+
+        for word in segment_words(segment) {
+            if let Some(first) = current.first() {
+                let label = cue_label(segment, &previous_speaker);
+                let candidate = format!("{} {}", join_words(&current), word.text);
+                let too_long = wrap_cue_text(&candidate, label, max_line_chars).len() > max_lines;
+                let too_slow = word.end - first.start > options.max_cue_seconds;
+                if too_long || too_slow {
+                    cues.push(make_cue(&current, max_line_chars, options, label));
+                    current.clear();
+                    if segment.speaker.is_some() {
+                        previous_speaker = segment.speaker.clone();
+                    }
+                }
+            }
+            current.push(word);
+        }
+
+        if !current.is_empty() {
+            cues.push(make_cue(&current, max_line_chars, options, cue_label(segment, &previous_speaker)));
+            if segment.speaker.is_some() {
+                previous_speaker = segment.speaker.clone();
+            }
+        }
+    }
+
+    // Durée minimale, sans chevaucher le sous-titre suivant
+    for i in 0..cues.len() {
+        let min_end = cues[i].start + options.min_cue_seconds;
+        if cues[i].end < min_end {
+            cues[i].end = min_end;
+        }
+        if let Some(next_start) = cues.get(i + 1).map(|c| c.start) {
+            if cues[i].end > next_start {
+                cues[i].end = next_start.max(cues[i].start);
+            }
+        }
+    }
+
+    cues
+}
+
+/// Mots horodatés d'un segment. Le texte du segment (corrigé par le
+/// dictionnaire) est préféré quand il correspond mot pour mot ; à défaut
+/// d'horodatage par mot, la durée est répartie selon la longueur des mots.
+fn segment_words(segment: &Segment) -> Vec<WordTiming> {
+    let tokens: Vec<&str> = segment.text.split_whitespace().collect();
+
+    if !segment.words.is_empty() {
+        if segment.words.len() == tokens.len() {
+            return segment
+                .words
+                .iter()
+                .zip(tokens)
+                .map(|(word, token)| WordTiming {
+                    text: token.to_string(),
+                    ..word.clone()
+                })
+                .collect();
+        }
+        return segment.words.clone();
+    }
+
+    let total_chars: usize = tokens.iter().map(|t| t.chars().count() + 1).sum();
+    let duration = (segment.end - segment.start).max(0.0);
+    let mut cursor = segment.start;
+
+    tokens
+        .iter()
+        .map(|token| {
+            let share = duration * (token.chars().count() + 1) as f32 / total_chars as f32;
+            let word = WordTiming {
+                start: cursor,
+                end: cursor + share,
+                text: token.to_string(),
+                confidence: None,
+            };
+            cursor += share;
+            word
+        })
+        .collect()
+}
+
+fn join_words(words: &[WordTiming]) -> String {
+    words
+        .iter()
+        .map(|w| w.text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Intervenant à indiquer sur le sous-titre qui commence, s'il a changé
+fn cue_label<'a>(segment: &'a Segment, previous_speaker: &Option<String>) -> Option<&'a str> {
+    segment
+        .speaker
+        .as_deref()
+        .filter(|speaker| previous_speaker.as_deref() != Some(*speaker))
+}
+
+fn speaker_prefix(speaker: &str) -> String {
+    format!("{} : ", speaker)
+}
+
+/// Lignes d'un sous-titre ; la première garde la place du préfixe de
+/// l'intervenant ajouté par `labelled_lines`
+fn wrap_cue_text(text: &str, speaker: Option<&str>, max_chars: usize) -> Vec<String> {
+    let indent = speaker.map_or(0, |speaker| speaker_prefix(speaker).chars().count());
+    wrap_lines_after(text, max_chars, indent)
+}
+
+fn make_cue(
+    words: &[WordTiming],
+    max_line_chars: usize,
+    options: &SubtitleOptions,
+    speaker: Option<&str>,
+) -> Cue {
+    let start = words.first().map(|w| w.start).unwrap_or(0.0);
+    let end = words.last().map(|w| w.end).unwrap_or(start);
+
+    Cue {
+        start,
+        end: end.min(start + options.max_cue_seconds),
+        lines: wrap_cue_text(&join_words(words), speaker, max_line_chars),
+        speaker: speaker.map(str::to_string),
+    }
+}
+
+/// Texte brut ; avec diarisation, un paragraphe par prise de parole
+fn to_txt(result: &TranscriptionResult) -> String {
+    if result.segments.iter().all(|s| s.speaker.is_none()) {
+        return format!("{}\n", result.text.trim());
+    }
+
+    let mut paragraphs: Vec<(Option<&str>, Vec<&str>)> = Vec::new();
+    for segment in &result.segments {
+        let speaker = segment.speaker.as_deref();
+        match paragraphs.last_mut() {
+            Some((last, texts)) if *last == speaker => texts.push(segment.text.trim()),
+            _ => paragraphs.push((speaker, vec![segment.text.trim()])),
+        }
+    }
+
+    paragraphs
+        .into_iter()
+        .map(|(speaker, texts)| match speaker {
+            Some(speaker) => format!("{} : {}\n", speaker, texts.join(" ")),
+            None => format!("{}\n", texts.join(" ")),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Lignes d'un sous-titre, la première préfixée par l'intervenant
+fn labelled_lines(cue: &Cue) -> Vec<String> {
+    let mut lines = cue.lines.clone();
+    if let (Some(speaker), Some(first)) = (&cue.speaker, lines.first_mut()) {
+        *first = format!("{}{}", speaker_prefix(speaker), first);
+    }
+    lines
+}
+
+/// Répartit le texte en lignes d'au plus `max_chars` caractères
+/// (un mot plus long que la limite occupe sa propre ligne)
+pub fn wrap_lines(text: &str, max_chars: usize) -> Vec<String> {
+    wrap_lines_after(text, max_chars, 0)
+}
+
+/// Comme `wrap_lines`, la première ligne étant déjà occupée par `indent`
+/// caractères
+fn wrap_lines_after(text: &str, max_chars: usize, indent: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split_whitespace() {
+        let used = if lines.is_empty() { indent } else { 0 };
+        if !current.is_empty() && used + current.chars().count() + 1 + word.chars().count() > max_chars {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// Formate un horodatage `HH:MM:SS<sep>mmm`
+fn format_timestamp(seconds: f32, separator: char) -> String {
+    let total_ms = (seconds.max(0.0) as f64 * 1000.0).round() as u64;
+    let ms = total_ms % 1000;
+    let total_secs = total_ms / 1000;
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        total_secs / 3600,
+        (total_secs / 60) % 60,
+        total_secs % 60,
+        separator,
+        ms
+    )
+}
+
+pub fn to_srt(cues: &[Cue]) -> String {
+    let mut out = String::new();
+    for (index, cue) in cues.iter().enumerate() {
+        out.push_str(&format!(
+            "{}\n{} --> {}\n{}\n\n",
+            index + 1,
+            format_timestamp(cue.start, ','),
+            format_timestamp(cue.end, ','),
+            labelled_lines(cue).join("\n")
+        ));
+    }
+    out
+}
+
+pub fn to_vtt(cues: &[Cue]) -> String {
+    let mut out = String::from("WEBVTT\n\n");
+    for cue in cues {
+        // Texte échappé : il ne contient plus ni balise ni `-->`
+        let text = cue
+            .lines
+            .iter()
+            .map(|line| escape_vtt(line))
+            .collect::<Vec<_>>()
+            .join("\n");
+        // Balise de voix WebVTT pour l'intervenant
+        let text = match &cue.speaker {
+            Some(speaker) => format!("<v {}>{}", escape_vtt(speaker), text),
+            None => text,
+        };
+        out.push_str(&format!(
+            "{} --> {}\n{}\n\n",
+            format_timestamp(cue.start, '.'),
+            format_timestamp(cue.end, '.'),
+            text
+        ));
+    }
+    out
+}
+
+pub fn to_ttml(cues: &[Cue], language: Option<&str>) -> String {
+    let language = language.filter(|l| !l.is_empty() && *l != "auto").unwrap_or("und");
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str(&format!(
+        "<tt xmlns=\"http://www.w3.org/ns/ttml\" xml:lang=\"{}\">\n  <body>\n    <div>\n",
+        escape_xml(language)
+    ));
+    for cue in cues {
+        out.push_str(&format!(
+            "      <p begin=\"{}\" end=\"{}\">{}</p>\n",
+            format_timestamp(cue.start, '.'),
+            format_timestamp(cue.end, '.'),
+            labelled_lines(cue)
+                .iter()
+                .map(|line| escape_xml(line))
+                .collect::<Vec<_>>()
+                .join("<br/>")
+        ));
+    }
+    out.push_str("    </div>\n  </body>\n</tt>\n");
+    out
+}
+
+fn escape_vtt(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result_with_segments(segments: Vec<Segment>) -> TranscriptionResult {
+        TranscriptionResult {
+            text: segments
+                .iter()
+                .map(|s| s.text.as_str())
+                .collect::<Vec<_>>()
+                .join(" "),
+            confidence: 0.9,
+            duration_seconds: segments.last().map(|s| s.end).unwrap_or(0.0),
+            processing_time_ms: 0,
+            detected_language: Some("fr".to_string()),
+            timestamp: 0,
+            model_used: None,
+            segments,
+            translation: None,
+        }
+    }
+
+    fn segment(text: &str, start: f32, end: f32) -> Segment {
+        Segment {
+            start,
+            end,
+            text: text.to_string(),
+            words: Vec::new(),
+            confidence: 0.9,
+            speaker: None,
+        }
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0.0, ','), "00:00:00,000");
+        assert_eq!(format_timestamp(3725.0456, '.'), "01:02:05.046");
+    }
+
+    #[test]
+    fn test_wrap_lines() {
+        assert_eq!(
+            wrap_lines("le petit chat est mort ce matin", 12),
+            vec!["le petit", "chat est", "mort ce", "matin"]
+        );
+        assert_eq!(wrap_lines("anticonstitutionnellement oui", 10).len(), 2);
+    }
+
+    #[test]
+    fn test_build_cues_enforces_limits() {
+        let options = SubtitleOptions {
+            max_line_chars: 20,
+            max_lines: 2,
+            min_cue_seconds: 1.0,
+            max_cue_seconds: 4.0,
+        };
+        let long = "Ceci est une phrase assez longue pour être découpée en plusieurs sous-titres distincts";
+        let result = result_with_segments(vec![segment(long, 0.0, 12.0), segment("Fin.", 12.0, 12.2)]);
+
+        let cues = build_cues(&result, &options);
+        assert!(cues.len() > 3);
+        for cue in &cues {
+            assert!(cue.lines.len() <= 2);
+            assert!(cue.lines.iter().all(|l| l.chars().count() <= 20));
+            assert!(cue.end - cue.start <= 4.0 + 1e-3);
+        }
+        for pair in cues.windows(2) {
+            assert!(pair[0].end <= pair[1].start + 1e-3);
+        }
+        // La durée minimale est appliquée au dernier sous-titre
+        assert!((cues.last().unwrap().end - 13.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_render_srt_and_vtt() {
+        let file = FileTranscriptionResult {
+            file_path: "/tmp/a.wav".to_string(),
+            file_name: "a.wav".to_string(),
+            transcription: Some(result_with_segments(vec![segment("Bonjour à tous.", 0.5, 2.0)])),
+            error: None,
+            history_id: None,
+        };
+        let options = SubtitleOptions::default();
+
+        let srt = render(&file, ExportFormat::Srt, &options).unwrap();
+        assert_eq!(srt, "1\n00:00:00,500 --> 00:00:02,000\nBonjour à tous.\n\n");
+
+        let vtt = render(&file, ExportFormat::Vtt, &options).unwrap();
+        assert!(vtt.starts_with("WEBVTT\n\n00:00:00.500 --> 00:00:02.000\n"));
+
+        let ttml = render(&file, ExportFormat::Ttml, &options).unwrap();
+        assert!(ttml.contains("xml:lang=\"fr\""));
+        assert!(ttml.contains("<p begin=\"00:00:00.500\" end=\"00:00:02.000\">Bonjour à tous.</p>"));
+
+        let empty = FileTranscriptionResult { transcription: None, ..file };
+        assert!(render(&empty, ExportFormat::Srt, &options).is_err());
+    }
+
+    #[test]
+    fn test_speaker_labels() {
+        let mut segments = vec![
+            segment("Bonjour.", 0.0, 1.0),
+            segment("Ça va ?", 1.0, 2.0),
+            segment("Oui.", 2.0, 3.0),
+        ];
+        segments[0].speaker = Some("Alice".to_string());
+        segments[1].speaker = Some("Alice".to_string());
+        segments[2].speaker = Some("Bob".to_string());
+        let file = FileTranscriptionResult {
+            file_path: "/tmp/a.wav".to_string(),
+            file_name: "a.wav".to_string(),
+            transcription: Some(result_with_segments(segments)),
+            error: None,
+            history_id: None,
+        };
+        let options = SubtitleOptions::default();
+
+        let srt = render(&file, ExportFormat::Srt, &options).unwrap();
+        assert!(srt.contains("Alice : Bonjour.\n"));
+        assert!(srt.contains("\nÇa va ?\n"));
+        assert!(srt.contains("Bob : Oui.\n"));
+
+        let vtt = render(&file, ExportFormat::Vtt, &options).unwrap();
+        assert!(vtt.contains("<v Bob>Oui."));
+
+        let txt = render(&file, ExportFormat::Txt, &options).unwrap();
+        assert_eq!(txt, "Alice : Bonjour. Ça va ?\n\nBob : Oui.\n");
+
+        // Préfixe compris dans la largeur ; WebVTT échappé
+        let narrow = SubtitleOptions { max_line_chars: 16, max_lines: 2, ..SubtitleOptions::default() };
+        let mut segments = vec![segment("Tom & Léa <3 ont dit --> bonjour à tous", 0.0, 4.0)];
+        segments[0].speaker = Some("Alice <A>".to_string());
+        let escaped = FileTranscriptionResult { transcription: Some(result_with_segments(segments)), ..file };
+        let cues = build_cues(escaped.transcription.as_ref().unwrap(), &narrow);
+        assert_eq!(cues[0].speaker.as_deref(), Some("Alice <A>"));
+        for cue in &cues {
+            assert!(labelled_lines(cue).iter().all(|l| l.chars().count() <= 16), "{:?}", labelled_lines(cue));
+        }
+        let vtt = render(&escaped, ExportFormat::Vtt, &narrow).unwrap();
+        assert!(vtt.contains("<v Alice &lt;A&gt;>"));
+        assert!(vtt.contains("&amp;") && !vtt.contains(" & "));
+        assert!(!vtt.contains("<3") && vtt.matches("-->").count() == vtt.matches(" --> ").count());
+    }
+}
@@ -1,37 +1,615 @@
-use crate::types::{HistoryData, TranscriptionResult};
+//! Historique des transcriptions (history.db, SQLite). Le texte est indexé
+//! en FTS5 ; l'ancien history.json est importé à la première ouverture.
+//! L'audio conservé (voir `recordings`) est référencé par `audio_path`. Avec
+//! `encrypt_storage`, la base est chiffrée par SQLCipher.
+
+use super::crypto::{self, KEY_LEN};
+use crate::types::{HistoryData, HistoryEntry, HistoryFacets, HistoryPage, HistoryQuery, TranscriptionResult};
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Connection, DatabaseName, OptionalExtension};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Taille maximale d'une page
+const MAX_PAGE_SIZE: u32 = 200;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS transcriptions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    timestamp INTEGER NOT NULL,
+    text TEXT NOT NULL,
+    language TEXT,
+    model TEXT,
+    app TEXT,
+    favorite INTEGER NOT NULL DEFAULT 0,
+    data TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS transcriptions_timestamp ON transcriptions(timestamp);
+
+CREATE TABLE IF NOT EXISTS tags (
+    transcription_id INTEGER NOT NULL REFERENCES transcriptions(id) ON DELETE CASCADE,
+    tag TEXT NOT NULL,
+    PRIMARY KEY (transcription_id, tag)
+);
 
-const MAX_HISTORY: usize = 50;
+CREATE VIRTUAL TABLE IF NOT EXISTS transcriptions_fts USING fts5(
+    text,
+    content = 'transcriptions',
+    content_rowid = 'id',
+    tokenize = 'unicode61 remove_diacritics 2'
+);
 
-fn history_path() -> PathBuf {
+CREATE TRIGGER IF NOT EXISTS transcriptions_ai AFTER INSERT ON transcriptions BEGIN
+    INSERT INTO transcriptions_fts(rowid, text) VALUES (new.id, new.text);
+END;
+CREATE TRIGGER IF NOT EXISTS transcriptions_ad AFTER DELETE ON transcriptions BEGIN
+    INSERT INTO transcriptions_fts(transcriptions_fts, rowid, text) VALUES ('delete', old.id, old.text);
+END;
+CREATE TRIGGER IF NOT EXISTS transcriptions_au AFTER UPDATE OF text ON transcriptions BEGIN
+    INSERT INTO transcriptions_fts(transcriptions_fts, rowid, text) VALUES ('delete', old.id, old.text);
+    INSERT INTO transcriptions_fts(rowid, text) VALUES (new.id, new.text);
+END;
+";
+
+/// Évolutions du schéma, appliquées dans l'ordre selon `PRAGMA user_version`
+const MIGRATIONS: &[&str] = &["ALTER TABLE transcriptions ADD COLUMN audio_path TEXT;"];
+
+/// Connexion ouverte à la première utilisation
+static DB: Mutex<Option<Connection>> = Mutex::new(None);
+
+fn db_path() -> PathBuf {
+    super::get_app_data_dir().join("history.db")
+}
+
+fn legacy_path() -> PathBuf {
     super::get_app_data_dir().join("history.json")
 }
 
-pub fn load_history() -> HistoryData {
-    let path = history_path();
-    if path.exists() {
-        let content = fs::read_to_string(&path).unwrap_or_default();
-        serde_json::from_str(&content).unwrap_or_default()
+/// Clé brute au format SQLCipher, vide pour une base en clair
+fn sqlcipher_key(key: Option<&[u8; KEY_LEN]>) -> String {
+    key.map(|key| format!("x'{}'", crypto::to_hex(key))).unwrap_or_default()
+}
+
+fn open(path: &Path, key: Option<&[u8; KEY_LEN]>) -> rusqlite::Result<Connection> {
+    let conn = Connection::open(path)?;
+    if key.is_some() {
+        conn.pragma_update(None, "key", sqlcipher_key(key))?;
+    }
+    init(&conn)?;
+    Ok(conn)
+}
+
+fn init(conn: &Connection) -> rusqlite::Result<()> {
+    conn.pragma_update(None, "foreign_keys", true)?;
+    conn.execute_batch(SCHEMA)?;
+
+    let version: usize = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+        conn.execute_batch(migration)?;
+        conn.pragma_update(None, "user_version", (index + 1) as i64)?;
+    }
+    Ok(())
+}
+
+fn with_db<T>(f: impl FnOnce(&mut Connection) -> rusqlite::Result<T>) -> Result<T, String> {
+    let mut guard = DB.lock().map_err(|e| e.to_string())?;
+    if guard.is_none() {
+        super::ensure_app_data_dir().map_err(|e| e.to_string())?;
+        let key = match super::config::load_settings().encrypt_storage {
+            true => Some(super::secrets::storage_key()?),
+            false => None,
+        };
+        let mut conn =
+            open(&db_path(), key.as_ref()).map_err(|e| format!("Failed to open history database: {}", e))?;
+        migrate_json(&mut conn, &legacy_path());
+        *guard = Some(conn);
+    }
+    let conn = guard.as_mut().ok_or("History database unavailable")?;
+    f(conn).map_err(|e| e.to_string())
+}
+
+/// Copie la base dans `target` avec une autre clé (`sqlcipher_export`)
+fn export_to(conn: &Connection, target: &Path, key: Option<&[u8; KEY_LEN]>) -> rusqlite::Result<()> {
+    conn.execute(
+        "ATTACH DATABASE ?1 AS target KEY ?2",
+        params![target.to_string_lossy(), sqlcipher_key(key)],
+    )?;
+    conn.query_row("SELECT sqlcipher_export('target')", [], |_| Ok(()))?;
+    let version: i64 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    conn.pragma_update(Some(DatabaseName::Attached("target")), "user_version", version)?;
+    conn.execute_batch("DETACH DATABASE target;")
+}
+
+/// Chiffre la base avec `key` ou la remet en clair
+pub fn set_encryption(key: &[u8; KEY_LEN], encrypt: bool) -> Result<(), String> {
+    let mut guard = DB.lock().map_err(|e| e.to_string())?;
+    let path = db_path();
+    let (from, to) = if encrypt { (None, Some(key)) } else { (Some(key), None) };
+    let conn = match guard.take() {
+        Some(conn) => conn,
+        None if path.exists() => open(&path, from).map_err(|e| format!("Failed to open history database: {}", e))?,
+        // Base créée directement avec la bonne clé à la première ouverture
+        None => return Ok(()),
+    };
+
+    let target = path.with_extension("db.tmp");
+    let _ = fs::remove_file(&target);
+    let exported = export_to(&conn, &target, to);
+    drop(conn);
+    if let Err(e) = exported {
+        let _ = fs::remove_file(&target);
+        return Err(format!("Failed to re-encrypt history database: {}", e));
+    }
+    fs::rename(&target, &path).map_err(|e| format!("Failed to replace {}: {}", path.display(), e))?;
+    *guard = Some(open(&path, to).map_err(|e| format!("Failed to open history database: {}", e))?);
+    Ok(())
+}
+
+/// Importe history.json (du plus ancien au plus récent) puis le renomme en
+/// history.json.bak pour ne pas l'importer deux fois
+fn migrate_json(conn: &mut Connection, path: &Path) {
+    if !path.exists() {
+        return;
+    }
+    let content = fs::read_to_string(path).unwrap_or_default();
+    let data: HistoryData = match serde_json::from_str(&content) {
+        Ok(data) => data,
+        Err(e) => {
+            log::warn!("Invalid {}, not imported: {}", path.display(), e);
+            return;
+        }
+    };
+
+    let imported = conn.transaction().and_then(|tx| {
+        for result in data.transcriptions.iter().rev() {
+            insert(&tx, result, None)?;
+        }
+        tx.commit()
+    });
+    match imported {
+        Ok(()) => {
+            log::info!("Imported {} transcriptions from {}", data.transcriptions.len(), path.display());
+            if let Err(e) = fs::rename(path, path.with_extension("json.bak")) {
+                log::warn!("Failed to rename {}: {}", path.display(), e);
+            }
+        }
+        Err(e) => log::error!("Failed to import {}: {}", path.display(), e),
+    }
+}
+
+fn insert(conn: &Connection, result: &TranscriptionResult, app: Option<&str>) -> rusqlite::Result<i64> {
+    let data = serde_json::to_string(result).map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+    conn.execute(
+        "INSERT INTO transcriptions (timestamp, text, language, model, app, data) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![result.timestamp, result.text, result.detected_language, result.model_used, app, data],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// Requête FTS5 : chaque mot est un préfixe, tous doivent apparaître
+fn fts_query(text: &str) -> Option<String> {
+    let terms: Vec<String> = text
+        .split_whitespace()
+        .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+/// Clause WHERE et paramètres correspondant aux filtres
+fn filters(query: &HistoryQuery) -> (String, Vec<Value>) {
+    let mut clauses = Vec::new();
+    let mut values = Vec::new();
+
+    if let Some(fts) = query.text.as_deref().and_then(fts_query) {
+        clauses.push("t.id IN (SELECT rowid FROM transcriptions_fts WHERE transcriptions_fts MATCH ?)");
+        values.push(Value::Text(fts));
+    }
+    if let Some(from) = query.from {
+        clauses.push("t.timestamp >= ?");
+        values.push(Value::Integer(from));
+    }
+    if let Some(to) = query.to {
+        clauses.push("t.timestamp <= ?");
+        values.push(Value::Integer(to));
+    }
+    if let Some(ref model) = query.model {
+        clauses.push("t.model = ?");
+        values.push(Value::Text(model.clone()));
+    }
+    if let Some(ref language) = query.language {
+        clauses.push("t.language = ?");
+        values.push(Value::Text(language.clone()));
+    }
+    if let Some(ref app) = query.app {
+        clauses.push("instr(lower(t.app), lower(?)) > 0");
+        values.push(Value::Text(app.clone()));
+    }
+    if let Some(ref tag) = query.tag {
+        clauses.push("EXISTS (SELECT 1 FROM tags WHERE transcription_id = t.id AND tag = ?)");
+        values.push(Value::Text(tag.clone()));
+    }
+    if query.favorites_only {
+        clauses.push("t.favorite = 1");
+    }
+
+    let clause = if clauses.is_empty() {
+        String::new()
     } else {
-        HistoryData::default()
+        format!("WHERE {}", clauses.join(" AND "))
+    };
+    (clause, values)
+}
+
+fn tags_of(conn: &Connection, id: i64) -> rusqlite::Result<Vec<String>> {
+    let mut stmt = conn.prepare_cached("SELECT tag FROM tags WHERE transcription_id = ?1 ORDER BY tag")?;
+    let tags = stmt.query_map([id], |row| row.get(0))?.collect();
+    tags
+}
+
+/// Entrées correspondant à `tail` (clause WHERE, tri, limite)
+fn select_entries(conn: &Connection, tail: &str, values: &[Value]) -> rusqlite::Result<Vec<HistoryEntry>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT t.id, t.text, t.app, t.favorite, t.audio_path IS NOT NULL, t.data FROM transcriptions t {}",
+        tail
+    ))?;
+    let rows = stmt
+        .query_map(params_from_iter(values.iter()), |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, bool>(3)?,
+                row.get::<_, bool>(4)?,
+                row.get::<_, String>(5)?,
+            ))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let mut entries = Vec::with_capacity(rows.len());
+    for (id, text, app, favorite, has_audio, data) in rows {
+        let mut transcription: TranscriptionResult = match serde_json::from_str(&data) {
+            Ok(transcription) => transcription,
+            Err(e) => {
+                log::warn!("Skipping unreadable history entry {}: {}", id, e);
+                continue;
+            }
+        };
+        transcription.text = text;
+        entries.push(HistoryEntry {
+            id,
+            transcription,
+            app,
+            tags: tags_of(conn, id)?,
+            favorite,
+            has_audio,
+        });
     }
+    Ok(entries)
+}
+
+fn search_in(conn: &Connection, query: &HistoryQuery) -> rusqlite::Result<HistoryPage> {
+    let (clause, mut values) = filters(query);
+    let total: i64 = conn.query_row(
+        &format!("SELECT COUNT(*) FROM transcriptions t {}", clause),
+        params_from_iter(values.iter()),
+        |row| row.get(0),
+    )?;
+
+    values.push(Value::Integer(query.limit.clamp(1, MAX_PAGE_SIZE).into()));
+    values.push(Value::Integer(query.offset.into()));
+    let entries = select_entries(
+        conn,
+        &format!("{} ORDER BY t.timestamp DESC, t.id DESC LIMIT ? OFFSET ?", clause),
+        &values,
+    )?;
+    Ok(HistoryPage { entries, total: total as u64 })
+}
+
+fn entry_in(conn: &Connection, id: i64) -> rusqlite::Result<Option<HistoryEntry>> {
+    Ok(select_entries(conn, "WHERE t.id = ?", &[Value::Integer(id)])?.into_iter().next())
 }
 
-pub fn save_history(data: &HistoryData) -> Result<(), String> {
-    super::ensure_app_data_dir().map_err(|e| e.to_string())?;
-    let path = history_path();
-    let content = serde_json::to_string_pretty(data).map_err(|e| e.to_string())?;
-    fs::write(&path, content).map_err(|e| e.to_string())
+fn facets_in(conn: &Connection) -> rusqlite::Result<HistoryFacets> {
+    let distinct = |sql: &str| -> rusqlite::Result<Vec<String>> {
+        let mut stmt = conn.prepare(sql)?;
+        let values = stmt.query_map([], |row| row.get(0))?.collect();
+        values
+    };
+    Ok(HistoryFacets {
+        models: distinct("SELECT DISTINCT model FROM transcriptions WHERE model IS NOT NULL ORDER BY model")?,
+        languages: distinct("SELECT DISTINCT language FROM transcriptions WHERE language IS NOT NULL ORDER BY language")?,
+        apps: distinct("SELECT DISTINCT app FROM transcriptions WHERE app IS NOT NULL ORDER BY app")?,
+        tags: distinct("SELECT DISTINCT tag FROM tags ORDER BY tag")?,
+    })
+}
+
+fn set_tags_in(conn: &mut Connection, id: i64, tags: &[String]) -> rusqlite::Result<()> {
+    let tx = conn.transaction()?;
+    tx.execute("DELETE FROM tags WHERE transcription_id = ?1", [id])?;
+    for tag in tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+        tx.execute("INSERT OR IGNORE INTO tags (transcription_id, tag) VALUES (?1, ?2)", params![id, tag])?;
+    }
+    tx.commit()
 }
 
-pub fn add_transcription(result: TranscriptionResult) -> Result<(), String> {
-    let mut data = load_history();
-    data.transcriptions.insert(0, result);
-    data.transcriptions.truncate(MAX_HISTORY);
-    save_history(&data)
+/// Enregistre une transcription ; `app` est l'application active pendant la dictée
+pub fn add_transcription(result: TranscriptionResult, app: Option<&str>) -> Result<i64, String> {
+    with_db(|conn| insert(conn, &result, app))
 }
 
-pub fn clear_history() -> Result<(), String> {
-    save_history(&HistoryData::default())
+pub fn search(query: &HistoryQuery) -> Result<HistoryPage, String> {
+    with_db(|conn| search_in(conn, query))
+}
+
+pub fn get_page(offset: u32, limit: u32) -> Result<HistoryPage, String> {
+    search(&HistoryQuery {
+        offset,
+        limit,
+        ..Default::default()
+    })
+}
+
+/// Transcription la plus récente
+pub fn latest() -> Option<TranscriptionResult> {
+    get_page(0, 1)
+        .ok()
+        .and_then(|page| page.entries.into_iter().next())
+        .map(|entry| entry.transcription)
+}
+
+pub fn facets() -> Result<HistoryFacets, String> {
+    with_db(|conn| facets_in(conn))
+}
+
+fn ensure_exists(conn: &Connection, id: i64) -> rusqlite::Result<bool> {
+    conn.query_row("SELECT 1 FROM transcriptions WHERE id = ?1", [id], |_| Ok(()))
+        .optional()
+        .map(|row| row.is_some())
+}
+
+pub fn get_entry(id: i64) -> Result<HistoryEntry, String> {
+    with_db(|conn| entry_in(conn, id))?.ok_or_else(|| format!("History entry not found: {}", id))
+}
+
+/// Supprime l'entrée et son audio éventuel
+pub fn delete_entry(id: i64) -> Result<(), String> {
+    let audio = audio_path(id)?;
+    let deleted = with_db(|conn| conn.execute("DELETE FROM transcriptions WHERE id = ?1", [id]))?;
+    if deleted == 0 {
+        return Err(format!("History entry not found: {}", id));
+    }
+    if let Some(path) = audio {
+        if let Err(e) = fs::remove_file(&path) {
+            log::warn!("Failed to remove {}: {}", path.display(), e);
+        }
+    }
+    Ok(())
+}
+
+/// Remplace le résultat d'une entrée (re-transcription) ; étiquettes,
+/// favori, application et audio sont conservés
+pub fn update_transcription(id: i64, result: &TranscriptionResult) -> Result<HistoryEntry, String> {
+    let data = serde_json::to_string(result).map_err(|e| e.to_string())?;
+    let updated = with_db(|conn| {
+        conn.execute(
+            "UPDATE transcriptions SET text = ?1, language = ?2, model = ?3, data = ?4 WHERE id = ?5",
+            params![result.text, result.detected_language, result.model_used, data, id],
+        )
+    })?;
+    if updated == 0 {
+        return Err(format!("History entry not found: {}", id));
+    }
+    get_entry(id)
+}
+
+pub fn audio_path(id: i64) -> Result<Option<PathBuf>, String> {
+    let path: Option<Option<String>> = with_db(|conn| {
+        conn.query_row("SELECT audio_path FROM transcriptions WHERE id = ?1", [id], |row| row.get(0))
+            .optional()
+    })?;
+    match path {
+        Some(path) => Ok(path.map(PathBuf::from)),
+        None => Err(format!("History entry not found: {}", id)),
+    }
+}
+
+pub fn attach_audio(id: i64, path: &Path) -> Result<(), String> {
+    with_db(|conn| {
+        conn.execute(
+            "UPDATE transcriptions SET audio_path = ?1 WHERE id = ?2",
+            params![path.to_string_lossy(), id],
+        )
+    })
+    .map(|_| ())
+}
+
+/// Oublie les fichiers audio supprimés par la politique de conservation
+pub fn detach_audio(paths: &[PathBuf]) -> Result<(), String> {
+    with_db(|conn| {
+        let tx = conn.transaction()?;
+        for path in paths {
+            tx.execute(
+                "UPDATE transcriptions SET audio_path = NULL WHERE audio_path = ?1",
+                [path.to_string_lossy()],
+            )?;
+        }
+        tx.commit()
+    })
+}
+
+pub fn set_favorite(id: i64, favorite: bool) -> Result<(), String> {
+    let updated = with_db(|conn| conn.execute("UPDATE transcriptions SET favorite = ?1 WHERE id = ?2", params![favorite, id]))?;
+    if updated == 0 {
+        return Err(format!("History entry not found: {}", id));
+    }
+    Ok(())
+}
+
+pub fn set_tags(id: i64, tags: &[String]) -> Result<(), String> {
+    let found = with_db(|conn| {
+        if !ensure_exists(conn, id)? {
+            return Ok(false);
+        }
+        set_tags_in(conn, id, tags).map(|_| true)
+    })?;
+    if !found {
+        return Err(format!("History entry not found: {}", id));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(text: &str, timestamp: i64, language: &str) -> TranscriptionResult {
+        TranscriptionResult {
+            text: text.to_string(),
+            confidence: 0.9,
+            duration_seconds: 2.0,
+            processing_time_ms: 100,
+            detected_language: Some(language.to_string()),
+            timestamp,
+            model_used: Some("whisper-small".to_string()),
+            segments: Vec::new(),
+            translation: None,
+        }
+    }
+
+    fn memory_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        init(&conn).unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_search_filters_and_paging() {
+        let mut conn = memory_db();
+        let first = insert(&conn, &result("Réunion budget à préparer", 100, "fr"), Some("thunderbird")).unwrap();
+        insert(&conn, &result("Meeting notes for the budget", 200, "en"), Some("obsidian")).unwrap();
+        insert(&conn, &result("Appeler le garage", 300, "fr"), None).unwrap();
+
+        let text = |text: &str| HistoryQuery {
+            text: Some(text.to_string()),
+            ..Default::default()
+        };
+        // Préfixes et accents ignorés
+        assert_eq!(search_in(&conn, &text("reunion")).unwrap().total, 1);
+        assert_eq!(search_in(&conn, &text("budg")).unwrap().total, 2);
+        assert_eq!(search_in(&conn, &text("budget \"notes")).unwrap().total, 1);
+
+        let french = HistoryQuery {
+            language: Some("fr".to_string()),
+            ..Default::default()
+        };
+        let page = search_in(&conn, &french).unwrap();
+        assert_eq!(page.total, 2);
+        assert_eq!(page.entries[0].transcription.text, "Appeler le garage");
+
+        let window = HistoryQuery {
+            from: Some(150),
+            to: Some(300),
+            app: Some("OBSID".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(search_in(&conn, &window).unwrap().entries[0].transcription.timestamp, 200);
+
+        let paged = search_in(&conn, &HistoryQuery { offset: 1, limit: 1, ..Default::default() }).unwrap();
+        assert_eq!((paged.total, paged.entries.len()), (3, 1));
+        assert_eq!(paged.entries[0].transcription.timestamp, 200);
+
+        set_tags_in(&mut conn, first, &["travail".to_string(), " ".to_string()]).unwrap();
+        conn.execute("UPDATE transcriptions SET favorite = 1 WHERE id = ?1", [first]).unwrap();
+        let tagged = HistoryQuery {
+            tag: Some("travail".to_string()),
+            favorites_only: true,
+            ..Default::default()
+        };
+        let page = search_in(&conn, &tagged).unwrap();
+        assert_eq!(page.entries[0].tags, vec!["travail".to_string()]);
+        assert!(page.entries[0].favorite);
+        assert_eq!(facets_in(&conn).unwrap().apps, vec!["obsidian".to_string(), "thunderbird".to_string()]);
+
+        // La suppression retire aussi l'entrée de l'index et ses étiquettes
+        conn.execute("DELETE FROM transcriptions WHERE id = ?1", [first]).unwrap();
+        assert_eq!(search_in(&conn, &text("reunion")).unwrap().total, 0);
+        assert!(facets_in(&conn).unwrap().tags.is_empty());
+    }
+
+    #[test]
+    fn test_schema_migrations() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(SCHEMA).unwrap();
+        insert_v0(&conn);
+        init(&conn).unwrap();
+        init(&conn).unwrap();
+
+        let version: usize = conn.pragma_query_value(None, "user_version", |row| row.get(0)).unwrap();
+        assert_eq!(version, MIGRATIONS.len());
+        let entry = entry_in(&conn, 1).unwrap().unwrap();
+        assert!(!entry.has_audio);
+        conn.execute("UPDATE transcriptions SET audio_path = '/tmp/take.flac' WHERE id = 1", []).unwrap();
+        assert!(entry_in(&conn, 1).unwrap().unwrap().has_audio);
+    }
+
+    /// Entrée écrite avant l'ajout de `audio_path`
+    fn insert_v0(conn: &Connection) {
+        let data = serde_json::to_string(&result("avant", 1, "fr")).unwrap();
+        conn.execute(
+            "INSERT INTO transcriptions (timestamp, text, data) VALUES (1, 'avant', ?1)",
+            [data],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_export_encrypted() {
+        let dir = std::env::temp_dir().join(format!("history-encryption-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let key = crypto::generate_key();
+        let encrypted = dir.join("encrypted.db");
+
+        let conn = open(&dir.join("plain.db"), None).unwrap();
+        insert(&conn, &result("Note confidentielle", 100, "fr"), None).unwrap();
+        export_to(&conn, &encrypted, Some(&key)).unwrap();
+        drop(conn);
+
+        assert!(open(&encrypted, None).is_err());
+        assert!(open(&encrypted, Some(&crypto::generate_key())).is_err());
+        let conn = open(&encrypted, Some(&key)).unwrap();
+        let query = HistoryQuery {
+            text: Some("confidentielle".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(search_in(&conn, &query).unwrap().total, 1);
+        let version: usize = conn.pragma_query_value(None, "user_version", |row| row.get(0)).unwrap();
+        assert_eq!(version, MIGRATIONS.len());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_migrate_json() {
+        let dir = std::env::temp_dir().join(format!("history-migration-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("history.json");
+        let legacy = HistoryData {
+            transcriptions: vec![result("plus récent", 200, "fr"), result("plus ancien", 100, "fr")],
+        };
+        fs::write(&path, serde_json::to_string(&legacy).unwrap()).unwrap();
+
+        let mut conn = memory_db();
+        migrate_json(&mut conn, &path);
+        let page = search_in(&conn, &HistoryQuery::default()).unwrap();
+        assert_eq!(page.total, 2);
+        assert_eq!(page.entries[0].transcription.text, "plus récent");
+        assert!(!path.exists());
+        assert!(dir.join("history.json.bak").exists());
+
+        // Déjà importé : rien ne se passe
+        migrate_json(&mut conn, &path);
+        assert_eq!(search_in(&conn, &HistoryQuery::default()).unwrap().total, 2);
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -0,0 +1,324 @@
+//! Fichier JSON partagé entre les threads de l'application et d'autres
+//! processus (CLI, seconde instance) : verrou en mémoire et verrou de fichier,
+//! écriture dans un fichier temporaire synchronisé puis renommé, lecture en
+//! cache tant que le fichier n'a pas changé sur disque.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::SystemTime;
+
+/// Décodage du contenu : valeur lue et besoin de réécrire le fichier
+/// (migration, champ corrigé)
+pub type Decode<T> = fn(&str) -> Result<(T, bool), String>;
+
+type Listener<T> = Box<dyn Fn(&T) + Send + Sync>;
+
+/// Décodage JSON simple, sans réécriture
+pub fn decode_json<T: DeserializeOwned>(content: &str) -> Result<(T, bool), String> {
+    serde_json::from_str(content)
+        .map(|value| (value, false))
+        .map_err(|e| e.to_string())
+}
+
+/// Remplace le fichier sans jamais laisser de version tronquée : écriture dans
+/// un fichier temporaire synchronisé, puis renommage
+pub fn write_atomic(path: &Path, content: &[u8]) -> Result<(), String> {
+    replace(path, content, false)
+}
+
+/// Comme `write_atomic`, pour un fichier lisible par son seul propriétaire dès
+/// sa création (secrets)
+pub fn write_private(path: &Path, content: &[u8]) -> Result<(), String> {
+    replace(path, content, true)
+}
+
+fn replace(path: &Path, content: &[u8], private: bool) -> Result<(), String> {
+    let file_name = path.file_name().ok_or("Invalid file path")?.to_string_lossy();
+    let tmp = path.with_file_name(format!("{}.tmp", file_name));
+    // Les droits ne s'appliquent qu'à la création : pas de reste d'une écriture
+    // interrompue
+    let _ = fs::remove_file(&tmp);
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    if private {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    #[cfg(not(unix))]
+    let _ = private;
+    let mut file = options.open(&tmp).map_err(|e| format!("Failed to create {}: {}", tmp.display(), e))?;
+    file.write_all(content)
+        .and_then(|_| file.sync_all())
+        .map_err(|e| format!("Failed to write {}: {}", tmp.display(), e))?;
+    drop(file);
+    fs::rename(&tmp, path).map_err(|e| format!("Failed to replace {}: {}", path.display(), e))?;
+
+    // Le renommage lui-même doit survivre à une coupure
+    #[cfg(unix)]
+    if let Some(dir) = path.parent() {
+        let _ = File::open(dir).and_then(|dir| dir.sync_all());
+    }
+    Ok(())
+}
+
+/// Verrou exclusif entre processus sur `lock_path`, rendu à la fermeture du
+/// fichier renvoyé
+pub fn lock_file(lock_path: &Path) -> Result<File, String> {
+    if let Some(dir) = lock_path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(lock_path)
+        .map_err(|e| format!("Failed to open {}: {}", lock_path.display(), e))?;
+    file.lock()
+        .map_err(|e| format!("Failed to lock {}: {}", lock_path.display(), e))?;
+    Ok(file)
+}
+
+/// Identité du fichier sur disque ; chaque écriture (renommage) la change
+#[derive(Debug, Clone, PartialEq)]
+struct Stamp {
+    modified: Option<SystemTime>,
+    len: u64,
+    #[cfg(unix)]
+    inode: u64,
+}
+
+impl Stamp {
+    fn of(path: &Path) -> Option<Stamp> {
+        let metadata = fs::metadata(path).ok()?;
+        Some(Stamp {
+            modified: metadata.modified().ok(),
+            len: metadata.len(),
+            #[cfg(unix)]
+            inode: std::os::unix::fs::MetadataExt::ino(&metadata),
+        })
+    }
+}
+
+struct Cached<T> {
+    value: T,
+    stamp: Option<Stamp>,
+}
+
+pub struct JsonStore<T> {
+    file_name: &'static str,
+    /// Dossier de données de l'application si `None`
+    dir: Option<PathBuf>,
+    /// Valeur d'un fichier absent ou illisible
+    init: fn() -> T,
+    decode: Decode<T>,
+    cache: Mutex<Option<Cached<T>>>,
+    listeners: Mutex<Vec<Listener<T>>>,
+}
+
+impl<T: Clone + Serialize> JsonStore<T> {
+    pub const fn new(file_name: &'static str, init: fn() -> T, decode: Decode<T>) -> Self {
+        Self {
+            file_name,
+            dir: None,
+            init,
+            decode,
+            cache: Mutex::new(None),
+            listeners: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn path(&self) -> PathBuf {
+        self.dir.clone().unwrap_or_else(super::get_app_data_dir).join(self.file_name)
+    }
+
+    fn cache(&self) -> MutexGuard<'_, Option<Cached<T>>> {
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Verrou exclusif entre processus, sur un fichier voisin (le fichier de
+    /// données est remplacé à chaque écriture)
+    fn lock_file(&self, path: &Path) -> Result<File, String> {
+        lock_file(&path.with_file_name(format!("{}.lock", self.file_name)))
+    }
+
+    fn read(&self, path: &Path) -> (T, bool) {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return ((self.init)(), false),
+            Err(e) => {
+                log::error!("Failed to read {}: {}", path.display(), e);
+                return ((self.init)(), false);
+            }
+        };
+        (self.decode)(&content).unwrap_or_else(|e| {
+            log::warn!("Invalid {}, using defaults: {}", path.display(), e);
+            ((self.init)(), false)
+        })
+    }
+
+    fn write(&self, path: &Path, value: &T) -> Result<(), String> {
+        let content = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+        write_atomic(path, content.as_bytes())
+    }
+
+    fn reload(&self, cache: &mut Option<Cached<T>>, path: &Path) -> Result<(), String> {
+        let _lock = self.lock_file(path)?;
+        let (value, rewrite) = self.read(path);
+        if rewrite {
+            if let Err(e) = self.write(path, &value) {
+                log::error!("Failed to rewrite {}: {}", path.display(), e);
+            }
+        }
+        *cache = Some(Cached {
+            value,
+            stamp: Stamp::of(path),
+        });
+        Ok(())
+    }
+
+    /// Valeur actuelle, relue seulement si le fichier a changé
+    pub fn get(&self) -> T {
+        let mut cache = self.cache();
+        let path = self.path();
+        let stamp = Stamp::of(&path);
+        if let Some(cached) = cache.as_ref().filter(|cached| cached.stamp == stamp) {
+            return cached.value.clone();
+        }
+
+        if stamp.is_none() {
+            // Fichier absent : rien à verrouiller
+            let value = (self.init)();
+            *cache = Some(Cached {
+                value: value.clone(),
+                stamp: None,
+            });
+            return value;
+        }
+        match self.reload(&mut cache, &path) {
+            Ok(()) => cache.as_ref().map(|cached| cached.value.clone()).unwrap_or_else(self.init),
+            Err(e) => {
+                log::error!("{}", e);
+                self.read(&path).0
+            }
+        }
+    }
+
+    /// Lecture-modification-écriture sous les deux verrous ; rien n'est écrit
+    /// si `f` échoue
+    pub fn update<R>(&self, f: impl FnOnce(&mut T) -> Result<R, String>) -> Result<R, String> {
+        let (value, result) = {
+            let mut cache = self.cache();
+            let path = self.path();
+            let _lock = self.lock_file(&path)?;
+
+            let stamp = Stamp::of(&path);
+            let mut value = match cache.as_ref() {
+                Some(cached) if cached.stamp == stamp => cached.value.clone(),
+                _ => self.read(&path).0,
+            };
+            let result = f(&mut value)?;
+            self.write(&path, &value)?;
+            *cache = Some(Cached {
+                value: value.clone(),
+                stamp: Stamp::of(&path),
+            });
+            (value, result)
+        };
+        self.notify(&value);
+        Ok(result)
+    }
+
+    pub fn save(&self, value: T) -> Result<(), String> {
+        self.update(|current| {
+            *current = value;
+            Ok(())
+        })
+    }
+
+    /// Appelé après chaque écriture de ce processus, hors verrous
+    pub fn subscribe(&self, listener: impl Fn(&T) + Send + Sync + 'static) {
+        self.listeners
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(Box::new(listener));
+    }
+
+    fn notify(&self, value: &T) {
+        for listener in self.listeners.lock().unwrap_or_else(PoisonError::into_inner).iter() {
+            listener(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    type Counters = BTreeMap<String, u64>;
+
+    fn counters_store(dir: &Path) -> JsonStore<Counters> {
+        JsonStore {
+            dir: Some(dir.to_path_buf()),
+            ..JsonStore::new("counters.json", Counters::new, decode_json)
+        }
+    }
+
+    #[test]
+    fn test_concurrent_updates_and_external_changes() {
+        let dir = std::env::temp_dir().join(format!("wakascribe-store-{}", std::process::id()));
+        let store = Arc::new(counters_store(&dir));
+        let notified = Arc::new(AtomicUsize::new(0));
+        let counter = notified.clone();
+        store.subscribe(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let store = store.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..25 {
+                        store
+                            .update(|counters| {
+                                *counters.entry("words".to_string()).or_default() += 1;
+                                Ok(())
+                            })
+                            .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        assert_eq!(store.get()["words"], 100);
+        assert_eq!(notified.load(Ordering::SeqCst), 100);
+        assert!(!dir.join("counters.json.tmp").exists());
+
+        // Un autre processus (ici une autre instance) écrit : le cache suit
+        counters_store(&dir).save(Counters::from([("words".to_string(), 7)])).unwrap();
+        assert_eq!(store.get()["words"], 7);
+
+        // Un échec de `f` n'écrit rien
+        assert!(store.update(|counters| {
+            counters.clear();
+            Err::<(), _>("refused".to_string())
+        })
+        .is_err());
+        assert_eq!(store.get()["words"], 7);
+
+        // Contenu illisible : valeur initiale
+        fs::write(dir.join("counters.json"), "{").unwrap();
+        assert!(store.get().is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
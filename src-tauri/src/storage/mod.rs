@@ -1,6 +1,13 @@
 pub mod config;
+pub mod crypto;
 pub mod dictionary;
+pub mod export;
 pub mod history;
+pub mod json_store;
+pub mod prompts;
+pub mod recordings;
+pub mod secrets;
+pub mod settings_archive;
 pub mod snippets;
 pub mod stats;
 
@@ -16,3 +23,12 @@ pub fn ensure_app_data_dir() -> std::io::Result<PathBuf> {
     std::fs::create_dir_all(&dir)?;
     Ok(dir)
 }
+
+/// Chiffre (ou remet en clair) l'historique et l'audio conservé
+pub fn set_encryption(encrypt: bool) -> Result<(), String> {
+    let key = secrets::storage_key()?;
+    history::set_encryption(&key, encrypt)?;
+    recordings::set_encryption(&key, encrypt)?;
+    log::info!("Storage encryption {}", if encrypt { "enabled" } else { "disabled" });
+    Ok(())
+}
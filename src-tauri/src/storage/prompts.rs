@@ -0,0 +1,214 @@
+//! Profils de prompts du post-traitement LLM (prompts.json). Tant que le
+//! fichier n'existe pas, les profils intégrés reprennent les modes basique,
+//! intelligent, email, code et notes.
+
+use super::json_store::{decode_json, JsonStore};
+use crate::types::{DictationMode, PromptProfile, PromptProfilesData};
+
+const PROMPT_BASIC: &str = "Tu es un correcteur de texte. Corrige uniquement la ponctuation, les majuscules et les fautes de grammaire évidentes. Ne modifie pas le sens ni le style. Retourne uniquement le texte corrigé, sans explication.";
+
+const PROMPT_SMART: &str = "Tu es un assistant d'écriture. Corrige la ponctuation et la grammaire, supprime les hésitations (euh, hum, ben) et les répétitions inutiles. Reformule légèrement pour plus de clarté si nécessaire. Retourne uniquement le texte amélioré.";
+
+const PROMPT_EMAIL: &str = "Tu es un assistant d'écriture professionnelle. Transforme ce texte dicté en email professionnel. Ajoute les formules de politesse appropriées si absentes. Garde un ton formel mais naturel. Retourne uniquement l'email formaté.";
+
+const PROMPT_CODE: &str = "Tu es un assistant technique. Formate ce texte en documentation de code ou commentaire technique. Utilise la terminologie appropriée. Structure clairement. Retourne uniquement le texte formaté.";
+
+const PROMPT_NOTES: &str = "Tu es un assistant de prise de notes. Organise ce texte en notes structurées avec puces si approprié. Garde les points essentiels, supprime le superflu. Retourne uniquement les notes formatées.";
+
+fn builtin(id: &str, name: &str, system_prompt: &str, dictation_mode: Option<DictationMode>) -> PromptProfile {
+    PromptProfile {
+        id: id.to_string(),
+        name: name.to_string(),
+        system_prompt: system_prompt.to_string(),
+        temperature: 0.3,
+        max_tokens: 2048,
+        provider: None,
+        example: None,
+        dictation_mode,
+        hotkey: None,
+    }
+}
+
+/// Profils des modes basique et intelligent : ni supprimés ni renommés
+pub const REQUIRED_PROFILES: [&str; 2] = ["basic", "smart"];
+
+/// Profils livrés avec l'application
+pub fn builtin_profiles() -> Vec<PromptProfile> {
+    vec![
+        builtin("basic", "Basique", PROMPT_BASIC, None),
+        builtin("smart", "Intelligent", PROMPT_SMART, None),
+        builtin("email", "Email", PROMPT_EMAIL, Some(DictationMode::Email)),
+        builtin("code", "Code", PROMPT_CODE, Some(DictationMode::Code)),
+        builtin("notes", "Notes", PROMPT_NOTES, Some(DictationMode::Notes)),
+    ]
+}
+
+fn default_profiles() -> PromptProfilesData {
+    PromptProfilesData {
+        profiles: builtin_profiles(),
+    }
+}
+
+static STORE: JsonStore<PromptProfilesData> = JsonStore::new("prompts.json", default_profiles, decode_json);
+
+pub fn load_profiles() -> PromptProfilesData {
+    STORE.get()
+}
+
+/// Remplace tous les profils ; les profils requis absents sont rétablis
+pub fn save_profiles(data: &PromptProfilesData) -> Result<(), String> {
+    let mut data = data.clone();
+    for builtin in builtin_profiles() {
+        if REQUIRED_PROFILES.contains(&builtin.id.as_str()) && !data.profiles.iter().any(|p| p.id == builtin.id) {
+            data.profiles.push(builtin);
+        }
+    }
+    STORE.save(data)
+}
+
+pub fn get_profile(id: &str) -> Option<PromptProfile> {
+    load_profiles().profiles.into_iter().find(|p| p.id == id)
+}
+
+fn validate(profile: &PromptProfile) -> Result<(), String> {
+    if profile.id.trim().is_empty() || profile.name.trim().is_empty() {
+        return Err("Profile id and name are required".to_string());
+    }
+    if profile.system_prompt.trim().is_empty() {
+        return Err("System prompt is required".to_string());
+    }
+    if !(0.0..=2.0).contains(&profile.temperature) {
+        return Err(format!("Temperature must be between 0 and 2, got {}", profile.temperature));
+    }
+    if profile.max_tokens == 0 {
+        return Err("max_tokens must be greater than 0".to_string());
+    }
+    Ok(())
+}
+
+/// Profils d'une autre installation (import) : chacun valide, identifiants
+/// et raccourcis uniques
+pub fn validate_profiles(data: &PromptProfilesData) -> Result<(), String> {
+    for (i, profile) in data.profiles.iter().enumerate() {
+        validate(profile).map_err(|e| format!("Prompt profile '{}': {}", profile.id, e))?;
+        let earlier = &data.profiles[..i];
+        if earlier.iter().any(|p| p.id == profile.id) {
+            return Err(format!("A prompt profile with id '{}' already exists", profile.id));
+        }
+        if let Some(hotkey) = profile.hotkey.as_deref().filter(|h| !h.trim().is_empty()) {
+            if earlier.iter().any(|p| p.hotkey.as_deref() == Some(hotkey)) {
+                return Err(format!("Hotkey {} is used by several prompt profiles", hotkey));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Remplace le profil `id` (ou l'ajoute si `id` est `None`). Un mode de
+/// dictée et un raccourci ne servent qu'un profil : les autres les perdent.
+fn put(data: &mut PromptProfilesData, id: Option<&str>, mut profile: PromptProfile) -> Result<(), String> {
+    validate(&profile)?;
+    profile.hotkey = profile.hotkey.filter(|h| !h.trim().is_empty());
+
+    let index = match id {
+        Some(id) => Some(
+            data.profiles
+                .iter()
+                .position(|p| p.id == id)
+                .ok_or_else(|| format!("Prompt profile not found: {}", id))?,
+        ),
+        None => None,
+    };
+    if let Some(id) = id.filter(|id| REQUIRED_PROFILES.contains(id) && *id != profile.id) {
+        return Err(format!("Built-in prompt profile '{}' cannot be renamed", id));
+    }
+    if data
+        .profiles
+        .iter()
+        .enumerate()
+        .any(|(i, p)| p.id == profile.id && Some(i) != index)
+    {
+        return Err(format!("A prompt profile with id '{}' already exists", profile.id));
+    }
+
+    for (i, other) in data.profiles.iter_mut().enumerate() {
+        if Some(i) == index {
+            continue;
+        }
+        if profile.dictation_mode.is_some() && other.dictation_mode == profile.dictation_mode {
+            other.dictation_mode = None;
+        }
+        if profile.hotkey.is_some() && other.hotkey == profile.hotkey {
+            other.hotkey = None;
+        }
+    }
+
+    match index {
+        Some(i) => data.profiles[i] = profile,
+        None => data.profiles.push(profile),
+    }
+    Ok(())
+}
+
+pub fn add_profile(profile: PromptProfile) -> Result<(), String> {
+    STORE.update(|data| put(data, None, profile))
+}
+
+pub fn update_profile(id: &str, profile: PromptProfile) -> Result<(), String> {
+    STORE.update(|data| put(data, Some(id), profile))
+}
+
+pub fn remove_profile(id: &str) -> Result<(), String> {
+    if REQUIRED_PROFILES.contains(&id) {
+        return Err(format!("Built-in prompt profile '{}' cannot be deleted", id));
+    }
+    STORE.update(|data| {
+        data.profiles.retain(|p| p.id != id);
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(id: &str) -> PromptProfile {
+        builtin(id, id, "Rewrite as a commit message.", None)
+    }
+
+    #[test]
+    fn test_put_moves_bindings_and_rejects_duplicates() {
+        let mut data = PromptProfilesData { profiles: builtin_profiles() };
+
+        let mut commit = profile("commit");
+        commit.dictation_mode = Some(DictationMode::Code);
+        commit.hotkey = Some("Control+Alt+G".to_string());
+        put(&mut data, None, commit.clone()).unwrap();
+        let code = data.profiles.iter().find(|p| p.id == "code").unwrap();
+        assert_eq!(code.dictation_mode, None);
+
+        // Renommer l'identifiant en place est permis, pas le doublon
+        let mut renamed = commit.clone();
+        renamed.id = "commit-en".to_string();
+        put(&mut data, Some("commit"), renamed).unwrap();
+        assert!(data.profiles.iter().any(|p| p.id == "commit-en"));
+        assert!(put(&mut data, None, profile("email")).is_err());
+        assert!(put(&mut data, Some("missing"), profile("x")).is_err());
+        assert!(put(&mut data, Some("basic"), profile("basic-fr")).is_err());
+        assert!(remove_profile("smart").is_err());
+
+        let mut hot = profile("hot");
+        hot.hotkey = Some("Control+Alt+G".to_string());
+        hot.temperature = 3.0;
+        assert!(put(&mut data, None, hot.clone()).is_err());
+        hot.temperature = 0.0;
+        put(&mut data, None, hot).unwrap();
+        let commit = data.profiles.iter().find(|p| p.id == "commit-en").unwrap();
+        assert_eq!(commit.hotkey, None);
+        assert_eq!(commit.dictation_mode, Some(DictationMode::Code));
+
+        assert!(validate_profiles(&data).is_ok());
+        data.profiles.push(profile("hot"));
+        assert!(validate_profiles(&data).is_err());
+    }
+}
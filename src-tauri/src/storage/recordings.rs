@@ -0,0 +1,196 @@
+//! Audio des dictées conservé avec l'historique (recordings/*.flac). Les
+//! prises trop anciennes, puis les plus anciennes au-delà de la taille
+//! maximale, sont supprimées après chaque enregistrement. Avec
+//! `encrypt_storage`, les fichiers sont chiffrés (voir `crypto`).
+
+use super::crypto::{self, KEY_LEN};
+use super::json_store::write_atomic;
+use super::secrets;
+use crate::audio::encoder::encode_flac;
+use crate::audio::AudioDecoder;
+use crate::types::AudioRetentionConfig;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Fichier audio conservé
+#[derive(Debug, Clone, PartialEq)]
+struct Recording {
+    path: PathBuf,
+    size: u64,
+    /// Horodatage Unix de la dernière modification
+    modified: i64,
+}
+
+fn recordings_dir() -> PathBuf {
+    super::get_app_data_dir().join("recordings")
+}
+
+/// Fichier nommé d'après l'entrée de l'historique : deux prises ne peuvent
+/// pas se disputer le même nom
+fn save(id: i64, audio: &[f32], sample_rate: u32, encrypt: bool) -> Result<PathBuf, String> {
+    let dir = recordings_dir();
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let mut bytes = encode_flac(audio, sample_rate)?;
+    if encrypt {
+        bytes = crypto::encrypt(&secrets::storage_key()?, &bytes)?;
+    }
+    let path = dir.join(format!("{}.flac", id));
+    write_atomic(&path, &bytes)?;
+    Ok(path)
+}
+
+/// Contenu FLAC du fichier, déchiffré au besoin
+pub fn read(path: &Path) -> Result<Vec<u8>, String> {
+    let data = fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    if crypto::is_encrypted(&data) {
+        crypto::decrypt(&secrets::storage_key()?, &data)
+    } else {
+        Ok(data)
+    }
+}
+
+/// Audio mono 16 kHz prêt pour un moteur
+pub fn load(path: &Path) -> Result<(Vec<f32>, u32), String> {
+    AudioDecoder::decode_bytes(read(path)?, "flac")
+}
+
+/// Chiffre avec `key` ou remet en clair les fichiers existants, sans changer
+/// leur date (utilisée par la politique de conservation)
+pub fn set_encryption(key: &[u8; KEY_LEN], encrypt: bool) -> Result<(), String> {
+    for recording in list_recordings() {
+        let path = &recording.path;
+        let data = fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        if crypto::is_encrypted(&data) == encrypt {
+            continue;
+        }
+        let data = if encrypt { crypto::encrypt(key, &data)? } else { crypto::decrypt(key, &data)? };
+        // Remplacé d'un bloc : une interruption ne laisse pas de prise tronquée
+        write_atomic(path, &data)?;
+
+        let modified = std::time::UNIX_EPOCH + std::time::Duration::from_secs(recording.modified as u64);
+        if let Err(e) = fs::File::options().write(true).open(path).and_then(|file| file.set_modified(modified)) {
+            log::warn!("Failed to restore the date of {}: {}", path.display(), e);
+        }
+    }
+    Ok(())
+}
+
+fn list_recordings() -> Vec<Recording> {
+    let Ok(entries) = fs::read_dir(recordings_dir()) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "flac"))
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            let modified = metadata.modified().ok()?.duration_since(std::time::UNIX_EPOCH).ok()?;
+            Some(Recording {
+                path: entry.path(),
+                size: metadata.len(),
+                modified: modified.as_secs() as i64,
+            })
+        })
+        .collect()
+}
+
+/// Fichiers à supprimer : trop anciens, puis les plus anciens tant que la
+/// taille totale dépasse la limite
+fn select_expired(mut recordings: Vec<Recording>, now: i64, config: &AudioRetentionConfig) -> Vec<PathBuf> {
+    recordings.sort_by_key(|recording| std::cmp::Reverse(recording.modified));
+    let max_age = i64::from(config.max_age_days) * SECONDS_PER_DAY;
+    let max_bytes = config.max_size_mb * 1024 * 1024;
+
+    let mut kept_bytes = 0u64;
+    let mut expired = Vec::new();
+    for recording in recordings {
+        let too_old = config.max_age_days > 0 && now - recording.modified > max_age;
+        let too_big = config.max_size_mb > 0 && kept_bytes + recording.size > max_bytes;
+        if too_old || too_big {
+            expired.push(recording.path);
+        } else {
+            kept_bytes += recording.size;
+        }
+    }
+    expired
+}
+
+/// Applique la politique de conservation et renvoie les fichiers supprimés
+fn cleanup(config: &AudioRetentionConfig) -> Vec<PathBuf> {
+    let now = chrono::Utc::now().timestamp();
+    select_expired(list_recordings(), now, config)
+        .into_iter()
+        .filter(|path| match fs::remove_file(path) {
+            Ok(()) => true,
+            Err(e) => {
+                log::warn!("Failed to remove {}: {}", path.display(), e);
+                false
+            }
+        })
+        .collect()
+}
+
+fn store(id: i64, audio: &[f32], sample_rate: u32, config: &AudioRetentionConfig, encrypt: bool) -> Result<(), String> {
+    let path = save(id, audio, sample_rate, encrypt)?;
+    super::history::attach_audio(id, &path)?;
+
+    let removed = cleanup(config);
+    if !removed.is_empty() {
+        log::info!("Audio retention removed {} recordings", removed.len());
+        super::history::detach_audio(&removed)?;
+    }
+    Ok(())
+}
+
+/// Conserve en arrière-plan l'audio de l'entrée `id` de l'historique, puis
+/// applique la politique de conservation
+pub fn retain(id: i64, audio: Vec<f32>, sample_rate: u32, config: AudioRetentionConfig, encrypt: bool) {
+    std::thread::spawn(move || {
+        if let Err(e) = store(id, &audio, sample_rate, &config, encrypt) {
+            log::warn!("Failed to retain audio for history entry {}: {}", id, e);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recording(name: &str, size_mb: u64, age_days: i64) -> Recording {
+        Recording {
+            path: PathBuf::from(name),
+            size: size_mb * 1024 * 1024,
+            modified: 100 * SECONDS_PER_DAY - age_days * SECONDS_PER_DAY,
+        }
+    }
+
+    #[test]
+    fn test_select_expired() {
+        let now = 100 * SECONDS_PER_DAY;
+        let recordings = vec![
+            recording("old.flac", 1, 40),
+            recording("new.flac", 3, 0),
+            recording("week.flac", 2, 7),
+            recording("month.flac", 2, 20),
+        ];
+        let config = AudioRetentionConfig {
+            enabled: true,
+            max_age_days: 30,
+            max_size_mb: 6,
+        };
+        // Les plus récentes sont gardées dans la limite de taille
+        assert_eq!(
+            select_expired(recordings.clone(), now, &config),
+            vec![PathBuf::from("month.flac"), PathBuf::from("old.flac")]
+        );
+
+        let unlimited = AudioRetentionConfig {
+            enabled: true,
+            max_age_days: 0,
+            max_size_mb: 0,
+        };
+        assert!(select_expired(recordings, now, &unlimited).is_empty());
+    }
+}
@@ -0,0 +1,262 @@
+//! Secrets de l'application : clés API, jeton du serveur local et clé de
+//! chiffrement du stockage. Ils sont rangés dans le trousseau du système ; sous
+//! Linux sans Secret Service, dans un coffre (secrets.vault) dont la clé dérive
+//! (Argon2id) de la phrase secrète `SCRIBE_VAULT_PASSPHRASE`. Sans elle, le
+//! coffre n'est lié qu'à l'identifiant de la machine : il n'est alors pas
+//! réellement chiffré, ce que les réglages signalent.
+
+use super::crypto::{self, KEY_LEN};
+use super::json_store::{lock_file, write_private};
+use argon2::Argon2;
+use keyring::Entry;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+const SERVICE_NAME: &str = "wakascribe";
+
+pub const GROQ_API_KEY: &str = "groq_api_key";
+pub const OPENAI_COMPATIBLE_API_KEY: &str = "openai_compatible_api_key";
+pub const LOCAL_SERVER_TOKEN: &str = "local_server_token";
+const STORAGE_KEY: &str = "storage_encryption_key";
+
+/// Phrase secrète du coffre, lue dans l'environnement (application et CLI)
+pub const VAULT_PASSPHRASE_ENV: &str = "SCRIBE_VAULT_PASSPHRASE";
+
+const VAULT_MAGIC: &[u8; 4] = b"SCV1";
+/// Type de coffre, après l'en-tête
+const MACHINE_VAULT: u8 = 0;
+const PASSPHRASE_VAULT: u8 = 1;
+const SALT_LEN: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Backend {
+    Keyring,
+    Vault,
+}
+
+/// Protection effective des secrets, affichée dans les réglages
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Protection {
+    Keyring,
+    /// Coffre chiffré avec la phrase secrète
+    Passphrase,
+    /// Coffre lié à l'identifiant de la machine : lisible par quiconque
+    /// accède au dossier de données sur cette machine
+    Machine,
+}
+
+static BACKEND: OnceLock<Backend> = OnceLock::new();
+/// Sérialise les lectures-écritures du coffre
+static VAULT_LOCK: Mutex<()> = Mutex::new(());
+/// Sérialise la création de la clé de stockage
+static STORAGE_KEY_LOCK: Mutex<()> = Mutex::new(());
+
+/// Trousseau si le système en fournit un, coffre chiffré sinon (Linux)
+fn backend() -> Backend {
+    *BACKEND.get_or_init(|| {
+        match Entry::new(SERVICE_NAME, GROQ_API_KEY).and_then(|entry| entry.get_password()) {
+            Ok(_) | Err(keyring::Error::NoEntry) => Backend::Keyring,
+            Err(e) if cfg!(target_os = "linux") => {
+                log::warn!("OS keyring unavailable ({}), using the encrypted vault", e);
+                Backend::Vault
+            }
+            Err(e) => {
+                log::warn!("OS keyring probe failed: {}", e);
+                Backend::Keyring
+            }
+        }
+    })
+}
+
+pub fn protection() -> Protection {
+    match backend() {
+        Backend::Keyring => Protection::Keyring,
+        Backend::Vault if passphrase().is_some() => Protection::Passphrase,
+        Backend::Vault => Protection::Machine,
+    }
+}
+
+// ===== Coffre chiffré =====
+
+fn vault_path() -> PathBuf {
+    super::get_app_data_dir().join("secrets.vault")
+}
+
+fn passphrase() -> Option<String> {
+    std::env::var(VAULT_PASSPHRASE_ENV).ok().filter(|passphrase| !passphrase.is_empty())
+}
+
+fn machine_id() -> String {
+    ["/etc/machine-id", "/var/lib/dbus/machine-id"]
+        .iter()
+        .find_map(|path| fs::read_to_string(path).ok())
+        .map(|id| id.trim().to_string())
+        .unwrap_or_default()
+}
+
+fn vault_key(secret: &str, salt: &[u8]) -> Result<[u8; KEY_LEN], String> {
+    let mut key = [0u8; KEY_LEN];
+    Argon2::default()
+        .hash_password_into(secret.as_bytes(), salt, &mut key)
+        .map_err(|e| format!("Failed to derive the vault key: {}", e))?;
+    Ok(key)
+}
+
+/// Contenu du coffre : `en-tête || type || sel || données chiffrées` (JSON
+/// compte -> secret). Un coffre lié à la machine reste lisible après l'ajout
+/// d'une phrase secrète et passe sous celle-ci à l'écriture suivante.
+fn read_vault(path: &Path, passphrase: Option<&str>, machine_id: &str) -> Result<BTreeMap<String, String>, String> {
+    let data = match fs::read(path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+    let header = VAULT_MAGIC.len() + 1 + SALT_LEN;
+    if data.len() < header || !data.starts_with(VAULT_MAGIC) {
+        return Err("Invalid secrets vault".to_string());
+    }
+    let secret = match data[VAULT_MAGIC.len()] {
+        MACHINE_VAULT => machine_id,
+        PASSPHRASE_VAULT => passphrase.ok_or_else(|| format!("Secrets vault is locked, set {}", VAULT_PASSPHRASE_ENV))?,
+        kind => return Err(format!("Unknown secrets vault type {}", kind)),
+    };
+    let key = vault_key(secret, &data[VAULT_MAGIC.len() + 1..header])?;
+    let content = crypto::decrypt(&key, &data[header..])?;
+    serde_json::from_slice(&content).map_err(|e| format!("Invalid secrets vault: {}", e))
+}
+
+fn write_vault(
+    path: &Path,
+    passphrase: Option<&str>,
+    machine_id: &str,
+    secrets: &BTreeMap<String, String>,
+) -> Result<(), String> {
+    let (kind, secret) = match passphrase {
+        Some(passphrase) => (PASSPHRASE_VAULT, passphrase),
+        None => (MACHINE_VAULT, machine_id),
+    };
+    let salt: [u8; SALT_LEN] = crypto::random_bytes();
+    let content = serde_json::to_vec(secrets).map_err(|e| e.to_string())?;
+    let mut data = VAULT_MAGIC.to_vec();
+    data.push(kind);
+    data.extend(salt);
+    data.extend(crypto::encrypt(&vault_key(secret, &salt)?, &content)?);
+    write_private(path, &data)
+}
+
+/// Verrou du coffre entre threads puis entre processus (CLI, seconde instance)
+fn lock_vault() -> Result<(MutexGuard<'static, ()>, File), String> {
+    let guard = VAULT_LOCK.lock().map_err(|e| e.to_string())?;
+    let file = lock_file(&vault_path().with_extension("vault.lock"))?;
+    Ok((guard, file))
+}
+
+fn update_vault(f: impl FnOnce(&mut BTreeMap<String, String>)) -> Result<(), String> {
+    let _lock = lock_vault()?;
+    let (passphrase, machine_id) = (passphrase(), machine_id());
+    let mut secrets = read_vault(&vault_path(), passphrase.as_deref(), &machine_id)?;
+    f(&mut secrets);
+    write_vault(&vault_path(), passphrase.as_deref(), &machine_id, &secrets)
+}
+
+// ===== API =====
+
+/// Secret du compte, `None` s'il n'existe pas
+fn read(account: &str) -> Result<Option<String>, String> {
+    let secret = match backend() {
+        Backend::Keyring => {
+            let entry = Entry::new(SERVICE_NAME, account).map_err(|e| e.to_string())?;
+            match entry.get_password() {
+                Ok(secret) => Some(secret),
+                Err(keyring::Error::NoEntry) => None,
+                Err(e) => return Err(format!("Failed to read {} from the keyring: {}", account, e)),
+            }
+        }
+        Backend::Vault => {
+            let _lock = lock_vault()?;
+            read_vault(&vault_path(), passphrase().as_deref(), &machine_id())?.remove(account)
+        }
+    };
+    Ok(secret.filter(|secret| !secret.is_empty()))
+}
+
+pub fn get(account: &str) -> Option<String> {
+    read(account).unwrap_or_else(|e| {
+        log::warn!("{}", e);
+        None
+    })
+}
+
+pub fn set(account: &str, secret: &str) -> Result<(), String> {
+    match backend() {
+        Backend::Keyring => Entry::new(SERVICE_NAME, account)
+            .and_then(|entry| entry.set_password(secret))
+            .map_err(|e| format!("Failed to store {} in the keyring: {}", account, e)),
+        Backend::Vault => update_vault(|secrets| {
+            secrets.insert(account.to_string(), secret.to_string());
+        }),
+    }
+}
+
+pub fn delete(account: &str) -> Result<(), String> {
+    match backend() {
+        Backend::Keyring => match Entry::new(SERVICE_NAME, account).and_then(|entry| entry.delete_credential()) {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(format!("Failed to delete {} from the keyring: {}", account, e)),
+        },
+        Backend::Vault => update_vault(|secrets| {
+            secrets.remove(account);
+        }),
+    }
+}
+
+/// Clé de chiffrement de l'historique et de l'audio, créée au premier appel.
+/// Lecture et création sont verrouillées entre threads et processus : deux
+/// créations simultanées chiffreraient avec des clés différentes.
+pub fn storage_key() -> Result<[u8; KEY_LEN], String> {
+    let _guard = STORAGE_KEY_LOCK.lock().map_err(|e| e.to_string())?;
+    let _lock = lock_file(&super::get_app_data_dir().join("storage_key.lock"))?;
+    if let Some(hex) = read(STORAGE_KEY)? {
+        return crypto::key_from_hex(&hex).ok_or_else(|| "Invalid storage encryption key".to_string());
+    }
+    let key = crypto::generate_key();
+    set(STORAGE_KEY, &crypto::to_hex(&key))?;
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vault_roundtrip() {
+        let path = std::env::temp_dir().join(format!("wakascribe-vault-{}", std::process::id()));
+        let mut secrets = BTreeMap::new();
+        secrets.insert(GROQ_API_KEY.to_string(), "gsk_test".to_string());
+        write_vault(&path, None, "machine-a", &secrets).unwrap();
+
+        assert_eq!(read_vault(&path, None, "machine-a").unwrap(), secrets);
+        assert!(!String::from_utf8_lossy(&fs::read(&path).unwrap()).contains("gsk_test"));
+        // Illisible sur une autre machine
+        assert!(read_vault(&path, None, "machine-b").is_err());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+
+        // Sous phrase secrète : l'identifiant de la machine ne suffit plus
+        assert_eq!(read_vault(&path, Some("phrase"), "machine-a").unwrap(), secrets);
+        write_vault(&path, Some("phrase"), "machine-a", &secrets).unwrap();
+        assert_eq!(read_vault(&path, Some("phrase"), "machine-b").unwrap(), secrets);
+        assert!(read_vault(&path, None, "machine-a").is_err());
+        assert!(read_vault(&path, Some("autre"), "machine-a").is_err());
+
+        fs::remove_file(&path).unwrap();
+        assert!(read_vault(&path, None, "machine-a").unwrap().is_empty());
+    }
+}
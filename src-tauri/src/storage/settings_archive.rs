@@ -0,0 +1,145 @@
+//! Archive zip des réglages : config, dictionnaire, snippets et profils de
+//! prompts, pour sauvegarder ou recopier une installation. Les secrets (clés
+//! API, jeton du serveur) n'y figurent pas.
+
+use crate::types::{AppSettings, DictionaryData, PromptProfilesData, SnippetsData};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fs::File;
+use std::io::{Read, Seek, Write};
+use std::path::Path;
+use zip::result::ZipError;
+use zip::write::FileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+const MANIFEST: &str = "manifest.json";
+const SETTINGS: &str = "settings.json";
+const DICTIONARY: &str = "dictionary.json";
+const SNIPPETS: &str = "snippets.json";
+const PROMPTS: &str = "prompts.json";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+    app_version: String,
+    schema_version: u32,
+    exported_at: i64,
+}
+
+/// Contenu d'une archive ; les réglages restent en JSON brut pour passer par
+/// les migrations de `config`
+#[derive(Debug)]
+pub struct SettingsArchive {
+    pub settings: String,
+    pub dictionary: Option<DictionaryData>,
+    pub snippets: Option<SnippetsData>,
+    pub prompts: Option<PromptProfilesData>,
+}
+
+fn add_json<W: Write + Seek, T: Serialize>(zip: &mut ZipWriter<W>, name: &str, value: &T) -> Result<(), String> {
+    let content = serde_json::to_vec_pretty(value).map_err(|e| e.to_string())?;
+    zip.start_file(name, FileOptions::default()).map_err(|e| e.to_string())?;
+    zip.write_all(&content).map_err(|e| e.to_string())
+}
+
+fn read_entry<R: Read + Seek>(archive: &mut ZipArchive<R>, name: &str) -> Result<Option<String>, String> {
+    let mut entry = match archive.by_name(name) {
+        Ok(entry) => entry,
+        Err(ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(format!("Failed to read {}: {}", name, e)),
+    };
+    let mut content = String::new();
+    entry.read_to_string(&mut content).map_err(|e| format!("Failed to read {}: {}", name, e))?;
+    Ok(Some(content))
+}
+
+fn read_json<R: Read + Seek, T: DeserializeOwned>(archive: &mut ZipArchive<R>, name: &str) -> Result<Option<T>, String> {
+    read_entry(archive, name)?
+        .map(|content| serde_json::from_str(&content).map_err(|e| format!("Invalid {}: {}", name, e)))
+        .transpose()
+}
+
+pub fn write<W: Write + Seek>(
+    writer: W,
+    settings: &AppSettings,
+    dictionary: &DictionaryData,
+    snippets: &SnippetsData,
+    prompts: &PromptProfilesData,
+) -> Result<(), String> {
+    let mut zip = ZipWriter::new(writer);
+    let manifest = Manifest {
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        schema_version: settings.schema_version,
+        exported_at: chrono::Utc::now().timestamp(),
+    };
+    add_json(&mut zip, MANIFEST, &manifest)?;
+    add_json(&mut zip, SETTINGS, settings)?;
+    add_json(&mut zip, DICTIONARY, dictionary)?;
+    add_json(&mut zip, SNIPPETS, snippets)?;
+    add_json(&mut zip, PROMPTS, prompts)?;
+    zip.finish().map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+pub fn read<R: Read + Seek>(reader: R) -> Result<SettingsArchive, String> {
+    let mut archive = ZipArchive::new(reader).map_err(|e| format!("Failed to read archive: {}", e))?;
+    let manifest: Manifest = read_json(&mut archive, MANIFEST)?.ok_or("Not a settings archive (no manifest)")?;
+    log::info!(
+        "Reading settings archive from version {} (schema {})",
+        manifest.app_version,
+        manifest.schema_version
+    );
+
+    Ok(SettingsArchive {
+        settings: read_entry(&mut archive, SETTINGS)?.ok_or("Settings archive has no settings.json")?,
+        dictionary: read_json(&mut archive, DICTIONARY)?,
+        snippets: read_json(&mut archive, SNIPPETS)?,
+        prompts: read_json(&mut archive, PROMPTS)?,
+    })
+}
+
+/// Archive des réglages et données actuels
+pub fn export_to_file(path: &Path) -> Result<(), String> {
+    let file = File::create(path).map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+    write(
+        file,
+        &super::config::load_settings(),
+        &super::dictionary::load_dictionary(),
+        &super::snippets::load_snippets(),
+        &super::prompts::load_profiles(),
+    )
+}
+
+pub fn read_file(path: &Path) -> Result<SettingsArchive, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    read(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_archive_roundtrip() {
+        let settings = AppSettings {
+            theme: "dark".to_string(),
+            ..AppSettings::default()
+        };
+        let dictionary = DictionaryData {
+            words: vec!["Tauri".to_string()],
+        };
+        let mut buffer = Cursor::new(Vec::new());
+        write(&mut buffer, &settings, &dictionary, &SnippetsData::default(), &PromptProfilesData::default()).unwrap();
+
+        let archive = read(Cursor::new(buffer.into_inner())).unwrap();
+        let restored: AppSettings = serde_json::from_str(&archive.settings).unwrap();
+        assert_eq!(restored.theme, "dark");
+        assert_eq!(archive.dictionary.unwrap().words, vec!["Tauri".to_string()]);
+        assert!(archive.snippets.unwrap().snippets.is_empty());
+
+        // Un zip quelconque est refusé
+        let mut other = ZipWriter::new(Cursor::new(Vec::new()));
+        other.start_file("notes.txt", FileOptions::default()).unwrap();
+        let other = other.finish().unwrap();
+        assert!(read(Cursor::new(other.into_inner())).is_err());
+    }
+}
@@ -148,8 +148,7 @@ fn handle_tray_menu_event(app: &tauri::AppHandle, menu_id: &str) {
         }
         "paste_last" => {
             log::info!("Paste last transcript clicked");
-            let history = storage::history::load_history();
-            if let Some(last) = history.transcriptions.last() {
+            if let Some(last) = storage::history::latest() {
                 paste_text(&last.text);
             }
         }
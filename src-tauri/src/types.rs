@@ -7,6 +7,8 @@ pub enum LlmMode {
     Basic,
     Smart,
     Contextual,
+    /// Profil choisi dans `AppSettings::llm_profile`
+    Profile,
 }
 
 impl Default for LlmMode {
@@ -22,84 +24,138 @@ pub enum LlmProvider {
     #[default]
     Groq,
     Local,
+    /// Serveur compatible OpenAI (Ollama, llama.cpp, LM Studio, passerelle interne)
+    #[serde(rename = "openai_compatible")]
+    OpenAiCompatible,
 }
 
-/// Taille des modèles LLM locaux
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
-pub enum LocalLlmModel {
-    #[serde(rename = "smollm2_360m")]
-    SmolLM2_360M,    // ~386 MB - Ultra rapide, résumés basiques
-    #[default]
-    #[serde(rename = "phi3_mini")]
-    Phi3Mini,        // ~2.2 GB - Excellent rapport qualité/taille (recommandé)
-    #[serde(rename = "qwen2_5_3b")]
-    Qwen2_5_3B,      // ~2.0 GB - Très bonne qualité
+/// En-tête HTTP ajouté aux requêtes vers un serveur compatible OpenAI
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HttpHeader {
+    pub name: String,
+    pub value: String,
 }
 
-impl LocalLlmModel {
-    pub fn file_name(&self) -> &'static str {
-        match self {
-            LocalLlmModel::SmolLM2_360M => "SmolLM2-360M-Instruct-Q8_0.gguf",
-            LocalLlmModel::Phi3Mini => "Phi-3-mini-4k-instruct-Q4_K_M.gguf",
-            LocalLlmModel::Qwen2_5_3B => "qwen2.5-3b-instruct-q4_k_m.gguf",
+/// Configuration du provider compatible OpenAI (la clé API est dans le trousseau)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct OpenAiCompatibleConfig {
+    /// URL de base, sans `/chat/completions` (ex. `http://localhost:11434/v1`)
+    pub base_url: String,
+    pub model: String,
+    pub headers: Vec<HttpHeader>,
+}
+
+impl Default for OpenAiCompatibleConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "http://localhost:11434/v1".to_string(),
+            model: "llama3.2".to_string(),
+            headers: Vec::new(),
         }
     }
+}
 
-    pub fn download_url(&self) -> &'static str {
-        match self {
-            LocalLlmModel::SmolLM2_360M => "https://huggingface.co/bartowski/SmolLM2-360M-Instruct-GGUF/resolve/main/SmolLM2-360M-Instruct-Q8_0.gguf",
-            LocalLlmModel::Phi3Mini => "https://huggingface.co/bartowski/Phi-3-mini-4k-instruct-GGUF/resolve/main/Phi-3-mini-4k-instruct-Q4_K_M.gguf",
-            LocalLlmModel::Qwen2_5_3B => "https://huggingface.co/Qwen/Qwen2.5-3B-Instruct-GGUF/resolve/main/qwen2.5-3b-instruct-q4_k_m.gguf",
+/// Serveur local de transcription (le jeton d'accès est dans le trousseau)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct LocalServerConfig {
+    pub enabled: bool,
+    /// Port d'écoute sur 127.0.0.1
+    pub port: u16,
+}
+
+impl Default for LocalServerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 8178,
         }
     }
+}
 
-    pub fn size_bytes(&self) -> u64 {
-        match self {
-            LocalLlmModel::SmolLM2_360M => 386_000_000,    // ~386 MB
-            LocalLlmModel::Phi3Mini => 2_200_000_000,      // ~2.2 GB
-            LocalLlmModel::Qwen2_5_3B => 2_000_000_000,    // ~2.0 GB
+/// Contexte de l'application active transmis au LLM (désactivé par défaut)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct ContextCaptureConfig {
+    pub enabled: bool,
+    pub app_name: bool,
+    pub window_title: bool,
+    /// Copie la sélection au démarrage (le presse-papiers est restauré)
+    pub selected_text: bool,
+    /// Masque emails, liens, numéros et jetons avant l'envoi
+    pub redact: bool,
+}
+
+impl Default for ContextCaptureConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            app_name: true,
+            window_title: true,
+            selected_text: false,
+            redact: true,
         }
     }
+}
 
-    pub fn display_name(&self) -> &'static str {
-        match self {
-            LocalLlmModel::SmolLM2_360M => "SmolLM2 360M (386 MB) - Rapide",
-            LocalLlmModel::Phi3Mini => "Phi-3 Mini (2.2 GB) - Recommandé",
-            LocalLlmModel::Qwen2_5_3B => "Qwen2.5 3B (2 GB) - Qualité",
+/// Conservation de l'audio des dictées (FLAC, lié à l'historique)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct AudioRetentionConfig {
+    pub enabled: bool,
+    /// Les enregistrements plus anciens sont supprimés (0 : sans limite)
+    pub max_age_days: u32,
+    /// Au-delà, les plus anciens sont supprimés (0 : sans limite)
+    pub max_size_mb: u64,
+}
+
+impl Default for AudioRetentionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_age_days: 30,
+            max_size_mb: 500,
         }
     }
+}
+
+/// Formats de conversation des modèles GGUF supportés
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChatTemplate {
+    /// `<|im_start|>role ... <|im_end|>` (SmolLM2, Qwen2.5)
+    ChatMl,
+    /// `<|user|> ... <|end|>` (Phi-3, sans rôle système : l'instruction précède le message)
+    Phi3,
+}
 
-    /// Format du prompt pour ce modèle
-    pub fn format_prompt(&self, _instruction: &str, text: &str) -> String {
+impl ChatTemplate {
+    pub fn format(&self, system: &str, user: &str) -> String {
+        let system = system.trim();
         match self {
-            LocalLlmModel::SmolLM2_360M => {
-                // SmolLM2 - format ChatML simplifié
-                format!(
-                    "<|im_start|>user\nResume ce texte en 2 phrases:\n\n{}<|im_end|>\n<|im_start|>assistant\n",
-                    text
-                )
-            }
-            LocalLlmModel::Phi3Mini => {
-                // Phi-3 utilise un format spécifique
-                format!(
-                    "<|user|>\nResume ce texte en 2-3 phrases concises en francais:\n\n{}<|end|>\n<|assistant|>\n",
-                    text
-                )
+            ChatTemplate::ChatMl => {
+                let mut prompt = String::new();
+                if !system.is_empty() {
+                    prompt.push_str(&format!("<|im_start|>system\n{}<|im_end|>\n", system));
+                }
+                prompt.push_str(&format!(
+                    "<|im_start|>user\n{}<|im_end|>\n<|im_start|>assistant\n",
+                    user
+                ));
+                prompt
             }
-            LocalLlmModel::Qwen2_5_3B => {
-                // Qwen2.5 - format ChatML
-                format!(
-                    "<|im_start|>user\nResume ce texte en 2-3 phrases concises:\n\n{}<|im_end|>\n<|im_start|>assistant\n",
-                    text
-                )
+            ChatTemplate::Phi3 => {
+                if system.is_empty() {
+                    format!("<|user|>\n{}<|end|>\n<|assistant|>\n", user)
+                } else {
+                    format!("<|user|>\n{}\n\n{}<|end|>\n<|assistant|>\n", system, user)
+                }
             }
         }
     }
 }
 
-// Alias pour compatibilité
-pub type QwenModelSize = LocalLlmModel;
-
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum DictationMode {
@@ -115,52 +171,48 @@ impl Default for DictationMode {
     }
 }
 
+/// Jeu de commandes vocales reconnu pendant une dictée
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
-pub enum ModelSize {
-    Tiny,
-    Small,
-    Medium,
+pub enum VoiceCommandSet {
+    Off,
+    General,
+    Email,
+    Code,
+    Notes,
 }
 
-impl ModelSize {
-    pub fn file_name(&self) -> &'static str {
+impl VoiceCommandSet {
+    /// Mode dont les commandes sont reconnues, `None` pour aucune commande
+    pub fn mode(self) -> Option<DictationMode> {
         match self {
-            ModelSize::Tiny => "ggml-tiny.bin",
-            ModelSize::Small => "ggml-small.bin",
-            ModelSize::Medium => "ggml-medium.bin",
-        }
-    }
-
-    pub fn download_url(&self) -> &'static str {
-        match self {
-            ModelSize::Tiny => "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-tiny.bin",
-            ModelSize::Small => "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-small.bin",
-            ModelSize::Medium => "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-medium.bin",
-        }
-    }
-
-    pub fn size_bytes(&self) -> u64 {
-        match self {
-            ModelSize::Tiny => 75_000_000,
-            ModelSize::Small => 466_000_000,
-            ModelSize::Medium => 1_500_000_000,
-        }
-    }
-
-    pub fn display_name(&self) -> &'static str {
-        match self {
-            ModelSize::Tiny => "Tiny (75 MB)",
-            ModelSize::Small => "Small (466 MB)",
-            ModelSize::Medium => "Medium (1.5 GB)",
+            VoiceCommandSet::Off => None,
+            VoiceCommandSet::General => Some(DictationMode::General),
+            VoiceCommandSet::Email => Some(DictationMode::Email),
+            VoiceCommandSet::Code => Some(DictationMode::Code),
+            VoiceCommandSet::Notes => Some(DictationMode::Notes),
         }
     }
 }
 
-impl Default for ModelSize {
-    fn default() -> Self {
-        ModelSize::Tiny
-    }
+/// Règle par application : les champs renseignés remplacent les réglages
+/// pendant une dictée lancée depuis cette application
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AppRule {
+    /// Motif recherché (sans casse) dans l'identifiant de l'application
+    /// (classe de fenêtre X11 : `code`, `thunderbird`…)
+    pub app: String,
+    #[serde(default)]
+    pub dictation_mode: Option<DictationMode>,
+    /// Profil de prompts appliqué (active le post-traitement LLM)
+    #[serde(default)]
+    pub llm_profile: Option<String>,
+    /// Langue de transcription (`auto` pour la détection automatique)
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Par défaut, les commandes du mode de dictée
+    #[serde(default)]
+    pub voice_commands: Option<VoiceCommandSet>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
@@ -172,27 +224,6 @@ pub enum EngineType {
     Vosk,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
-#[serde(rename_all = "lowercase")]
-pub enum ParakeetModelSize {
-    #[default]
-    Tdt06bV3,
-}
-
-impl ParakeetModelSize {
-    pub fn model_name(&self) -> &'static str {
-        match self {
-            ParakeetModelSize::Tdt06bV3 => "parakeet-tdt-0.6b-v3",
-        }
-    }
-
-    pub fn display_name(&self) -> &'static str {
-        match self {
-            ParakeetModelSize::Tdt06bV3 => "Parakeet TDT 0.6B v3 (Multilingual)",
-        }
-    }
-}
-
 impl EngineType {
     pub fn display_name(&self) -> &'static str {
         match self {
@@ -203,92 +234,40 @@ impl EngineType {
     }
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+/// Détecteur d'activité vocale
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
 #[serde(rename_all = "lowercase")]
-pub enum VoskLanguage {
-    En, Fr, De, Es, It, Ru, Zh, Ja, Ko, Pt, Nl, Pl, Uk, Tr, Vi, Ar, Hi, Fa, Ca, Cs,
+pub enum VadEngine {
+    /// Énergie + platitude spectrale (aucun modèle requis)
+    #[default]
+    Energy,
+    /// Modèle Silero VAD (ONNX)
+    Silero,
 }
 
-impl VoskLanguage {
-    pub fn model_name(&self) -> &'static str {
-        match self {
-            VoskLanguage::En => "vosk-model-small-en-us-0.15",
-            VoskLanguage::Fr => "vosk-model-small-fr-0.22",
-            VoskLanguage::De => "vosk-model-small-de-0.15",
-            VoskLanguage::Es => "vosk-model-small-es-0.42",
-            VoskLanguage::It => "vosk-model-small-it-0.22",
-            VoskLanguage::Ru => "vosk-model-small-ru-0.22",
-            VoskLanguage::Zh => "vosk-model-small-cn-0.22",
-            VoskLanguage::Ja => "vosk-model-small-ja-0.22",
-            VoskLanguage::Ko => "vosk-model-small-ko-0.22",
-            VoskLanguage::Pt => "vosk-model-small-pt-0.3",
-            VoskLanguage::Nl => "vosk-model-small-nl-0.22",
-            VoskLanguage::Pl => "vosk-model-small-pl-0.22",
-            VoskLanguage::Uk => "vosk-model-small-uk-v3-small",
-            VoskLanguage::Tr => "vosk-model-small-tr-0.3",
-            VoskLanguage::Vi => "vosk-model-small-vn-0.4",
-            VoskLanguage::Ar => "vosk-model-ar-mgb2-0.4",
-            VoskLanguage::Hi => "vosk-model-small-hi-0.22",
-            VoskLanguage::Fa => "vosk-model-small-fa-0.5",
-            VoskLanguage::Ca => "vosk-model-small-ca-0.4",
-            VoskLanguage::Cs => "vosk-model-small-cs-0.4-rhasspy",
-        }
-    }
 
-    pub fn download_url(&self) -> String {
-        format!("https://alphacephei.com/vosk/models/{}.zip", self.model_name())
-    }
-
-    pub fn display_name(&self) -> &'static str {
-        match self {
-            VoskLanguage::En => "English",
-            VoskLanguage::Fr => "Français",
-            VoskLanguage::De => "Deutsch",
-            VoskLanguage::Es => "Español",
-            VoskLanguage::It => "Italiano",
-            VoskLanguage::Ru => "Русский",
-            VoskLanguage::Zh => "中文",
-            VoskLanguage::Ja => "日本語",
-            VoskLanguage::Ko => "한국어",
-            VoskLanguage::Pt => "Português",
-            VoskLanguage::Nl => "Nederlands",
-            VoskLanguage::Pl => "Polski",
-            VoskLanguage::Uk => "Українська",
-            VoskLanguage::Tr => "Türkçe",
-            VoskLanguage::Vi => "Tiếng Việt",
-            VoskLanguage::Ar => "العربية",
-            VoskLanguage::Hi => "हिन्दी",
-            VoskLanguage::Fa => "فارسی",
-            VoskLanguage::Ca => "Català",
-            VoskLanguage::Cs => "Čeština",
-        }
-    }
+/// Mot horodaté (secondes depuis le début de l'audio)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WordTiming {
+    pub start: f32,
+    pub end: f32,
+    pub text: String,
+    #[serde(default)]
+    pub confidence: Option<f32>,
+}
 
-    pub fn from_language_code(code: &str) -> Option<Self> {
-        match code.to_lowercase().as_str() {
-            "en" | "english" => Some(VoskLanguage::En),
-            "fr" | "french" => Some(VoskLanguage::Fr),
-            "de" | "german" => Some(VoskLanguage::De),
-            "es" | "spanish" => Some(VoskLanguage::Es),
-            "it" | "italian" => Some(VoskLanguage::It),
-            "ru" | "russian" => Some(VoskLanguage::Ru),
-            "zh" | "chinese" => Some(VoskLanguage::Zh),
-            "ja" | "japanese" => Some(VoskLanguage::Ja),
-            "ko" | "korean" => Some(VoskLanguage::Ko),
-            "pt" | "portuguese" => Some(VoskLanguage::Pt),
-            "nl" | "dutch" => Some(VoskLanguage::Nl),
-            "pl" | "polish" => Some(VoskLanguage::Pl),
-            "uk" | "ukrainian" => Some(VoskLanguage::Uk),
-            "tr" | "turkish" => Some(VoskLanguage::Tr),
-            "vi" | "vietnamese" => Some(VoskLanguage::Vi),
-            "ar" | "arabic" => Some(VoskLanguage::Ar),
-            "hi" | "hindi" => Some(VoskLanguage::Hi),
-            "fa" | "persian" => Some(VoskLanguage::Fa),
-            "ca" | "catalan" => Some(VoskLanguage::Ca),
-            "cs" | "czech" => Some(VoskLanguage::Cs),
-            _ => None,
-        }
-    }
+/// Segment horodaté d'une transcription (phrase ou énoncé)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Segment {
+    pub start: f32,
+    pub end: f32,
+    pub text: String,
+    #[serde(default)]
+    pub words: Vec<WordTiming>,
+    pub confidence: f32,
+    /// Intervenant identifié par la diarisation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -301,11 +280,89 @@ pub struct TranscriptionResult {
     pub timestamp: i64,
     #[serde(default)]
     pub model_used: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub segments: Vec<Segment>,
+    /// Renseigné quand `text` est une traduction de la parole
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub translation: Option<TranslationInfo>,
 }
 
+/// Langues d'une transcription traduite
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TranslationInfo {
+    /// Langue parlée, si elle a été détectée
+    pub source_language: Option<String>,
+    pub target_language: String,
+}
+
+/// Résultat de la transcription d'un fichier audio
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileTranscriptionResult {
+    pub file_path: String,
+    pub file_name: String,
+    pub transcription: Option<TranscriptionResult>,
+    pub error: Option<String>,
+    /// Entrée d'historique de cette transcription, pour y enregistrer les
+    /// noms d'intervenants
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history_id: Option<i64>,
+}
+
+/// Format d'export d'une transcription
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Srt,
+    Vtt,
+    Ttml,
+    Json,
+    Txt,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Srt => "srt",
+            ExportFormat::Vtt => "vtt",
+            ExportFormat::Ttml => "ttml",
+            ExportFormat::Json => "json",
+            ExportFormat::Txt => "txt",
+        }
+    }
+}
+
+/// Contraintes de mise en forme des sous-titres
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SubtitleOptions {
+    /// Nombre maximal de caractères par ligne
+    pub max_line_chars: usize,
+    /// Nombre maximal de lignes par sous-titre
+    pub max_lines: usize,
+    /// Durée minimale d'affichage d'un sous-titre (secondes)
+    pub min_cue_seconds: f32,
+    /// Durée maximale d'affichage d'un sous-titre (secondes)
+    pub max_cue_seconds: f32,
+}
+
+impl Default for SubtitleOptions {
+    fn default() -> Self {
+        Self {
+            max_line_chars: 42,
+            max_lines: 2,
+            min_cue_seconds: 1.0,
+            max_cue_seconds: 7.0,
+        }
+    }
+}
+
+/// Version du schéma de config.json (voir `storage::config`)
+pub const SETTINGS_SCHEMA_VERSION: u32 = 1;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct AppSettings {
+    pub schema_version: u32,
     pub microphone_id: Option<String>,
     pub hotkey_push_to_talk: String,
     pub hotkey_toggle_record: String,
@@ -315,9 +372,13 @@ pub struct AppSettings {
     pub minimize_to_tray: bool,
     pub auto_copy_to_clipboard: bool,
     pub notification_on_complete: bool,
-    pub whisper_model: ModelSize,
+    /// Identifiant Whisper du catalogue de modèles (tiny, small, large-v3-turbo…)
+    pub whisper_model: String,
     pub llm_enabled: bool,
     pub llm_mode: LlmMode,
+    /// Identifiant du profil de prompts utilisé en mode `LlmMode::Profile`
+    #[serde(default)]
+    pub llm_profile: Option<String>,
     pub voice_commands_enabled: bool,
     pub dictation_mode: DictationMode,
     #[serde(default = "default_true")]
@@ -336,18 +397,24 @@ pub struct AppSettings {
     pub hotkey_translate: String,
     #[serde(default = "default_hotkey_voice_action")]
     pub hotkey_voice_action: String,
+    /// Dictée traduite en anglais par Whisper (tâche `translate`), sans LLM
+    #[serde(default = "default_hotkey_speech_translate")]
+    pub hotkey_speech_translate: String,
     #[serde(default)]
     pub engine_type: EngineType,
+    /// Identifiant Vosk du catalogue (à défaut, le modèle de la langue de transcription)
     #[serde(default)]
-    pub vosk_language: Option<VoskLanguage>,
-    #[serde(default)]
-    pub parakeet_model: ParakeetModelSize,
-    #[serde(default)]
-    pub groq_api_key: Option<String>,
+    pub vosk_language: Option<String>,
+    /// Identifiant Parakeet du catalogue
+    #[serde(default = "default_parakeet_model")]
+    pub parakeet_model: String,
     #[serde(default)]
     pub llm_provider: LlmProvider,
     #[serde(default)]
-    pub local_llm_model: LocalLlmModel,
+    pub openai_compatible: OpenAiCompatibleConfig,
+    /// Identifiant LLM du catalogue
+    #[serde(default = "default_local_llm_model")]
+    pub local_llm_model: String,
     #[serde(default)]
     pub onboarding_completed: bool,
     #[serde(default)]
@@ -362,12 +429,69 @@ pub struct AppSettings {
     pub stats_tracking_enabled: bool,
     #[serde(default)]
     pub integrations: IntegrationConfig,
+    #[serde(default)]
+    pub vad_engine: VadEngine,
+    #[serde(default = "default_true")]
+    pub trim_silence: bool,
+    #[serde(default)]
+    pub hands_free_enabled: bool,
+    #[serde(default = "default_hands_free_silence_ms")]
+    pub hands_free_silence_ms: u32,
+    #[serde(default)]
+    pub diarization_enabled: bool,
+    #[serde(default)]
+    pub diarization_max_speakers: Option<u32>,
+    /// Manifeste distant complétant le catalogue de modèles embarqué
+    #[serde(default)]
+    pub model_catalog_url: Option<String>,
+    /// Miroir privé remplaçant les hôtes amont (huggingface.co, alphacephei.com…)
+    #[serde(default)]
+    pub model_mirror_url: Option<String>,
+    #[serde(default)]
+    pub local_server: LocalServerConfig,
+    /// Réglages appliqués selon l'application active au démarrage du push-to-talk
+    #[serde(default = "default_app_rules")]
+    pub app_rules: Vec<AppRule>,
+    /// Contexte de l'application active ajouté à l'invite du post-traitement
+    #[serde(default)]
+    pub context_capture: ContextCaptureConfig,
+    #[serde(default)]
+    pub audio_retention: AudioRetentionConfig,
+    /// Historique et audio conservé chiffrés avec une clé du trousseau
+    #[serde(default)]
+    pub encrypt_storage: bool,
 }
 
 fn default_true() -> bool {
     true
 }
 
+fn default_app_rules() -> Vec<AppRule> {
+    let rule = |app: &str, mode: DictationMode| AppRule {
+        app: app.to_string(),
+        dictation_mode: Some(mode),
+        llm_profile: None,
+        language: None,
+        voice_commands: None,
+    };
+    vec![
+        rule("code", DictationMode::Code),
+        rule("thunderbird", DictationMode::Email),
+        rule("obsidian", DictationMode::Notes),
+    ]
+}
+
+/// Modèle Parakeet par défaut, le seul embarqué par le sidecar CoreML
+pub const DEFAULT_PARAKEET_MODEL: &str = "tdt06bv3";
+
+fn default_parakeet_model() -> String {
+    DEFAULT_PARAKEET_MODEL.to_string()
+}
+
+fn default_local_llm_model() -> String {
+    "phi3_mini".to_string()
+}
+
 fn default_translation_language() -> String {
     "en".to_string()
 }
@@ -380,13 +504,22 @@ fn default_hotkey_voice_action() -> String {
     "Control+Alt+A".to_string()
 }
 
+fn default_hotkey_speech_translate() -> String {
+    "Control+Alt+E".to_string()
+}
+
 fn default_subtitles_font_size() -> u32 {
     20
 }
 
+fn default_hands_free_silence_ms() -> u32 {
+    1500
+}
+
 impl Default for AppSettings {
     fn default() -> Self {
         Self {
+            schema_version: SETTINGS_SCHEMA_VERSION,
             microphone_id: None,
             hotkey_push_to_talk: "Control+Space".to_string(),
             hotkey_toggle_record: "Control+Shift+R".to_string(),
@@ -396,9 +529,10 @@ impl Default for AppSettings {
             minimize_to_tray: true,
             auto_copy_to_clipboard: true,
             notification_on_complete: true,
-            whisper_model: ModelSize::Tiny,
+            whisper_model: "tiny".to_string(),
             llm_enabled: false,
             llm_mode: LlmMode::default(),
+            llm_profile: None,
             voice_commands_enabled: true,
             dictation_mode: DictationMode::default(),
             streaming_enabled: true,
@@ -409,12 +543,13 @@ impl Default for AppSettings {
             translation_target_language: "en".to_string(),
             hotkey_translate: "Control+Alt+T".to_string(),
             hotkey_voice_action: "Control+Alt+A".to_string(),
+            hotkey_speech_translate: "Control+Alt+E".to_string(),
             engine_type: EngineType::default(),
             vosk_language: None,
-            parakeet_model: ParakeetModelSize::default(),
-            groq_api_key: None,
+            parakeet_model: default_parakeet_model(),
             llm_provider: LlmProvider::default(),
-            local_llm_model: LocalLlmModel::default(),
+            openai_compatible: OpenAiCompatibleConfig::default(),
+            local_llm_model: default_local_llm_model(),
             onboarding_completed: false,
             tour_completed: false,
             system_commands_enabled: false,
@@ -422,10 +557,56 @@ impl Default for AppSettings {
             subtitles_font_size: 20,
             stats_tracking_enabled: true,
             integrations: IntegrationConfig::default(),
+            vad_engine: VadEngine::default(),
+            trim_silence: true,
+            hands_free_enabled: false,
+            hands_free_silence_ms: 1500,
+            diarization_enabled: false,
+            diarization_max_speakers: None,
+            model_catalog_url: None,
+            model_mirror_url: None,
+            local_server: LocalServerConfig::default(),
+            app_rules: default_app_rules(),
+            context_capture: ContextCaptureConfig::default(),
+            audio_retention: AudioRetentionConfig::default(),
+            encrypt_storage: false,
         }
     }
 }
 
+/// Réglage refusé ou réinitialisé ; `field` suit les objets imbriqués avec
+/// des points (`integrations.obsidian_vault_path`), vide pour tout le fichier
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SettingsError {
+    pub field: String,
+    pub message: String,
+}
+
+impl SettingsError {
+    pub fn new(field: &str, message: impl Into<String>) -> Self {
+        Self {
+            field: field.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Champs illisibles de config.json et copie du fichier d'origine
+#[derive(Debug, Clone, Serialize)]
+pub struct SettingsLoadReport {
+    pub errors: Vec<SettingsError>,
+    pub backup_path: Option<String>,
+}
+
+/// Bilan d'un import : champs non repris (valeur actuelle conservée)
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct SettingsImportReport {
+    pub errors: Vec<SettingsError>,
+    pub dictionary_words: usize,
+    pub snippets: usize,
+    pub prompt_profiles: usize,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AudioDevice {
     pub id: String,
@@ -438,11 +619,78 @@ pub struct DictionaryData {
     pub words: Vec<String>,
 }
 
+/// Ancien format de l'historique (history.json), importé dans history.db
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct HistoryData {
     pub transcriptions: Vec<TranscriptionResult>,
 }
 
+/// Transcription enregistrée dans l'historique
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub id: i64,
+    #[serde(flatten)]
+    pub transcription: TranscriptionResult,
+    /// Application active pendant la dictée
+    pub app: Option<String>,
+    pub tags: Vec<String>,
+    pub favorite: bool,
+    /// L'audio de la dictée est conservé (voir `AppSettings::audio_retention`)
+    pub has_audio: bool,
+}
+
+/// Filtres de recherche dans l'historique ; les critères absents sont ignorés
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct HistoryQuery {
+    /// Recherche plein texte (préfixes, sans accents)
+    pub text: Option<String>,
+    /// Horodatages Unix inclus
+    pub from: Option<i64>,
+    pub to: Option<i64>,
+    pub model: Option<String>,
+    pub language: Option<String>,
+    pub app: Option<String>,
+    pub tag: Option<String>,
+    pub favorites_only: bool,
+    pub offset: u32,
+    pub limit: u32,
+}
+
+impl Default for HistoryQuery {
+    fn default() -> Self {
+        Self {
+            text: None,
+            from: None,
+            to: None,
+            model: None,
+            language: None,
+            app: None,
+            tag: None,
+            favorites_only: false,
+            offset: 0,
+            limit: 50,
+        }
+    }
+}
+
+/// Page de résultats, du plus récent au plus ancien
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryPage {
+    pub entries: Vec<HistoryEntry>,
+    /// Nombre total de résultats pour ces filtres
+    pub total: u64,
+}
+
+/// Valeurs présentes dans l'historique, pour les filtres de l'interface
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct HistoryFacets {
+    pub models: Vec<String>,
+    pub languages: Vec<String>,
+    pub apps: Vec<String>,
+    pub tags: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Snippet {
     pub id: String,
@@ -456,6 +704,49 @@ pub struct SnippetsData {
     pub snippets: Vec<Snippet>,
 }
 
+/// Exemple entrée/sortie montré au modèle avant le texte dicté
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PromptExample {
+    pub input: String,
+    pub output: String,
+}
+
+/// Profil de post-traitement LLM : style de réécriture nommé
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PromptProfile {
+    pub id: String,
+    pub name: String,
+    pub system_prompt: String,
+    #[serde(default = "default_profile_temperature")]
+    pub temperature: f32,
+    #[serde(default = "default_profile_max_tokens")]
+    pub max_tokens: u32,
+    /// Provider dédié ; `None` reprend celui des réglages
+    #[serde(default)]
+    pub provider: Option<LlmProvider>,
+    #[serde(default)]
+    pub example: Option<PromptExample>,
+    /// Mode de dictée servi par ce profil en mode LLM contextuel
+    #[serde(default)]
+    pub dictation_mode: Option<DictationMode>,
+    /// Raccourci (maintenir) : dicte puis réécrit avec ce profil
+    #[serde(default)]
+    pub hotkey: Option<String>,
+}
+
+fn default_profile_temperature() -> f32 {
+    0.3
+}
+
+fn default_profile_max_tokens() -> u32 {
+    2048
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PromptProfilesData {
+    pub profiles: Vec<PromptProfile>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct DailyStats {
     pub words: u64,
@@ -481,3 +772,36 @@ pub struct IntegrationConfig {
     #[serde(default)]
     pub obsidian_vault_path: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chat_templates_include_instruction() {
+        let chatml = ChatTemplate::ChatMl.format("Traduis en anglais.", "Bonjour");
+        assert_eq!(
+            chatml,
+            "<|im_start|>system\nTraduis en anglais.<|im_end|>\n<|im_start|>user\nBonjour<|im_end|>\n<|im_start|>assistant\n"
+        );
+
+        let phi = ChatTemplate::Phi3.format("Traduis en anglais.", "Bonjour");
+        assert_eq!(phi, "<|user|>\nTraduis en anglais.\n\nBonjour<|end|>\n<|assistant|>\n");
+
+        let no_system = ChatTemplate::ChatMl.format("  ", "Bonjour");
+        assert!(no_system.starts_with("<|im_start|>user\n"));
+    }
+
+    #[test]
+    fn test_model_ids_keep_settings_format() {
+        // Les anciennes valeurs d'enum sont déjà les identifiants du catalogue
+        let mut json = serde_json::to_value(AppSettings::default()).unwrap();
+        json["vosk_language"] = "fr".into();
+        json["local_llm_model"] = "qwen2_5_3b".into();
+        json.as_object_mut().unwrap().remove("parakeet_model");
+        let settings: AppSettings = serde_json::from_value(json).unwrap();
+        assert_eq!(settings.vosk_language.as_deref(), Some("fr"));
+        assert_eq!(settings.local_llm_model, "qwen2_5_3b");
+        assert_eq!(settings.parakeet_model, DEFAULT_PARAKEET_MODEL);
+    }
+}
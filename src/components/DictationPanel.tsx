@@ -35,7 +35,7 @@ export function DictationPanel() {
         // Transcription finale complète
         setStreamingText(chunk.text);
       } else if (chunk.text) {
-        // Nouveau chunk de texte partiel - afficher le dernier segment transcrit
+        // Texte validé suivi de l'hypothèse en cours
         setStreamingText(chunk.text);
       }
       // Les chunks vides (text: '') sont des indicateurs de durée, on les ignore pour le texte
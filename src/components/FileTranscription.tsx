@@ -1,8 +1,8 @@
 import { useState, useCallback, useEffect } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
-import { open } from '@tauri-apps/plugin-dialog';
-import { FileTranscriptionResult, FileTranscriptionProgress, LlmProvider } from '../types';
+import { open, save } from '@tauri-apps/plugin-dialog';
+import { ExportFormat, FileTranscriptionResult, FileTranscriptionProgress, LlmProvider, TranscriptionResult } from '../types';
 import { useSettingsStore } from '../stores/settingsStore';
 
 interface FileTranscriptionProps {
@@ -11,6 +11,35 @@ interface FileTranscriptionProps {
   initialFiles?: string[];
 }
 
+const EXPORT_FORMATS: { format: ExportFormat; label: string }[] = [
+  { format: 'srt', label: 'SubRip (.srt)' },
+  { format: 'vtt', label: 'WebVTT (.vtt)' },
+  { format: 'ttml', label: 'TTML (.ttml)' },
+  { format: 'json', label: 'JSON (.json)' },
+  { format: 'txt', label: 'Texte (.txt)' },
+];
+
+interface SpeakerEdit {
+  index: number;
+  speaker: string;
+  value: string;
+}
+
+/** Prises de parole consécutives d'un même intervenant */
+function speakerTurns(transcription: TranscriptionResult): { speaker: string; text: string }[] {
+  const turns: { speaker: string; text: string }[] = [];
+  for (const segment of transcription.segments ?? []) {
+    if (!segment.speaker) continue;
+    const last = turns[turns.length - 1];
+    if (last && last.speaker === segment.speaker) {
+      last.text += ` ${segment.text.trim()}`;
+    } else {
+      turns.push({ speaker: segment.speaker, text: segment.text.trim() });
+    }
+  }
+  return turns;
+}
+
 interface SummaryState {
   [key: number]: {
     loading: boolean;
@@ -27,12 +56,19 @@ export function FileTranscription({ isOpen, initialFiles }: FileTranscriptionPro
   const [supportedFormats, setSupportedFormats] = useState<string[]>([]);
   const [summaries, setSummaries] = useState<SummaryState>({});
   const [localLlmAvailable, setLocalLlmAvailable] = useState(false);
+  const [hasGroqKey, setHasGroqKey] = useState(false);
+  const [speakerEdit, setSpeakerEdit] = useState<SpeakerEdit | null>(null);
+  const [translateTo, setTranslateTo] = useState('');
   const settings = useSettingsStore(state => state.settings);
 
   useEffect(() => {
     invoke<string[]>('get_supported_audio_formats').then(setSupportedFormats).catch(console.error);
   }, []);
 
+  useEffect(() => {
+    invoke<boolean>('has_groq_api_key').then(setHasGroqKey).catch(() => setHasGroqKey(false));
+  }, [settings?.llm_provider]);
+
   // Vérifier si le modèle LLM local est disponible
   useEffect(() => {
     if (settings?.local_llm_model) {
@@ -93,6 +129,7 @@ export function FileTranscription({ isOpen, initialFiles }: FileTranscriptionPro
     try {
       const transcriptionResults = await invoke<FileTranscriptionResult[]>('transcribe_files', {
         paths: files,
+        translateTo: translateTo || null,
       });
       setResults(transcriptionResults);
     } catch (e) {
@@ -101,7 +138,7 @@ export function FileTranscription({ isOpen, initialFiles }: FileTranscriptionPro
       setIsProcessing(false);
       setProgress(null);
     }
-  }, [files]);
+  }, [files, translateTo]);
 
   const handleCopyResult = useCallback((text: string) => {
     navigator.clipboard.writeText(text);
@@ -149,6 +186,37 @@ export function FileTranscription({ isOpen, initialFiles }: FileTranscriptionPro
     }
   }, []);
 
+  const handleExport = useCallback(async (result: FileTranscriptionResult, format: ExportFormat) => {
+    const baseName = result.file_name.replace(/\.[^.]+$/, '');
+    try {
+      const path = await save({
+        defaultPath: `${baseName}.${format}`,
+        filters: [{ name: format.toUpperCase(), extensions: [format] }],
+      });
+      if (!path) return;
+      await invoke('export_transcription', { result, format, path });
+    } catch (e) {
+      console.error(`Failed to export as ${format}:`, e);
+    }
+  }, []);
+
+  const handleRenameSpeaker = useCallback(async (edit: SpeakerEdit) => {
+    setSpeakerEdit(null);
+    const result = results[edit.index];
+    if (!result?.transcription || !edit.value.trim() || edit.value.trim() === edit.speaker) return;
+    try {
+      const transcription = await invoke<TranscriptionResult>('rename_speaker', {
+        result: result.transcription,
+        from: edit.speaker,
+        to: edit.value,
+        historyId: result.history_id ?? null,
+      });
+      setResults(prev => prev.map((r, i) => (i === edit.index ? { ...r, transcription } : r)));
+    } catch (e) {
+      console.error('Failed to rename speaker:', e);
+    }
+  }, [results]);
+
   if (!isOpen) return null;
 
   return (
@@ -240,7 +308,18 @@ export function FileTranscription({ isOpen, initialFiles }: FileTranscriptionPro
               <div className="flex items-center gap-3">
                 <div className="led-frost processing" />
                 <span className="text-[0.9375rem] text-[var(--text-primary)] font-medium">
-                  {progress.status === 'transcribing' ? 'Transcription en cours...' : progress.status}
+                  {progress.status === 'transcribing'
+                    ? 'Transcription en cours...'
+                    : progress.status === 'diarizing'
+                      ? 'Identification des intervenants...'
+                      : progress.status === 'translating'
+                        ? 'Traduction en cours...'
+                        : progress.status}
+                  {progress.total_chunks && progress.total_chunks > 1 && (
+                    <span className="text-[var(--text-muted)]">
+                      {' '}(segment {Math.min((progress.chunk ?? 0) + 1, progress.total_chunks)}/{progress.total_chunks})
+                    </span>
+                  )}
                 </span>
               </div>
               <span className="tag-frost accent">
@@ -250,7 +329,11 @@ export function FileTranscription({ isOpen, initialFiles }: FileTranscriptionPro
             <div className="progress-frost">
               <div
                 className="bar"
-                style={{ width: `${(progress.current / progress.total) * 100}%` }}
+                style={{
+                  width: `${(progress.total_chunks
+                    ? (progress.current - 1 + (progress.chunk ?? 0) / progress.total_chunks) / progress.total
+                    : progress.current / progress.total) * 100}%`,
+                }}
               />
             </div>
             {progress.file_name && (
@@ -261,6 +344,26 @@ export function FileTranscription({ isOpen, initialFiles }: FileTranscriptionPro
           </div>
         )}
 
+        {/* Translation target */}
+        {files.length > 0 && !isProcessing && (
+          <div className="animate-fade-in-up">
+            <label className="text-[0.8rem] text-[var(--text-muted)] mb-2 block">Traduire en</label>
+            <select
+              value={translateTo}
+              onChange={(e) => setTranslateTo(e.target.value)}
+              className="select-glass"
+            >
+              <option value="">Pas de traduction</option>
+              <option value="en">English</option>
+              <option value="fr">Francais</option>
+              <option value="de">Deutsch</option>
+              <option value="es">Espanol</option>
+              <option value="it">Italiano</option>
+              <option value="pt">Portugues</option>
+            </select>
+          </div>
+        )}
+
         {/* Transcribe button */}
         {files.length > 0 && !isProcessing && (
           <button
@@ -312,7 +415,7 @@ export function FileTranscription({ isOpen, initialFiles }: FileTranscriptionPro
                             <div className="w-3 h-3 border-2 border-current border-t-transparent rounded-full animate-spin" />
                             Resume...
                           </button>
-                        ) : localLlmAvailable && settings?.groq_api_key ? (
+                        ) : localLlmAvailable && hasGroqKey ? (
                           // Les deux providers sont disponibles - afficher un dropdown
                           <div className="relative group">
                             <button
@@ -350,9 +453,9 @@ export function FileTranscription({ isOpen, initialFiles }: FileTranscriptionPro
                           // Un seul provider disponible
                           <button
                             onClick={() => handleSummarize(index, result.transcription!.text)}
-                            disabled={!localLlmAvailable && !settings?.groq_api_key}
+                            disabled={!localLlmAvailable && !hasGroqKey}
                             className="btn-glass text-[0.75rem] py-1.5 px-3 disabled:opacity-50"
-                            title={localLlmAvailable ? 'Resume (local)' : settings?.groq_api_key ? 'Resume (cloud)' : 'Configurez un LLM dans les parametres'}
+                            title={localLlmAvailable ? 'Resume (local)' : hasGroqKey ? 'Resume (cloud)' : 'Configurez un LLM dans les parametres'}
                           >
                             <svg width="12" height="12" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2">
                               <path d="M14 2H6a2 2 0 0 0-2 2v16a2 2 0 0 0 2 2h12a2 2 0 0 0 2-2V8z" />
@@ -360,7 +463,7 @@ export function FileTranscription({ isOpen, initialFiles }: FileTranscriptionPro
                               <line x1="16" y1="13" x2="8" y2="13" />
                               <line x1="16" y1="17" x2="8" y2="17" />
                             </svg>
-                            Resumer {localLlmAvailable ? '(Local)' : settings?.groq_api_key ? '(Cloud)' : ''}
+                            Resumer {localLlmAvailable ? '(Local)' : hasGroqKey ? '(Cloud)' : ''}
                           </button>
                         )}
                         <button
@@ -373,6 +476,27 @@ export function FileTranscription({ isOpen, initialFiles }: FileTranscriptionPro
                           </svg>
                           Copier
                         </button>
+                        <div className="relative group">
+                          <button className="btn-glass text-[0.75rem] py-1.5 px-3">
+                            <svg width="12" height="12" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2">
+                              <path d="M21 15v4a2 2 0 0 1-2 2H5a2 2 0 0 1-2-2v-4" />
+                              <polyline points="7 10 12 15 17 10" />
+                              <line x1="12" y1="15" x2="12" y2="3" />
+                            </svg>
+                            Exporter
+                          </button>
+                          <div className="absolute top-full right-0 mt-1 py-1 min-w-[140px] bg-[var(--glass-bg)] backdrop-blur-xl border border-[var(--glass-border)] rounded-lg shadow-lg opacity-0 invisible group-hover:opacity-100 group-hover:visible transition-all z-10">
+                            {EXPORT_FORMATS.map(({ format, label }) => (
+                              <button
+                                key={format}
+                                onClick={() => handleExport(result, format)}
+                                className="w-full px-3 py-2 text-left text-[0.75rem] text-[var(--text-secondary)] hover:bg-[rgba(255,255,255,0.08)]"
+                              >
+                                {label}
+                              </button>
+                            ))}
+                          </div>
+                        </div>
                         {(settings?.integrations?.apple_notes_enabled || settings?.integrations?.obsidian_enabled) && (
                           <div className="relative group">
                             <button className="btn-glass text-[0.75rem] py-1.5 px-3">
@@ -410,6 +534,35 @@ export function FileTranscription({ isOpen, initialFiles }: FileTranscriptionPro
                   <div className="card-content space-y-4">
                     {result.error ? (
                       <p className="text-[var(--accent-danger)] text-[0.9375rem]">{result.error}</p>
+                    ) : result.transcription && speakerTurns(result.transcription).length > 0 ? (
+                      <div className="space-y-3">
+                        {speakerTurns(result.transcription).map((turn, turnIndex) => (
+                          <p key={turnIndex} className="text-[var(--text-secondary)] text-[0.9375rem] leading-relaxed">
+                            {speakerEdit?.index === index && speakerEdit.speaker === turn.speaker ? (
+                              <input
+                                autoFocus
+                                value={speakerEdit.value}
+                                onChange={(e) => setSpeakerEdit({ ...speakerEdit, value: e.target.value })}
+                                onBlur={() => handleRenameSpeaker(speakerEdit)}
+                                onKeyDown={(e) => {
+                                  if (e.key === 'Enter') handleRenameSpeaker(speakerEdit);
+                                  if (e.key === 'Escape') setSpeakerEdit(null);
+                                }}
+                                className="input-glass text-[0.8rem] py-0.5 px-2 mr-2 w-36"
+                              />
+                            ) : (
+                              <button
+                                onClick={() => setSpeakerEdit({ index, speaker: turn.speaker, value: turn.speaker })}
+                                className="tag-frost accent mr-2"
+                                title="Renommer l'intervenant"
+                              >
+                                {turn.speaker}
+                              </button>
+                            )}
+                            {turn.text}
+                          </p>
+                        ))}
+                      </div>
                     ) : result.transcription ? (
                       <p className="text-[var(--text-secondary)] text-[0.9375rem] leading-relaxed whitespace-pre-wrap">
                         {result.transcription.text}
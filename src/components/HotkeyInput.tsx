@@ -4,9 +4,11 @@ interface HotkeyInputProps {
   value: string;
   onChange: (hotkey: string) => void;
   disabled?: boolean;
+  /** Raccourci facultatif : affiche un bouton pour le désactiver */
+  onClear?: () => void;
 }
 
-export function HotkeyInput({ value, onChange, disabled }: HotkeyInputProps) {
+export function HotkeyInput({ value, onChange, disabled, onClear }: HotkeyInputProps) {
   const [isRecording, setIsRecording] = useState(false);
   const [currentKeys, setCurrentKeys] = useState<Set<string>>(new Set());
 
@@ -95,11 +97,16 @@ export function HotkeyInput({ value, onChange, disabled }: HotkeyInputProps) {
     setCurrentKeys(new Set());
   };
 
+  const handleClear = (e: React.MouseEvent) => {
+    e.stopPropagation();
+    onClear?.();
+  };
+
   const displayValue = isRecording
     ? currentKeys.size > 0
       ? formatHotkey(currentKeys)
       : 'Appuyez sur les touches...'
-    : value;
+    : value || 'Desactive';
 
   return (
     <div className="relative">
@@ -126,6 +133,13 @@ export function HotkeyInput({ value, onChange, disabled }: HotkeyInputProps) {
             >
               Annuler
             </button>
+          ) : onClear && value ? (
+            <button
+              onClick={handleClear}
+              className="text-[0.65rem] uppercase tracking-wider text-[var(--text-muted)] hover:underline"
+            >
+              Desactiver
+            </button>
           ) : (
             <svg width="14" height="14" viewBox="0 0 24 24" fill="none" stroke="var(--text-muted)" strokeWidth="2">
               <path d="M11 4H4a2 2 0 0 0-2 2v14a2 2 0 0 0 2 2h14a2 2 0 0 0 2-2v-7" />
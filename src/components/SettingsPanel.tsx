@@ -12,7 +12,9 @@ import {
   ShortcutsSection,
   DictionarySection,
   SnippetsSection,
+  PromptProfilesSection,
   IntegrationsSection,
+  BackupSection,
 } from './settings';
 import { StatisticsPanel } from './StatisticsPanel';
 import logoSvg from '../assets/logo.svg';
@@ -25,6 +27,13 @@ interface SettingsPanelProps {
 export function SettingsPanel({ isOpen, onClose }: SettingsPanelProps) {
   const { settings, devices, dictionary, loadSettings, loadDevices, loadDictionary, updateSettings, addWord, removeWord } = useSettingsStore();
   const [apiKeyStatus, setApiKeyStatus] = useState<'valid' | 'invalid' | null>(null);
+  const [importCount, setImportCount] = useState(0);
+
+  const handleImported = () => {
+    loadSettings();
+    loadDictionary();
+    setImportCount((count) => count + 1);
+  };
 
   useEffect(() => {
     if (isOpen) {
@@ -82,7 +91,9 @@ export function SettingsPanel({ isOpen, onClose }: SettingsPanelProps) {
           <IntegrationsSection settings={settings} updateSettings={updateSettings} />
           <ShortcutsSection settings={settings} updateSettings={updateSettings} />
           <DictionarySection dictionary={dictionary} addWord={addWord} removeWord={removeWord} />
-          <SnippetsSection />
+          <SnippetsSection key={`snippets-${importCount}`} />
+          <PromptProfilesSection key={`prompts-${importCount}`} />
+          <BackupSection onImported={handleImported} />
         </div>
 
         {/* Footer */}
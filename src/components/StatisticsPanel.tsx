@@ -1,5 +1,6 @@
 import { useState, useEffect, useCallback } from 'react';
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import { UsageStats } from '../types';
 
 export function StatisticsPanel() {
@@ -16,6 +17,11 @@ export function StatisticsPanel() {
 
   useEffect(() => { loadStats(); }, [loadStats]);
 
+  useEffect(() => {
+    const unlisten = listen<UsageStats>('stats-updated', (event) => setStats(event.payload));
+    return () => { unlisten.then(fn => fn()); };
+  }, []);
+
   const handleReset = async () => {
     try {
       await invoke('reset_stats');
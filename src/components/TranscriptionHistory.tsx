@@ -1,8 +1,22 @@
-import { useEffect, useState, useCallback } from 'react';
+import { useEffect, useState, useCallback, useRef } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { useTranscriptionStore } from '../stores/transcriptionStore';
 import { useSettingsStore } from '../stores/settingsStore';
-import { LlmProvider } from '../types';
+import {
+  EngineType,
+  HistoryFacets,
+  HistoryQuery,
+  LlmProvider,
+  ModelInfo,
+  ParakeetModelInfo,
+  VoskModelInfo,
+} from '../types';
+
+interface EngineOption {
+  engine: EngineType;
+  model: string;
+  label: string;
+}
 
 interface SummaryState {
   [key: number]: {
@@ -13,15 +27,113 @@ interface SummaryState {
 }
 
 export function TranscriptionHistory() {
-  const { history, loadHistory, clearHistory } = useTranscriptionStore();
+  const {
+    history,
+    historyTotal,
+    historyQuery,
+    loadHistory,
+    loadMoreHistory,
+    deleteHistoryEntry,
+    toggleFavorite,
+    setHistoryTags,
+    retranscribeHistoryEntry,
+  } = useTranscriptionStore();
   const settings = useSettingsStore(state => state.settings);
   const [summaries, setSummaries] = useState<SummaryState>({});
   const [localLlmAvailable, setLocalLlmAvailable] = useState(false);
+  const [hasGroqKey, setHasGroqKey] = useState(false);
+  const [search, setSearch] = useState(historyQuery.text ?? '');
+  const [facets, setFacets] = useState<HistoryFacets>({ models: [], languages: [], apps: [], tags: [] });
+  const [taggingId, setTaggingId] = useState<number | null>(null);
+  const [tagInput, setTagInput] = useState('');
+  const [engineOptions, setEngineOptions] = useState<EngineOption[]>([]);
+  const [retranscribing, setRetranscribing] = useState<{ [id: number]: string | null }>({});
+  const audioRef = useRef<HTMLAudioElement | null>(null);
+
+  // Modeles installes, proposes pour une nouvelle transcription
+  useEffect(() => {
+    Promise.all([
+      invoke<ModelInfo[]>('get_available_models'),
+      invoke<VoskModelInfo[]>('get_vosk_models'),
+      invoke<ParakeetModelInfo[]>('get_parakeet_models'),
+    ]).then(([whisper, vosk, parakeet]) => {
+      setEngineOptions([
+        ...whisper.filter((m) => m.available).map((m) => ({ engine: 'whisper' as const, model: m.size, label: `Whisper ${m.display_name}` })),
+        ...parakeet.filter((m) => m.available).map((m) => ({ engine: 'parakeet' as const, model: m.size, label: m.display_name })),
+        ...vosk.filter((m) => m.available).map((m) => ({ engine: 'vosk' as const, model: m.language, label: `Vosk ${m.display_name}` })),
+      ]);
+    }).catch((e) => console.error('Failed to load models:', e));
+  }, []);
+
+  const handlePlay = useCallback(async (id: number) => {
+    try {
+      const bytes = await invoke<ArrayBuffer>('get_history_audio', { id });
+      const url = URL.createObjectURL(new Blob([bytes], { type: 'audio/flac' }));
+      audioRef.current?.pause();
+      const audio = new Audio(url);
+      audio.onended = () => URL.revokeObjectURL(url);
+      audioRef.current = audio;
+      await audio.play();
+    } catch (e) {
+      console.error('Failed to play audio:', e);
+    }
+  }, []);
+
+  const handleRetranscribe = async (id: number, option: EngineOption) => {
+    setRetranscribing((prev) => ({ ...prev, [id]: '' }));
+    try {
+      await retranscribeHistoryEntry(id, option.engine, option.model);
+      setRetranscribing((prev) => ({ ...prev, [id]: null }));
+    } catch (e) {
+      setRetranscribing((prev) => ({ ...prev, [id]: String(e) }));
+    }
+  };
 
   useEffect(() => {
     loadHistory();
+    invoke<HistoryFacets>('get_history_facets').then(setFacets).catch(() => {});
   }, [loadHistory]);
 
+  // Recherche plein texte après une courte pause de frappe
+  const submittedSearch = useRef(search);
+  useEffect(() => {
+    if (search === submittedSearch.current) return;
+    const timer = setTimeout(() => {
+      submittedSearch.current = search;
+      loadHistory({ ...useTranscriptionStore.getState().historyQuery, text: search || null });
+    }, 250);
+    return () => clearTimeout(timer);
+  }, [search, loadHistory]);
+
+  const updateQuery = (patch: Partial<HistoryQuery>) => loadHistory({ ...historyQuery, ...patch });
+
+  const toTimestamp = (date: string, endOfDay: boolean) =>
+    date ? Math.floor(new Date(`${date}T${endOfDay ? '23:59:59' : '00:00:00'}`).getTime() / 1000) : null;
+  const toDateInput = (timestamp?: number | null) => {
+    if (timestamp == null) return '';
+    const date = new Date(timestamp * 1000);
+    return `${date.getFullYear()}-${String(date.getMonth() + 1).padStart(2, '0')}-${String(date.getDate()).padStart(2, '0')}`;
+  };
+
+  const hasFilters = Boolean(
+    historyQuery.text || historyQuery.from || historyQuery.to || historyQuery.model
+    || historyQuery.language || historyQuery.app || historyQuery.tag || historyQuery.favorites_only
+  );
+
+  const handleAddTag = (id: number, tags: string[]) => {
+    const tag = tagInput.trim();
+    if (tag && !tags.includes(tag)) {
+      setHistoryTags(id, [...tags, tag]);
+      setFacets((current) => current.tags.includes(tag) ? current : { ...current, tags: [...current.tags, tag].sort() });
+    }
+    setTagInput('');
+    setTaggingId(null);
+  };
+
+  useEffect(() => {
+    invoke<boolean>('has_groq_api_key').then(setHasGroqKey).catch(() => setHasGroqKey(false));
+  }, [settings?.llm_provider]);
+
   // Vérifier si le modèle LLM local est disponible
   useEffect(() => {
     if (settings?.local_llm_model) {
@@ -31,22 +143,22 @@ export function TranscriptionHistory() {
     }
   }, [settings?.local_llm_model]);
 
-  const handleSummarize = useCallback(async (index: number, text: string, provider?: LlmProvider) => {
+  const handleSummarize = useCallback(async (id: number, text: string, provider?: LlmProvider) => {
     setSummaries(prev => ({
       ...prev,
-      [index]: { loading: true, text: null, error: null }
+      [id]: { loading: true, text: null, error: null }
     }));
 
     try {
       const summary = await invoke<string>('summarize_text_smart', { text, provider });
       setSummaries(prev => ({
         ...prev,
-        [index]: { loading: false, text: summary, error: null }
+        [id]: { loading: false, text: summary, error: null }
       }));
     } catch (e) {
       setSummaries(prev => ({
         ...prev,
-        [index]: { loading: false, text: null, error: String(e) }
+        [id]: { loading: false, text: null, error: String(e) }
       }));
     }
   }, []);
@@ -77,7 +189,7 @@ export function TranscriptionHistory() {
     });
   };
 
-  if (history.length === 0) {
+  if (history.length === 0 && !hasFilters) {
     return (
       <div className="h-full flex flex-col items-center justify-center p-8 text-center animate-fade-in-up">
         <div className="w-20 h-20 rounded-3xl bg-[rgba(255,255,255,0.06)] backdrop-blur-xl border border-[var(--glass-border)] flex items-center justify-center mb-5 shadow-lg">
@@ -95,32 +207,81 @@ export function TranscriptionHistory() {
   return (
     <div className="h-full flex flex-col overflow-hidden">
       {/* Header */}
-      <div className="flex-shrink-0 px-5 py-4 bg-[rgba(255,255,255,0.02)] border-b border-[rgba(255,255,255,0.06)] flex justify-between items-center">
+      <div className="flex-shrink-0 px-5 py-4 bg-[rgba(255,255,255,0.02)] border-b border-[rgba(255,255,255,0.06)] space-y-3">
         <div className="flex items-center gap-4">
           <span className="text-[0.875rem] text-[var(--text-secondary)] font-medium">
             Historique
           </span>
           <span className="tag-frost accent">
-            {history.length}
+            {historyTotal}
           </span>
+          <input
+            type="search"
+            value={search}
+            onChange={(e) => setSearch(e.target.value)}
+            placeholder="Rechercher..."
+            className="input-glass flex-1"
+          />
+          <button
+            onClick={() => updateQuery({ favorites_only: !historyQuery.favorites_only })}
+            className={`btn-glass px-3 ${historyQuery.favorites_only ? 'text-[var(--accent-warning)] border-[var(--accent-warning)]' : ''}`}
+            title="Favoris uniquement"
+          >
+            ★
+          </button>
+        </div>
+        <div className="flex flex-wrap items-center gap-2">
+          <input
+            type="date"
+            value={toDateInput(historyQuery.from)}
+            onChange={(e) => updateQuery({ from: toTimestamp(e.target.value, false) })}
+            className="input-glass text-[0.75rem] py-1"
+            title="Depuis le"
+          />
+          <input
+            type="date"
+            value={toDateInput(historyQuery.to)}
+            onChange={(e) => updateQuery({ to: toTimestamp(e.target.value, true) })}
+            className="input-glass text-[0.75rem] py-1"
+            title="Jusqu'au"
+          />
+          {([
+            ['language', 'Langue', facets.languages],
+            ['model', 'Modele', facets.models],
+            ['app', 'Application', facets.apps],
+            ['tag', 'Etiquette', facets.tags],
+          ] as const).map(([key, label, values]) => values.length > 0 && (
+            <select
+              key={key}
+              value={historyQuery[key] ?? ''}
+              onChange={(e) => updateQuery({ [key]: e.target.value || null })}
+              className="select-glass text-[0.75rem] py-1"
+            >
+              <option value="">{label}</option>
+              {values.map((value) => (
+                <option key={value} value={value}>{value}</option>
+              ))}
+            </select>
+          ))}
+          {hasFilters && (
+            <button
+              onClick={() => { submittedSearch.current = ''; setSearch(''); loadHistory({}); }}
+              className="btn-glass text-[0.75rem] py-1 px-2"
+            >
+              Reinitialiser
+            </button>
+          )}
         </div>
-        <button
-          onClick={clearHistory}
-          className="btn-glass text-[var(--accent-danger)] border-[var(--accent-danger-soft)] hover:bg-[var(--accent-danger-soft)]"
-        >
-          <svg width="14" height="14" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2">
-            <polyline points="3 6 5 6 21 6" />
-            <path d="M19 6v14a2 2 0 0 1-2 2H7a2 2 0 0 1-2-2V6m3 0V4a2 2 0 0 1 2-2h4a2 2 0 0 1 2 2v2" />
-          </svg>
-          Effacer
-        </button>
       </div>
 
       {/* List */}
       <div className="flex-1 overflow-y-auto p-5 space-y-4 scrollbar-thin stagger-children">
-        {history.map((item, index) => (
+        {history.length === 0 && (
+          <p className="text-center text-[var(--text-muted)] text-sm py-8">Aucun resultat</p>
+        )}
+        {history.map((item) => (
           <div
-            key={`${item.timestamp}-${index}`}
+            key={item.id}
             className="result-card-frost cursor-default"
           >
             {/* Item header */}
@@ -135,17 +296,62 @@ export function TranscriptionHistory() {
                     {item.model_used}
                   </span>
                 )}
+                {item.app && (
+                  <span className="tag-frost text-[0.6rem]">
+                    {item.app}
+                  </span>
+                )}
+                {item.translation && (
+                  <span className="tag-frost accent text-[0.6rem]">
+                    {(item.translation.source_language ?? '?').toUpperCase()} → {item.translation.target_language.toUpperCase()}
+                  </span>
+                )}
               </div>
               <div className="flex items-center gap-2">
+                <button
+                  onClick={() => toggleFavorite(item)}
+                  className={`text-[0.85rem] transition-colors ${item.favorite ? 'text-[var(--accent-warning)]' : 'text-[var(--text-muted)] hover:text-[var(--accent-warning)]'}`}
+                  title={item.favorite ? 'Retirer des favoris' : 'Ajouter aux favoris'}
+                >
+                  {item.favorite ? '★' : '☆'}
+                </button>
+                {item.has_audio && (
+                  <>
+                    <button
+                      onClick={() => handlePlay(item.id)}
+                      className="btn-glass text-[0.7rem] py-1 px-2"
+                      title="Reecouter"
+                    >
+                      ▶
+                    </button>
+                    {engineOptions.length > 0 && (
+                      <select
+                        value=""
+                        disabled={retranscribing[item.id] === ''}
+                        onChange={(e) => {
+                          const option = engineOptions[Number(e.target.value)];
+                          if (option) handleRetranscribe(item.id, option);
+                        }}
+                        className="select-glass text-[0.7rem] py-1 disabled:opacity-50"
+                        title="Transcrire a nouveau"
+                      >
+                        <option value="">{retranscribing[item.id] === '' ? 'Transcription...' : 'Retranscrire'}</option>
+                        {engineOptions.map((option, i) => (
+                          <option key={`${option.engine}-${option.model}`} value={i}>{option.label}</option>
+                        ))}
+                      </select>
+                    )}
+                  </>
+                )}
                 {/* Bouton résumé */}
-                {summaries[index]?.loading ? (
+                {summaries[item.id]?.loading ? (
                   <button
                     disabled
                     className="btn-glass text-[0.7rem] py-1 px-2 opacity-50"
                   >
                     <div className="w-3 h-3 border-2 border-current border-t-transparent rounded-full animate-spin" />
                   </button>
-                ) : localLlmAvailable && settings?.groq_api_key ? (
+                ) : localLlmAvailable && hasGroqKey ? (
                   <div className="relative group">
                     <button className="btn-glass text-[0.7rem] py-1 px-2 flex items-center gap-1">
                       <svg width="10" height="10" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2">
@@ -159,14 +365,14 @@ export function TranscriptionHistory() {
                     </button>
                     <div className="absolute top-full right-0 mt-1 py-1 min-w-[120px] bg-[var(--glass-bg)] backdrop-blur-xl border border-[var(--glass-border)] rounded-lg shadow-lg opacity-0 invisible group-hover:opacity-100 group-hover:visible transition-all z-10">
                       <button
-                        onClick={() => handleSummarize(index, item.text, 'local')}
+                        onClick={() => handleSummarize(item.id, item.text, 'local')}
                         className="w-full px-3 py-1.5 text-left text-[0.7rem] text-[var(--text-secondary)] hover:bg-[rgba(255,255,255,0.08)] flex items-center gap-2"
                       >
                         <span className="w-1.5 h-1.5 rounded-full bg-green-500" />
                         Local
                       </button>
                       <button
-                        onClick={() => handleSummarize(index, item.text, 'groq')}
+                        onClick={() => handleSummarize(item.id, item.text, 'groq')}
                         className="w-full px-3 py-1.5 text-left text-[0.7rem] text-[var(--text-secondary)] hover:bg-[rgba(255,255,255,0.08)] flex items-center gap-2"
                       >
                         <span className="w-1.5 h-1.5 rounded-full bg-blue-500" />
@@ -174,9 +380,9 @@ export function TranscriptionHistory() {
                       </button>
                     </div>
                   </div>
-                ) : (localLlmAvailable || settings?.groq_api_key) ? (
+                ) : (localLlmAvailable || hasGroqKey) ? (
                   <button
-                    onClick={() => handleSummarize(index, item.text)}
+                    onClick={() => handleSummarize(item.id, item.text)}
                     className="btn-glass text-[0.7rem] py-1 px-2"
                     title={localLlmAvailable ? 'Resume (local)' : 'Resume (cloud)'}
                   >
@@ -224,6 +430,16 @@ export function TranscriptionHistory() {
                     </div>
                   </div>
                 )}
+                <button
+                  onClick={() => deleteHistoryEntry(item.id)}
+                  className="text-[var(--text-muted)] hover:text-[var(--accent-danger)] transition-colors"
+                  title="Supprimer"
+                >
+                  <svg width="12" height="12" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2">
+                    <polyline points="3 6 5 6 21 6" />
+                    <path d="M19 6v14a2 2 0 0 1-2 2H7a2 2 0 0 1-2-2V6m3 0V4a2 2 0 0 1 2-2h4a2 2 0 0 1 2 2v2" />
+                  </svg>
+                </button>
               </div>
             </div>
 
@@ -233,15 +449,56 @@ export function TranscriptionHistory() {
                 {item.text}
               </p>
 
+              {/* Etiquettes */}
+              <div className="flex flex-wrap items-center gap-1.5">
+                {item.tags.map((tag) => (
+                  <span key={tag} className="tag-frost text-[0.6rem] flex items-center gap-1">
+                    <button onClick={() => updateQuery({ tag })} title="Filtrer par etiquette">#{tag}</button>
+                    <button
+                      onClick={() => setHistoryTags(item.id, item.tags.filter((t) => t !== tag))}
+                      className="opacity-60 hover:opacity-100"
+                      title="Retirer"
+                    >
+                      ✕
+                    </button>
+                  </span>
+                ))}
+                {taggingId === item.id ? (
+                  <input
+                    autoFocus
+                    value={tagInput}
+                    onChange={(e) => setTagInput(e.target.value)}
+                    onKeyDown={(e) => {
+                      if (e.key === 'Enter') handleAddTag(item.id, item.tags);
+                      if (e.key === 'Escape') setTaggingId(null);
+                    }}
+                    onBlur={() => handleAddTag(item.id, item.tags)}
+                    placeholder="etiquette"
+                    className="input-glass text-[0.7rem] py-0.5 px-2 w-28"
+                  />
+                ) : (
+                  <button
+                    onClick={() => { setTaggingId(item.id); setTagInput(''); }}
+                    className="text-[0.65rem] text-[var(--text-muted)] hover:text-[var(--accent-primary)]"
+                  >
+                    + etiquette
+                  </button>
+                )}
+              </div>
+
+              {retranscribing[item.id] && (
+                <p className="text-[0.75rem] text-[var(--accent-danger)]">{retranscribing[item.id]}</p>
+              )}
+
               {/* Erreur de résumé */}
-              {summaries[index]?.error && (
+              {summaries[item.id]?.error && (
                 <div className="p-2 rounded-lg bg-[var(--accent-danger-soft)] border border-[var(--accent-danger)]">
-                  <p className="text-[0.75rem] text-[var(--accent-danger)]">{summaries[index].error}</p>
+                  <p className="text-[0.75rem] text-[var(--accent-danger)]">{summaries[item.id].error}</p>
                 </div>
               )}
 
               {/* Affichage du résumé */}
-              {summaries[index]?.text && (
+              {summaries[item.id]?.text && (
                 <div className="p-3 rounded-xl bg-[rgba(139,92,246,0.08)] border border-[var(--accent-primary-soft)]">
                   <div className="flex items-center justify-between mb-2">
                     <div className="flex items-center gap-2">
@@ -253,7 +510,7 @@ export function TranscriptionHistory() {
                       <span className="text-[0.7rem] font-medium text-[var(--accent-primary)]">Resume</span>
                     </div>
                     <button
-                      onClick={() => handleCopySummary(summaries[index].text!)}
+                      onClick={() => handleCopySummary(summaries[item.id].text!)}
                       className="text-[var(--text-muted)] hover:text-[var(--accent-primary)] transition-colors"
                       title="Copier le resume"
                     >
@@ -264,13 +521,18 @@ export function TranscriptionHistory() {
                     </button>
                   </div>
                   <p className="text-[var(--text-primary)] text-[0.8rem] leading-relaxed">
-                    {summaries[index].text}
+                    {summaries[item.id].text}
                   </p>
                 </div>
               )}
             </div>
           </div>
         ))}
+        {history.length < historyTotal && (
+          <button onClick={loadMoreHistory} className="btn-glass w-full justify-center text-[0.8rem]">
+            Charger plus ({historyTotal - history.length})
+          </button>
+        )}
       </div>
     </div>
   );
@@ -171,13 +171,14 @@ export function ModelStep({ onValidChange }: StepProps) {
     }
   };
 
-  const qualityLabels: Record<ModelSize, string> = {
+  // Les modeles ajoutes par le catalogue (large-v3-turbo...) prennent le style "medium"
+  const qualityLabels: Record<string, string> = {
     tiny: 'Basique',
     small: 'Bonne',
     medium: 'Tres bonne',
   };
 
-  const qualityColors: Record<ModelSize, string> = {
+  const qualityColors: Record<string, string> = {
     tiny: 'var(--text-muted)',
     small: 'var(--accent-primary)',
     medium: 'var(--accent-success)',
@@ -244,8 +245,8 @@ export function ModelStep({ onValidChange }: StepProps) {
                       ? 'bg-[rgba(124,138,255,0.15)]'
                       : 'bg-[rgba(122,239,178,0.15)]'
                   }`}>
-                    <span className="text-[0.8rem] font-medium" style={{ color: qualityColors[model.size] }}>
-                      {model.size === 'tiny' ? 'T' : model.size === 'small' ? 'S' : 'M'}
+                    <span className="text-[0.8rem] font-medium" style={{ color: qualityColors[model.size] ?? qualityColors.medium }}>
+                      {model.size.charAt(0).toUpperCase()}
                     </span>
                   </div>
                   <div>
@@ -261,7 +262,7 @@ export function ModelStep({ onValidChange }: StepProps) {
                       )}
                     </div>
                     <span className="text-[0.7rem]" style={{ color: qualityColors[model.size] }}>
-                      Qualite: {qualityLabels[model.size]}
+                      Qualite: {qualityLabels[model.size] ?? qualityLabels.medium}
                     </span>
                   </div>
                 </div>
@@ -1,4 +1,4 @@
-import { AppSettings, AudioDevice } from '../../types';
+import { AppSettings, AudioDevice, VadEngine } from '../../types';
 
 interface AudioSectionProps {
   settings: AppSettings;
@@ -27,6 +27,55 @@ export function AudioSection({ settings, devices, updateSettings }: AudioSection
             ))}
           </select>
         </label>
+
+        <label className="block">
+          <span className="text-[0.8rem] text-[rgba(255,255,255,0.75)] mb-2 block">Detection de la voix</span>
+          <select
+            value={settings.vad_engine}
+            onChange={(e) => updateSettings({ vad_engine: e.target.value as VadEngine })}
+            className="select-glass"
+          >
+            <option value="energy">Energie (integre)</option>
+            <option value="silero">Silero VAD (modele ONNX)</option>
+          </select>
+        </label>
+
+        <label className="checkbox-frost">
+          <input
+            type="checkbox"
+            checked={settings.trim_silence}
+            onChange={(e) => updateSettings({ trim_silence: e.target.checked })}
+          />
+          <span className="check-box" />
+          <span className="check-label">Supprimer les silences en debut et fin d'enregistrement</span>
+        </label>
+
+        <label className="checkbox-frost">
+          <input
+            type="checkbox"
+            checked={settings.hands_free_enabled}
+            onChange={(e) => updateSettings({ hands_free_enabled: e.target.checked })}
+          />
+          <span className="check-box" />
+          <span className="check-label">Mains libres : arreter l'enregistrement apres un silence</span>
+        </label>
+
+        {settings.hands_free_enabled && (
+          <label className="block">
+            <span className="text-[0.8rem] text-[rgba(255,255,255,0.75)] mb-2 block">
+              Duree du silence : {(settings.hands_free_silence_ms / 1000).toFixed(1)}s
+            </span>
+            <input
+              type="range"
+              min={500}
+              max={5000}
+              step={250}
+              value={settings.hands_free_silence_ms}
+              onChange={(e) => updateSettings({ hands_free_silence_ms: Number(e.target.value) })}
+              className="w-full"
+            />
+          </label>
+        )}
       </div>
     </section>
   );
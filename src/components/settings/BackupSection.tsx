@@ -0,0 +1,115 @@
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { open, save } from '@tauri-apps/plugin-dialog';
+import { SettingsError, SettingsImportReport, SettingsLoadReport } from '../../types';
+
+interface BackupSectionProps {
+  onImported: () => void;
+}
+
+function ErrorList({ errors }: { errors: SettingsError[] }) {
+  return (
+    <ul className="space-y-1">
+      {errors.map((error, i) => (
+        <li key={`${error.field}-${i}`} className="text-[0.75rem] text-[var(--text-muted)]">
+          <span className="text-[var(--text-secondary)]">{error.field || 'config.json'}</span> : {error.message}
+        </li>
+      ))}
+    </ul>
+  );
+}
+
+export function BackupSection({ onImported }: BackupSectionProps) {
+  const [loadReport, setLoadReport] = useState<SettingsLoadReport | null>(null);
+  const [importReport, setImportReport] = useState<SettingsImportReport | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [busy, setBusy] = useState(false);
+
+  useEffect(() => {
+    invoke<SettingsLoadReport | null>('get_settings_load_report')
+      .then(setLoadReport)
+      .catch((e) => console.error('Failed to load settings report:', e));
+  }, []);
+
+  const handleExport = async () => {
+    setError(null);
+    try {
+      const date = new Date().toISOString().slice(0, 10);
+      const path = await save({
+        defaultPath: `phonon-reglages-${date}.zip`,
+        filters: [{ name: 'Archive', extensions: ['zip'] }],
+      });
+      if (!path) return;
+      await invoke('export_settings', { path });
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const handleImport = async () => {
+    setError(null);
+    setImportReport(null);
+    try {
+      const path = await open({
+        multiple: false,
+        filters: [{ name: 'Archive', extensions: ['zip'] }],
+      });
+      if (!path || Array.isArray(path)) return;
+      setBusy(true);
+      const report = await invoke<SettingsImportReport>('import_settings', { path });
+      setImportReport(report);
+      onImported();
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  return (
+    <section className="space-y-4">
+      <h3 className="section-title">Sauvegarde</h3>
+
+      {loadReport && (
+        <div className="p-3 rounded-xl border border-[var(--accent-danger)] space-y-2">
+          <p className="text-[0.8rem] text-[var(--text-primary)]">
+            Certains reglages illisibles ont ete remis a leur valeur par defaut.
+          </p>
+          <ErrorList errors={loadReport.errors} />
+          {loadReport.backup_path && (
+            <p className="text-[0.75rem] text-[var(--text-muted)] break-all">Copie du fichier d'origine : {loadReport.backup_path}</p>
+          )}
+        </div>
+      )}
+
+      <p className="text-[0.75rem] text-[var(--text-muted)]">
+        Reglages, dictionnaire, snippets et profils de prompts dans une archive. Les cles API n'y figurent pas.
+      </p>
+
+      <div className="flex gap-2">
+        <button onClick={handleExport} className="btn-glass text-[0.8rem] py-2 px-3">
+          Exporter
+        </button>
+        <button onClick={handleImport} disabled={busy} className="btn-glass text-[0.8rem] py-2 px-3 disabled:opacity-50">
+          {busy ? 'Import...' : 'Importer'}
+        </button>
+      </div>
+
+      {error && <p className="text-[0.75rem] text-[var(--accent-danger)]">{error}</p>}
+
+      {importReport && (
+        <div className="space-y-2">
+          <p className="text-[0.75rem] text-[var(--text-muted)]">
+            Importe : {importReport.dictionary_words} mots, {importReport.snippets} snippets, {importReport.prompt_profiles} profils.
+          </p>
+          {importReport.errors.length > 0 && (
+            <>
+              <p className="text-[0.75rem] text-[var(--text-secondary)]">Valeurs actuelles conservees :</p>
+              <ErrorList errors={importReport.errors} />
+            </>
+          )}
+        </div>
+      )}
+    </section>
+  );
+}
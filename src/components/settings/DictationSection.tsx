@@ -1,11 +1,38 @@
-import { AppSettings } from '../../types';
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { AppRule, AppSettings, DictationMode, PromptProfile, VoiceCommandSet } from '../../types';
 
 interface DictationSectionProps {
   settings: AppSettings;
   updateSettings: (settings: Partial<AppSettings>) => Promise<void>;
 }
 
+const EMPTY_RULE: AppRule = {
+  app: '',
+  dictation_mode: null,
+  llm_profile: null,
+  language: null,
+  voice_commands: null,
+};
+
 export function DictationSection({ settings, updateSettings }: DictationSectionProps) {
+  const [profiles, setProfiles] = useState<PromptProfile[]>([]);
+
+  useEffect(() => {
+    invoke<PromptProfile[]>('get_prompt_profiles')
+      .then(setProfiles)
+      .catch((e) => console.error('Failed to load prompt profiles:', e));
+  }, []);
+
+  const updateRule = (index: number, patch: Partial<AppRule>) => {
+    const app_rules = settings.app_rules.map((rule, i) => (i === index ? { ...rule, ...patch } : rule));
+    updateSettings({ app_rules });
+  };
+
+  const removeRule = (index: number) => {
+    updateSettings({ app_rules: settings.app_rules.filter((_, i) => i !== index) });
+  };
+
   return (
     <section className="space-y-4">
       <h3 className="section-title secondary">Mode de dictee</h3>
@@ -66,6 +93,85 @@ export function DictationSection({ settings, updateSettings }: DictationSectionP
           <span className="check-box" />
           <span className="check-label">Sous-titres en direct</span>
         </label>
+
+        <div>
+          <label className="text-[0.8rem] text-[var(--text-muted)] mb-2 block">Regles par application</label>
+          <p className="text-[0.7rem] text-[var(--text-muted)] mb-3">
+            Appliquees au debut du push-to-talk selon l'application active (classe de fenetre, ex: code, thunderbird). Linux X11 uniquement pour l'instant.
+          </p>
+          <div className="space-y-2">
+            {settings.app_rules.map((rule, index) => (
+              <div key={index} className="glass-card p-3 space-y-2">
+                <div className="flex gap-2">
+                  <input
+                    type="text"
+                    value={rule.app}
+                    onChange={(e) => updateRule(index, { app: e.target.value })}
+                    placeholder="Application"
+                    className="input-glass flex-1"
+                  />
+                  <button
+                    onClick={() => removeRule(index)}
+                    className="p-1.5 rounded-lg hover:bg-[rgba(255,255,255,0.08)] text-[var(--text-muted)] hover:text-[var(--accent-danger)] transition-colors"
+                  >
+                    <svg width="14" height="14" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2">
+                      <line x1="18" y1="6" x2="6" y2="18" />
+                      <line x1="6" y1="6" x2="18" y2="18" />
+                    </svg>
+                  </button>
+                </div>
+                <div className="grid grid-cols-2 gap-2">
+                  <select
+                    value={rule.dictation_mode ?? ''}
+                    onChange={(e) => updateRule(index, { dictation_mode: (e.target.value || null) as DictationMode | null })}
+                    className="select-glass"
+                  >
+                    <option value="">Mode inchange</option>
+                    <option value="general">General</option>
+                    <option value="email">Email</option>
+                    <option value="code">Code</option>
+                    <option value="notes">Notes</option>
+                  </select>
+                  <select
+                    value={rule.llm_profile ?? ''}
+                    onChange={(e) => updateRule(index, { llm_profile: e.target.value || null })}
+                    className="select-glass"
+                  >
+                    <option value="">Profil LLM inchange</option>
+                    {profiles.map((profile) => (
+                      <option key={profile.id} value={profile.id}>{profile.name}</option>
+                    ))}
+                  </select>
+                  <input
+                    type="text"
+                    value={rule.language ?? ''}
+                    onChange={(e) => updateRule(index, { language: e.target.value.trim() || null })}
+                    placeholder="Langue (fr, en, auto)"
+                    className="input-glass"
+                  />
+                  <select
+                    value={rule.voice_commands ?? ''}
+                    onChange={(e) => updateRule(index, { voice_commands: (e.target.value || null) as VoiceCommandSet | null })}
+                    className="select-glass"
+                  >
+                    <option value="">Commandes du mode</option>
+                    <option value="off">Aucune commande</option>
+                    <option value="general">Commandes General</option>
+                    <option value="email">Commandes Email</option>
+                    <option value="code">Commandes Code</option>
+                    <option value="notes">Commandes Notes</option>
+                  </select>
+                </div>
+              </div>
+            ))}
+          </div>
+          <button
+            onClick={() => updateSettings({ app_rules: [...settings.app_rules, EMPTY_RULE] })}
+            className="btn-glass px-4 mt-2 text-[var(--accent-primary)]"
+          >
+            Ajouter une regle
+          </button>
+        </div>
       </div>
     </section>
   );
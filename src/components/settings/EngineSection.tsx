@@ -1,6 +1,7 @@
 import { useEffect, useState } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
+import { open } from '@tauri-apps/plugin-dialog';
 import {
   AppSettings,
   ModelSize,
@@ -30,6 +31,12 @@ export function EngineSection({ settings, updateSettings }: EngineSectionProps)
   const [parakeetModels, setParakeetModels] = useState<ParakeetModelInfo[]>([]);
   const [downloadingParakeet, setDownloadingParakeet] = useState<ParakeetModelSize | null>(null);
   const [parakeetDownloadProgress, setParakeetDownloadProgress] = useState<DownloadProgress | null>(null);
+  const [catalogUrl, setCatalogUrl] = useState(settings.model_catalog_url ?? '');
+  const [refreshingCatalog, setRefreshingCatalog] = useState(false);
+  const [catalogError, setCatalogError] = useState<string | null>(null);
+  const [mirrorUrl, setMirrorUrl] = useState(settings.model_mirror_url ?? '');
+  const [importing, setImporting] = useState(false);
+  const [importMessage, setImportMessage] = useState<string | null>(null);
 
   const loadModels = async () => {
     try {
@@ -125,6 +132,14 @@ export function EngineSection({ settings, updateSettings }: EngineSectionProps)
     }
   };
 
+  const handleCancelDownload = async (downloadId: string) => {
+    try {
+      await invoke('cancel_model_download', { downloadId });
+    } catch (e) {
+      console.error('Cancel failed:', e);
+    }
+  };
+
   const handleSwitchModel = async (size: ModelSize) => {
     try {
       await invoke('switch_model', { size });
@@ -198,6 +213,65 @@ export function EngineSection({ settings, updateSettings }: EngineSectionProps)
     }
   };
 
+  const handleRefreshCatalog = async () => {
+    setRefreshingCatalog(true);
+    setCatalogError(null);
+    try {
+      const url = catalogUrl.trim() || null;
+      if (url !== settings.model_catalog_url) {
+        await updateSettings({ model_catalog_url: url });
+      }
+      await invoke('refresh_model_catalog');
+      await Promise.all([loadModels(), loadVoskModels(), loadParakeetModels()]);
+    } catch (e) {
+      setCatalogError(String(e));
+    } finally {
+      setRefreshingCatalog(false);
+    }
+  };
+
+  const handleSaveMirror = async () => {
+    const url = mirrorUrl.trim() || null;
+    if (url !== settings.model_mirror_url) {
+      await updateSettings({ model_mirror_url: url });
+    }
+  };
+
+  // Whisper : fichier ggml enregistre comme nouveau modele ;
+  // Vosk / Parakeet : dossier remplacant le modele selectionne
+  // (sans modele Vosk selectionne, enregistre comme nouveau modele)
+  const handleImportModel = async () => {
+    const engine = settings.engine_type;
+    const selected = await open(
+      engine === 'whisper'
+        ? { multiple: false, filters: [{ name: 'Modele ggml', extensions: ['bin', 'gguf'] }] }
+        : { directory: true, multiple: false }
+    );
+    if (!selected || typeof selected !== 'string') return;
+
+    const id =
+      engine === 'vosk'
+        ? settings.vosk_language
+        : engine === 'parakeet'
+          ? settings.parakeet_model
+          : null;
+    setImporting(true);
+    setImportMessage(null);
+    try {
+      const imported = await invoke<{ display_name: string }>('import_model', {
+        path: selected,
+        engine,
+        id,
+      });
+      setImportMessage(`${imported.display_name} importe`);
+      await Promise.all([loadModels(), loadVoskModels(), loadParakeetModels()]);
+    } catch (e) {
+      setImportMessage(String(e));
+    } finally {
+      setImporting(false);
+    }
+  };
+
   return (
     <section className="space-y-4">
       <h3 className="section-title success">Moteur de transcription</h3>
@@ -259,6 +333,13 @@ export function EngineSection({ settings, updateSettings }: EngineSectionProps)
                     <span className="text-[0.75rem] text-[var(--text-muted)] w-12 text-right tabular-nums">
                       {Math.round(downloadProgress?.percent || 0)}%
                     </span>
+                    <button
+                      onClick={() => handleCancelDownload(`whisper:${model.size}`)}
+                      className="text-[0.75rem] text-[var(--text-muted)] hover:text-[var(--accent-danger)]"
+                      title="Annuler (reprise possible)"
+                    >
+                      ✕
+                    </button>
                   </div>
                 ) : model.available ? (
                   <div className="flex items-center gap-3">
@@ -334,6 +415,13 @@ export function EngineSection({ settings, updateSettings }: EngineSectionProps)
                       <div className="w-12 progress-frost">
                         <div className="bar" style={{ width: `${voskDownloadProgress?.percent || 0}%` }} />
                       </div>
+                      <button
+                        onClick={() => handleCancelDownload(`vosk:${model.language}`)}
+                        className="text-[0.65rem] text-[var(--text-muted)] hover:text-[var(--accent-danger)]"
+                        title="Annuler (reprise possible)"
+                      >
+                        ✕
+                      </button>
                     </div>
                   ) : model.available ? (
                     settings.vosk_language === model.language ? (
@@ -403,6 +491,13 @@ export function EngineSection({ settings, updateSettings }: EngineSectionProps)
                     <span className="text-[0.75rem] text-[var(--text-muted)] w-12 text-right tabular-nums">
                       {Math.round(parakeetDownloadProgress?.percent || 0)}%
                     </span>
+                    <button
+                      onClick={() => handleCancelDownload(`parakeet:${model.size}`)}
+                      className="text-[0.75rem] text-[var(--text-muted)] hover:text-[var(--accent-danger)]"
+                      title="Annuler (reprise possible)"
+                    >
+                      ✕
+                    </button>
                   </div>
                 ) : model.available ? (
                   <div className="flex items-center gap-3">
@@ -444,6 +539,59 @@ export function EngineSection({ settings, updateSettings }: EngineSectionProps)
           ))}
         </div>
       )}
+
+      {/* Model catalog */}
+      <div className="space-y-2 pt-2">
+        <label className="text-[0.75rem] text-[var(--text-muted)]">
+          Catalogue de modeles distant (optionnel)
+        </label>
+        <div className="flex gap-2">
+          <input
+            type="text"
+            value={catalogUrl}
+            onChange={(e) => setCatalogUrl(e.target.value)}
+            placeholder="https://exemple.com/catalog.json"
+            className="input-glass flex-1"
+          />
+          <button
+            onClick={handleRefreshCatalog}
+            disabled={refreshingCatalog}
+            className="btn-glass text-[0.8rem]"
+          >
+            {refreshingCatalog ? 'Actualisation...' : 'Rafraichir'}
+          </button>
+        </div>
+        {catalogError && (
+          <p className="text-[0.75rem] text-[var(--accent-danger)]">{catalogError}</p>
+        )}
+      </div>
+
+      {/* Private mirror & local import */}
+      <div className="space-y-2">
+        <label className="text-[0.75rem] text-[var(--text-muted)]">
+          Miroir prive (remplace huggingface.co, alphacephei.com...)
+        </label>
+        <input
+          type="text"
+          value={mirrorUrl}
+          onChange={(e) => setMirrorUrl(e.target.value)}
+          onBlur={handleSaveMirror}
+          placeholder="http://miroir.local/modeles"
+          className="input-glass w-full"
+        />
+        <div className="flex items-center gap-3">
+          <button
+            onClick={handleImportModel}
+            disabled={importing}
+            className="btn-glass text-[0.8rem]"
+          >
+            {importing ? 'Import...' : 'Importer un modele local'}
+          </button>
+          {importMessage && (
+            <span className="text-[0.75rem] text-[var(--text-muted)]">{importMessage}</span>
+          )}
+        </div>
+      </div>
     </section>
   );
 }
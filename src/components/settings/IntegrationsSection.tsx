@@ -1,5 +1,8 @@
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
 import { open } from '@tauri-apps/plugin-dialog';
-import { AppSettings } from '../../types';
+import { writeText } from '@tauri-apps/plugin-clipboard-manager';
+import { AppSettings, LocalServerConfig, LocalServerStatus } from '../../types';
 
 interface IntegrationsSectionProps {
   settings: AppSettings;
@@ -8,6 +11,40 @@ interface IntegrationsSectionProps {
 
 export function IntegrationsSection({ settings, updateSettings }: IntegrationsSectionProps) {
   const isMacOS = navigator.userAgent.includes('Mac');
+  const [serverStatus, setServerStatus] = useState<LocalServerStatus | null>(null);
+  const [serverToken, setServerToken] = useState<string | null>(null);
+  const [showToken, setShowToken] = useState(false);
+
+  const loadServerStatus = async () => {
+    try {
+      setServerStatus(await invoke<LocalServerStatus>('get_local_server_status'));
+    } catch (e) {
+      console.error('Failed to load local server status:', e);
+    }
+  };
+
+  useEffect(() => {
+    loadServerStatus();
+  }, [settings.local_server.enabled, settings.local_server.port]);
+
+  useEffect(() => {
+    if (settings.local_server.enabled && serverToken === null) {
+      invoke<string>('get_local_server_token')
+        .then(setServerToken)
+        .catch((e) => console.error('Failed to load server token:', e));
+    }
+  }, [settings.local_server.enabled]);
+
+  const updateServer = (config: Partial<LocalServerConfig>) =>
+    updateSettings({ local_server: { ...settings.local_server, ...config } });
+
+  const handleRegenerateToken = async () => {
+    try {
+      setServerToken(await invoke<string>('regenerate_local_server_token'));
+    } catch (e) {
+      console.error('Failed to regenerate server token:', e);
+    }
+  };
 
   const handleSelectVault = async () => {
     try {
@@ -85,6 +122,73 @@ export function IntegrationsSection({ settings, updateSettings }: IntegrationsSe
           </div>
         )}
       </div>
+
+      {/* Local transcription server */}
+      <div className="space-y-3">
+        <label className="checkbox-frost">
+          <input
+            type="checkbox"
+            checked={settings.local_server.enabled}
+            onChange={(e) => updateServer({ enabled: e.target.checked })}
+          />
+          <span className="check-box" />
+          <span className="check-label">Serveur local (API compatible OpenAI)</span>
+        </label>
+
+        {settings.local_server.enabled && (
+          <div className="ml-6 space-y-2">
+            <div className="flex gap-2 items-center">
+              <span className="text-[0.8rem] text-[var(--text-muted)]">Port</span>
+              <input
+                type="number"
+                min={1024}
+                max={65535}
+                value={settings.local_server.port}
+                onChange={(e) => {
+                  const port = parseInt(e.target.value, 10);
+                  if (port >= 1024 && port <= 65535) updateServer({ port });
+                }}
+                className="input-glass w-28 text-[0.8rem]"
+              />
+            </div>
+            {serverStatus?.error ? (
+              <p className="text-[0.7rem] text-[var(--accent-danger)]">{serverStatus.error}</p>
+            ) : serverStatus?.url ? (
+              <p className="text-[0.7rem] text-[var(--text-muted)]">
+                {serverStatus.url}/v1/audio/transcriptions
+              </p>
+            ) : null}
+            {serverToken && (
+              <div className="flex gap-2 items-center">
+                <input
+                  type={showToken ? 'text' : 'password'}
+                  value={serverToken}
+                  readOnly
+                  className="input-glass flex-1 text-[0.8rem] font-mono"
+                />
+                <button
+                  onClick={() => setShowToken(!showToken)}
+                  className="btn-glass px-3 text-[0.8rem]"
+                >
+                  {showToken ? 'Masquer' : 'Afficher'}
+                </button>
+                <button
+                  onClick={() => writeText(serverToken)}
+                  className="btn-glass px-3 text-[0.8rem]"
+                >
+                  Copier
+                </button>
+                <button
+                  onClick={handleRegenerateToken}
+                  className="btn-glass px-3 text-[0.8rem] text-[var(--accent-danger)]"
+                >
+                  Regenerer
+                </button>
+              </div>
+            )}
+          </div>
+        )}
+      </div>
     </section>
   );
 }
@@ -4,10 +4,15 @@ import { listen } from '@tauri-apps/api/event';
 import { openUrl } from '@tauri-apps/plugin-opener';
 import {
   AppSettings,
+  ContextCaptureConfig,
   LocalLlmModel,
+  LocalLlmModelInfo,
   DownloadProgress,
   LlmDownloadProgress,
   GroqQuota,
+  HttpHeader,
+  OpenAiCompatibleConfig,
+  PromptProfile,
 } from '../../types';
 
 interface LlmSectionProps {
@@ -21,10 +26,22 @@ export function LlmSection({ settings, updateSettings, onApiKeyStatusChange }: L
   const [showApiKey, setShowApiKey] = useState(false);
   const [apiKeyStatus, setApiKeyStatus] = useState<'valid' | 'invalid' | null>(null);
   const [groqQuota, setGroqQuota] = useState<GroqQuota | null>(null);
-  const [llmModelsAvailable, setLlmModelsAvailable] = useState<LocalLlmModel[]>([]);
+  const [llmModels, setLlmModels] = useState<LocalLlmModelInfo[]>([]);
   const [downloadingLlm, setDownloadingLlm] = useState<LocalLlmModel | null>(null);
   const [llmDownloadProgress, setLlmDownloadProgress] = useState<DownloadProgress | null>(null);
   const [llmDownloadError, setLlmDownloadError] = useState<string | null>(null);
+  const [customApiKey, setCustomApiKey] = useState('');
+  const [hasCustomApiKey, setHasCustomApiKey] = useState(false);
+  const [customTest, setCustomTest] = useState<{ ok: boolean; message: string } | null>(null);
+  const [testingCustom, setTestingCustom] = useState(false);
+  const [promptProfiles, setPromptProfiles] = useState<PromptProfile[]>([]);
+
+  useEffect(() => {
+    if (settings.llm_mode !== 'profile') return;
+    invoke<PromptProfile[]>('get_prompt_profiles')
+      .then(setPromptProfiles)
+      .catch((e) => console.error('Failed to load prompt profiles:', e));
+  }, [settings.llm_mode]);
 
   // Propager le statut de la clé API au parent
   useEffect(() => {
@@ -33,8 +50,8 @@ export function LlmSection({ settings, updateSettings, onApiKeyStatusChange }: L
 
   const loadLlmModels = async () => {
     try {
-      const result = await invoke<LocalLlmModel[]>('get_available_llm_models');
-      setLlmModelsAvailable(result);
+      const result = await invoke<LocalLlmModelInfo[]>('get_llm_models');
+      setLlmModels(result);
     } catch (e) {
       console.error('Failed to load LLM models:', e);
     }
@@ -71,6 +88,7 @@ export function LlmSection({ settings, updateSettings, onApiKeyStatusChange }: L
     loadLlmModels();
     checkApiKey();
     loadGroqQuota();
+    invoke<boolean>('has_openai_compatible_api_key').then(setHasCustomApiKey).catch(console.error);
   }, []);
 
   useEffect(() => {
@@ -111,6 +129,47 @@ export function LlmSection({ settings, updateSettings, onApiKeyStatusChange }: L
     }
   };
 
+  const updateCustomConfig = (patch: Partial<OpenAiCompatibleConfig>) => {
+    setCustomTest(null);
+    updateSettings({ openai_compatible: { ...settings.openai_compatible, ...patch } });
+  };
+
+  const updateContextCapture = (patch: Partial<ContextCaptureConfig>) => {
+    updateSettings({ context_capture: { ...settings.context_capture, ...patch } });
+  };
+
+  const updateCustomHeader = (index: number, patch: Partial<HttpHeader>) => {
+    updateCustomConfig({
+      headers: settings.openai_compatible.headers.map((h, i) => (i === index ? { ...h, ...patch } : h)),
+    });
+  };
+
+  const handleSaveCustomApiKey = async () => {
+    try {
+      if (customApiKey) {
+        await invoke('set_openai_compatible_api_key', { key: customApiKey });
+      } else {
+        await invoke('delete_openai_compatible_api_key');
+      }
+      setHasCustomApiKey(!!customApiKey);
+      setCustomApiKey('');
+    } catch (e) {
+      console.error('Failed to save API key:', e);
+    }
+  };
+
+  const handleTestCustomProvider = async () => {
+    setTestingCustom(true);
+    try {
+      const reply = await invoke<string>('test_openai_compatible_provider', { config: settings.openai_compatible });
+      setCustomTest({ ok: true, message: `Connexion reussie : ${reply}` });
+    } catch (e) {
+      setCustomTest({ ok: false, message: String(e) });
+    } finally {
+      setTestingCustom(false);
+    }
+  };
+
   const handleDownloadLlmModel = async (size: LocalLlmModel) => {
     setDownloadingLlm(size);
     setLlmDownloadError(null);
@@ -122,7 +181,10 @@ export function LlmSection({ settings, updateSettings, onApiKeyStatusChange }: L
       await loadLlmModels();
     } catch (e) {
       console.error('LLM download failed:', e);
-      setLlmDownloadError(String(e));
+      // Une annulation volontaire n'est pas une erreur : le fichier partiel sera repris
+      if (String(e) !== 'Téléchargement annulé') {
+        setLlmDownloadError(String(e));
+      }
     } finally {
       setDownloadingLlm(null);
       setLlmDownloadProgress(null);
@@ -173,9 +235,111 @@ export function LlmSection({ settings, updateSettings, onApiKeyStatusChange }: L
                   <span className="w-2 h-2 rounded-full bg-green-500" />
                   Local
                 </button>
+                <button
+                  onClick={() => updateSettings({ llm_provider: 'openai_compatible' })}
+                  className={`btn-glass flex-1 ${settings.llm_provider === 'openai_compatible' ? 'border-[var(--accent-primary)] bg-[var(--accent-primary-soft)]' : ''}`}
+                >
+                  <span className="w-2 h-2 rounded-full bg-purple-500" />
+                  Serveur OpenAI
+                </button>
               </div>
             </div>
 
+            {/* OpenAI-compatible server (Ollama, llama.cpp, LM Studio, passerelle) */}
+            {settings.llm_provider === 'openai_compatible' && (
+              <div className="space-y-3">
+                <div>
+                  <label className="text-[0.8rem] text-[var(--text-muted)] mb-2 block">URL de base</label>
+                  <input
+                    type="text"
+                    value={settings.openai_compatible.base_url}
+                    onChange={(e) => updateCustomConfig({ base_url: e.target.value })}
+                    placeholder="http://localhost:11434/v1"
+                    className="input-glass w-full"
+                  />
+                </div>
+                <div>
+                  <label className="text-[0.8rem] text-[var(--text-muted)] mb-2 block">Modele</label>
+                  <input
+                    type="text"
+                    value={settings.openai_compatible.model}
+                    onChange={(e) => updateCustomConfig({ model: e.target.value })}
+                    placeholder="llama3.2"
+                    className="input-glass w-full"
+                  />
+                </div>
+                <div>
+                  <label className="text-[0.8rem] text-[var(--text-muted)] mb-2 block">
+                    Cle API (optionnelle) {hasCustomApiKey && <span className="tag-frost success text-[0.65rem] ml-2">Enregistree</span>}
+                  </label>
+                  <div className="flex gap-2">
+                    <input
+                      type="password"
+                      value={customApiKey}
+                      onChange={(e) => setCustomApiKey(e.target.value)}
+                      placeholder={hasCustomApiKey ? '••••••••••••••••' : 'sk-...'}
+                      className="input-glass flex-1"
+                    />
+                    <button onClick={handleSaveCustomApiKey} className="btn-glass px-3 text-[var(--accent-success)]">
+                      ✓
+                    </button>
+                  </div>
+                </div>
+                <div>
+                  <label className="text-[0.8rem] text-[var(--text-muted)] mb-2 block">En-tetes HTTP</label>
+                  <div className="space-y-2">
+                    {settings.openai_compatible.headers.map((header, index) => (
+                      <div key={index} className="flex gap-2">
+                        <input
+                          type="text"
+                          value={header.name}
+                          onChange={(e) => updateCustomHeader(index, { name: e.target.value })}
+                          placeholder="X-Header"
+                          className="input-glass flex-1"
+                        />
+                        <input
+                          type="text"
+                          value={header.value}
+                          onChange={(e) => updateCustomHeader(index, { value: e.target.value })}
+                          placeholder="valeur"
+                          className="input-glass flex-1"
+                        />
+                        <button
+                          onClick={() => updateCustomConfig({
+                            headers: settings.openai_compatible.headers.filter((_, i) => i !== index),
+                          })}
+                          className="btn-glass px-3"
+                          title="Supprimer"
+                        >
+                          ✕
+                        </button>
+                      </div>
+                    ))}
+                    <button
+                      onClick={() => updateCustomConfig({
+                        headers: [...settings.openai_compatible.headers, { name: '', value: '' }],
+                      })}
+                      className="btn-glass text-[0.75rem] py-1 px-2"
+                    >
+                      + Ajouter un en-tete
+                    </button>
+                  </div>
+                </div>
+                <button
+                  onClick={handleTestCustomProvider}
+                  disabled={testingCustom}
+                  className="btn-glass text-[0.75rem] py-1.5 px-3 disabled:opacity-50"
+                >
+                  {testingCustom ? 'Test en cours...' : 'Tester la connexion'}
+                </button>
+                {customTest && (
+                  <p className={`text-[0.75rem] ${customTest.ok ? 'text-[var(--accent-success)]' : 'text-[var(--accent-danger)]'}`}>
+                    {customTest.ok ? '✓' : '✗'} {customTest.message}
+                  </p>
+                )}
+              </div>
+            )}
+
             {/* Groq Configuration */}
             {settings.llm_provider === 'groq' && (
               <div>
@@ -279,15 +443,9 @@ export function LlmSection({ settings, updateSettings, onApiKeyStatusChange }: L
                 <div>
                   <label className="text-[0.8rem] text-[var(--text-muted)] mb-3 block">Modele LLM Local</label>
                   <div className="space-y-2">
-                    {(['smollm2_360m', 'phi3_mini', 'qwen2_5_3b'] as LocalLlmModel[]).map((size) => {
-                      const isAvailable = llmModelsAvailable.includes(size);
+                    {llmModels.map(({ size, display_name: displayName, available: isAvailable }) => {
                       const isDownloading = downloadingLlm === size;
                       const isSelected = settings.local_llm_model === size;
-                      const displayName = size === 'smollm2_360m'
-                        ? 'SmolLM2 360M (386 MB) - Rapide'
-                        : size === 'phi3_mini'
-                        ? 'Phi-3 Mini (2.2 GB) - Recommande'
-                        : 'Qwen2.5 3B (2 GB) - Qualite';
 
                       return (
                         <div
@@ -325,6 +483,13 @@ export function LlmSection({ settings, updateSettings, onApiKeyStatusChange }: L
                                 <span className="text-[0.7rem] text-[var(--text-muted)]">
                                   {llmDownloadProgress?.percent || 0}%
                                 </span>
+                                <button
+                                  onClick={() => invoke('cancel_model_download', { downloadId: `llm:${size}` })}
+                                  className="text-[0.7rem] text-[var(--text-muted)] hover:text-[var(--accent-danger)]"
+                                  title="Annuler (reprise possible)"
+                                >
+                                  ✕
+                                </button>
                               </div>
                             ) : isAvailable ? (
                               <button
@@ -360,7 +525,7 @@ export function LlmSection({ settings, updateSettings, onApiKeyStatusChange }: L
                   </div>
                 )}
 
-                {llmModelsAvailable.length === 0 && !llmDownloadError && (
+                {!llmModels.some((model) => model.available) && !llmDownloadError && (
                   <div className="glass-card p-4 border-[var(--accent-warning)]">
                     <p className="text-[0.8rem] text-[var(--accent-warning)]">
                       ⚠️ Aucun modele LLM local installe. Telechargez un modele ci-dessus.
@@ -373,7 +538,7 @@ export function LlmSection({ settings, updateSettings, onApiKeyStatusChange }: L
             <div>
               <label className="text-[0.8rem] text-[var(--text-muted)] mb-3 block">Mode de correction</label>
               <div className="space-y-2">
-                {(['basic', 'smart', 'contextual'] as const).map((mode) => (
+                {(['basic', 'smart', 'contextual', 'profile'] as const).map((mode) => (
                   <label key={mode} className="radio-frost">
                     <input
                       type="radio"
@@ -385,10 +550,59 @@ export function LlmSection({ settings, updateSettings, onApiKeyStatusChange }: L
                       {mode === 'basic' && 'Basique - ponctuation et grammaire'}
                       {mode === 'smart' && 'Intelligent - reformulation claire'}
                       {mode === 'contextual' && 'Contextuel - adapte au mode de dictee'}
+                      {mode === 'profile' && 'Profil personnalise'}
                     </span>
                   </label>
                 ))}
               </div>
+              {settings.llm_mode === 'profile' && (
+                <select
+                  value={settings.llm_profile ?? ''}
+                  onChange={(e) => updateSettings({ llm_profile: e.target.value || null })}
+                  className="select-glass mt-3"
+                >
+                  <option value="">Choisir un profil...</option>
+                  {promptProfiles.map((profile) => (
+                    <option key={profile.id} value={profile.id}>{profile.name}</option>
+                  ))}
+                </select>
+              )}
+            </div>
+
+            {/* Contexte de l'application active */}
+            <div className="space-y-2">
+              <label className="checkbox-frost">
+                <input
+                  type="checkbox"
+                  checked={settings.context_capture.enabled}
+                  onChange={(e) => updateContextCapture({ enabled: e.target.checked })}
+                />
+                <span className="check-box" />
+                <span className="check-label">Transmettre le contexte de l'application active</span>
+              </label>
+              <p className="text-[0.75rem] text-[var(--text-muted)]">
+                Capture au debut du push-to-talk pour adapter la reecriture a la conversation en cours. Ces informations sont envoyees au provider LLM.
+              </p>
+              {settings.context_capture.enabled && (
+                <div className="space-y-2 pl-4">
+                  {([
+                    ['app_name', "Nom de l'application"],
+                    ['window_title', 'Titre de la fenetre'],
+                    ['selected_text', 'Texte selectionne, envoye tel quel au provider (copie puis restaure le presse-papiers)'],
+                    ['redact', 'Masquer emails, liens, numeros et cles'],
+                  ] as const).map(([key, label]) => (
+                    <label key={key} className="checkbox-frost">
+                      <input
+                        type="checkbox"
+                        checked={settings.context_capture[key]}
+                        onChange={(e) => updateContextCapture({ [key]: e.target.checked })}
+                      />
+                      <span className="check-box" />
+                      <span className="check-label">{label}</span>
+                    </label>
+                  ))}
+                </div>
+              )}
             </div>
           </>
         )}
@@ -1,4 +1,6 @@
-import { AppSettings } from '../../types';
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { AppSettings, AudioRetentionConfig, SecretsProtection } from '../../types';
 
 interface OptionsSectionProps {
   settings: AppSettings;
@@ -6,6 +8,32 @@ interface OptionsSectionProps {
 }
 
 export function OptionsSection({ settings, updateSettings }: OptionsSectionProps) {
+  const updateRetention = (patch: Partial<AudioRetentionConfig>) => {
+    updateSettings({ audio_retention: { ...settings.audio_retention, ...patch } });
+  };
+
+  const [encryptionError, setEncryptionError] = useState<string | null>(null);
+  const [encrypting, setEncrypting] = useState(false);
+  const [protection, setProtection] = useState<SecretsProtection | null>(null);
+
+  useEffect(() => {
+    invoke<SecretsProtection>('get_secrets_protection')
+      .then(setProtection)
+      .catch((e) => console.error('Failed to load secrets protection:', e));
+  }, []);
+
+  const updateEncryption = async (enabled: boolean) => {
+    setEncrypting(true);
+    setEncryptionError(null);
+    try {
+      await updateSettings({ encrypt_storage: enabled });
+    } catch (e) {
+      setEncryptionError(String(e));
+    } finally {
+      setEncrypting(false);
+    }
+  };
+
   return (
     <section className="space-y-4">
       <h3 className="section-title">Options</h3>
@@ -40,6 +68,61 @@ export function OptionsSection({ settings, updateSettings }: OptionsSectionProps
           <span className="check-box" />
           <span className="check-label">Minimiser dans la barre systeme</span>
         </label>
+
+        <label className="checkbox-frost">
+          <input
+            type="checkbox"
+            checked={settings.audio_retention.enabled}
+            onChange={(e) => updateRetention({ enabled: e.target.checked })}
+          />
+          <span className="check-box" />
+          <span className="check-label">Conserver l'audio des dictees (reecoute, nouvelle transcription)</span>
+        </label>
+
+        {settings.audio_retention.enabled && (
+          <div className="grid grid-cols-2 gap-3 pl-4">
+            <div>
+              <label className="text-[0.8rem] text-[var(--text-muted)] mb-2 block">Duree max (jours, 0 = illimitee)</label>
+              <input
+                type="number"
+                min={0}
+                value={settings.audio_retention.max_age_days}
+                onChange={(e) => updateRetention({ max_age_days: Math.max(0, Number(e.target.value) || 0) })}
+                className="input-glass w-full"
+              />
+            </div>
+            <div>
+              <label className="text-[0.8rem] text-[var(--text-muted)] mb-2 block">Taille max (Mo, 0 = illimitee)</label>
+              <input
+                type="number"
+                min={0}
+                value={settings.audio_retention.max_size_mb}
+                onChange={(e) => updateRetention({ max_size_mb: Math.max(0, Number(e.target.value) || 0) })}
+                className="input-glass w-full"
+              />
+            </div>
+          </div>
+        )}
+
+        <label className="checkbox-frost">
+          <input
+            type="checkbox"
+            checked={settings.encrypt_storage}
+            disabled={encrypting}
+            onChange={(e) => updateEncryption(e.target.checked)}
+          />
+          <span className="check-box" />
+          <span className="check-label">Chiffrer l'historique et l'audio conserve (cle dans le trousseau)</span>
+        </label>
+        {protection === 'machine' && (
+          <p className="text-[0.75rem] text-[var(--accent-danger)] pl-4">
+            Pas de trousseau systeme : les cles API et la cle de chiffrement sont dans un coffre lie a cette machine, non chiffre.
+            Definissez SCRIBE_VAULT_PASSPHRASE pour le proteger par une phrase secrete.
+          </p>
+        )}
+        {encryptionError && (
+          <p className="text-[0.75rem] text-[var(--accent-danger)] pl-4">{encryptionError}</p>
+        )}
       </div>
     </section>
   );
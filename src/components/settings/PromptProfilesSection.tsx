@@ -0,0 +1,240 @@
+import { useState, useEffect, useCallback } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { DictationMode, LlmProvider, PromptProfile } from '../../types';
+import { HotkeyInput } from '../HotkeyInput';
+import { useSettingsStore } from '../../stores/settingsStore';
+
+/** Profils des modes basique et intelligent, non supprimables */
+const REQUIRED_PROFILES = ['basic', 'smart'];
+
+const EMPTY_PROFILE: PromptProfile = {
+  id: '',
+  name: '',
+  system_prompt: '',
+  temperature: 0.3,
+  max_tokens: 2048,
+  provider: null,
+  example: null,
+  dictation_mode: null,
+  hotkey: null,
+};
+
+export function PromptProfilesSection() {
+  const [profiles, setProfiles] = useState<PromptProfile[]>([]);
+  const [draft, setDraft] = useState<PromptProfile>(EMPTY_PROFILE);
+  const [editingId, setEditingId] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const { loadSettings } = useSettingsStore();
+
+  const loadProfiles = useCallback(async () => {
+    try {
+      const data = await invoke<PromptProfile[]>('get_prompt_profiles');
+      setProfiles(data);
+    } catch (e) {
+      console.error('Failed to load prompt profiles:', e);
+    }
+  }, []);
+
+  useEffect(() => { loadProfiles(); }, [loadProfiles]);
+
+  const update = (patch: Partial<PromptProfile>) => setDraft((current) => ({ ...current, ...patch }));
+
+  const handleSave = async () => {
+    if (!draft.name.trim() || !draft.system_prompt.trim()) return;
+    const profile: PromptProfile = {
+      ...draft,
+      id: editingId ?? Date.now().toString(),
+      name: draft.name.trim(),
+      example: draft.example && draft.example.input.trim() && draft.example.output.trim() ? draft.example : null,
+    };
+    try {
+      if (editingId) {
+        await invoke('update_prompt_profile', { id: editingId, profile });
+      } else {
+        await invoke('add_prompt_profile', { profile });
+      }
+      handleCancel();
+      loadProfiles();
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const handleEdit = (profile: PromptProfile) => {
+    setEditingId(profile.id);
+    setDraft(profile);
+    setError(null);
+  };
+
+  const handleRemove = async (id: string) => {
+    try {
+      await invoke('remove_prompt_profile', { id });
+      loadProfiles();
+      // Le profil a pu etre retire des reglages
+      loadSettings();
+    } catch (e) {
+      console.error('Failed to remove prompt profile:', e);
+    }
+  };
+
+  const handleCancel = () => {
+    setEditingId(null);
+    setDraft(EMPTY_PROFILE);
+    setError(null);
+  };
+
+  return (
+    <section className="space-y-4">
+      <h3 className="section-title secondary">Profils de reecriture</h3>
+      <p className="text-[0.75rem] text-[var(--text-muted)]">
+        Styles de post-traitement LLM. Liez un profil a un mode de dictee (mode contextuel) ou a un raccourci a maintenir.
+      </p>
+
+      <div className="space-y-3">
+        <input
+          type="text"
+          value={draft.name}
+          onChange={(e) => update({ name: e.target.value })}
+          placeholder="Nom du profil (ex: Commit message)"
+          className="input-glass w-full"
+        />
+        <textarea
+          value={draft.system_prompt}
+          onChange={(e) => update({ system_prompt: e.target.value })}
+          placeholder="Instructions pour le modele..."
+          rows={4}
+          className="input-glass w-full resize-none"
+        />
+        <div className="grid grid-cols-2 gap-3">
+          <div>
+            <label className="text-[0.8rem] text-[var(--text-muted)] mb-2 block">Temperature ({draft.temperature.toFixed(1)})</label>
+            <input
+              type="range"
+              min={0}
+              max={2}
+              step={0.1}
+              value={draft.temperature}
+              onChange={(e) => update({ temperature: Number(e.target.value) })}
+              className="w-full"
+            />
+          </div>
+          <div>
+            <label className="text-[0.8rem] text-[var(--text-muted)] mb-2 block">Tokens max</label>
+            <input
+              type="number"
+              min={1}
+              value={draft.max_tokens}
+              onChange={(e) => update({ max_tokens: Math.max(1, Number(e.target.value) || 1) })}
+              className="input-glass w-full"
+            />
+          </div>
+          <div>
+            <label className="text-[0.8rem] text-[var(--text-muted)] mb-2 block">Fournisseur</label>
+            <select
+              value={draft.provider ?? ''}
+              onChange={(e) => update({ provider: (e.target.value || null) as LlmProvider | null })}
+              className="select-glass"
+            >
+              <option value="">Celui des reglages</option>
+              <option value="groq">Groq</option>
+              <option value="local">Local</option>
+              <option value="openai_compatible">Compatible OpenAI</option>
+            </select>
+          </div>
+          <div>
+            <label className="text-[0.8rem] text-[var(--text-muted)] mb-2 block">Mode de dictee</label>
+            <select
+              value={draft.dictation_mode ?? ''}
+              onChange={(e) => update({ dictation_mode: (e.target.value || null) as DictationMode | null })}
+              className="select-glass"
+            >
+              <option value="">Aucun</option>
+              <option value="general">General</option>
+              <option value="email">Email</option>
+              <option value="code">Code</option>
+              <option value="notes">Notes</option>
+            </select>
+          </div>
+        </div>
+        <div>
+          <label className="text-[0.8rem] text-[var(--text-muted)] mb-2 block">Raccourci (maintenir, optionnel)</label>
+          <HotkeyInput
+            value={draft.hotkey ?? ''}
+            onChange={(hotkey) => update({ hotkey: hotkey || null })}
+            onClear={() => update({ hotkey: null })}
+          />
+        </div>
+        <div className="grid grid-cols-2 gap-3">
+          <textarea
+            value={draft.example?.input ?? ''}
+            onChange={(e) => update({ example: { input: e.target.value, output: draft.example?.output ?? '' } })}
+            placeholder="Exemple dicte (optionnel)"
+            rows={2}
+            className="input-glass w-full resize-none"
+          />
+          <textarea
+            value={draft.example?.output ?? ''}
+            onChange={(e) => update({ example: { input: draft.example?.input ?? '', output: e.target.value } })}
+            placeholder="Resultat attendu"
+            rows={2}
+            className="input-glass w-full resize-none"
+          />
+        </div>
+        {error && <p className="text-[0.75rem] text-[var(--accent-danger)]">{error}</p>}
+        <div className="flex gap-2">
+          <button
+            onClick={handleSave}
+            disabled={!draft.name.trim() || !draft.system_prompt.trim()}
+            className="btn-glass px-4 text-[var(--accent-primary)] disabled:opacity-50"
+          >
+            {editingId ? 'Modifier' : 'Ajouter'}
+          </button>
+          {editingId && (
+            <button onClick={handleCancel} className="btn-glass px-4 text-[var(--text-muted)]">
+              Annuler
+            </button>
+          )}
+        </div>
+      </div>
+
+      {profiles.length > 0 && (
+        <div className="space-y-2">
+          {profiles.map((p) => (
+            <div key={p.id} className="glass-card p-3 flex items-start justify-between gap-3">
+              <div className="flex-1 min-w-0">
+                <div className="flex items-center gap-2 mb-1">
+                  <span className="text-[0.875rem] font-medium text-[var(--text-primary)]">{p.name}</span>
+                  {p.dictation_mode && <span className="tag-frost text-[0.65rem]">{p.dictation_mode}</span>}
+                  {p.hotkey && <span className="tag-frost text-[0.65rem]">{p.hotkey}</span>}
+                </div>
+                <p className="text-[0.75rem] text-[var(--text-muted)] truncate">{p.system_prompt}</p>
+              </div>
+              <div className="flex gap-1 flex-shrink-0">
+                <button
+                  onClick={() => handleEdit(p)}
+                  className="p-1.5 rounded-lg hover:bg-[rgba(255,255,255,0.08)] text-[var(--text-muted)] hover:text-[var(--accent-primary)] transition-colors"
+                >
+                  <svg width="14" height="14" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2">
+                    <path d="M11 4H4a2 2 0 0 0-2 2v14a2 2 0 0 0 2 2h14a2 2 0 0 0 2-2v-7" />
+                    <path d="M18.5 2.5a2.121 2.121 0 0 1 3 3L12 15l-4 1 1-4 9.5-9.5z" />
+                  </svg>
+                </button>
+                {!REQUIRED_PROFILES.includes(p.id) && (
+                  <button
+                    onClick={() => handleRemove(p.id)}
+                    className="p-1.5 rounded-lg hover:bg-[rgba(255,255,255,0.08)] text-[var(--text-muted)] hover:text-[var(--accent-danger)] transition-colors"
+                  >
+                    <svg width="14" height="14" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2">
+                      <line x1="18" y1="6" x2="6" y2="18" />
+                      <line x1="6" y1="6" x2="18" y2="18" />
+                    </svg>
+                  </button>
+                )}
+              </div>
+            </div>
+          ))}
+        </div>
+      )}
+    </section>
+  );
+}
@@ -36,6 +36,15 @@ export function ShortcutsSection({ settings, updateSettings }: ShortcutsSectionP
           />
           <p className="text-[0.65rem] text-[var(--text-muted)] mt-1">Traduit le texte selectionne vers la langue cible</p>
         </div>
+        <div>
+          <label className="text-[0.8rem] text-[var(--text-muted)] mb-2 block">Dictee traduite en anglais (maintenir)</label>
+          <HotkeyInput
+            value={settings.hotkey_speech_translate}
+            onChange={(hotkey) => updateSettings({ hotkey_speech_translate: hotkey })}
+            onClear={() => updateSettings({ hotkey_speech_translate: '' })}
+          />
+          <p className="text-[0.65rem] text-[var(--text-muted)] mt-1">Parlez dans votre langue, Whisper colle le texte en anglais (hors ligne, modele multilingue)</p>
+        </div>
         <div>
           <label className="text-[0.8rem] text-[var(--text-muted)] mb-2 block">Toggle enregistrement</label>
           <HotkeyInput
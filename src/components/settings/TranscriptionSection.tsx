@@ -41,6 +41,37 @@ export function TranscriptionSection({ settings, updateSettings }: Transcription
           <option value="ko">Hangugeo</option>
         </select>
       </div>
+
+      <label className="checkbox-frost">
+        <input
+          type="checkbox"
+          checked={settings.diarization_enabled}
+          onChange={(e) => updateSettings({ diarization_enabled: e.target.checked })}
+        />
+        <span className="check-box" />
+        <span className="check-label">Identifier les intervenants (fichiers et reunions)</span>
+      </label>
+
+      {settings.diarization_enabled && (
+        <div>
+          <label className="text-[0.8rem] text-[var(--text-muted)] mb-2 block">Nombre d'intervenants</label>
+          <select
+            value={settings.diarization_max_speakers ?? ''}
+            onChange={(e) => updateSettings({
+              diarization_max_speakers: e.target.value ? Number(e.target.value) : null
+            })}
+            className="select-glass"
+          >
+            <option value="">Automatique</option>
+            {[2, 3, 4, 5, 6, 8].map((count) => (
+              <option key={count} value={count}>{count} maximum</option>
+            ))}
+          </select>
+          <p className="text-[0.75rem] text-[var(--text-muted)] mt-2">
+            Necessite le modele speaker_embedding.onnx dans le dossier des modeles
+          </p>
+        </div>
+      )}
     </section>
   );
 }
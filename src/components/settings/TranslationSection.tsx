@@ -23,7 +23,7 @@ export function TranslationSection({ settings, updateSettings, apiKeyStatus }: T
           <div>
             <span className="check-label block">Traduction instantanee</span>
             <span className="text-[0.75rem] text-[var(--text-muted)]">
-              Traduit le texte du presse-papier via Groq
+              Traduit le texte du presse-papier avec le fournisseur LLM configure
             </span>
           </div>
         </label>
@@ -63,7 +63,7 @@ export function TranslationSection({ settings, updateSettings, apiKeyStatus }: T
               </p>
             </div>
 
-            {!apiKeyStatus && (
+            {settings.llm_provider === 'groq' && !apiKeyStatus && (
               <div className="glass-card p-4 border-[var(--accent-warning)]">
                 <p className="text-[0.8rem] text-[var(--accent-warning)]">
                   ⚠️ Une cle API Groq est requise pour la traduction.
@@ -9,4 +9,6 @@ export { SystemSection } from './SystemSection';
 export { ShortcutsSection } from './ShortcutsSection';
 export { DictionarySection } from './DictionarySection';
 export { SnippetsSection } from './SnippetsSection';
+export { PromptProfilesSection } from './PromptProfilesSection';
 export { IntegrationsSection } from './IntegrationsSection';
+export { BackupSection } from './BackupSection';
@@ -1,5 +1,6 @@
 import { useEffect, useRef } from 'react';
 import { register, unregister } from '@tauri-apps/plugin-global-shortcut';
+import { listen } from '@tauri-apps/api/event';
 import { useTranscriptionStore } from '../stores/transcriptionStore';
 import { useSettingsStore } from '../stores/settingsStore';
 import { writeText } from '@tauri-apps/plugin-clipboard-manager';
@@ -46,4 +47,22 @@ export function useHotkeys() {
       unregister(settings.hotkey_toggle_record).catch(console.error);
     };
   }, [settings?.hotkey_toggle_record, settings?.auto_copy_to_clipboard, startRecording, stopRecording]);
+
+  // Mode mains libres : le backend signale un silence prolongé
+  useEffect(() => {
+    const unlisten = listen<number>('recording-silence-detected', async () => {
+      if (statusRef.current !== 'recording') return;
+      try {
+        const result = await stopRecording();
+        if (settings?.auto_copy_to_clipboard && result.text) {
+          await writeText(result.text);
+        }
+      } catch (error) {
+        console.error('Hands-free stop failed:', error);
+      }
+    });
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, [settings?.auto_copy_to_clipboard, stopRecording]);
 }
@@ -30,11 +30,16 @@ const defaultSettings: AppSettings = {
   engine_type: 'whisper',
   vosk_language: null,
   parakeet_model: 'tdt06bv3',
-  groq_api_key: null,
   llm_provider: 'groq',
+  openai_compatible: {
+    base_url: 'http://localhost:11434/v1',
+    model: 'llama3.2',
+    headers: [],
+  },
   local_llm_model: 'phi3_mini',
   llm_enabled: false,
   llm_mode: 'basic',
+  llm_profile: null,
   voice_commands_enabled: true,
   dictation_mode: 'general',
   streaming_enabled: true,
@@ -44,6 +49,7 @@ const defaultSettings: AppSettings = {
   translation_target_language: 'en',
   hotkey_translate: 'Control+Alt+T',
   hotkey_voice_action: 'Control+Alt+A',
+  hotkey_speech_translate: 'Control+Alt+E',
   onboarding_completed: false,
   tour_completed: false,
   system_commands_enabled: false,
@@ -55,6 +61,37 @@ const defaultSettings: AppSettings = {
     obsidian_enabled: false,
     obsidian_vault_path: null,
   },
+  vad_engine: 'energy',
+  trim_silence: true,
+  hands_free_enabled: false,
+  hands_free_silence_ms: 1500,
+  diarization_enabled: false,
+  diarization_max_speakers: null,
+  model_catalog_url: null,
+  model_mirror_url: null,
+  local_server: {
+    enabled: false,
+    port: 8178,
+  },
+  app_rules: [
+    { app: 'code', dictation_mode: 'code', llm_profile: null, language: null, voice_commands: null },
+    { app: 'thunderbird', dictation_mode: 'email', llm_profile: null, language: null, voice_commands: null },
+    { app: 'obsidian', dictation_mode: 'notes', llm_profile: null, language: null, voice_commands: null },
+  ],
+  context_capture: {
+    enabled: false,
+    app_name: true,
+    window_title: true,
+    selected_text: false,
+    redact: true,
+  },
+  audio_retention: {
+    enabled: false,
+    max_age_days: 30,
+    max_size_mb: 500,
+  },
+  encrypt_storage: false,
+  schema_version: 1,
 };
 
 export const useSettingsStore = create<SettingsStore>((set, get) => ({
@@ -1,28 +1,38 @@
 import { create } from 'zustand';
 import { invoke } from '@tauri-apps/api/core';
-import { TranscriptionResult, TranscriptionStatus } from '../types';
+import { EngineType, HistoryEntry, HistoryPage, HistoryQuery, TranscriptionResult, TranscriptionStatus } from '../types';
 import { playStartSound, playStopSound } from '../utils/sounds';
 
 interface TranscriptionStore {
   status: TranscriptionStatus;
   result: TranscriptionResult | null;
-  history: TranscriptionResult[];
+  history: HistoryEntry[];
+  historyTotal: number;
+  historyQuery: HistoryQuery;
   error: string | null;
 
   setStatus: (status: TranscriptionStatus) => void;
   startRecording: () => Promise<void>;
   stopRecording: () => Promise<TranscriptionResult>;
-  loadHistory: () => Promise<void>;
-  clearHistory: () => Promise<void>;
+  loadHistory: (query?: HistoryQuery) => Promise<void>;
+  loadMoreHistory: () => Promise<void>;
+  deleteHistoryEntry: (id: number) => Promise<void>;
+  toggleFavorite: (entry: HistoryEntry) => Promise<void>;
+  setHistoryTags: (id: number, tags: string[]) => Promise<void>;
+  retranscribeHistoryEntry: (id: number, engine: EngineType, model: string) => Promise<void>;
   clearError: () => void;
   resetRecordingState: () => Promise<void>;
   initialize: () => Promise<void>;
 }
 
-export const useTranscriptionStore = create<TranscriptionStore>((set) => ({
+const HISTORY_PAGE_SIZE = 50;
+
+export const useTranscriptionStore = create<TranscriptionStore>((set, get) => ({
   status: 'idle',
   result: null,
   history: [],
+  historyTotal: 0,
+  historyQuery: {},
   error: null,
 
   setStatus: (status) => set({ status }),
@@ -52,11 +62,8 @@ export const useTranscriptionStore = create<TranscriptionStore>((set) => ({
       set({ status: 'processing' });
       playStopSound();
       const result = await invoke<TranscriptionResult>('stop_recording');
-      set((state) => ({
-        status: 'completed',
-        result,
-        history: [result, ...state.history].slice(0, 50),
-      }));
+      set({ status: 'completed', result });
+      get().loadHistory();
       return result;
     } catch (error) {
       set({ status: 'error', error: String(error) });
@@ -64,24 +71,71 @@ export const useTranscriptionStore = create<TranscriptionStore>((set) => ({
     }
   },
 
-  loadHistory: async () => {
+  loadHistory: async (query) => {
+    const historyQuery = query ?? get().historyQuery;
+    try {
+      const page = await invoke<HistoryPage>('search_history', {
+        query: { ...historyQuery, offset: 0, limit: HISTORY_PAGE_SIZE },
+      });
+      set({ history: page.entries, historyTotal: page.total, historyQuery });
+    } catch (error) {
+      console.error('Failed to load history:', error);
+    }
+  },
+
+  loadMoreHistory: async () => {
+    const { history, historyQuery } = get();
     try {
-      const history = await invoke<TranscriptionResult[]>('get_history');
-      set({ history });
+      const page = await invoke<HistoryPage>('search_history', {
+        query: { ...historyQuery, offset: history.length, limit: HISTORY_PAGE_SIZE },
+      });
+      set((state) => ({ history: [...state.history, ...page.entries], historyTotal: page.total }));
     } catch (error) {
       console.error('Failed to load history:', error);
     }
   },
 
-  clearHistory: async () => {
+  deleteHistoryEntry: async (id) => {
     try {
-      await invoke('clear_history');
-      set({ history: [] });
+      await invoke('delete_history_entry', { id });
+      set((state) => ({
+        history: state.history.filter((entry) => entry.id !== id),
+        historyTotal: Math.max(0, state.historyTotal - 1),
+      }));
     } catch (error) {
-      console.error('Failed to clear history:', error);
+      console.error('Failed to delete history entry:', error);
     }
   },
 
+  toggleFavorite: async (entry) => {
+    try {
+      await invoke('set_history_favorite', { id: entry.id, favorite: !entry.favorite });
+      set((state) => ({
+        history: state.history.map((e) => (e.id === entry.id ? { ...e, favorite: !entry.favorite } : e)),
+      }));
+    } catch (error) {
+      console.error('Failed to update favorite:', error);
+    }
+  },
+
+  setHistoryTags: async (id, tags) => {
+    try {
+      await invoke('set_history_tags', { id, tags });
+      set((state) => ({
+        history: state.history.map((e) => (e.id === id ? { ...e, tags } : e)),
+      }));
+    } catch (error) {
+      console.error('Failed to update tags:', error);
+    }
+  },
+
+  retranscribeHistoryEntry: async (id, engine, model) => {
+    const entry = await invoke<HistoryEntry>('retranscribe_history_entry', { id, engine, model });
+    set((state) => ({
+      history: state.history.map((e) => (e.id === id ? entry : e)),
+    }));
+  },
+
   clearError: () => set({ error: null, status: 'idle' }),
 
   resetRecordingState: async () => {
@@ -6,15 +6,74 @@ export interface TranscriptionResult {
   detected_language: string | null;
   timestamp: number;
   model_used: string | null;
+  segments?: Segment[];
+  translation?: TranslationInfo;
 }
 
-export type ModelSize = 'tiny' | 'small' | 'medium';
+export interface HistoryEntry extends TranscriptionResult {
+  id: number;
+  app: string | null;
+  tags: string[];
+  favorite: boolean;
+  has_audio: boolean;
+}
+
+export interface HistoryQuery {
+  text?: string | null;
+  from?: number | null;
+  to?: number | null;
+  model?: string | null;
+  language?: string | null;
+  app?: string | null;
+  tag?: string | null;
+  favorites_only?: boolean;
+  offset?: number;
+  limit?: number;
+}
+
+export interface HistoryPage {
+  entries: HistoryEntry[];
+  total: number;
+}
+
+export interface HistoryFacets {
+  models: string[];
+  languages: string[];
+  apps: string[];
+  tags: string[];
+}
+
+export interface TranslationInfo {
+  source_language: string | null;
+  target_language: string;
+}
+
+export interface WordTiming {
+  start: number;
+  end: number;
+  text: string;
+  confidence?: number | null;
+}
+
+export interface Segment {
+  start: number;
+  end: number;
+  text: string;
+  words: WordTiming[];
+  confidence: number;
+  speaker?: string | null;
+}
+
+// Whisper model id from the model catalog (tiny, small, medium, large-v3-turbo...)
+export type ModelSize = string;
 
 export type EngineType = 'whisper' | 'parakeet' | 'vosk';
 
-export type VoskLanguage = 'en' | 'fr' | 'de' | 'es' | 'it' | 'ru' | 'zh' | 'ja' | 'ko' | 'pt' | 'nl' | 'pl' | 'uk' | 'tr' | 'vi' | 'ar' | 'hi' | 'fa' | 'ca' | 'cs';
+// Vosk model id from the model catalog (en, fr... or an imported model)
+export type VoskLanguage = string;
 
-export type ParakeetModelSize = 'tdt06bv3';
+// Parakeet model id from the model catalog (tdt06bv3...)
+export type ParakeetModelSize = string;
 
 export interface ParakeetModelInfo {
   size: ParakeetModelSize;
@@ -23,11 +82,58 @@ export interface ParakeetModelInfo {
   size_bytes: number;
 }
 
-export type LlmMode = 'off' | 'basic' | 'smart' | 'contextual';
+export type LlmMode = 'off' | 'basic' | 'smart' | 'contextual' | 'profile';
+
+export type LlmProvider = 'groq' | 'local' | 'openai_compatible';
+
+export interface HttpHeader {
+  name: string;
+  value: string;
+}
+
+export interface OpenAiCompatibleConfig {
+  base_url: string;
+  model: string;
+  headers: HttpHeader[];
+}
+
+export interface LocalServerConfig {
+  enabled: boolean;
+  port: number;
+}
+
+export interface AudioRetentionConfig {
+  enabled: boolean;
+  max_age_days: number;
+  max_size_mb: number;
+}
 
-export type LlmProvider = 'groq' | 'local';
+export interface ContextCaptureConfig {
+  enabled: boolean;
+  app_name: boolean;
+  window_title: boolean;
+  selected_text: boolean;
+  redact: boolean;
+}
 
-export type LocalLlmModel = 'smollm2_360m' | 'phi3_mini' | 'qwen2_5_3b';
+export type VoiceCommandSet = 'off' | 'general' | 'email' | 'code' | 'notes';
+
+export interface AppRule {
+  app: string;
+  dictation_mode: DictationMode | null;
+  llm_profile: string | null;
+  language: string | null;
+  voice_commands: VoiceCommandSet | null;
+}
+
+export interface LocalServerStatus {
+  running: boolean;
+  url: string | null;
+  error: string | null;
+}
+
+// Local LLM model id from the model catalog (smollm2_360m, phi3_mini, qwen2_5_3b...)
+export type LocalLlmModel = string;
 
 export interface LocalLlmModelInfo {
   size: LocalLlmModel;
@@ -46,6 +152,8 @@ export interface ModelInfo {
   display_name: string;
   available: boolean;
   size_bytes: number;
+  languages: string[];
+  license: string | null;
 }
 
 export interface DownloadProgress {
@@ -68,11 +176,12 @@ export interface AppSettings {
   engine_type: EngineType;
   vosk_language: VoskLanguage | null;
   parakeet_model: ParakeetModelSize;
-  groq_api_key: string | null;
   llm_provider: LlmProvider;
+  openai_compatible: OpenAiCompatibleConfig;
   local_llm_model: LocalLlmModel;
   llm_enabled: boolean;
   llm_mode: LlmMode;
+  llm_profile: string | null;
   voice_commands_enabled: boolean;
   dictation_mode: DictationMode;
   streaming_enabled: boolean;
@@ -82,6 +191,7 @@ export interface AppSettings {
   translation_target_language: string;
   hotkey_translate: string;
   hotkey_voice_action: string;
+  hotkey_speech_translate: string;
   onboarding_completed: boolean;
   tour_completed: boolean;
   system_commands_enabled: boolean;
@@ -89,8 +199,43 @@ export interface AppSettings {
   subtitles_font_size: number;
   stats_tracking_enabled: boolean;
   integrations: IntegrationConfig;
+  vad_engine: VadEngine;
+  trim_silence: boolean;
+  hands_free_enabled: boolean;
+  hands_free_silence_ms: number;
+  diarization_enabled: boolean;
+  diarization_max_speakers: number | null;
+  model_catalog_url: string | null;
+  model_mirror_url: string | null;
+  local_server: LocalServerConfig;
+  app_rules: AppRule[];
+  context_capture: ContextCaptureConfig;
+  audio_retention: AudioRetentionConfig;
+  encrypt_storage: boolean;
+  schema_version: number;
+}
+
+export type SecretsProtection = 'keyring' | 'passphrase' | 'machine';
+
+export interface SettingsError {
+  field: string;
+  message: string;
+}
+
+export interface SettingsLoadReport {
+  errors: SettingsError[];
+  backup_path: string | null;
+}
+
+export interface SettingsImportReport {
+  errors: SettingsError[];
+  dictionary_words: number;
+  snippets: number;
+  prompt_profiles: number;
 }
 
+export type VadEngine = 'energy' | 'silero';
+
 export interface VoskModelInfo {
   language: VoskLanguage;
   display_name: string;
@@ -102,6 +247,16 @@ export interface FileTranscriptionResult {
   file_name: string;
   transcription: TranscriptionResult | null;
   error: string | null;
+  history_id?: number;
+}
+
+export type ExportFormat = 'srt' | 'vtt' | 'ttml' | 'json' | 'txt';
+
+export interface SubtitleOptions {
+  max_line_chars: number;
+  max_lines: number;
+  min_cue_seconds: number;
+  max_cue_seconds: number;
 }
 
 export interface FileTranscriptionProgress {
@@ -109,6 +264,8 @@ export interface FileTranscriptionProgress {
   total: number;
   file_name: string;
   status: string;
+  chunk?: number | null;
+  total_chunks?: number | null;
 }
 
 export interface Snippet {
@@ -118,6 +275,23 @@ export interface Snippet {
   content: string;
 }
 
+export interface PromptExample {
+  input: string;
+  output: string;
+}
+
+export interface PromptProfile {
+  id: string;
+  name: string;
+  system_prompt: string;
+  temperature: number;
+  max_tokens: number;
+  provider: LlmProvider | null;
+  example: PromptExample | null;
+  dictation_mode: DictationMode | null;
+  hotkey: string | null;
+}
+
 export interface DailyStats {
   words: number;
   transcriptions: number;